@@ -30,12 +30,13 @@ pub(crate) mod displacement_map_filter;
 pub(crate) mod drop_shadow_filter;
 pub(crate) mod error;
 mod external_interface;
+pub(crate) mod file_reference;
 mod function;
 pub(crate) mod glow_filter;
 pub(crate) mod gradient_filter;
 mod key;
 mod load_vars;
-mod local_connection;
+pub(crate) mod local_connection;
 mod math;
 mod matrix;
 pub(crate) mod mouse;
@@ -578,6 +579,22 @@ pub fn create_globals<'gc>(
         movie_clip_loader_proto,
     );
 
+    let file_reference_proto = file_reference::create_proto(
+        context,
+        object_proto,
+        function_proto,
+        array_proto,
+        broadcaster_functions,
+    );
+
+    let file_reference = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(file_reference::constructor),
+        constructor_to_fn!(file_reference::constructor),
+        function_proto,
+        file_reference_proto,
+    );
+
     let video_proto = video::create_proto(context, object_proto, function_proto);
     let netstream_proto = netstream::create_proto(context, object_proto, function_proto);
     let xml_socket_proto = xml_socket::create_proto(context, object_proto, function_proto);
@@ -681,6 +698,7 @@ pub fn create_globals<'gc>(
     let geom = ScriptObject::new(gc_context, Some(object_proto));
     let filters = ScriptObject::new(gc_context, Some(object_proto));
     let display = ScriptObject::new(gc_context, Some(object_proto));
+    let net = ScriptObject::new(gc_context, Some(object_proto));
 
     let matrix = matrix::create_matrix_object(context, matrix_proto, function_proto);
     let point = point::create_point_object(context, point_proto, function_proto);
@@ -704,6 +722,13 @@ pub fn create_globals<'gc>(
     flash.define_value(gc_context, "geom", geom.into(), Attribute::empty());
     flash.define_value(gc_context, "filters", filters.into(), Attribute::empty());
     flash.define_value(gc_context, "display", display.into(), Attribute::empty());
+    flash.define_value(gc_context, "net", net.into(), Attribute::empty());
+    net.define_value(
+        gc_context,
+        "FileReference",
+        file_reference.into(),
+        Attribute::empty(),
+    );
     geom.define_value(gc_context, "Matrix", matrix.into(), Attribute::empty());
     geom.define_value(gc_context, "Point", point.into(), Attribute::empty());
     geom.define_value(
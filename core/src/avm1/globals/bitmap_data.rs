@@ -622,6 +622,10 @@ fn apply_filter<'gc>(
                 .unwrap_or(&Value::Undefined)
                 .coerce_to_object(activation);
             let filter = bitmap_filter::avm1_to_filter(filter_object, &mut activation.context);
+            let displacement_map = match filter_object.native() {
+                NativeObject::DisplacementMapFilter(filter) => filter.map_bitmap_data(),
+                _ => None,
+            };
 
             if let Some(filter) = filter {
                 operations::apply_filter(
@@ -632,6 +636,7 @@ fn apply_filter<'gc>(
                     (src_width, src_height),
                     (dest_x, dest_y),
                     filter,
+                    displacement_map,
                 );
                 return Ok(0.into());
             }
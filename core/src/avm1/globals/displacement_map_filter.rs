@@ -140,6 +140,11 @@ impl<'gc> DisplacementMapFilter<'gc> {
         Self(GcCell::new(gc_context, self.0.read().clone()))
     }
 
+    /// The `BitmapData` used as the displacement map, if any.
+    pub fn map_bitmap_data(&self) -> Option<BitmapDataWrapper<'gc>> {
+        self.0.read().map_bitmap
+    }
+
     fn map_bitmap(&self, context: &mut UpdateContext<'_, 'gc>) -> Option<Object<'gc>> {
         if let Some(map_bitmap) = self.0.read().map_bitmap {
             let proto = context.avm1.prototypes().bitmap_data;
@@ -0,0 +1,362 @@
+//! `flash.net.FileReference` impl
+
+use crate::avm1::globals::as_broadcaster::BroadcasterFunctions;
+use crate::avm1::object::{NativeObject, Object};
+use crate::avm1::property::Attribute;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{Activation, ArrayObject, Error, ScriptObject, TObject, Value};
+use crate::backend::navigator::Request;
+use crate::backend::ui::{FileDialogResult, FileFilter};
+use crate::context::GcContext;
+use crate::display_object::TDisplayObject;
+use crate::loader::{self, FileReference, Handle};
+use crate::string::AvmString;
+use gc_arena::{Collect, Gc};
+use std::cell::{Cell, RefCell};
+use std::fmt;
+
+#[derive(Collect)]
+#[collect(require_static)]
+struct FileReferenceData {
+    /// The file that was picked for this `FileReference`, if any.
+    file: RefCell<Option<Box<dyn FileDialogResult>>>,
+
+    /// The loader running the dialog or transfer that this `FileReference` is busy with.
+    loader: Cell<Option<Handle>>,
+}
+
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct FileReferenceObject<'gc>(Gc<'gc, FileReferenceData>);
+
+impl<'gc> FileReferenceObject<'gc> {
+    pub fn has_file(&self) -> bool {
+        self.0.file.borrow().is_some()
+    }
+
+    /// Calls `f` with the file that was picked for this `FileReference`, if any.
+    pub fn with_file<R>(&self, f: impl FnOnce(&dyn FileDialogResult) -> R) -> Option<R> {
+        self.0.file.borrow().as_deref().map(f)
+    }
+
+    pub fn set_file(&self, file: Option<Box<dyn FileDialogResult>>) {
+        *self.0.file.borrow_mut() = file;
+    }
+
+    pub fn loader(&self) -> Option<Handle> {
+        self.0.loader.get()
+    }
+
+    pub fn set_loader(&self, loader: Option<Handle>) -> Option<Handle> {
+        self.0.loader.replace(loader)
+    }
+
+    pub fn cast(value: Value<'gc>) -> Option<Self> {
+        if let Value::Object(object) = value {
+            if let NativeObject::FileReference(file_reference) = object.native() {
+                return Some(file_reference);
+            }
+        }
+        None
+    }
+}
+
+impl fmt::Debug for FileReferenceObject<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileReferenceObject")
+            .field("ptr", &Gc::as_ptr(self.0))
+            .finish()
+    }
+}
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "creationDate" => property(creation_date);
+    "creator" => property(creator);
+    "modificationDate" => property(modification_date);
+    "name" => property(name);
+    "size" => property(size);
+    "type" => property(file_type);
+    "browse" => method(browse);
+    "cancel" => method(cancel);
+    "download" => method(download);
+    "upload" => method(upload);
+};
+
+fn creation_date<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(file_reference) = FileReferenceObject::cast(this.into()) else {
+        return Ok(Value::Undefined);
+    };
+    let creation_time = file_reference
+        .with_file(|file| file.creation_time())
+        .flatten();
+    date_value(
+        activation,
+        creation_time.map(|time| time.timestamp_millis()),
+    )
+}
+
+fn creator<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Creator codes only exist on classic Mac OS.
+    Ok(Value::Null)
+}
+
+fn modification_date<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(file_reference) = FileReferenceObject::cast(this.into()) else {
+        return Ok(Value::Undefined);
+    };
+    let modification_time = file_reference
+        .with_file(|file| file.modification_time())
+        .flatten();
+    date_value(
+        activation,
+        modification_time.map(|time| time.timestamp_millis()),
+    )
+}
+
+fn name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = FileReferenceObject::cast(this.into())
+        .and_then(|file_reference| file_reference.with_file(|file| file.file_name()));
+    Ok(match name {
+        Some(name) => AvmString::new_utf8(activation.context.gc_context, name).into(),
+        None => Value::Undefined,
+    })
+}
+
+fn size<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let size = FileReferenceObject::cast(this.into())
+        .and_then(|file_reference| file_reference.with_file(|file| file.size()).flatten());
+    Ok(match size {
+        Some(size) => (size as f64).into(),
+        None => Value::Undefined,
+    })
+}
+
+fn file_type<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let extension = FileReferenceObject::cast(this.into())
+        .and_then(|file_reference| file_reference.with_file(|file| file.extension()).flatten());
+    Ok(match extension {
+        Some(extension) => {
+            AvmString::new_utf8(activation.context.gc_context, format!(".{extension}")).into()
+        }
+        None => Value::Undefined,
+    })
+}
+
+/// Creates a `Date` for the given number of milliseconds since the epoch, if there is one.
+fn date_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    millis: Option<i64>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    match millis {
+        Some(millis) => {
+            let date_constructor = activation.context.avm1.prototypes().date_constructor;
+            Ok(date_constructor
+                .construct(activation, &[(millis as f64).into()])?
+                .into())
+        }
+        None => Ok(Value::Undefined),
+    }
+}
+
+fn browse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if FileReferenceObject::cast(this.into()).is_none() {
+        return Ok(false.into());
+    }
+
+    let mut filters = Vec::new();
+    if let Some(Value::Object(type_list)) = args.get(0) {
+        for i in 0..type_list.length(activation)? {
+            let Value::Object(filter) = type_list.get_element(activation, i) else {
+                return Ok(false.into());
+            };
+            let description = filter.get("description", activation)?;
+            let extension = filter.get("extension", activation)?;
+            if description == Value::Undefined || extension == Value::Undefined {
+                return Ok(false.into());
+            }
+            let mac_type = match filter.get("macType", activation)? {
+                Value::Undefined | Value::Null => None,
+                mac_type => Some(mac_type.coerce_to_string(activation)?.to_string()),
+            };
+            filters.push(FileFilter {
+                description: description.coerce_to_string(activation)?.to_string(),
+                extensions: extension.coerce_to_string(activation)?.to_string(),
+                mac_type,
+            });
+        }
+    }
+
+    let Ok(dialog) = activation
+        .context
+        .ui
+        .display_file_open_dialog(filters, false)
+    else {
+        return Ok(false.into());
+    };
+
+    let future = activation.context.load_manager.browse_file_reference(
+        activation.context.player.clone(),
+        FileReference::Avm1(this),
+        dialog,
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(true.into())
+}
+
+fn cancel<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(file_reference) = FileReferenceObject::cast(this.into()) {
+        if let Some(handle) = file_reference.set_loader(None) {
+            activation.context.load_manager.remove_loader(handle);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn download<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if FileReferenceObject::cast(this.into()).is_none() {
+        return Ok(false.into());
+    }
+
+    let url = match args.get(0) {
+        Some(Value::String(url)) => url.to_utf8_lossy().into_owned(),
+        _ => return Ok(false.into()),
+    };
+    let file_name = match args.get(1) {
+        Some(Value::Undefined | Value::Null) | None => loader::file_name_from_url(&url),
+        Some(file_name) => file_name.coerce_to_string(activation)?.to_string(),
+    };
+    if !loader::is_valid_file_name(&file_name) {
+        return Ok(false.into());
+    }
+
+    let Ok(dialog) = activation.context.ui.display_file_save_dialog(file_name) else {
+        return Ok(false.into());
+    };
+
+    let requester_url = activation.base_clip().movie().url().to_string();
+    let future = activation.context.load_manager.download_file_reference(
+        activation.context.player.clone(),
+        FileReference::Avm1(this),
+        dialog,
+        Request::get(url),
+        requester_url,
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(true.into())
+}
+
+fn upload<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(file_reference) = FileReferenceObject::cast(this.into()) else {
+        return Ok(false.into());
+    };
+    if !file_reference.has_file() {
+        return Ok(false.into());
+    }
+
+    let url = match args.get(0) {
+        Some(Value::String(url)) => url.to_utf8_lossy().into_owned(),
+        _ => return Ok(false.into()),
+    };
+    let field_name = match args.get(1) {
+        Some(Value::Undefined | Value::Null) | None => "Filedata".to_string(),
+        Some(field_name) => field_name.coerce_to_string(activation)?.to_string(),
+    };
+
+    let requester_url = activation.base_clip().movie().url().to_string();
+    let future = activation.context.load_manager.upload_file_reference(
+        activation.context.player.clone(),
+        FileReference::Avm1(this),
+        Request::get(url),
+        Vec::new(),
+        field_name,
+        requester_url,
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(true.into())
+}
+
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let file_reference = FileReferenceObject(Gc::new(
+        activation.gc(),
+        FileReferenceData {
+            file: RefCell::new(None),
+            loader: Cell::new(None),
+        },
+    ));
+    this.set_native(activation.gc(), NativeObject::FileReference(file_reference));
+
+    let listeners = ArrayObject::new(
+        activation.context.gc_context,
+        activation.context.avm1.prototypes().array,
+        [],
+    );
+    this.define_value(
+        activation.context.gc_context,
+        "_listeners",
+        Value::Object(listeners.into()),
+        Attribute::DONT_ENUM,
+    );
+
+    Ok(this.into())
+}
+
+pub fn create_proto<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+    array_proto: Object<'gc>,
+    broadcaster_functions: BroadcasterFunctions<'gc>,
+) -> Object<'gc> {
+    let file_reference_proto = ScriptObject::new(context.gc_context, Some(proto));
+    broadcaster_functions.initialize(context.gc_context, file_reference_proto.into(), array_proto);
+    define_properties_on(PROTO_DECLS, context, file_reference_proto, fn_proto);
+    file_reference_proto.into()
+}
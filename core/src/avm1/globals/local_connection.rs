@@ -2,38 +2,178 @@
 
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
+use crate::avm1::globals::shared_object::{deserialize_value, serialize_value};
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Object, ScriptObject, Value};
-use crate::context::GcContext;
+use crate::context::{GcContext, UpdateContext};
 use crate::display_object::TDisplayObject;
+use crate::local_connection::{LocalConnectionKind, LocalConnections, MAX_ARGUMENTS_SIZE};
 use crate::string::AvmString;
+use flash_lso::amf0::writer::{Amf0Writer, ObjWriter};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 
 const PROTO_DECLS: &[Declaration] = declare_properties! {
     "domain" => method(domain; DONT_DELETE | READ_ONLY);
+    "connect" => method(connect; DONT_ENUM | DONT_DELETE);
+    "send" => method(send; DONT_ENUM | DONT_DELETE);
+    "close" => method(close; DONT_ENUM | DONT_DELETE);
 };
 
+/// Methods that can't be called by a message.
+pub const RESERVED_METHODS: &[&str] = &[
+    "send",
+    "connect",
+    "close",
+    "allowDomain",
+    "allowInsecureDomain",
+    "domain",
+];
+
+/// The domain of the movie that is running this code.
+fn movie_domain(activation: &mut Activation<'_, '_>) -> Option<String> {
+    let movie = activation.base_clip().movie();
+    let domain = LocalConnections::domain(movie.url());
+    if domain.is_none() {
+        tracing::error!("LocalConnection::domain: Unable to parse movie URL");
+    }
+    domain
+}
+
 pub fn domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let movie = activation.base_clip().movie();
+    match movie_domain(activation) {
+        Some(domain) => Ok(AvmString::new_utf8(activation.context.gc_context, domain).into()),
+        None => Ok(Value::Null),
+    }
+}
+
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = match args.get(0) {
+        Some(Value::String(name)) if !name.is_empty() => name.to_utf8_lossy().into_owned(),
+        _ => return Ok(false.into()),
+    };
+    let Some(domain) = movie_domain(activation) else {
+        return Ok(false.into());
+    };
+
+    let UpdateContext {
+        local_connections,
+        local_connection,
+        ..
+    } = &mut activation.context;
+
+    let connected = local_connections.connect(
+        *local_connection,
+        LocalConnectionKind::Avm1(this),
+        &domain,
+        &name,
+    );
+    Ok(connected.into())
+}
+
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (name, method_name) = match (args.get(0), args.get(1)) {
+        (Some(Value::String(name)), Some(Value::String(method_name)))
+            if !name.is_empty() && !method_name.is_empty() =>
+        {
+            (
+                name.to_utf8_lossy().into_owned(),
+                method_name.to_utf8_lossy().into_owned(),
+            )
+        }
+        _ => return Ok(false.into()),
+    };
+    let Some(domain) = movie_domain(activation) else {
+        return Ok(false.into());
+    };
+
+    let arguments = serialize_arguments(activation, &args[2..]);
+    if arguments.len() > MAX_ARGUMENTS_SIZE {
+        return Ok(false.into());
+    }
 
-    let domain = if let Ok(url) = url::Url::parse(movie.url()) {
-        if url.scheme() == "file" {
-            "localhost".into()
-        } else if let Some(domain) = url.domain() {
-            AvmString::new_utf8(activation.context.gc_context, domain)
-        } else {
-            // no domain?
-            "localhost".into()
+    let UpdateContext {
+        local_connections,
+        local_connection,
+        ..
+    } = &mut activation.context;
+
+    local_connections.send(
+        *local_connection,
+        LocalConnectionKind::Avm1(this),
+        &domain,
+        &name,
+        method_name,
+        arguments,
+    );
+    Ok(true.into())
+}
+
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let UpdateContext {
+        local_connections,
+        local_connection,
+        ..
+    } = &mut activation.context;
+
+    local_connections.close(*local_connection, LocalConnectionKind::Avm1(this));
+    Ok(Value::Undefined)
+}
+
+/// Encodes the arguments of a message as an AMF0 LSO, with one element per argument.
+fn serialize_arguments<'gc>(activation: &mut Activation<'_, 'gc>, args: &[Value<'gc>]) -> Vec<u8> {
+    let mut writer = Amf0Writer::default();
+    for (index, &arg) in args.iter().enumerate() {
+        let name = Cow::Owned(index.to_string());
+        match arg {
+            // Functions can't be sent, but still take up their place in the arguments.
+            Value::Object(o) if o.as_executable().is_some() => writer.undefined(name.as_ref()),
+            _ => serialize_value(activation, name, arg, &mut writer),
         }
-    } else {
-        tracing::error!("LocalConnection::domain: Unable to parse movie URL");
-        return Ok(Value::Null);
+    }
+    let mut lso = writer.commit_lso("");
+    flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default()
+}
+
+/// Decodes the arguments of a message sent by an AVM1 or AVM2 movie.
+pub fn deserialize_arguments<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    bytes: &[u8],
+) -> Vec<Value<'gc>> {
+    let mut reader = flash_lso::read::Reader::default();
+    let Ok(lso) = reader.parse(bytes) else {
+        tracing::warn!("LocalConnection: Received arguments that couldn't be decoded");
+        return vec![];
     };
 
-    Ok(Value::String(domain))
+    let mut reference_cache = BTreeMap::default();
+    lso.body
+        .iter()
+        .map(|element| {
+            deserialize_value(
+                activation,
+                element.value(),
+                &reader.amf0_decoder,
+                &mut reference_cache,
+            )
+        })
+        .collect()
 }
 
 pub fn constructor<'gc>(
@@ -22,8 +22,13 @@ pub fn constructor<'gc>(
 const PROTO_DECLS: &[Declaration] = declare_properties! {
     "bytesLoaded" => property(get_bytes_loaded);
     "bytesTotal" => property(get_bytes_total);
+    "time" => property(get_time);
+    "bufferLength" => property(get_buffer_length);
+    "bufferTime" => property(get_buffer_time);
     "play" => method(play; DONT_ENUM | DONT_DELETE);
     "pause" => method(pause; DONT_ENUM | DONT_DELETE);
+    "seek" => method(seek; DONT_ENUM | DONT_DELETE);
+    "setBufferTime" => method(set_buffer_time; DONT_ENUM | DONT_DELETE);
 };
 
 fn get_bytes_loaded<'gc>(
@@ -50,6 +55,42 @@ fn get_bytes_total<'gc>(
     Ok(Value::Undefined)
 }
 
+fn get_time<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::NetStream(ns) = this.native() {
+        return Ok(ns.time().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn get_buffer_length<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::NetStream(ns) = this.native() {
+        return Ok(ns.buffer_length().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn get_buffer_time<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::NetStream(ns) = this.native() {
+        return Ok(ns.buffer_time().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
 fn play<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -89,6 +130,42 @@ fn pause<'gc>(
     Ok(Value::Undefined)
 }
 
+fn seek<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::NetStream(ns) = this.native() {
+        let offset = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_f64(activation)?;
+
+        ns.seek(&mut activation.context, offset);
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn set_buffer_time<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let NativeObject::NetStream(ns) = this.native() {
+        let buffer_time = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_f64(activation)?;
+
+        ns.set_buffer_time(activation.context.gc_context, buffer_time);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn create_proto<'gc>(
     context: &mut GcContext<'_, 'gc>,
     proto: Object<'gc>,
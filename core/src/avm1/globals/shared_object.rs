@@ -78,59 +78,67 @@ fn recursive_serialize<'gc>(
     // Reversed to match flash player ordering
     for element_name in obj.get_keys(activation, false).into_iter().rev() {
         if let Ok(elem) = obj.get(element_name, activation) {
-            let name = element_name.to_utf8_lossy();
-
-            match elem {
-                Value::Object(o) => {
-                    if o.as_executable().is_some() {
-                    } else if o.as_display_object().is_some() {
-                        writer.undefined(name.as_ref())
-                    } else if o.as_array_object().is_some() {
-                        let (aw, token) = writer.array(CacheKey::from_ptr(o.as_ptr()));
-
-                        if let Some(mut aw) = aw {
-                            recursive_serialize(activation, o, &mut aw);
-
-                            // TODO: What happens if an exception is thrown here?
-                            let length = o
-                                .length(activation)
-                                .expect("Failed to get length for SharedObject array");
-
-                            aw.commit(name, length as u32);
-                        } else {
-                            writer.reference(name.as_ref(), token);
-                        }
-                    } else if let Some(xml_node) = o.as_xml_node() {
-                        // TODO: What happens if an exception is thrown here?
-                        let string = xml_node
-                            .into_string(activation)
-                            .expect("Failed to convert xml to string in SharedObject");
-                        writer.xml(name.as_ref(), string.to_utf8_lossy().as_ref(), true)
-                    } else if let NativeObject::Date(date) = o.native() {
-                        writer.date(name.as_ref(), date.get().time(), None)
-                    } else {
-                        let (ow, token) = writer.object(CacheKey::from_ptr(o.as_ptr()));
-
-                        if let Some(mut ow) = ow {
-                            recursive_serialize(activation, o, &mut ow);
-                            ow.commit(name);
-                        } else {
-                            writer.reference(name.as_ref(), token);
-                        }
-                    }
+            serialize_value(activation, element_name.to_utf8_lossy(), elem, writer);
+        }
+    }
+}
+
+/// Serialize a Value, and any children, as an element with the given name
+pub(crate) fn serialize_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    name: Cow<'_, str>,
+    elem: Value<'gc>,
+    writer: &mut dyn ObjWriter<'_>,
+) {
+    match elem {
+        Value::Object(o) => {
+            if o.as_executable().is_some() {
+            } else if o.as_display_object().is_some() {
+                writer.undefined(name.as_ref())
+            } else if o.as_array_object().is_some() {
+                let (aw, token) = writer.array(CacheKey::from_ptr(o.as_ptr()));
+
+                if let Some(mut aw) = aw {
+                    recursive_serialize(activation, o, &mut aw);
+
+                    // TODO: What happens if an exception is thrown here?
+                    let length = o
+                        .length(activation)
+                        .expect("Failed to get length for SharedObject array");
+
+                    aw.commit(name, length as u32);
+                } else {
+                    writer.reference(name.as_ref(), token);
+                }
+            } else if let Some(xml_node) = o.as_xml_node() {
+                // TODO: What happens if an exception is thrown here?
+                let string = xml_node
+                    .into_string(activation)
+                    .expect("Failed to convert xml to string in SharedObject");
+                writer.xml(name.as_ref(), string.to_utf8_lossy().as_ref(), true)
+            } else if let NativeObject::Date(date) = o.native() {
+                writer.date(name.as_ref(), date.get().time(), None)
+            } else {
+                let (ow, token) = writer.object(CacheKey::from_ptr(o.as_ptr()));
+
+                if let Some(mut ow) = ow {
+                    recursive_serialize(activation, o, &mut ow);
+                    ow.commit(name);
+                } else {
+                    writer.reference(name.as_ref(), token);
                 }
-                Value::Number(f) => writer.number(name.as_ref(), f),
-                Value::String(s) => writer.string(name.as_ref(), s.to_utf8_lossy().as_ref()),
-                Value::Undefined | Value::MovieClip(_) => writer.undefined(name.as_ref()),
-                Value::Null => writer.null(name.as_ref()),
-                Value::Bool(b) => writer.bool(name.as_ref(), b),
             }
         }
+        Value::Number(f) => writer.number(name.as_ref(), f),
+        Value::String(s) => writer.string(name.as_ref(), s.to_utf8_lossy().as_ref()),
+        Value::Undefined | Value::MovieClip(_) => writer.undefined(name.as_ref()),
+        Value::Null => writer.null(name.as_ref()),
+        Value::Bool(b) => writer.bool(name.as_ref(), b),
     }
 }
 
 /// Deserialize a AmfValue to a Value
-fn deserialize_value<'gc>(
+pub(crate) fn deserialize_value<'gc>(
     activation: &mut Activation<'_, 'gc>,
     val: &AmfValue,
     lso: &AMF0Decoder,
@@ -140,6 +148,8 @@ fn deserialize_value<'gc>(
         AmfValue::Null => Value::Null,
         AmfValue::Undefined => Value::Undefined,
         AmfValue::Number(f) => (*f).into(),
+        // Only sent by AVM2 movies, through `LocalConnection`.
+        AmfValue::Integer(num) => (*num).into(),
         AmfValue::String(s) => Value::String(AvmString::new_utf8(activation.context.gc_context, s)),
         AmfValue::Bool(b) => (*b).into(),
         AmfValue::ECMAArray(_, associative, len) => {
@@ -9,6 +9,7 @@ use crate::avm1_stub;
 use crate::context::{GcContext, UpdateContext};
 use bitflags::bitflags;
 use core::fmt;
+use serde::Deserialize;
 
 const OBJECT_DECLS: &[Declaration] = declare_properties! {
     "exactSettings" => property(get_exact_settings, set_exact_settings);
@@ -41,6 +42,8 @@ impl fmt::Display for CpuArchitecture {
 
 /// Available type of sandbox for a given SWF
 #[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub enum SandboxType {
     Remote,
     LocalWithFile,
@@ -17,30 +17,64 @@ const OBJECT_DECLS: &[Declaration] = declare_properties! {
     "chooseLocalSwfPath" => property(get_choose_local_swf_path);
 };
 
+/// Lets the movies from `domains` access the movie of the calling code.
+fn allow_domains<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+    insecure: bool,
+) -> Result<(), Error<'gc>> {
+    let domains = args
+        .iter()
+        .map(|domain| {
+            domain
+                .coerce_to_string(activation)
+                .map(|domain| domain.to_utf8_lossy().into_owned())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let movie = activation.base_clip().movie();
+    activation
+        .context
+        .security
+        .allow_domain(movie.url(), &domains, insecure);
+    Ok(())
+}
+
 fn allow_domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "System.security", "allowDomain");
+    allow_domains(activation, args, false)?;
     Ok(Value::Undefined)
 }
 
 fn allow_insecure_domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "System.security", "allowInsecureDomain");
+    allow_domains(activation, args, true)?;
     Ok(Value::Undefined)
 }
 
 fn load_policy_file<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "System.security", "loadPolicyFile");
+    let url = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?
+        .to_utf8_lossy()
+        .into_owned();
+    match activation.context.navigator.resolve_url(&url) {
+        Ok(url) => activation
+            .context
+            .security
+            .load_policy_file(url.to_string()),
+        Err(e) => tracing::warn!("System.security.loadPolicyFile: Invalid URL {}: {}", url, e),
+    }
     Ok(Value::Undefined)
 }
 
@@ -3,9 +3,9 @@ use crate::avm1::object::{NativeObject, Object};
 use crate::avm1::property_decl::define_properties_on;
 use crate::avm1::{property_decl::Declaration, ScriptObject};
 use crate::avm1::{Activation, Error, Executable, ExecutionReason, TObject, Value};
-use crate::context::{GcContext, UpdateContext};
+use crate::context::GcContext;
 use crate::display_object::TDisplayObject;
-use crate::socket::SocketHandle;
+use crate::socket::{SocketHandle, Sockets};
 use crate::string::AvmString;
 use gc_arena::{Collect, Gc};
 use std::cell::{Cell, RefCell, RefMut};
@@ -140,11 +140,14 @@ pub fn connect<'gc>(
             .unwrap_or(&Value::Undefined)
             .coerce_to_u16(activation)?;
 
-        let UpdateContext {
-            sockets, navigator, ..
-        } = &mut activation.context;
-
-        sockets.connect_avm1(*navigator, this, host.to_utf8_lossy().into_owned(), port);
+        let requester_url = activation.base_clip().movie().url().to_string();
+        Sockets::connect_avm1(
+            &mut activation.context,
+            requester_url,
+            this,
+            host.to_utf8_lossy().into_owned(),
+            port,
+        );
 
         // NOTE: At this point we do not know if the connection will succeed
         //       because connecting is an asynchronous process, so we just return true.
@@ -106,6 +106,8 @@ pub struct SystemClasses<'gc> {
     pub transform: ClassObject<'gc>,
     pub colortransform: ClassObject<'gc>,
     pub matrix: ClassObject<'gc>,
+    pub matrix3d: ClassObject<'gc>,
+    pub perspectiveprojection: ClassObject<'gc>,
     pub illegaloperationerror: ClassObject<'gc>,
     pub eventdispatcher: ClassObject<'gc>,
     pub rectangle: ClassObject<'gc>,
@@ -132,6 +134,7 @@ pub struct SystemClasses<'gc> {
     pub vertexbuffer3d: ClassObject<'gc>,
     pub program3d: ClassObject<'gc>,
     pub urlvariables: ClassObject<'gc>,
+    pub urlrequestheader: ClassObject<'gc>,
     pub bevelfilter: ClassObject<'gc>,
     pub bitmapfilter: ClassObject<'gc>,
     pub blurfilter: ClassObject<'gc>,
@@ -145,14 +148,18 @@ pub struct SystemClasses<'gc> {
     pub texture: ClassObject<'gc>,
     pub cubetexture: ClassObject<'gc>,
     pub rectangletexture: ClassObject<'gc>,
+    pub videotexture: ClassObject<'gc>,
     pub morphshape: ClassObject<'gc>,
     pub shader: ClassObject<'gc>,
     pub shaderinput: ClassObject<'gc>,
     pub shaderparameter: ClassObject<'gc>,
     pub netstatusevent: ClassObject<'gc>,
+    pub sampledataevent: ClassObject<'gc>,
     pub shaderfilter: ClassObject<'gc>,
     pub statusevent: ClassObject<'gc>,
     pub contextmenuevent: ClassObject<'gc>,
+    pub dataevent: ClassObject<'gc>,
+    pub filereference: ClassObject<'gc>,
 }
 
 impl<'gc> SystemClasses<'gc> {
@@ -231,6 +238,8 @@ impl<'gc> SystemClasses<'gc> {
             transform: object,
             colortransform: object,
             matrix: object,
+            matrix3d: object,
+            perspectiveprojection: object,
             illegaloperationerror: object,
             eventdispatcher: object,
             rectangle: object,
@@ -257,6 +266,7 @@ impl<'gc> SystemClasses<'gc> {
             vertexbuffer3d: object,
             program3d: object,
             urlvariables: object,
+            urlrequestheader: object,
             bevelfilter: object,
             bitmapfilter: object,
             blurfilter: object,
@@ -270,14 +280,18 @@ impl<'gc> SystemClasses<'gc> {
             texture: object,
             cubetexture: object,
             rectangletexture: object,
+            videotexture: object,
             morphshape: object,
             shader: object,
             shaderinput: object,
             shaderparameter: object,
             netstatusevent: object,
+            sampledataevent: object,
             shaderfilter: object,
             statusevent: object,
             contextmenuevent: object,
+            dataevent: object,
+            filereference: object,
         }
     }
 }
@@ -791,6 +805,7 @@ fn load_playerglobal<'gc>(
                 "RectangleTexture",
                 rectangletexture
             ),
+            ("flash.display3D.textures", "VideoTexture", videotexture),
             ("flash.display3D", "VertexBuffer3D", vertexbuffer3d),
             (
                 "flash.errors",
@@ -812,9 +827,13 @@ fn load_playerglobal<'gc>(
             ("flash.events", "FullScreenEvent", fullscreenevent),
             ("flash.events", "UncaughtErrorEvents", uncaughterrorevents),
             ("flash.events", "NetStatusEvent", netstatusevent),
+            ("flash.events", "SampleDataEvent", sampledataevent),
             ("flash.events", "StatusEvent", statusevent),
             ("flash.events", "ContextMenuEvent", contextmenuevent),
+            ("flash.events", "DataEvent", dataevent),
             ("flash.geom", "Matrix", matrix),
+            ("flash.geom", "Matrix3D", matrix3d),
+            ("flash.geom", "PerspectiveProjection", perspectiveprojection),
             ("flash.geom", "Point", point),
             ("flash.geom", "Rectangle", rectangle),
             ("flash.geom", "Transform", transform),
@@ -823,6 +842,8 @@ fn load_playerglobal<'gc>(
             ("flash.media", "SoundTransform", soundtransform),
             ("flash.media", "Video", video),
             ("flash.net", "URLVariables", urlvariables),
+            ("flash.net", "URLRequestHeader", urlrequestheader),
+            ("flash.net", "FileReference", filereference),
             ("flash.utils", "ByteArray", bytearray),
             ("flash.system", "ApplicationDomain", application_domain),
             ("flash.text", "StaticText", statictext),
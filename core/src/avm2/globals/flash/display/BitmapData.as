@@ -25,6 +25,8 @@ package flash.display {
 
         public native function getPixels(rect:Rectangle):ByteArray;
         public native function getVector(rect:Rectangle):Vector.<uint>;
+        public native function copyPixelsToByteArray(rect:Rectangle, data:ByteArray):void;
+        public native function histogram(hRect:Rectangle = null):Vector.<Vector.<Number>>;
         public native function getPixel(x:int, y:int):uint;
         public native function getPixel32(x:int, y:int):uint;
         public native function setPixel(x:int, y:int, color:uint):void;
@@ -70,13 +72,36 @@ package flash.display {
         public native function merge(
             sourceBitmapData:BitmapData, sourceRect:Rectangle, destPoint:Point, redMultiplier:uint, greenMultiplier:uint, blueMultiplier:uint, alphaMultiplier:uint
         ):void 
+        public function encode(rect:Rectangle, compressor:Object, byteArray:ByteArray = null):ByteArray {
+            if (compressor == null) {
+                throw new TypeError("Error #2007: Parameter compressor must be non-null.", 2007);
+            }
+            if (byteArray == null) {
+                byteArray = new ByteArray();
+            }
+            if (compressor is PNGEncoderOptions) {
+                this.encodePNG(rect, PNGEncoderOptions(compressor).fastCompression, byteArray);
+            } else if (compressor is JPEGEncoderOptions) {
+                this.encodeJPEG(rect, JPEGEncoderOptions(compressor).quality, byteArray);
+            } else if (compressor is JPEGXREncoderOptions) {
+                stub_method("flash.display.BitmapData", "encode", "with JPEGXREncoderOptions");
+            } else {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            return byteArray;
+        }
+
+        private native function encodePNG(rect:Rectangle, fastCompression:Boolean, byteArray:ByteArray):void;
+        private native function encodeJPEG(rect:Rectangle, quality:uint, byteArray:ByteArray):void;
+
         public function generateFilterRect(sourceRect:Rectangle, filter:BitmapFilter):Rectangle {
             // Flash always reports that a ShaderFilter affects the entire BitampData, ignoring SourceRect.
             if (filter is ShaderFilter) {
                 return this.rect.clone();
             }
-            stub_method("flash.display.BitmapData", "generateFilterRect");
-            return sourceRect.clone();
+            return this.generateFilterRectInternal(sourceRect, filter);
         }
+
+        private native function generateFilterRectInternal(sourceRect:Rectangle, filter:BitmapFilter):Rectangle;
     }
 }
@@ -1,7 +1,7 @@
 //! `flash.display.BitmapData` builtin/prototype
 
 use crate::avm2::activation::Activation;
-use crate::avm2::error::{argument_error, make_error_2008, range_error};
+use crate::avm2::error::{argument_error, make_error_2008, range_error, security_error};
 use crate::avm2::filters::FilterAvm2Ext;
 pub use crate::avm2::object::bitmap_data_allocator;
 use crate::avm2::object::{BitmapDataObject, ByteArrayObject, Object, TObject, VectorObject};
@@ -16,8 +16,9 @@ use crate::bitmap::bitmap_data::{
 use crate::bitmap::bitmap_data::{BitmapDataDrawError, IBitmapDrawable};
 use crate::bitmap::{is_size_valid, operations};
 use crate::character::Character;
+use crate::config::SecurityMode;
 use crate::display_object::Bitmap;
-use crate::display_object::TDisplayObject;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
 use crate::ecma_conversions::round_to_even;
 use crate::swf::BlendMode;
 use gc_arena::GcCell;
@@ -306,6 +307,136 @@ pub fn get_pixels<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `BitmapData.copyPixelsToByteArray`.
+pub fn copy_pixels_to_byte_array<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.as_bitmap_data() {
+        bitmap_data.check_valid(activation)?;
+        let rectangle = args.get_object(activation, 0, "rect")?;
+        let bytearray = args.get_object(activation, 1, "data")?;
+        let (x, y, width, height) = get_rectangle_x_y_width_height(activation, rectangle)?;
+
+        let mut ba_write = bytearray
+            .as_bytearray_mut(activation.context.gc_context)
+            .ok_or("ArgumentError: Parameter must be a bytearray")?;
+        operations::copy_pixels_to_byte_array(bitmap_data, x, y, width, height, &mut ba_write)
+            .map_err(|e| e.to_avm(activation))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.histogram`.
+pub fn histogram<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.as_bitmap_data() {
+        bitmap_data.check_valid(activation)?;
+        let (x, y, width, height) = match args.try_get_object(activation, 0) {
+            Some(rectangle) => get_rectangle_x_y_width_height(activation, rectangle)?,
+            None => (
+                0,
+                0,
+                bitmap_data.width() as i32,
+                bitmap_data.height() as i32,
+            ),
+        };
+
+        let channels = operations::histogram(bitmap_data, x, y, width, height);
+
+        let number_class = activation.avm2().classes().number;
+        let channel_class = activation
+            .avm2()
+            .classes()
+            .generic_vector
+            .apply(activation, &[number_class.into()])?;
+        let mut vectors = Vec::with_capacity(channels.len());
+        for channel in channels {
+            let storage = VectorStorage::from_values(
+                channel.iter().map(|count| (*count).into()).collect(),
+                false,
+                Some(number_class),
+            );
+            vectors.push(VectorObject::from_vector(storage, activation)?.into());
+        }
+
+        let storage = VectorStorage::from_values(vectors, false, Some(channel_class));
+        return Ok(VectorObject::from_vector(storage, activation)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.encode` with `PNGEncoderOptions`.
+pub fn encode_png<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let fast_compression = args.get_bool(1);
+    encode(
+        activation,
+        this,
+        args,
+        operations::BitmapEncoding::Png { fast_compression },
+    )
+}
+
+/// Implements `BitmapData.encode` with `JPEGEncoderOptions`.
+pub fn encode_jpeg<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let quality = args.get_u32(activation, 1)?.clamp(1, 100) as u8;
+    encode(
+        activation,
+        this,
+        args,
+        operations::BitmapEncoding::Jpeg { quality },
+    )
+}
+
+fn encode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+    encoding: operations::BitmapEncoding,
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.as_bitmap_data() {
+        bitmap_data.check_valid(activation)?;
+        let rectangle = args.get_object(activation, 0, "rect")?;
+        let bytearray = args.get_object(activation, 2, "byteArray")?;
+        let (x, y, width, height) = get_rectangle_x_y_width_height(activation, rectangle)?;
+
+        let encoded = match operations::encode(bitmap_data, x, y, width, height, encoding) {
+            Ok(encoded) => encoded,
+            Err(e) => {
+                tracing::error!("Failed to encode BitmapData: {e}");
+                return Err(Error::AvmError(argument_error(
+                    activation,
+                    "Error #2004: One of the parameters is invalid.",
+                    2004,
+                )?));
+            }
+        };
+
+        let mut ba_write = bytearray
+            .as_bytearray_mut(activation.context.gc_context)
+            .ok_or("ArgumentError: Parameter must be a bytearray")?;
+        ba_write
+            .write_bytes(&encoded)
+            .map_err(|e| e.to_avm(activation))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_vector<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -805,6 +936,41 @@ pub fn hit_test<'gc>(
     Ok(false.into())
 }
 
+/// Checks that the calling movie can read the pixels of `source`, and of everything inside it.
+fn check_draw_access<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    source: DisplayObject<'gc>,
+    method: &str,
+) -> Result<(), Error<'gc>> {
+    if activation.context.security.mode() == SecurityMode::Permissive {
+        return Ok(());
+    }
+    let Some(caller) = activation.caller_movie() else {
+        return Ok(());
+    };
+
+    let mut pending = vec![source];
+    while let Some(object) = pending.pop() {
+        let movie = object.movie();
+        if !activation
+            .context
+            .security
+            .can_access(caller.url(), movie.url())
+        {
+            let message = format!(
+                "Error #2122: Security sandbox violation: {method}: {} cannot access {}. A policy file is required, but the checkPolicyFile flag was not set when this media was loaded.",
+                caller.url(),
+                movie.url()
+            );
+            return Err(Error::AvmError(security_error(activation, &message, 2122)?));
+        }
+        if let Some(container) = object.as_container() {
+            pending.extend(container.iter_render_list());
+        }
+    }
+    Ok(())
+}
+
 /// Implements `BitmapData.draw`
 pub fn draw<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -851,6 +1017,7 @@ pub fn draw<'gc>(
         let source = args.get_object(activation, 0, "source")?;
 
         let source = if let Some(source_object) = source.as_display_object() {
+            check_draw_access(activation, source_object, "BitmapData.draw")?;
             IBitmapDrawable::DisplayObject(source_object)
         } else if let Some(source_bitmap) = source.as_bitmap_data() {
             IBitmapDrawable::BitmapData(source_bitmap)
@@ -930,6 +1097,7 @@ pub fn draw_with_quality<'gc>(
         let source = args.get_object(activation, 0, "source")?;
 
         let source = if let Some(source_object) = source.as_display_object() {
+            check_draw_access(activation, source_object, "BitmapData.drawWithQuality")?;
             IBitmapDrawable::DisplayObject(source_object)
         } else if let Some(source_bitmap) = source.as_bitmap_data() {
             IBitmapDrawable::BitmapData(source_bitmap)
@@ -1052,8 +1220,16 @@ pub fn apply_filter<'gc>(
             })?;
         let source_rect = args.get_object(activation, 1, "sourceRect")?;
         let mut source_rect = super::display_object::object_to_rectangle(activation, source_rect)?;
-        let filter = args.get_object(activation, 3, "filter")?;
-        let filter = Filter::from_avm2_object(activation, filter)?;
+        let filter_object = args.get_object(activation, 3, "filter")?;
+        let filter = Filter::from_avm2_object(activation, filter_object)?;
+        let displacement_map = if matches!(filter, Filter::DisplacementMapFilter(_)) {
+            filter_object
+                .get_public_property("mapBitmap", activation)?
+                .as_object()
+                .and_then(|map| map.as_bitmap_data())
+        } else {
+            None
+        };
 
         if matches!(filter, Filter::ShaderFilter(_)) {
             let source_bitmap_rect = Rectangle {
@@ -1111,11 +1287,50 @@ pub fn apply_filter<'gc>(
             source_size,
             dest_point,
             filter,
+            displacement_map,
         );
     }
     Ok(Value::Undefined)
 }
 
+/// Implement `BitmapData.generateFilterRect`
+pub fn generate_filter_rect_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.as_bitmap_data() {
+        bitmap_data.check_valid(activation)?;
+        let source_rect = args.get_object(activation, 0, "sourceRect")?;
+        let source_rect = super::display_object::object_to_rectangle(activation, source_rect)?;
+        let filter = args.get_object(activation, 1, "filter")?;
+        let filter = Filter::from_avm2_object(activation, filter)?;
+
+        // Filters are applied to whole pixels, so the affected area is too.
+        let dest_rect = filter.calculate_dest_rect(source_rect);
+        let x_min = dest_rect.x_min.to_pixels().floor();
+        let y_min = dest_rect.y_min.to_pixels().floor();
+        let x_max = dest_rect.x_max.to_pixels().ceil();
+        let y_max = dest_rect.y_max.to_pixels().ceil();
+
+        return Ok(activation
+            .avm2()
+            .classes()
+            .rectangle
+            .construct(
+                activation,
+                &[
+                    x_min.into(),
+                    y_min.into(),
+                    (x_max - x_min).into(),
+                    (y_max - y_min).into(),
+                ],
+            )?
+            .into());
+    }
+    Ok(Value::Undefined)
+}
+
 /// Implement `BitmapData.clone`
 pub fn clone<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -2,17 +2,21 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::error::make_error_2008;
-use crate::avm2::globals::flash::geom::transform::object_to_matrix;
-use crate::avm2::object::{Object, TObject, VectorObject};
+use crate::avm2::globals::flash::geom::transform::{matrix_to_object, object_to_matrix};
+use crate::avm2::object::{ArrayObject, BitmapDataObject, Object, TObject, VectorObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::vector::VectorStorage;
 use crate::avm2::{ArrayStorage, Error};
 use crate::avm2_stub_method;
-use crate::display_object::TDisplayObject;
+use crate::bitmap::bitmap_data::{BitmapData, BitmapDataDrawError, BitmapDataWrapper};
+use crate::bitmap::operations;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
 use crate::drawing::Drawing;
 use crate::string::{AvmString, WStr};
-use ruffle_render::shape_utils::{DrawCommand, GradientType};
+use gc_arena::{GcCell, Mutation};
+use ruffle_render::bitmap::BitmapInfo;
+use ruffle_render::shape_utils::{DrawCommand, DrawPath, GradientType};
 use std::f64::consts::FRAC_1_SQRT_2;
 use swf::{
     Color, FillStyle, Fixed16, Fixed8, Gradient, GradientInterpolation, GradientRecord,
@@ -66,7 +70,7 @@ pub fn begin_bitmap_fill<'gc>(
         let handle =
             bitmap.bitmap_handle(activation.context.gc_context, activation.context.renderer);
 
-        let bitmap = ruffle_render::bitmap::BitmapInfo {
+        let bitmap = BitmapInfo {
             handle,
             width: bitmap.width() as u16,
             height: bitmap.height() as u16,
@@ -666,6 +670,94 @@ fn draw_round_rect_internal(
     });
 }
 
+/// Draw a roundrect with a separate radius for each corner.
+///
+/// Radii are given in top-left, top-right, bottom-left, bottom-right order,
+/// and are clamped to half of the shorter side of the rectangle.
+fn draw_round_rect_complex_internal(
+    draw: &mut Drawing,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+    radii: [f64; 4],
+) {
+    let max_radius = (width.min(height) / 2.0).max(0.0);
+    let [tl_radius, tr_radius, bl_radius, br_radius] =
+        radii.map(|radius| radius.clamp(0.0, max_radius));
+
+    // Reflects the unit circle quadrant into one of the corners of the rectangle,
+    // scaled to that corner's radius.
+    let ucp = UNIT_CIRCLE_POINTS;
+    let corner = |radius: f64, right: bool, bottom: bool, i: usize| {
+        let (center_x, sign_x) = if right {
+            (x + width - radius, 1.0)
+        } else {
+            (x + radius, -1.0)
+        };
+        let (center_y, sign_y) = if bottom {
+            (y + height - radius, 1.0)
+        } else {
+            (y + radius, -1.0)
+        };
+        Point::from_pixels(
+            center_x + sign_x * radius * ucp[i].0,
+            center_y + sign_y * radius * ucp[i].1,
+        )
+    };
+    let br = |i| corner(br_radius, true, true, i);
+    let bl = |i| corner(bl_radius, false, true, i);
+    let tl = |i| corner(tl_radius, false, false, i);
+    let tr = |i| corner(tr_radius, true, false, i);
+
+    // Like `draw_round_rect_internal`, start from the middle of the bottom-right corner.
+    draw.draw_command(DrawCommand::MoveTo(br(2)));
+    draw.draw_command(DrawCommand::QuadraticCurveTo {
+        control: br(3),
+        anchor: br(4),
+    });
+
+    // Bottom side & bottom-left corner
+    draw.draw_command(DrawCommand::LineTo(bl(4)));
+    draw.draw_command(DrawCommand::QuadraticCurveTo {
+        control: bl(3),
+        anchor: bl(2),
+    });
+    draw.draw_command(DrawCommand::QuadraticCurveTo {
+        control: bl(1),
+        anchor: bl(0),
+    });
+
+    // Left side & top-left corner
+    draw.draw_command(DrawCommand::LineTo(tl(0)));
+    draw.draw_command(DrawCommand::QuadraticCurveTo {
+        control: tl(1),
+        anchor: tl(2),
+    });
+    draw.draw_command(DrawCommand::QuadraticCurveTo {
+        control: tl(3),
+        anchor: tl(4),
+    });
+
+    // Top side & top-right corner
+    draw.draw_command(DrawCommand::LineTo(tr(4)));
+    draw.draw_command(DrawCommand::QuadraticCurveTo {
+        control: tr(3),
+        anchor: tr(2),
+    });
+    draw.draw_command(DrawCommand::QuadraticCurveTo {
+        control: tr(1),
+        anchor: tr(0),
+    });
+
+    // Right side & other half of bottom-right corner
+    draw.draw_command(DrawCommand::LineTo(br(0)));
+    draw.draw_command(DrawCommand::QuadraticCurveTo {
+        control: br(1),
+        anchor: br(2),
+    });
+}
+
 /// Implements `Graphics.drawRoundRect`.
 pub fn draw_round_rect<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -891,10 +983,36 @@ pub fn draw_path<'gc>(
 /// Implements `Graphics.drawRoundRectComplex`
 pub fn draw_round_rect_complex<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.display.Graphics", "drawRoundRectComplex");
+    if let Some(this) = this.as_display_object() {
+        let x = args.get_f64(activation, 0)?;
+        let y = args.get_f64(activation, 1)?;
+        let width = args.get_f64(activation, 2)?;
+        let height = args.get_f64(activation, 3)?;
+        let top_left_radius = args.get_f64(activation, 4)?;
+        let top_right_radius = args.get_f64(activation, 5)?;
+        let bottom_left_radius = args.get_f64(activation, 6)?;
+        let bottom_right_radius = args.get_f64(activation, 7)?;
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            draw_round_rect_complex_internal(
+                &mut draw,
+                x,
+                y,
+                width,
+                height,
+                [
+                    top_left_radius,
+                    top_right_radius,
+                    bottom_left_radius,
+                    bottom_right_radius,
+                ],
+            );
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -1111,7 +1229,7 @@ pub fn line_bitmap_style<'gc>(
         let handle =
             bitmap.bitmap_handle(activation.context.gc_context, activation.context.renderer);
 
-        let bitmap = ruffle_render::bitmap::BitmapInfo {
+        let bitmap = BitmapInfo {
             handle,
             width: bitmap.width() as u16,
             height: bitmap.height() as u16,
@@ -1138,13 +1256,357 @@ pub fn line_bitmap_style<'gc>(
 /// Implements `Graphics.readGraphicsData`
 pub fn read_graphics_data<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.display.Graphics", "readGraphicsData");
+    let recurse = args.get_bool(0);
+    let mut graphics_data = Vec::new();
+
+    if let Some(this) = this.as_display_object() {
+        let mut paths = Vec::new();
+        record_paths(
+            activation.context.gc_context,
+            this,
+            Default::default(),
+            recurse,
+            &mut paths,
+        );
+
+        for (matrix, path) in paths {
+            match path {
+                RecordedPath::Fill {
+                    style,
+                    bitmap,
+                    commands,
+                } => {
+                    graphics_data.push(fill_style_to_object(activation, &style, bitmap, matrix)?);
+                    graphics_data.push(commands_to_object(activation, &commands, matrix)?);
+                    let end_fill = activation
+                        .avm2()
+                        .classes()
+                        .graphicsendfill
+                        .construct(activation, &[])?;
+                    graphics_data.push(end_fill.into());
+                }
+                RecordedPath::Stroke {
+                    style,
+                    bitmap,
+                    commands,
+                } => {
+                    graphics_data.push(line_style_to_object(activation, &style, bitmap, matrix)?);
+                    graphics_data.push(commands_to_object(activation, &commands, matrix)?);
+                    // A stroke without a thickness ends the line, like `lineStyle()` does.
+                    let end_stroke = activation
+                        .avm2()
+                        .classes()
+                        .graphicsstroke
+                        .construct(activation, &[f64::NAN.into()])?;
+                    graphics_data.push(end_stroke.into());
+                }
+            }
+        }
+    }
+
     let value_type = activation.avm2().classes().igraphicsdata;
-    let new_storage = VectorStorage::new(0, false, Some(value_type), activation);
-    Ok(VectorObject::from_vector(new_storage, activation)?.into())
+    let storage = VectorStorage::from_values(graphics_data, false, Some(value_type));
+    Ok(VectorObject::from_vector(storage, activation)?.into())
+}
+
+/// A fill or stroke copied out of a `Drawing`, so that it can be converted
+/// into `IGraphicsData` objects without holding a borrow of the drawing.
+enum RecordedPath {
+    Fill {
+        style: FillStyle,
+        bitmap: Option<BitmapInfo>,
+        commands: Vec<DrawCommand>,
+    },
+    Stroke {
+        style: LineStyle,
+        bitmap: Option<BitmapInfo>,
+        commands: Vec<DrawCommand>,
+    },
+}
+
+/// Collects the paths drawn on `object`, and optionally all of its descendants.
+///
+/// Each path is paired with the matrix that transforms it into the coordinate
+/// space of the object that `readGraphicsData` was called on.
+fn record_paths<'gc>(
+    mc: &Mutation<'gc>,
+    object: DisplayObject<'gc>,
+    matrix: ruffle_render::matrix::Matrix,
+    recurse: bool,
+    paths: &mut Vec<(ruffle_render::matrix::Matrix, RecordedPath)>,
+) {
+    if let Some(drawing) = object.as_drawing(mc) {
+        let bitmap = |style: &FillStyle| match style {
+            FillStyle::Bitmap { id, .. } => drawing.bitmap(*id).cloned(),
+            _ => None,
+        };
+
+        for path in drawing.draw_paths() {
+            let path = match path {
+                DrawPath::Fill {
+                    style, commands, ..
+                } => RecordedPath::Fill {
+                    style: style.clone(),
+                    bitmap: bitmap(style),
+                    commands,
+                },
+                DrawPath::Stroke {
+                    style, commands, ..
+                } => RecordedPath::Stroke {
+                    style: style.clone(),
+                    bitmap: bitmap(style.fill_style()),
+                    commands,
+                },
+            };
+            paths.push((matrix, path));
+        }
+    }
+
+    if recurse {
+        if let Some(container) = object.as_container() {
+            for child in container.iter_render_list() {
+                let child_matrix = matrix * *child.base().matrix();
+                record_paths(mc, child, child_matrix, recurse, paths);
+            }
+        }
+    }
+}
+
+fn fill_style_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style: &FillStyle,
+    bitmap: Option<BitmapInfo>,
+    matrix: ruffle_render::matrix::Matrix,
+) -> Result<Value<'gc>, Error<'gc>> {
+    match style {
+        FillStyle::Color(color) => {
+            let args = [color.to_rgb().into(), (color.a as f64 / 255.0).into()];
+            let fill = activation
+                .avm2()
+                .classes()
+                .graphicssolidfill
+                .construct(activation, &args)?;
+            Ok(fill.into())
+        }
+        FillStyle::LinearGradient(gradient) => {
+            gradient_to_object(activation, "linear", gradient, 0.0, matrix)
+        }
+        FillStyle::RadialGradient(gradient) => {
+            gradient_to_object(activation, "radial", gradient, 0.0, matrix)
+        }
+        FillStyle::FocalGradient {
+            gradient,
+            focal_point,
+        } => gradient_to_object(activation, "radial", gradient, focal_point.to_f64(), matrix),
+        FillStyle::Bitmap {
+            matrix: bitmap_matrix,
+            is_smoothed,
+            is_repeating,
+            ..
+        } => {
+            let bitmap_data = match bitmap {
+                Some(bitmap) => bitmap_info_to_object(activation, bitmap)?,
+                None => Value::Null,
+            };
+            // Bitmap fill matrices are stored in twips, see `beginBitmapFill`.
+            let pixels_per_twip = 1.0 / Twips::TWIPS_PER_PIXEL as f32;
+            let bitmap_matrix = matrix
+                * ruffle_render::matrix::Matrix::from(*bitmap_matrix)
+                * ruffle_render::matrix::Matrix::scale(pixels_per_twip, pixels_per_twip);
+            let args = [
+                bitmap_data,
+                matrix_to_object(bitmap_matrix, activation)?,
+                (*is_repeating).into(),
+                (*is_smoothed).into(),
+            ];
+            let fill = activation
+                .avm2()
+                .classes()
+                .graphicsbitmapfill
+                .construct(activation, &args)?;
+            Ok(fill.into())
+        }
+    }
+}
+
+fn gradient_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    gradient_type: &'static str,
+    gradient: &Gradient,
+    focal_point: f64,
+    matrix: ruffle_render::matrix::Matrix,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let colors: Vec<Value<'gc>> = gradient
+        .records
+        .iter()
+        .map(|record| record.color.to_rgb().into())
+        .collect();
+    let alphas: Vec<Value<'gc>> = gradient
+        .records
+        .iter()
+        .map(|record| (record.color.a as f64 / 255.0).into())
+        .collect();
+    let ratios: Vec<Value<'gc>> = gradient
+        .records
+        .iter()
+        .map(|record| record.ratio.into())
+        .collect();
+
+    let spread_method = match gradient.spread {
+        GradientSpread::Pad => "pad",
+        GradientSpread::Reflect => "reflect",
+        GradientSpread::Repeat => "repeat",
+    };
+    let interpolation_method = match gradient.interpolation {
+        GradientInterpolation::Rgb => "rgb",
+        GradientInterpolation::LinearRgb => "linearRGB",
+    };
+    let gradient_matrix = matrix * ruffle_render::matrix::Matrix::from(gradient.matrix);
+
+    let args = [
+        gradient_type.into(),
+        ArrayObject::from_storage(activation, ArrayStorage::from_args(&colors))?.into(),
+        ArrayObject::from_storage(activation, ArrayStorage::from_args(&alphas))?.into(),
+        ArrayObject::from_storage(activation, ArrayStorage::from_args(&ratios))?.into(),
+        matrix_to_object(gradient_matrix, activation)?,
+        spread_method.into(),
+        interpolation_method.into(),
+        focal_point.into(),
+    ];
+    let fill = activation
+        .avm2()
+        .classes()
+        .graphicsgradientfill
+        .construct(activation, &args)?;
+    Ok(fill.into())
+}
+
+/// Copies the pixels of a bitmap fill into a new `BitmapData`.
+fn bitmap_info_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    bitmap: BitmapInfo,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let bitmap_data = BitmapDataWrapper::new(GcCell::new(
+        activation.context.gc_context,
+        BitmapData::new(bitmap.width.into(), bitmap.height.into(), true, 0),
+    ));
+
+    match operations::copy_from_bitmap_handle(&mut activation.context, bitmap_data, bitmap.handle) {
+        Ok(()) => {}
+        Err(BitmapDataDrawError::Unimplemented) => {
+            // Hand back a blank bitmap of the right size rather than failing the whole call.
+            tracing::warn!(
+                "Render backend can't read back bitmap fills for Graphics.readGraphicsData"
+            );
+        }
+    }
+
+    let class = activation.avm2().classes().bitmapdata;
+    Ok(BitmapDataObject::from_bitmap_data_internal(activation, bitmap_data, class)?.into())
+}
+
+fn line_style_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style: &LineStyle,
+    bitmap: Option<BitmapInfo>,
+    matrix: ruffle_render::matrix::Matrix,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let scale_mode = match (style.allow_scale_x(), style.allow_scale_y()) {
+        (true, true) => "normal",
+        (true, false) => "horizontal",
+        (false, true) => "vertical",
+        (false, false) => "none",
+    };
+    let caps = match style.start_cap() {
+        LineCapStyle::None => "none",
+        LineCapStyle::Round => "round",
+        LineCapStyle::Square => "square",
+    };
+    let (joints, miter_limit) = match style.join_style() {
+        LineJoinStyle::Round => ("round", 3.0),
+        LineJoinStyle::Bevel => ("bevel", 3.0),
+        LineJoinStyle::Miter(miter_limit) => ("miter", miter_limit.to_f64()),
+    };
+    let fill = fill_style_to_object(activation, style.fill_style(), bitmap, matrix)?;
+
+    let args = [
+        style.width().to_pixels().into(),
+        style.is_pixel_hinted().into(),
+        scale_mode.into(),
+        caps.into(),
+        joints.into(),
+        miter_limit.into(),
+        fill,
+    ];
+    let stroke = activation
+        .avm2()
+        .classes()
+        .graphicsstroke
+        .construct(activation, &args)?;
+    Ok(stroke.into())
+}
+
+/// Converts recorded draw commands into a `GraphicsPath`, using the same
+/// command values that `process_commands` reads.
+fn commands_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    commands: &[DrawCommand],
+    matrix: ruffle_render::matrix::Matrix,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut command_values: Vec<Value<'gc>> = Vec::with_capacity(commands.len());
+    let mut data_values: Vec<Value<'gc>> = Vec::with_capacity(commands.len() * 2);
+    let mut push_point = |point: Point<Twips>| {
+        let point = matrix * point;
+        data_values.push(point.x.to_pixels().into());
+        data_values.push(point.y.to_pixels().into());
+    };
+
+    for command in commands {
+        match command {
+            DrawCommand::MoveTo(point) => {
+                command_values.push(1.into());
+                push_point(*point);
+            }
+            DrawCommand::LineTo(point) => {
+                command_values.push(2.into());
+                push_point(*point);
+            }
+            DrawCommand::QuadraticCurveTo { control, anchor } => {
+                command_values.push(3.into());
+                push_point(*control);
+                push_point(*anchor);
+            }
+            DrawCommand::CubicCurveTo {
+                control_a,
+                control_b,
+                anchor,
+            } => {
+                command_values.push(6.into());
+                push_point(*control_a);
+                push_point(*control_b);
+                push_point(*anchor);
+            }
+        }
+    }
+
+    let int_class = activation.avm2().classes().int;
+    let commands = VectorStorage::from_values(command_values, false, Some(int_class));
+    let number_class = activation.avm2().classes().number;
+    let data = VectorStorage::from_values(data_values, false, Some(number_class));
+
+    let args = [
+        VectorObject::from_vector(commands, activation)?.into(),
+        VectorObject::from_vector(data, activation)?.into(),
+    ];
+    let path = activation
+        .avm2()
+        .classes()
+        .graphicspath
+        .construct(activation, &args)?;
+    Ok(path.into())
 }
 
 fn process_commands<'gc>(
@@ -1618,7 +2080,7 @@ fn handle_bitmap_fill<'gc>(
     let handle =
         bitmap_data.bitmap_handle(activation.context.gc_context, activation.context.renderer);
 
-    let bitmap = ruffle_render::bitmap::BitmapInfo {
+    let bitmap = BitmapInfo {
         handle,
         width: bitmap_data.width() as u16,
         height: bitmap_data.height() as u16,
@@ -2,12 +2,13 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::bytearray::Endian;
-use crate::avm2::error::error;
+use crate::avm2::error::{error, security_error};
 use crate::avm2::object::{DomainObject, LoaderStream, Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::{AvmString, Error};
 use crate::avm2_stub_getter;
 use crate::display_object::TDisplayObject;
+use crate::security::SecurityDomain;
 use swf::{write_swf, Compression};
 
 pub use crate::avm2::object::loader_info_allocator;
@@ -117,6 +118,13 @@ pub fn get_bytes_loaded<'gc>(
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bytes_downloaded) = this
+        .as_loader_info_object()
+        .and_then(|o| o.bytes_downloaded())
+    {
+        return Ok(bytes_downloaded.into());
+    }
+
     if let Some(loader_stream) = this
         .as_loader_info_object()
         .and_then(|o| o.as_loader_stream())
@@ -138,7 +146,7 @@ pub fn get_bytes_loaded<'gc>(
 
 /// `content` getter
 pub fn get_content<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
@@ -147,7 +155,21 @@ pub fn get_content<'gc>(
         .and_then(|o| o.as_loader_stream())
     {
         match &*loader_stream {
-            LoaderStream::Swf(_, root) | LoaderStream::NotYetLoaded(_, Some(root), _) => {
+            LoaderStream::Swf(movie, root) | LoaderStream::NotYetLoaded(movie, Some(root), _) => {
+                if let Some(caller) = activation.caller_movie() {
+                    if !activation
+                        .context
+                        .security
+                        .can_access(caller.url(), movie.url())
+                    {
+                        let message = format!(
+                            "Error #2121: Security sandbox violation: LoaderInfo.content: {} cannot access {}. This may be worked around by calling Security.allowDomain.",
+                            caller.url(),
+                            movie.url()
+                        );
+                        return Err(Error::AvmError(security_error(activation, &message, 2121)?));
+                    }
+                }
                 return Ok(root.object2());
             }
             _ => {
@@ -250,9 +272,11 @@ pub fn get_same_domain<'gc>(
             LoaderStream::NotYetLoaded(_, _, _) => {
                 return Err(Error::AvmError(error(activation, INSUFFICIENT, 2099)?));
             }
-            LoaderStream::Swf(_root, _) => {
-                avm2_stub_getter!(activation, "flash.display.LoaderInfo", "sameDomain");
-                return Ok(false.into());
+            LoaderStream::Swf(root, _) => {
+                let loader_url = root.loader_url().unwrap_or_else(|| root.url());
+                let same_domain =
+                    SecurityDomain::from_url(root.url()) == SecurityDomain::from_url(loader_url);
+                return Ok(same_domain.into());
             }
         }
     }
@@ -274,9 +298,13 @@ pub fn get_child_allows_parent<'gc>(
             LoaderStream::NotYetLoaded(_, _, _) => {
                 return Err(Error::AvmError(error(activation, INSUFFICIENT, 2099)?));
             }
-            LoaderStream::Swf(_root, _) => {
-                avm2_stub_getter!(activation, "flash.display.LoaderInfo", "childAllowsParent");
-                return Ok(false.into());
+            LoaderStream::Swf(root, _) => {
+                let loader_url = root.loader_url().unwrap_or_else(|| root.url());
+                let allowed = activation
+                    .context
+                    .security
+                    .is_domain_allowed(root.url(), loader_url);
+                return Ok(allowed.into());
             }
         }
     }
@@ -298,9 +326,13 @@ pub fn get_parent_allows_child<'gc>(
             LoaderStream::NotYetLoaded(_, _, _) => {
                 return Err(Error::AvmError(error(activation, INSUFFICIENT, 2099)?));
             }
-            LoaderStream::Swf(_root, _) => {
-                avm2_stub_getter!(activation, "flash.display.LoaderInfo", "parentAllowsChild");
-                return Ok(false.into());
+            LoaderStream::Swf(root, _) => {
+                let loader_url = root.loader_url().unwrap_or_else(|| root.url());
+                let allowed = activation
+                    .context
+                    .security
+                    .is_domain_allowed(loader_url, root.url());
+                return Ok(allowed.into());
             }
         }
     }
@@ -1,11 +1,14 @@
 package flash.display3D {
+    import flash.display.BitmapData;
     import flash.events.EventDispatcher;
     import flash.geom.Matrix3D;
+    import flash.geom.Point;
     import flash.geom.Rectangle;
     import flash.display3D.textures.CubeTexture;
     import flash.display3D.textures.TextureBase;
     import flash.display3D.textures.RectangleTexture;
     import flash.display3D.textures.Texture;
+    import flash.display3D.textures.VideoTexture;
     import __ruffle__.stub_method;
     import __ruffle__.stub_getter;
 
@@ -26,6 +29,9 @@ package flash.display3D {
         public native function createTexture(width:int, height:int, format:String, optimizeForRenderToTexture:Boolean, streamingLevels:int = 0):Texture;
         public native function createCubeTexture(size:int, format:String, optimizeForRenderToTexture:Boolean, streamingLevels:int = 0):CubeTexture;
         public native function createRectangleTexture(width:int, height:int, format:String, optimizeForRenderToTexture:Boolean):RectangleTexture;
+        public native function createVideoTexture():VideoTexture;
+
+        public native function drawToBitmapData(destination:BitmapData, srcRect:Rectangle = null, destPoint:Point = null):void;
 
         public function get driverInfo():String {
             stub_getter("flash.display3D.Context3D", "driverInfo");
@@ -62,22 +68,18 @@ package flash.display3D {
             return 2048;
         }
 
-        public function setStencilReferenceValue(referenceValue:uint, readMask:uint = 255, writeMask:uint = 255):void {
-            stub_method("flash.display3D.Context3D", "setStencilReferenceValue");
-        }
+        public native function setStencilReferenceValue(referenceValue:uint, readMask:uint = 255, writeMask:uint = 255):void;
 
         public native function setSamplerStateAt(sampler:int, wrap:String, filter:String, mipfilter:String):void;
 
         public native function setRenderToTexture(texture:TextureBase, enableDepthAndStencil:Boolean = false, antiAlias:int = 0, surfaceSelector:int = 0, colorOutputIndex:int = 0):void;
 
-        public function setStencilActions(
+        public native function setStencilActions(
             triangleFace:String = "frontAndBack",
             compareMode:String = "always",
             actionOnBothPass:String = "keep",
             actionOnDepthFail:String = "keep",
             actionOnDepthPassStencilFail:String = "keep"
-        ):void {
-            stub_method("flash.display3D.Context3D", "setStencilActions");
-        }
+        ):void;
     }
 }
\ No newline at end of file
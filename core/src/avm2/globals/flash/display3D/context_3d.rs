@@ -8,9 +8,10 @@ use crate::avm2_stub_method;
 use ruffle_render::backend::Context3DTextureFilter;
 use ruffle_render::backend::Context3DWrapMode;
 use ruffle_render::backend::{
-    BufferUsage, Context3DBlendFactor, Context3DCompareMode, Context3DTextureFormat,
-    Context3DTriangleFace, Context3DVertexBufferFormat, ProgramType,
+    BufferUsage, Context3DBlendFactor, Context3DCompareMode, Context3DStencilAction,
+    Context3DTextureFormat, Context3DTriangleFace, Context3DVertexBufferFormat, ProgramType,
 };
+use ruffle_render::bitmap::PixelRegion;
 use swf::{Rectangle, Twips};
 
 pub fn create_index_buffer<'gc>(
@@ -447,6 +448,17 @@ pub fn create_rectangle_texture<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn create_video_texture<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(context) = this.as_context_3d() {
+        return context.create_video_texture(activation);
+    }
+    Ok(Value::Undefined)
+}
+
 pub fn create_cube_texture<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -500,7 +512,12 @@ pub fn set_texture_at<'gc>(
                     .inner_class_definition(),
                 &mut activation.context,
             );
-            Some(obj.as_texture().unwrap().handle())
+            if let Some(video_texture) = obj.as_video_texture() {
+                // A VideoTexture has no backing texture until its first frame arrives.
+                video_texture.handle()
+            } else {
+                Some(obj.as_texture().unwrap().handle())
+            }
         };
         context.set_texture_at(sampler, texture, cube);
     }
@@ -543,6 +560,70 @@ pub fn set_depth_test<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn set_stencil_actions<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(context) = this.as_context_3d() {
+        // This is a native method, so all of the arguments have been checked and coerced for us
+        let triangle_face = args.get_string(activation, 0)?;
+        let triangle_face = if &*triangle_face == b"none" {
+            Context3DTriangleFace::None
+        } else if &*triangle_face == b"back" {
+            Context3DTriangleFace::Back
+        } else if &*triangle_face == b"front" {
+            Context3DTriangleFace::Front
+        } else if &*triangle_face == b"frontAndBack" {
+            Context3DTriangleFace::FrontAndBack
+        } else {
+            return Err(format!("Unsupported stencil triangle face: {:?}", triangle_face).into());
+        };
+
+        let compare_mode = args.get_string(activation, 1)?;
+        let compare_mode = if let Some(mode) = Context3DCompareMode::from_wstr(&compare_mode) {
+            mode
+        } else {
+            return Err(format!("Unsupported stencil compare mode: {:?}", compare_mode).into());
+        };
+
+        let mut actions = [Context3DStencilAction::Keep; 3];
+        for (i, action) in actions.iter_mut().enumerate() {
+            let name = args.get_string(activation, i + 2)?;
+            *action = if let Some(action) = Context3DStencilAction::from_wstr(&name) {
+                action
+            } else {
+                return Err(format!("Unsupported stencil action: {:?}", name).into());
+            };
+        }
+        let [action_on_both_pass, action_on_depth_fail, action_on_depth_pass_stencil_fail] =
+            actions;
+
+        context.set_stencil_actions(
+            triangle_face,
+            compare_mode,
+            action_on_both_pass,
+            action_on_depth_fail,
+            action_on_depth_pass_stencil_fail,
+        );
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn set_stencil_reference_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(context) = this.as_context_3d() {
+        let reference_value = args.get_u32(activation, 0)?;
+        let read_mask = args.get_u32(activation, 1)?;
+        let write_mask = args.get_u32(activation, 2)?;
+        context.set_stencil_reference_value(reference_value, read_mask, write_mask);
+    }
+    Ok(Value::Undefined)
+}
+
 pub fn set_blend_factors<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -711,3 +792,67 @@ pub fn set_scissor_rectangle<'gc>(
     context3d.set_scissor_rectangle(rectangle);
     Ok(Value::Undefined)
 }
+
+pub fn draw_to_bitmap_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let context3d = this.as_context_3d().unwrap();
+    let destination = args
+        .get_object(activation, 0, "destination")?
+        .as_bitmap_data()
+        .unwrap();
+    destination.check_valid(activation)?;
+
+    let mut source_region = if let Some(rectangle) = args.try_get_object(activation, 1) {
+        let x = rectangle
+            .get_public_property("x", activation)?
+            .coerce_to_i32(activation)?;
+        let y = rectangle
+            .get_public_property("y", activation)?
+            .coerce_to_i32(activation)?;
+        let width = rectangle
+            .get_public_property("width", activation)?
+            .coerce_to_i32(activation)?;
+        let height = rectangle
+            .get_public_property("height", activation)?
+            .coerce_to_i32(activation)?;
+        PixelRegion::for_region_i32(x, y, width, height)
+    } else {
+        // The backend clamps this to the size of the back buffer.
+        PixelRegion::for_whole_size(destination.width(), destination.height())
+    };
+
+    let dest_point = if let Some(point) = args.try_get_object(activation, 2) {
+        let x = point
+            .get_public_property("x", activation)?
+            .coerce_to_i32(activation)?;
+        let y = point
+            .get_public_property("y", activation)?
+            .coerce_to_i32(activation)?;
+        (x, y)
+    } else {
+        (0, 0)
+    };
+
+    // A negative destination point clips off the left or top of the source rectangle,
+    // rather than moving the copy back inside the destination.
+    source_region.x_min = source_region
+        .x_min
+        .saturating_add(dest_point.0.min(0).unsigned_abs())
+        .min(source_region.x_max);
+    source_region.y_min = source_region
+        .y_min
+        .saturating_add(dest_point.1.min(0).unsigned_abs())
+        .min(source_region.y_max);
+    let dest_point = (dest_point.0.max(0) as u32, dest_point.1.max(0) as u32);
+
+    context3d.draw_to_bitmap_data(
+        &mut activation.context,
+        destination,
+        source_region,
+        dest_point,
+    );
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,16 @@
+package flash.display3D.textures {
+    import flash.media.Camera;
+    import flash.net.NetStream;
+    import __ruffle__.stub_method;
+
+    public final class VideoTexture extends TextureBase {
+        public native function attachNetStream(netStream:NetStream):void;
+
+        public function attachCamera(theCamera:Camera):void {
+            stub_method("flash.display3D.textures.VideoTexture", "attachCamera");
+        }
+
+        public native function get videoWidth():int;
+        public native function get videoHeight():int;
+    }
+}
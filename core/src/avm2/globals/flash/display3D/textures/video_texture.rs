@@ -0,0 +1,41 @@
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::Activation;
+use crate::avm2::TObject;
+use crate::avm2::Value;
+use crate::avm2::{Error, Object};
+
+pub fn attach_net_stream<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(texture) = this.as_video_texture() {
+        let stream = args
+            .try_get_object(activation, 0)
+            .and_then(|stream| stream.as_netstream());
+        texture.attach_net_stream(activation.context.gc_context, stream);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn get_video_width<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(texture) = this.as_video_texture() {
+        return Ok(texture.video_size().0.into());
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn get_video_height<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(texture) = this.as_video_texture() {
+        return Ok(texture.video_size().1.into());
+    }
+    Ok(Value::Undefined)
+}
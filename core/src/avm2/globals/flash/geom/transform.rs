@@ -1,11 +1,14 @@
+use crate::avm2::object::VectorObject;
 use crate::avm2::parameters::ParametersExt;
+use crate::avm2::vector::VectorStorage;
 use crate::avm2::Multiname;
 use crate::avm2::{Activation, Error, Object, TObject, Value};
 use crate::avm2_stub_getter;
-use crate::display_object::TDisplayObject;
+use crate::display_object::{PerspectiveProjection, TDisplayObject};
 use crate::prelude::{DisplayObject, Matrix, Twips};
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::quality::StageQuality;
-use swf::{ColorTransform, Fixed8, Rectangle};
+use swf::{ColorTransform, Fixed8, Point, Rectangle};
 
 fn get_display_object<'gc>(
     this: Object<'gc>,
@@ -64,7 +67,12 @@ pub fn get_matrix<'gc>(
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let matrix = *get_display_object(this, activation)?.base().matrix();
+    let dobj = get_display_object(this, activation)?;
+    // 3D objects only expose `matrix3D`.
+    if dobj.base().has_transform_3d() {
+        return Ok(Value::Null);
+    }
+    let matrix = *dobj.base().matrix();
     matrix_to_object(matrix, activation)
 }
 
@@ -75,6 +83,9 @@ pub fn set_matrix<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let matrix = object_to_matrix(args.get_object(activation, 0, "value")?, activation)?;
     let dobj = get_display_object(this, activation)?;
+    // Setting a 2D matrix turns a 3D object back into a 2D one.
+    dobj.base_mut(activation.context.gc_context)
+        .set_matrix_3d(None);
     dobj.set_matrix(activation.context.gc_context, matrix);
     if let Some(parent) = dobj.parent() {
         // Self-transform changes are automatically handled,
@@ -247,6 +258,211 @@ pub fn object_to_matrix<'gc>(
     Ok(Matrix { a, b, c, d, tx, ty })
 }
 
+pub fn get_matrix_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    let matrix = dobj.base_mut(activation.context.gc_context).matrix_3d();
+    match matrix {
+        Some(matrix) => matrix_3d_to_object(matrix, activation),
+        None => Ok(Value::Null),
+    }
+}
+
+pub fn set_matrix_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let matrix = match args.try_get_object(activation, 0) {
+        Some(object) => Some(object_to_matrix_3d(object, activation)?),
+        None => None,
+    };
+    let dobj = get_display_object(this, activation)?;
+    dobj.base_mut(activation.context.gc_context)
+        .set_matrix_3d(matrix);
+    if let Some(parent) = dobj.parent() {
+        parent.invalidate_cached_bitmap(activation.context.gc_context);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn get_perspective_projection<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    let (stage_width, stage_height) = activation.context.stage.stage_size();
+    let projection = dobj.base().perspective_projection().or_else(|| {
+        // Only the root has a projection by default.
+        let is_root = activation
+            .context
+            .stage
+            .root_clip()
+            .is_some_and(|root| DisplayObject::ptr_eq(root, dobj));
+        is_root.then(|| {
+            PerspectiveProjection::default_for_stage(stage_width.into(), stage_height.into())
+        })
+    });
+
+    match projection {
+        Some(projection) => perspective_projection_to_object(projection, activation),
+        None => Ok(Value::Null),
+    }
+}
+
+pub fn set_perspective_projection<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let projection = match args.try_get_object(activation, 0) {
+        Some(object) => Some(object_to_perspective_projection(object, activation)?),
+        None => None,
+    };
+    let dobj = get_display_object(this, activation)?;
+    dobj.base_mut(activation.context.gc_context)
+        .set_perspective_projection(projection);
+    dobj.invalidate_cached_bitmap(activation.context.gc_context);
+    Ok(Value::Undefined)
+}
+
+pub fn get_relative_matrix_3d<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    let Some(relative_to) = args
+        .try_get_object(activation, 0)
+        .and_then(|o| o.as_display_object())
+    else {
+        return Ok(Value::Null);
+    };
+
+    let matrix = concatenated_matrix_3d(dobj, activation);
+    let relative_to_matrix = concatenated_matrix_3d(relative_to, activation);
+    match relative_to_matrix.inverse() {
+        Some(inverse) => matrix_3d_to_object(inverse * matrix, activation),
+        None => Ok(Value::Null),
+    }
+}
+
+/// Calculates the 3D matrix from the local space of `dobj` to the stage, without any perspective.
+fn concatenated_matrix_3d<'gc>(
+    dobj: DisplayObject<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Matrix3D {
+    let mut matrix = Matrix3D::IDENTITY;
+    let mut node = Some(dobj);
+    while let Some(obj) = node {
+        if obj.as_stage().is_some() {
+            break;
+        }
+        let local = obj
+            .base_mut(activation.context.gc_context)
+            .matrix_3d()
+            .unwrap_or_else(|| Matrix3D::from(*obj.base().matrix()));
+        matrix = local * matrix;
+        node = obj.parent();
+    }
+    matrix
+}
+
+pub fn matrix_3d_to_object<'gc>(
+    matrix: Matrix3D,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let number_class = activation.avm2().classes().number;
+    let storage = VectorStorage::from_values(
+        matrix
+            .raw_data
+            .iter()
+            .map(|value| (*value).into())
+            .collect(),
+        false,
+        Some(number_class),
+    );
+    let raw_data = VectorObject::from_vector(storage, activation)?;
+    let object = activation
+        .avm2()
+        .classes()
+        .matrix3d
+        .construct(activation, &[raw_data.into()])?;
+    Ok(object.into())
+}
+
+pub fn object_to_matrix_3d<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Matrix3D, Error<'gc>> {
+    let raw_data = object
+        .get_public_property("rawData", activation)?
+        .coerce_to_object(activation)?;
+    let values: Vec<Value<'gc>> = raw_data
+        .as_vector_storage()
+        .map(|storage| storage.iter().collect())
+        .unwrap_or_default();
+
+    let mut matrix = Matrix3D::IDENTITY;
+    for (raw, value) in matrix.raw_data.iter_mut().zip(values) {
+        *raw = value.coerce_to_number(activation)?;
+    }
+    Ok(matrix)
+}
+
+pub fn perspective_projection_to_object<'gc>(
+    projection: PerspectiveProjection,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let object = activation
+        .avm2()
+        .classes()
+        .perspectiveprojection
+        .construct(activation, &[])?;
+    let stage_width = activation.context.stage.stage_size().0;
+    object.set_property(
+        &Multiname::new(activation.avm2().flash_geom_internal, "_stageWidth"),
+        stage_width.into(),
+        activation,
+    )?;
+    object.set_public_property("fieldOfView", projection.field_of_view.into(), activation)?;
+    let center = activation.avm2().classes().point.construct(
+        activation,
+        &[
+            projection.center.x.to_pixels().into(),
+            projection.center.y.to_pixels().into(),
+        ],
+    )?;
+    object.set_public_property("projectionCenter", center.into(), activation)?;
+    Ok(object.into())
+}
+
+pub fn object_to_perspective_projection<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<PerspectiveProjection, Error<'gc>> {
+    let field_of_view = object
+        .get_public_property("fieldOfView", activation)?
+        .coerce_to_number(activation)?;
+    let center = object
+        .get_public_property("projectionCenter", activation)?
+        .coerce_to_object(activation)?;
+    let x = center
+        .get_public_property("x", activation)?
+        .coerce_to_number(activation)?;
+    let y = center
+        .get_public_property("y", activation)?
+        .coerce_to_number(activation)?;
+    Ok(PerspectiveProjection {
+        field_of_view,
+        center: Point::from_pixels(x, y),
+    })
+}
+
 pub fn get_pixel_bounds<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
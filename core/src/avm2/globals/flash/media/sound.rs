@@ -1,15 +1,17 @@
 //! `flash.media.Sound` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::error::{make_error_2008, make_error_2037};
 use crate::avm2::object::{Object, QueuedPlay, SoundChannelObject, TObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::backend::audio::AudioManager;
 use crate::backend::navigator::Request;
 use crate::character::Character;
 use crate::display_object::SoundTransform;
 use crate::{avm2_stub_getter, avm2_stub_method};
-use swf::{SoundEvent, SoundInfo};
+use swf::{AudioCompression, SoundEvent, SoundFormat, SoundInfo};
 
 pub use crate::avm2::object::sound_allocator;
 
@@ -174,6 +176,42 @@ pub fn play<'gc>(
 
         let sound_channel = SoundChannelObject::empty(activation)?;
 
+        // A sound with no data of its own may generate it on demand instead.
+        if sound_object.sound_handle().is_none()
+            && this
+                .call_public_property("hasEventListener", &["sampleData".into()], activation)?
+                .coerce_to_boolean()
+        {
+            if num_loops > 1 {
+                avm2_stub_method!(
+                    activation,
+                    "flash.media.Sound",
+                    "play",
+                    "with loops for sampleData sounds"
+                );
+            }
+
+            // The first `sampleData` event asks for the audio at `startTime`.
+            let start_position = position.max(0.0) / 1000.0 * AudioManager::SAMPLE_DATA_RATE;
+            if let Some(instance) = activation
+                .context
+                .start_dynamic_sound(this, start_position.floor())
+            {
+                if let Some(sound_transform) = sound_transform {
+                    activation
+                        .context
+                        .set_local_sound_transform(instance, sound_transform);
+                }
+
+                sound_channel.set_sound_instance(activation, instance);
+                activation
+                    .context
+                    .attach_avm2_sound_channel(instance, sound_channel);
+            }
+
+            return Ok(sound_channel.into());
+        }
+
         let queued_play = QueuedPlay {
             position,
             sound_info,
@@ -194,27 +232,51 @@ pub fn play<'gc>(
 /// `Sound.extract`
 pub fn extract<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.media.Sound", "extract");
-
-    let bytearray = args
-        .get(0)
-        .unwrap_or(&Value::Undefined)
-        .coerce_to_object(activation)?;
-    let length = args
-        .get(1)
-        .unwrap_or(&Value::Number(0.0))
-        .coerce_to_number(activation)?;
-
-    if let Some(mut bytearray) = bytearray.as_bytearray_mut(activation.context.gc_context) {
-        bytearray
-            .write_bytes(vec![0u8; length.ceil() as usize].as_slice())
-            .map_err(|e| e.to_avm(activation))?;
+    let target = args.get_object(activation, 0, "target")?;
+    let length = args.get_f64(activation, 1)?;
+    let start_position = args.get_f64(activation, 2)?;
+
+    let Some(sound_object) = this.as_sound_object() else {
+        return Ok(0.into());
+    };
+    let Some(sound_handle) = sound_object.sound_handle() else {
+        return Ok(0.into());
+    };
+
+    let start_frame = if start_position >= 0.0 {
+        start_position as u32
+    } else {
+        sound_object.extract_position()
+    };
+    let num_frames = if length > 0.0 { length as u32 } else { 0 };
+
+    let frames = activation
+        .context
+        .audio
+        .extract_sound_data(sound_handle, start_frame, num_frames)
+        .unwrap_or_default();
+
+    if let Some(mut bytearray) = target.as_bytearray_mut(activation.context.gc_context) {
+        for [left, right] in &frames {
+            bytearray
+                .write_float(*left)
+                .map_err(|e| e.to_avm(activation))?;
+            bytearray
+                .write_float(*right)
+                .map_err(|e| e.to_avm(activation))?;
+        }
     }
 
-    Ok(Value::Undefined)
+    let num_extracted = frames.len() as u32;
+    sound_object.set_extract_position(
+        activation.context.gc_context,
+        start_frame.saturating_add(num_extracted),
+    );
+
+    Ok(num_extracted.into())
 }
 
 /// `Sound.close`
@@ -264,23 +326,105 @@ pub fn load<'gc>(
 /// `Sound.loadCompressedDataFromByteArray`
 pub fn load_compressed_data_from_byte_array<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(
-        activation,
-        "flash.media.Sound",
-        "loadCompressedDataFromByteArray"
-    );
+    let bytes = args.get_object(activation, 0, "bytes")?;
+    let bytes_length = args.get_u32(activation, 1)?;
+
+    let Some(sound_object) = this.as_sound_object() else {
+        return Ok(Value::Undefined);
+    };
+    if sound_object.sound_handle().is_some() {
+        return Err(make_error_2037(activation));
+    }
+
+    let data = if let Some(bytearray) = bytes.as_bytearray() {
+        let length = (bytes_length as usize).min(bytearray.bytes_available());
+        bytearray
+            .read_bytes(length)
+            .map_err(|e| e.to_avm(activation))?
+            .to_vec()
+    } else {
+        return Ok(Value::Undefined);
+    };
+
+    let handle = activation
+        .context
+        .audio
+        .register_mp3(&data)
+        .map_err(|e| format!("Sound.loadCompressedDataFromByteArray: {e}"))?;
+    sound_object.set_sound(&mut activation.context, handle)?;
+
     Ok(Value::Undefined)
 }
 
 /// `Sound.loadPCMFromByteArray`
 pub fn load_pcm_from_byte_array<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.media.Sound", "loadPCMFromByteArray");
+    let bytes = args.get_object(activation, 0, "bytes")?;
+    let samples = args.get_u32(activation, 1)?;
+    let format = args.get_string(activation, 2)?;
+    let stereo = args.get_bool(3);
+    let sample_rate = args.get_f64(activation, 4)?;
+
+    let is_float = if &format == b"float" {
+        true
+    } else if &format == b"short" {
+        false
+    } else {
+        return Err(make_error_2008(activation, "format"));
+    };
+    if !(1.0..=f64::from(u16::MAX)).contains(&sample_rate) {
+        return Err(make_error_2008(activation, "sampleRate"));
+    }
+
+    let Some(sound_object) = this.as_sound_object() else {
+        return Ok(Value::Undefined);
+    };
+    if sound_object.sound_handle().is_some() {
+        return Err(make_error_2037(activation));
+    }
+
+    let num_channels = if stereo { 2 } else { 1 };
+    let sample_size = if is_float { 4 } else { 2 };
+    let mut data = Vec::new();
+    if let Some(bytearray) = bytes.as_bytearray() {
+        let available_frames = bytearray.bytes_available() / (num_channels * sample_size);
+        let num_samples = (samples as usize).min(available_frames) * num_channels;
+        data.reserve(num_samples * 2);
+        for _ in 0..num_samples {
+            let sample = if is_float {
+                let sample = bytearray.read_float().map_err(|e| e.to_avm(activation))?;
+                (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16
+            } else {
+                bytearray.read_short().map_err(|e| e.to_avm(activation))?
+            };
+            data.extend_from_slice(&sample.to_le_bytes());
+        }
+    }
+
+    let num_samples = (data.len() / (num_channels * 2)) as u32;
+    let sound = swf::Sound {
+        id: 0,
+        format: SoundFormat {
+            compression: AudioCompression::Uncompressed,
+            sample_rate: sample_rate as u16,
+            is_stereo: stereo,
+            is_16_bit: true,
+        },
+        num_samples,
+        data: &data,
+    };
+    let handle = activation
+        .context
+        .audio
+        .register_sound(&sound)
+        .map_err(|e| format!("Sound.loadPCMFromByteArray: {e}"))?;
+    sound_object.set_sound(&mut activation.context, handle)?;
+
     Ok(Value::Undefined)
 }
@@ -3,6 +3,8 @@
 use crate::avm2::object::TObject;
 use crate::avm2::{Activation, Error, Object, Value};
 
+pub mod file_reference;
+pub mod file_reference_list;
 pub mod local_connection;
 pub mod net_connection;
 pub mod net_stream;
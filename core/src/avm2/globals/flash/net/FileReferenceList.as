@@ -1,11 +1,10 @@
 package flash.net
 {
     import flash.events.EventDispatcher;
-    import __ruffle__.stub_method;
 
     public class FileReferenceList extends EventDispatcher
     {
-        private var _fileList:Array;
+        internal var _fileList:Array;
         public function FileReferenceList()
         {
             _fileList = new Array();
@@ -16,10 +15,6 @@ package flash.net
             return this._fileList;
         }
 
-        public function browse(typeFilter:Array = null):Boolean
-        {
-            stub_method("flash.net.FileReferenceList", "browse");
-            return false;
-        }
+        public native function browse(typeFilter:Array = null):Boolean;
     }
-}
\ No newline at end of file
+}
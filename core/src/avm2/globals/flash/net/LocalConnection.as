@@ -1,36 +1,39 @@
 package flash.net {
     import flash.events.EventDispatcher;
-    import __ruffle__.stub_method;
-    import __ruffle__.stub_getter;
 
-    // NOTE: this entire class is a stub.
-    // Thankfully (hopefully) a lot of code like Mochicrypt doesn't actually require this to... well do anything.
     public class LocalConnection extends EventDispatcher {
 
         public var client: Object;
 
+        // The domains that can send messages to this connection, besides its own.
+        private var _allowedDomains: Array = [];
+
         public function LocalConnection() {
             this.client = this;
         }
 
         public native function get domain():String;
 
-        public function close(): void {
-            stub_method("flash.net.LocalConnection", "close");
-        }
+        public native function close(): void;
 
-        public function connect(connectionName:String): void {
-            stub_method("flash.net.LocalConnection", "connect");
-        }
+        public native function connect(connectionName:String): void;
 
         public native function send(connectionName: String, methodName: String, ... arguments);
 
         public function allowDomain(... domains): void {
-            stub_method("flash.net.LocalConnection", "allowDomain");
+            for each (var domain in domains) {
+                this._allowedDomains.push(String(domain).toLowerCase());
+            }
         }
 
         public function allowInsecureDomain(... domains): void {
-            stub_method("flash.net.LocalConnection", "allowInsecureDomain");
+            // Messages don't go over the network, so there's no insecure domain to tell apart.
+            this.allowDomain.apply(this, domains);
+        }
+
+        internal function isDomainAllowed(domain: String): Boolean {
+            return this._allowedDomains.indexOf("*") != -1 ||
+                this._allowedDomains.indexOf(domain.toLowerCase()) != -1;
         }
     }
 }
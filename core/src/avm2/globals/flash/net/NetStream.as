@@ -83,9 +83,7 @@ package flash.net {
 
         public native function resume();
 
-        public function seek(offset:Number) {
-            stub_method("flash.net.NetStream", "seek");
-        }
+        public native function seek(offset:Number);
 
         public function send(handlerName:String, ...args) {
             stub_method("flash.net.NetStream", "send");
@@ -133,19 +131,11 @@ package flash.net {
             stub_setter("flash.net.NetStream", "backBufferTime");
         }
 
-        public function get bufferLength():Number {
-            stub_getter("flash.net.NetStream", "bufferLength");
-            return 0.0;
-        }
+        public native function get bufferLength():Number;
 
-        public function get bufferTime():Number {
-            stub_getter("flash.net.NetStream", "bufferTime");
-            return 0.0;
-        }
+        public native function get bufferTime():Number;
 
-        public function set bufferTime(time:Number) {
-            stub_setter("flash.net.NetStream", "bufferTime");
-        }
+        public native function set bufferTime(time:Number);
 
         public function get bufferTimeMax():Number {
             stub_getter("flash.net.NetStream", "bufferTimeMax");
@@ -207,10 +197,19 @@ package flash.net {
         }
 
         public function get info():NetStreamInfo {
-            stub_getter("flash.net.NetStream", "info");
-            return new NetStreamInfo();
+            // Transfer rates aren't measured, so they are all reported as 0.
+            // The URI and resource name are only given for RTMFP streams, and
+            // progressive downloads are never live.
+            var bufferLength:Number = this.bufferLength;
+            return new NetStreamInfo(
+                0, this.bytesLoaded, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                bufferLength, bufferLength, bufferLength, 0, 0, 0,
+                this.getMetaData(), null, null, null, false
+            );
         }
 
+        private native function getMetaData():Object;
+
 
         public function get liveDelay(): Number {
             stub_getter("flash.net.NetStream", "liveDelay");
@@ -311,10 +310,7 @@ package flash.net {
             stub_setter("flash.net.NetStream", "soundTransform");
         };
 
-        public function get time(): Number {
-            stub_getter("flash.net.NetStream", "time");
-            return 0;
-        };
+        public native function get time(): Number;
 
         public function get useHardwareDecoder(): Boolean {
             stub_getter("flash.net.NetStream", "useHardwareDecoder");
@@ -26,6 +26,42 @@ package flash.net {
         private var _videoLossRate: Number;
         private var _xmpData: Object;
 
+        public function NetStreamInfo(
+            curBPS:Number, byteCount:Number, maxBPS:Number, audioBPS:Number, audioByteCount:Number,
+            videoBPS:Number, videoByteCount:Number, dataBPS:Number, dataByteCount:Number,
+            playbackBPS:Number, droppedFrames:Number, audioBufferByteLength:Number,
+            videoBufferByteLength:Number, dataBufferByteLength:Number, audioBufferLength:Number,
+            videoBufferLength:Number, dataBufferLength:Number, srtt:Number, audioLossRate:Number,
+            videoLossRate:Number, metaData:Object = null, xmpData:Object = null,
+            uri:String = null, resourceName:String = null, isLive:Boolean = true
+        ) {
+            this._currentBytesPerSecond = curBPS;
+            this._byteCount = byteCount;
+            this._maxBytesPerSecond = maxBPS;
+            this._audioBytesPerSecond = audioBPS;
+            this._audioByteCount = audioByteCount;
+            this._videoBytesPerSecond = videoBPS;
+            this._videoByteCount = videoByteCount;
+            this._dataBytesPerSecond = dataBPS;
+            this._dataByteCount = dataByteCount;
+            this._playbackBytesPerSecond = playbackBPS;
+            this._droppedFrames = droppedFrames;
+            this._audioBufferByteLength = audioBufferByteLength;
+            this._videoBufferByteLength = videoBufferByteLength;
+            this._dataBufferByteLength = dataBufferByteLength;
+            this._audioBufferLength = audioBufferLength;
+            this._videoBufferLength = videoBufferLength;
+            this._dataBufferLength = dataBufferLength;
+            this._SRTT = srtt;
+            this._audioLossRate = audioLossRate;
+            this._videoLossRate = videoLossRate;
+            this._metaData = metaData;
+            this._xmpData = xmpData;
+            this._uri = uri;
+            this._resourceName = resourceName;
+            this._isLive = isLive;
+        }
+
         public function toString():String {
             __ruffle__.stub_method("flash.net.NetStreamInfo", "toString")
             return super.toString();
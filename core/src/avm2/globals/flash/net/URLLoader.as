@@ -1,6 +1,7 @@
 package flash.net {
 	import flash.events.EventDispatcher;
 	import flash.net.URLRequest;
+	import flash.utils.ByteArray;
 	import __ruffle__.stub_method;
 
 	public class URLLoader extends EventDispatcher {
@@ -13,19 +14,12 @@ package flash.net {
 			}
 		}
 
-		// FIXME - this should be a normal property for consistency with Flash
-		public function get bytesTotal():uint {
-			if (this.data) {
-				return this.data.length;
-			}
-			return 0;
-		}
+		public var bytesLoaded: uint = 0;
+		public var bytesTotal: uint = 0;
+
+		// The binary data received so far, which `URLStream` reads while the load is in progress.
+		internal var _stream: ByteArray;
 
-		// FIXME - this should be a normal property for consistency with Flash
-		public function get bytesLoaded():uint {
-			// TODO - update this as the download progresses
-			return this.bytesTotal
-		}
 		public native function load(request:URLRequest):void;
 
 		public function close():void {
@@ -9,33 +9,27 @@ package flash.net {
     import flash.events.SecurityErrorEvent;
     import flash.events.ProgressEvent;
     import flash.events;
-    import __ruffle__.stub_constructor;
     import __ruffle__.stub_method;
     import __ruffle__.stub_getter;
     import __ruffle__.stub_setter;
 
     public class URLStream extends EventDispatcher implements IDataInput {
         private var _endian:String = Endian.BIG_ENDIAN;
-        // FIXME - we currently implement `URLStream` using a `URLLoader`,
-        // which means that content can't actually be "streamed" (it becomes
-        // available all at once when the entire download finishes).
-        // We should write an actual "streaming" implementation that exposes
-        // content as it comes in over the network, but this will require changes
-        // to `NavigatorBackend`. See https://github.com/ruffle-rs/ruffle/pull/11046
+        // `URLStream` is implemented using a `URLLoader`, which appends the data
+        // to `_loader._stream` as it comes in over the network.
         private var _loader:URLLoader = new URLLoader();
 
         public function URLStream() {
-            stub_constructor("flash.net.URLStream", "streaming support");
-
             this._loader.dataFormat = URLLoaderDataFormat.BINARY;
             var self = this;
 
             this._loader.addEventListener(Event.OPEN, function(e:*):void {
+                self._loader._stream.endian = self._endian;
                 self.dispatchEvent(new Event(Event.OPEN));
             });
 
             this._loader.addEventListener(Event.COMPLETE, function(e:*):void {
-                self._loader.data.endian = self._endian;
+                self._loader._stream.endian = self._endian;
                 self.dispatchEvent(new Event(Event.COMPLETE));
             });
             this._loader.addEventListener(IOErrorEvent.IO_ERROR, function(e:*):void {
@@ -45,14 +39,14 @@ package flash.net {
                 self.dispatchEvent(new SecurityErrorEvent(SecurityErrorEvent.SECURITY_ERROR));
             });
             this._loader.addEventListener(ProgressEvent.PROGRESS, function(e:*):void {
-                self._loader.data.endian = self._endian;
+                self._loader._stream.endian = self._endian;
                 self.dispatchEvent(new ProgressEvent(ProgressEvent.PROGRESS, false, false, e.bytesLoaded, e.bytesTotal));
             });
         }
 
         public function get bytesAvailable():uint {
-            if (this._loader.data) {
-                return this._loader.data.bytesAvailable;
+            if (this._loader._stream) {
+                return this._loader._stream.bytesAvailable;
             }
             return 0;
         }
@@ -64,8 +58,8 @@ package flash.net {
         public function set endian(value:String):void {
             if (value === Endian.BIG_ENDIAN || value === Endian.LITTLE_ENDIAN) {
                 this._endian = value;
-                if (this._loader.data) {
-                    this._loader.data.endian = value;
+                if (this._loader._stream) {
+                    this._loader._stream.endian = value;
                 }
             } else {
                 throw new ArgumentError("Error #2008: Parameter endian must be one of the accepted values.", 2008);
@@ -89,46 +83,46 @@ package flash.net {
         }
 
         public function readBoolean():Boolean {
-            return this._loader.data.readBoolean();
+            return this._loader._stream.readBoolean();
         }
         public function readByte():int {
-            return this._loader.data.readByte();
+            return this._loader._stream.readByte();
         }
         public function readBytes(bytes:ByteArray, offset:uint = 0, length:uint = 0):void {
-            this._loader.data.readBytes(bytes, offset, length);
+            this._loader._stream.readBytes(bytes, offset, length);
         }
         public function readDouble():Number {
-            return this._loader.data.readDouble();
+            return this._loader._stream.readDouble();
         }
         public function readFloat():Number {
-            return this._loader.data.readFloat();
+            return this._loader._stream.readFloat();
         }
         public function readInt():int {
-            return this._loader.data.readInt();
+            return this._loader._stream.readInt();
         }
         public function readMultiByte(length:uint, charSet:String):String {
-            return this._loader.data.readMultiByte(length, charSet);
+            return this._loader._stream.readMultiByte(length, charSet);
         }
         public function readObject():* {
-            return this._loader.data.readObject();
+            return this._loader._stream.readObject();
         }
         public function readShort():int {
-            return this._loader.data.readShort();
+            return this._loader._stream.readShort();
         }
         public function readUnsignedByte():uint {
-            return this._loader.data.readUnsignedByte();
+            return this._loader._stream.readUnsignedByte();
         }
         public function readUnsignedInt():uint {
-            return this._loader.data.readUnsignedInt();
+            return this._loader._stream.readUnsignedInt();
         }
         public function readUnsignedShort():uint {
-            return this._loader.data.readUnsignedShort();
+            return this._loader._stream.readUnsignedShort();
         }
         public function readUTF():String {
-            return this._loader.data.readUTF();
+            return this._loader._stream.readUTF();
         }
         public function readUTFBytes(length:uint):String {
-            return this._loader.data.readUTFBytes(length);
+            return this._loader._stream.readUTFBytes(length);
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,459 @@
+//! `flash.net.FileReference` native methods
+
+use crate::avm2::error::{argument_error, illegal_operation_error, type_error};
+use crate::avm2::globals::flash::display::loader::request_from_url_request;
+pub use crate::avm2::object::file_reference_allocator;
+use crate::avm2::object::{FileReferenceObject, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::string::AvmString;
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::avm2_stub_method;
+use crate::backend::navigator::NavigationMethod;
+use crate::backend::ui::{FileDialogFuture, FileDialogUnavailable, FileFilter};
+use crate::loader::{self, FileReference};
+use chrono::{DateTime, Utc};
+use url::form_urlencoded;
+
+/// Returns the `FileReference` that `this` is, if a file has been picked for it.
+///
+/// Flash throws an error when reading the details of a file before one has been picked.
+fn picked_file_reference<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<Option<FileReferenceObject<'gc>>, Error<'gc>> {
+    let Some(file_reference) = this.as_file_reference() else {
+        return Ok(None);
+    };
+    if !file_reference.has_file() {
+        return Err(Error::AvmError(illegal_operation_error(
+            activation,
+            "Error #2037: Functions called in incorrect sequence, or earlier call was unsuccessful.",
+            2037,
+        )?));
+    }
+    Ok(Some(file_reference))
+}
+
+/// Checks that a file dialog was displayed, which is `None` if the UI backend can't display any.
+pub fn displayed_dialog<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    dialog: Result<FileDialogFuture, FileDialogUnavailable>,
+) -> Result<Option<FileDialogFuture>, Error<'gc>> {
+    match dialog {
+        Ok(dialog) => Ok(Some(dialog)),
+        Err(FileDialogUnavailable::Unsupported) => Ok(None),
+        Err(FileDialogUnavailable::AlreadyOpen) => Err(Error::AvmError(illegal_operation_error(
+            activation,
+            "Error #2041: Only one file browsing session may be performed at a time.",
+            2041,
+        )?)),
+    }
+}
+
+fn invalid_file_name_error<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
+    match argument_error(
+        activation,
+        "Error #2087: The FileReference.download() file name contains prohibited characters.",
+        2087,
+    ) {
+        Ok(error) => Error::AvmError(error),
+        Err(error) => error,
+    }
+}
+
+fn date_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    time: Option<DateTime<Utc>>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    match time {
+        Some(time) => Ok(activation
+            .avm2()
+            .classes()
+            .date
+            .construct(activation, &[(time.timestamp_millis() as f64).into()])?
+            .into()),
+        None => Ok(Value::Null),
+    }
+}
+
+/// Converts an array of `FileFilter`s into the filters of a file open dialog.
+pub fn file_filters<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    type_filter: Value<'gc>,
+) -> Result<Vec<FileFilter>, Error<'gc>> {
+    let Some(type_filter) = type_filter.as_object() else {
+        return Ok(Vec::new());
+    };
+    let type_filter: Vec<_> = match type_filter.as_array_storage() {
+        Some(array) => array.iter().collect(),
+        None => return Ok(Vec::new()),
+    };
+
+    let mut filters = Vec::with_capacity(type_filter.len());
+    for filter in type_filter {
+        let filter = filter
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let description = filter
+            .get_public_property("description", activation)?
+            .coerce_to_string(activation)?;
+        let extension = filter
+            .get_public_property("extension", activation)?
+            .coerce_to_string(activation)?;
+        let mac_type = match filter.get_public_property("macType", activation)? {
+            Value::Null | Value::Undefined => None,
+            mac_type => Some(mac_type.coerce_to_string(activation)?.to_string()),
+        };
+        filters.push(FileFilter {
+            description: description.to_string(),
+            extensions: extension.to_string(),
+            mac_type,
+        });
+    }
+
+    Ok(filters)
+}
+
+pub fn get_creation_date<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(file_reference) = picked_file_reference(activation, this)? else {
+        return Ok(Value::Null);
+    };
+    let creation_time = file_reference
+        .with_file(|file| file.creation_time())
+        .flatten();
+    date_value(activation, creation_time)
+}
+
+pub fn get_creator<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    picked_file_reference(activation, this)?;
+
+    // Creator codes only exist on classic Mac OS.
+    Ok(Value::Null)
+}
+
+pub fn get_data<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_file_reference()
+        .and_then(|file_reference| file_reference.data())
+        .map_or(Value::Null, Value::Object))
+}
+
+pub fn get_extension<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(file_reference) = picked_file_reference(activation, this)? else {
+        return Ok(Value::Null);
+    };
+    Ok(
+        match file_reference.with_file(|file| file.extension()).flatten() {
+            Some(extension) => AvmString::new_utf8(activation.gc(), extension).into(),
+            None => Value::Null,
+        },
+    )
+}
+
+pub fn get_modification_date<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(file_reference) = picked_file_reference(activation, this)? else {
+        return Ok(Value::Null);
+    };
+    let modification_time = file_reference
+        .with_file(|file| file.modification_time())
+        .flatten();
+    date_value(activation, modification_time)
+}
+
+pub fn get_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(file_reference) = picked_file_reference(activation, this)? else {
+        return Ok(Value::Null);
+    };
+    Ok(match file_reference.with_file(|file| file.file_name()) {
+        Some(name) => AvmString::new_utf8(activation.gc(), name).into(),
+        None => Value::Null,
+    })
+}
+
+pub fn get_permission_status<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // The user is always asked in a dialog, so there's nothing to request up front.
+    Ok("granted".into())
+}
+
+pub fn get_size<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(file_reference) = picked_file_reference(activation, this)? else {
+        return Ok(Value::Undefined);
+    };
+    Ok(
+        match file_reference.with_file(|file| file.size()).flatten() {
+            Some(size) => (size as f64).into(),
+            None => 0.into(),
+        },
+    )
+}
+
+pub fn get_type<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(file_reference) = picked_file_reference(activation, this)? else {
+        return Ok(Value::Null);
+    };
+    Ok(
+        match file_reference.with_file(|file| file.extension()).flatten() {
+            Some(extension) => AvmString::new_utf8(activation.gc(), format!(".{extension}")).into(),
+            None => Value::Null,
+        },
+    )
+}
+
+pub fn browse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(file_reference) = this.as_file_reference() else {
+        return Ok(false.into());
+    };
+
+    let filters = file_filters(activation, args.get_value(0))?;
+    let dialog = activation
+        .context
+        .ui
+        .display_file_open_dialog(filters, false);
+    let Some(dialog) = displayed_dialog(activation, dialog)? else {
+        return Ok(false.into());
+    };
+
+    let future = activation.context.load_manager.browse_file_reference(
+        activation.context.player.clone(),
+        FileReference::Avm2(file_reference),
+        dialog,
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(true.into())
+}
+
+pub fn cancel<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(file_reference) = this.as_file_reference() {
+        if let Some(handle) = file_reference.set_loader(None) {
+            activation.context.load_manager.remove_loader(handle);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Displays a save dialog suggesting `file_name`, which must be a valid file name if given.
+fn save_dialog<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    file_name: String,
+) -> Result<Option<FileDialogFuture>, Error<'gc>> {
+    if !file_name.is_empty() && !loader::is_valid_file_name(&file_name) {
+        return Err(invalid_file_name_error(activation));
+    }
+
+    let dialog = activation.context.ui.display_file_save_dialog(file_name);
+    displayed_dialog(activation, dialog)
+}
+
+pub fn download<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(file_reference) = this.as_file_reference() else {
+        return Ok(Value::Undefined);
+    };
+
+    let url_request = args.get_object(activation, 0, "request")?;
+    let request = request_from_url_request(activation, url_request)?;
+    let file_name = match args.try_get_string(activation, 1)? {
+        Some(file_name) => file_name.to_utf8_lossy().into_owned(),
+        None => loader::file_name_from_url(request.url()),
+    };
+    let Some(dialog) = save_dialog(activation, file_name)? else {
+        return Ok(Value::Undefined);
+    };
+
+    let requester_url = activation
+        .caller_movie()
+        .unwrap_or_else(|| activation.context.swf.clone())
+        .url()
+        .to_string();
+    let future = activation.context.load_manager.download_file_reference(
+        activation.context.player.clone(),
+        FileReference::Avm2(file_reference),
+        dialog,
+        request,
+        requester_url,
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(Value::Undefined)
+}
+
+pub fn load<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(file_reference) = picked_file_reference(activation, this)? else {
+        return Ok(Value::Undefined);
+    };
+
+    let future = activation
+        .context
+        .load_manager
+        .load_file_reference(activation.context.player.clone(), file_reference);
+    activation.context.navigator.spawn_future(future);
+
+    Ok(Value::Undefined)
+}
+
+pub fn request_permission<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    avm2_stub_method!(activation, "flash.net.FileReference", "requestPermission");
+    Ok(Value::Undefined)
+}
+
+pub fn save<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(file_reference) = this.as_file_reference() else {
+        return Ok(Value::Undefined);
+    };
+
+    let data = match args.get_value(0) {
+        Value::Null | Value::Undefined => {
+            return Err(Error::AvmError(type_error(
+                activation,
+                "Error #2007: Parameter data must be non-null.",
+                2007,
+            )?));
+        }
+        Value::Object(object) if object.as_bytearray().is_some() => object
+            .as_bytearray()
+            .map(|bytearray| bytearray.bytes().to_vec())
+            .unwrap_or_default(),
+        Value::Object(object)
+            if object.as_xml_object().is_some() || object.as_xml_list_object().is_some() =>
+        {
+            object
+                .call_public_property("toXMLString", &[], activation)?
+                .coerce_to_string(activation)?
+                .to_utf8_lossy()
+                .into_owned()
+                .into_bytes()
+        }
+        data => data
+            .coerce_to_string(activation)?
+            .to_utf8_lossy()
+            .into_owned()
+            .into_bytes(),
+    };
+    let file_name = args
+        .try_get_string(activation, 1)?
+        .map(|file_name| file_name.to_utf8_lossy().into_owned())
+        .unwrap_or_default();
+    let Some(dialog) = save_dialog(activation, file_name)? else {
+        return Ok(Value::Undefined);
+    };
+
+    let future = activation.context.load_manager.save_file_reference(
+        activation.context.player.clone(),
+        FileReference::Avm2(file_reference),
+        dialog,
+        data,
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(Value::Undefined)
+}
+
+pub fn upload<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(file_reference) = picked_file_reference(activation, this)? else {
+        return Ok(Value::Undefined);
+    };
+
+    let url_request = args.get_object(activation, 0, "request")?;
+    let request = request_from_url_request(activation, url_request)?;
+    let field_name = args.get_string(activation, 1)?.to_utf8_lossy().into_owned();
+    if args.get_bool(2) {
+        avm2_stub_method!(
+            activation,
+            "flash.net.FileReference",
+            "upload",
+            "with testUpload"
+        );
+    }
+
+    // Variables sent with POST become fields of the form, rather than the body of the request.
+    let fields = match request.body() {
+        Some((body, _)) if matches!(request.method(), NavigationMethod::Post) => {
+            form_urlencoded::parse(body)
+                .map(|(name, value)| (name.into_owned(), value.into_owned()))
+                .collect()
+        }
+        _ => Vec::new(),
+    };
+
+    let requester_url = activation
+        .caller_movie()
+        .unwrap_or_else(|| activation.context.swf.clone())
+        .url()
+        .to_string();
+    let future = activation.context.load_manager.upload_file_reference(
+        activation.context.player.clone(),
+        FileReference::Avm2(file_reference),
+        request,
+        fields,
+        field_name,
+        requester_url,
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(Value::Undefined)
+}
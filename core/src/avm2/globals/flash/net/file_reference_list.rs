@@ -0,0 +1,30 @@
+//! `flash.net.FileReferenceList` native methods
+
+use crate::avm2::globals::flash::net::file_reference::{displayed_dialog, file_filters};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::loader::FileReference;
+
+pub fn browse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let filters = file_filters(activation, args.get_value(0))?;
+    let dialog = activation
+        .context
+        .ui
+        .display_file_open_dialog(filters, true);
+    let Some(dialog) = displayed_dialog(activation, dialog)? else {
+        return Ok(false.into());
+    };
+
+    let future = activation.context.load_manager.browse_file_reference(
+        activation.context.player.clone(),
+        FileReference::Avm2List(this),
+        dialog,
+    );
+    activation.context.navigator.spawn_future(future);
+
+    Ok(true.into())
+}
@@ -1,10 +1,20 @@
-use crate::avm2::error::type_error;
-use crate::avm2::object::TObject;
+use crate::avm2::amf::{deserialize_value, serialize_value};
+use crate::avm2::error::{argument_error, type_error};
 use crate::avm2::parameters::ParametersExt;
-use crate::avm2::{Activation, Avm2, Error, Object, Value};
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::context::UpdateContext;
+use crate::local_connection::{LocalConnectionKind, LocalConnections, MAX_ARGUMENTS_SIZE};
 use crate::string::AvmString;
+use flash_lso::types::{AMFVersion, Element, Lso, Value as AmfValue};
 
-use crate::avm2_stub_method;
+/// The domain of the movie that is running this code.
+fn movie_domain(activation: &mut Activation<'_, '_>) -> Option<String> {
+    let domain = LocalConnections::domain(activation.context.swf.url());
+    if domain.is_none() {
+        tracing::error!("LocalConnection::domain: Unable to parse movie URL");
+    }
+    domain
+}
 
 /// Implements `domain` getter
 pub fn get_domain<'gc>(
@@ -12,23 +22,75 @@ pub fn get_domain<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let movie = activation.context.swf;
-
-    let domain = if let Ok(url) = url::Url::parse(movie.url()) {
-        if url.scheme() == "file" {
-            "localhost".into()
-        } else if let Some(domain) = url.domain() {
-            AvmString::new_utf8(activation.context.gc_context, domain)
-        } else {
-            // no domain?
-            "localhost".into()
-        }
-    } else {
-        tracing::error!("LocalConnection::domain: Unable to parse movie URL");
-        return Ok(Value::Null);
+    match movie_domain(activation) {
+        Some(domain) => Ok(AvmString::new_utf8(activation.context.gc_context, domain).into()),
+        None => Ok(Value::Null),
+    }
+}
+
+/// Implements `LocalConnection.connect`
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if matches!(args.get_value(0), Value::Null) {
+        return Err(Error::AvmError(type_error(
+            activation,
+            "Error #2007: Parameter connectionName must be non-null.",
+            2007,
+        )?));
+    }
+
+    let name = args.get_string(activation, 0)?.to_utf8_lossy().into_owned();
+    let Some(domain) = movie_domain(activation) else {
+        return Ok(Value::Undefined);
     };
 
-    Ok(Value::String(domain))
+    let UpdateContext {
+        local_connections,
+        local_connection,
+        ..
+    } = &mut activation.context;
+
+    let connected = local_connections.connect(
+        *local_connection,
+        LocalConnectionKind::Avm2(this),
+        &domain,
+        &name,
+    );
+    if !connected {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2082: Connect failed because the object is already connected.",
+            2082,
+        )?));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.close`
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let UpdateContext {
+        local_connections,
+        local_connection,
+        ..
+    } = &mut activation.context;
+
+    if !local_connections.close(*local_connection, LocalConnectionKind::Avm2(this)) {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2083: Close failed because the object is not connected.",
+            2083,
+        )?));
+    }
+
+    Ok(Value::Undefined)
 }
 
 /// Implements `LocalConnection.send`
@@ -53,21 +115,69 @@ pub fn send<'gc>(
         )?));
     }
 
-    avm2_stub_method!(activation, "flash.net.LocalConnection", "send");
+    let name = args.get_string(activation, 0)?.to_utf8_lossy().into_owned();
+    let method_name = args.get_string(activation, 1)?.to_utf8_lossy().into_owned();
+    let Some(domain) = movie_domain(activation) else {
+        return Ok(Value::Undefined);
+    };
+
+    let arguments = serialize_arguments(activation, &args[2..]);
+    if arguments.len() > MAX_ARGUMENTS_SIZE {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2084: The AMF encoding of the arguments cannot exceed 40K.",
+            2084,
+        )?));
+    }
 
-    let event = activation.avm2().classes().statusevent.construct(
-        activation,
-        &[
-            "status".into(),
-            false.into(),
-            false.into(),
-            Value::Null,
-            "error".into(),
-        ],
-    )?;
+    let UpdateContext {
+        local_connections,
+        local_connection,
+        ..
+    } = &mut activation.context;
 
-    // FIXME: Adding the event listener after calling `send` works in FP.
-    Avm2::dispatch_event(&mut activation.context, event, this);
+    // Whether the message was received is reported with a status event, once the
+    // current code has finished running.
+    local_connections.send(
+        *local_connection,
+        LocalConnectionKind::Avm2(this),
+        &domain,
+        &name,
+        method_name,
+        arguments,
+    );
 
     Ok(Value::Undefined)
 }
+
+/// Encodes the arguments of a message as an AMF3 LSO, with one element per argument.
+fn serialize_arguments<'gc>(activation: &mut Activation<'_, 'gc>, args: &[Value<'gc>]) -> Vec<u8> {
+    let elements = args
+        .iter()
+        .enumerate()
+        .map(|(index, arg)| {
+            // Values that can't be sent still take up their place in the arguments.
+            let value =
+                serialize_value(activation, *arg, AMFVersion::AMF3).unwrap_or(AmfValue::Undefined);
+            Element::new(index.to_string(), value)
+        })
+        .collect();
+    let mut lso = Lso::new(elements, "", AMFVersion::AMF3);
+    flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default()
+}
+
+/// Decodes the arguments of a message sent by an AVM1 or AVM2 movie.
+pub fn deserialize_arguments<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    bytes: &[u8],
+) -> Result<Vec<Value<'gc>>, Error<'gc>> {
+    let Ok(lso) = flash_lso::read::Reader::default().parse(bytes) else {
+        tracing::warn!("LocalConnection: Received arguments that couldn't be decoded");
+        return Ok(vec![]);
+    };
+
+    lso.body
+        .iter()
+        .map(|element| deserialize_value(activation, element.value()))
+        .collect()
+}
@@ -83,6 +83,70 @@ pub fn toggle_pause<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn seek<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        let offset = args.get_f64(activation, 0)?;
+
+        ns.seek(&mut activation.context, offset);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_time<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        return Ok(ns.time().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_buffer_length<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        return Ok(ns.buffer_length().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_buffer_time<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        return Ok(ns.buffer_time().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_buffer_time<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        let buffer_time = args.get_f64(activation, 0)?;
+
+        ns.set_buffer_time(activation.context.gc_context, buffer_time);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_client<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -95,6 +159,18 @@ pub fn get_client<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_meta_data<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        return Ok(ns.avm2_metadata().map_or(Value::Null, Value::from));
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn set_client<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -5,6 +5,7 @@ use crate::avm2::parameters::ParametersExt;
 use crate::avm2::string::AvmString;
 use crate::avm2::{Activation, Error, Object, TObject, Value};
 use crate::context::UpdateContext;
+use crate::socket::Sockets;
 use encoding_rs::Encoding;
 use encoding_rs::UTF_8;
 use flash_lso::amf0::read::AMF0Decoder;
@@ -39,11 +40,18 @@ pub fn connect<'gc>(
         .try_into()
         .map_err(|_| invalid_port_number(activation))?;
 
-    let UpdateContext {
-        sockets, navigator, ..
-    } = &mut activation.context;
-
-    sockets.connect_avm2(*navigator, socket, host.to_utf8_lossy().into_owned(), port);
+    let requester_url = activation
+        .caller_movie()
+        .unwrap_or_else(|| activation.context.swf.clone())
+        .url()
+        .to_string();
+    Sockets::connect_avm2(
+        &mut activation.context,
+        requester_url,
+        socket,
+        host.to_utf8_lossy().into_owned(),
+        port,
+    );
 
     Ok(Value::Undefined)
 }
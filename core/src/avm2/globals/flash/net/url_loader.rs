@@ -43,12 +43,18 @@ fn spawn_fetch<'gc>(
     data_format: DataFormat,
 ) -> Result<Value<'gc>, Error<'gc>> {
     let request = request_from_url_request(activation, url_request)?;
+    let requester_url = activation
+        .caller_movie()
+        .unwrap_or_else(|| activation.context.swf.clone())
+        .url()
+        .to_string();
 
     let future = activation.context.load_manager.load_data_into_url_loader(
         activation.context.player.clone(),
         loader_object,
         request,
         data_format,
+        requester_url,
     );
     activation.context.navigator.spawn_future(future);
     Ok(Value::Undefined)
@@ -2,6 +2,7 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::avm2_stub_method;
@@ -16,30 +17,61 @@ pub fn get_sandbox_type<'gc>(
     return Ok(AvmString::new_utf8(activation.context.gc_context, sandbox_type).into());
 }
 
+/// Lets the movies from `domains` access the calling movie.
+fn allow_domains<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+    insecure: bool,
+) -> Result<(), Error<'gc>> {
+    let domains = args
+        .iter()
+        .map(|domain| {
+            domain
+                .coerce_to_string(activation)
+                .map(|domain| domain.to_utf8_lossy().into_owned())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let movie = activation
+        .caller_movie()
+        .unwrap_or_else(|| activation.context.swf.clone());
+    activation
+        .context
+        .security
+        .allow_domain(movie.url(), &domains, insecure);
+    Ok(())
+}
+
 pub fn allow_domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "allowDomain");
+    allow_domains(activation, args, false)?;
     Ok(Value::Undefined)
 }
 
 pub fn allow_insecure_domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "allowInsecureDomain");
+    allow_domains(activation, args, true)?;
     Ok(Value::Undefined)
 }
 
 pub fn load_policy_file<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "loadPolicyFile");
+    let url = args.get_string(activation, 0)?.to_utf8_lossy().into_owned();
+    match activation.context.navigator.resolve_url(&url) {
+        Ok(url) => activation
+            .context
+            .security
+            .load_policy_file(url.to_string()),
+        Err(e) => tracing::warn!("Security.loadPolicyFile: Invalid URL {}: {}", url, e),
+    }
     Ok(Value::Undefined)
 }
 
@@ -1,12 +1,14 @@
 //! `flash.text.Font` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::error::argument_error;
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::{ArrayObject, ArrayStorage, Error};
 use crate::avm2_stub_method;
 use crate::character::Character;
+use crate::font::FontType;
 use crate::string::AvmString;
 
 /// Implements `Font.fontName`
@@ -83,14 +85,16 @@ pub fn get_font_type<'gc>(
             .avm2_class_registry()
             .class_symbol(this)
     }) {
-        if let Some(Character::Font(_)) = activation
+        if let Some(Character::Font(font)) = activation
             .context
             .library
             .library_for_movie_mut(movie)
             .character_by_id(character_id)
         {
-            //TODO: How do we distinguish between CFF and non-CFF embedded fonts?
-            return Ok("embedded".into());
+            return match font.font_type() {
+                FontType::Embedded => Ok("embedded".into()),
+                FontType::EmbeddedCFF => Ok("embeddedCFF".into()),
+            };
         }
     }
 
@@ -129,18 +133,57 @@ pub fn has_glyphs<'gc>(
 pub fn enumerate_fonts<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.text.Font", "enumerateFonts");
-    Ok(ArrayObject::from_storage(activation, ArrayStorage::new(0))?.into())
+    if args.get_bool(0) {
+        avm2_stub_method!(
+            activation,
+            "flash.text.Font",
+            "enumerateFonts",
+            "with device fonts"
+        );
+    }
+
+    let classes: Vec<_> = activation.context.library.global_font_classes().collect();
+    let mut fonts = Vec::with_capacity(classes.len());
+    for class in classes {
+        fonts.push(class.construct(activation, &[])?.into());
+    }
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&fonts))?.into())
 }
 
 /// `Font.registerFont`
 pub fn register_font<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.text.Font", "registerFont");
-    Ok(Value::Undefined)
+    let class = args.get_object(activation, 0, "font")?.as_class_object();
+
+    if let Some(class) = class {
+        if let Some((movie, character_id)) = activation
+            .context
+            .library
+            .avm2_class_registry()
+            .class_symbol(class)
+        {
+            if let Some(Character::Font(font)) = activation
+                .context
+                .library
+                .library_for_movie_mut(movie)
+                .character_by_id(character_id)
+            {
+                let font = *font;
+                activation.context.library.register_global_font(class, font);
+                return Ok(Value::Undefined);
+            }
+        }
+    }
+
+    Err(Error::AvmError(argument_error(
+        activation,
+        "Error #1508: The value specified for argument font is invalid.",
+        1508,
+    )?))
 }
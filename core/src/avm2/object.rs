@@ -40,6 +40,7 @@ mod dispatch_object;
 mod domain_object;
 mod error_object;
 mod event_object;
+mod file_reference_object;
 mod function_object;
 mod index_buffer_3d_object;
 mod loaderinfo_object;
@@ -61,6 +62,7 @@ mod textformat_object;
 mod texture_object;
 mod vector_object;
 mod vertex_buffer_3d_object;
+mod video_texture_object;
 mod xml_list_object;
 mod xml_object;
 
@@ -83,6 +85,9 @@ pub use crate::avm2::object::domain_object::{
 };
 pub use crate::avm2::object::error_object::{error_allocator, ErrorObject, ErrorObjectWeak};
 pub use crate::avm2::object::event_object::{event_allocator, EventObject, EventObjectWeak};
+pub use crate::avm2::object::file_reference_object::{
+    file_reference_allocator, FileReferenceObject, FileReferenceObjectWeak,
+};
 pub use crate::avm2::object::function_object::{
     function_allocator, FunctionObject, FunctionObjectWeak,
 };
@@ -128,6 +133,7 @@ pub use crate::avm2::object::vector_object::{vector_allocator, VectorObject, Vec
 pub use crate::avm2::object::vertex_buffer_3d_object::{
     VertexBuffer3DObject, VertexBuffer3DObjectWeak,
 };
+pub use crate::avm2::object::video_texture_object::{VideoTextureObject, VideoTextureObjectWeak};
 pub use crate::avm2::object::xml_list_object::{
     xml_list_allocator, E4XOrXml, XmlListObject, XmlListObjectWeak,
 };
@@ -170,10 +176,12 @@ pub use crate::avm2::object::xml_object::{xml_allocator, XmlObject, XmlObjectWea
         IndexBuffer3DObject(IndexBuffer3DObject<'gc>),
         VertexBuffer3DObject(VertexBuffer3DObject<'gc>),
         TextureObject(TextureObject<'gc>),
+        VideoTextureObject(VideoTextureObject<'gc>),
         Program3DObject(Program3DObject<'gc>),
         NetStreamObject(NetStreamObject<'gc>),
         ShaderDataObject(ShaderDataObject<'gc>),
-        SocketObject(SocketObject<'gc>)
+        SocketObject(SocketObject<'gc>),
+        FileReferenceObject(FileReferenceObject<'gc>)
     }
 )]
 pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy {
@@ -1363,6 +1371,10 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         None
     }
 
+    fn as_video_texture(&self) -> Option<VideoTextureObject<'gc>> {
+        None
+    }
+
     fn as_netstream(self) -> Option<NetStream<'gc>> {
         None
     }
@@ -1370,6 +1382,10 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     fn as_socket(&self) -> Option<SocketObject<'gc>> {
         None
     }
+
+    fn as_file_reference(&self) -> Option<FileReferenceObject<'gc>> {
+        None
+    }
 }
 
 pub enum ObjectPtr {}
@@ -1412,10 +1428,12 @@ impl<'gc> Object<'gc> {
             Self::IndexBuffer3DObject(o) => WeakObject::IndexBuffer3DObject(IndexBuffer3DObjectWeak(Gc::downgrade(o.0))),
             Self::VertexBuffer3DObject(o) => WeakObject::VertexBuffer3DObject(VertexBuffer3DObjectWeak(Gc::downgrade(o.0))),
             Self::TextureObject(o) => WeakObject::TextureObject(TextureObjectWeak(Gc::downgrade(o.0))),
+            Self::VideoTextureObject(o) => WeakObject::VideoTextureObject(VideoTextureObjectWeak(Gc::downgrade(o.0))),
             Self::Program3DObject(o) => WeakObject::Program3DObject(Program3DObjectWeak(Gc::downgrade(o.0))),
             Self::NetStreamObject(o) => WeakObject::NetStreamObject(NetStreamObjectWeak(GcCell::downgrade(o.0))),
             Self::ShaderDataObject(o) => WeakObject::ShaderDataObject(ShaderDataObjectWeak(Gc::downgrade(o.0))),
-            Self::SocketObject(o) => WeakObject::SocketObject(SocketObjectWeak(Gc::downgrade(o.0)))
+            Self::SocketObject(o) => WeakObject::SocketObject(SocketObjectWeak(Gc::downgrade(o.0))),
+            Self::FileReferenceObject(o) => WeakObject::FileReferenceObject(FileReferenceObjectWeak(Gc::downgrade(o.0)))
         }
     }
 }
@@ -1468,10 +1486,12 @@ pub enum WeakObject<'gc> {
     IndexBuffer3DObject(IndexBuffer3DObjectWeak<'gc>),
     VertexBuffer3DObject(VertexBuffer3DObjectWeak<'gc>),
     TextureObject(TextureObjectWeak<'gc>),
+    VideoTextureObject(VideoTextureObjectWeak<'gc>),
     Program3DObject(Program3DObjectWeak<'gc>),
     NetStreamObject(NetStreamObjectWeak<'gc>),
     ShaderDataObject(ShaderDataObjectWeak<'gc>),
     SocketObject(SocketObjectWeak<'gc>),
+    FileReferenceObject(FileReferenceObjectWeak<'gc>),
 }
 
 impl<'gc> WeakObject<'gc> {
@@ -1507,10 +1527,12 @@ impl<'gc> WeakObject<'gc> {
             Self::IndexBuffer3DObject(o) => IndexBuffer3DObject(o.0.upgrade(mc)?).into(),
             Self::VertexBuffer3DObject(o) => VertexBuffer3DObject(o.0.upgrade(mc)?).into(),
             Self::TextureObject(o) => TextureObject(o.0.upgrade(mc)?).into(),
+            Self::VideoTextureObject(o) => VideoTextureObject(o.0.upgrade(mc)?).into(),
             Self::Program3DObject(o) => Program3DObject(o.0.upgrade(mc)?).into(),
             Self::NetStreamObject(o) => NetStreamObject(o.0.upgrade(mc)?).into(),
             Self::ShaderDataObject(o) => ShaderDataObject(o.0.upgrade(mc)?).into(),
             Self::SocketObject(o) => SocketObject(o.0.upgrade(mc)?).into(),
+            Self::FileReferenceObject(o) => FileReferenceObject(o.0.upgrade(mc)?).into(),
         })
     }
 }
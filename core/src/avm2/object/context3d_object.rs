@@ -6,25 +6,26 @@ use crate::avm2::object::{Object, ObjectPtr, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::avm2_stub_method;
-use crate::bitmap::bitmap_data::BitmapData;
-use crate::context::RenderContext;
+use crate::bitmap::bitmap_data::{BitmapData, BitmapDataWrapper};
+use crate::context::{RenderContext, UpdateContext};
 use gc_arena::barrier::unlock;
 use gc_arena::lock::RefLock;
 use gc_arena::{Collect, Gc, GcCell, GcWeak, Mutation};
 use ruffle_render::backend::{
     BufferUsage, Context3D, Context3DBlendFactor, Context3DCommand, Context3DCompareMode,
-    Context3DTextureFormat, Context3DTriangleFace, Context3DVertexBufferFormat, ProgramType,
-    Texture,
+    Context3DStencilAction, Context3DTextureFormat, Context3DTriangleFace,
+    Context3DVertexBufferFormat, ProgramType, Texture,
 };
-use ruffle_render::bitmap::{Bitmap, BitmapFormat};
+use ruffle_render::bitmap::{Bitmap, BitmapFormat, BitmapHandle, PixelRegion};
 use ruffle_render::commands::CommandHandler;
+use ruffle_render::error::Error as RenderError;
 use std::cell::{Cell, Ref, RefMut};
 use std::rc::Rc;
 use swf::{Rectangle, Twips};
 
 use super::program_3d_object::Program3DObject;
 use super::texture_object::TextureObject;
-use super::{ClassObject, IndexBuffer3DObject, VertexBuffer3DObject};
+use super::{ClassObject, IndexBuffer3DObject, VertexBuffer3DObject, VideoTextureObject};
 
 #[derive(Clone, Collect, Copy)]
 #[collect(no_drop)]
@@ -362,6 +363,80 @@ impl<'gc> Context3DObject<'gc> {
         });
     }
 
+    /// Creates a bare texture handle, for textures whose contents are managed by Ruffle.
+    pub(crate) fn create_texture_handle(
+        &self,
+        width: u32,
+        height: u32,
+        format: Context3DTextureFormat,
+    ) -> Result<Rc<dyn Texture>, RenderError> {
+        self.with_context_3d(|ctx| ctx.create_texture(width, height, format, false, 0))
+    }
+
+    pub fn create_video_texture(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(VideoTextureObject::from_context(
+            activation, *self,
+        )?))
+    }
+
+    pub(crate) fn copy_bitmap_handle_to_texture(
+        &self,
+        source: BitmapHandle,
+        dest: Rc<dyn Texture>,
+    ) {
+        self.with_context_3d(|ctx| {
+            ctx.process_command(Context3DCommand::CopyBitmapHandleToTexture { source, dest })
+        });
+    }
+
+    /// Copies `source_region` of the back buffer into `destination` at `dest_point`.
+    pub(crate) fn draw_to_bitmap_data(
+        &self,
+        context: &mut UpdateContext<'_, 'gc>,
+        destination: BitmapDataWrapper<'gc>,
+        source_region: PixelRegion,
+        dest_point: (u32, u32),
+    ) {
+        let mut dirty_region = PixelRegion::for_region(
+            dest_point.0,
+            dest_point.1,
+            source_region.width(),
+            source_region.height(),
+        );
+        dirty_region.clamp(destination.width(), destination.height());
+        if dirty_region.width() == 0 || dirty_region.height() == 0 {
+            return;
+        }
+
+        let handle = destination.bitmap_handle(context.gc_context, context.renderer);
+        let mut sync_handle = None;
+        self.with_context_3d(|ctx| {
+            ctx.process_command(Context3DCommand::DrawToBitmapData {
+                destination: handle,
+                source_region,
+                dest_point,
+                sync_handle: &mut sync_handle,
+            })
+        });
+
+        if let Some(sync_handle) = sync_handle {
+            let (destination, include_dirty_area) =
+                destination.overwrite_cpu_pixels_from_gpu(context.gc_context);
+            // If we have another dirty area to preserve, expand this to include it
+            if let Some(old) = include_dirty_area {
+                dirty_region.union(old);
+            }
+            destination.write(context.gc_context).set_gpu_dirty(
+                context.gc_context,
+                sync_handle,
+                dirty_region,
+            );
+        }
+    }
+
     pub(crate) fn set_texture_at(
         &self,
         sampler: u32,
@@ -397,6 +472,40 @@ impl<'gc> Context3DObject<'gc> {
         });
     }
 
+    pub(crate) fn set_stencil_actions(
+        &self,
+        triangle_face: Context3DTriangleFace,
+        compare_mode: Context3DCompareMode,
+        action_on_both_pass: Context3DStencilAction,
+        action_on_depth_fail: Context3DStencilAction,
+        action_on_depth_pass_stencil_fail: Context3DStencilAction,
+    ) {
+        self.with_context_3d(|ctx| {
+            ctx.process_command(Context3DCommand::SetStencilActions {
+                triangle_face,
+                compare_mode,
+                action_on_both_pass,
+                action_on_depth_fail,
+                action_on_depth_pass_stencil_fail,
+            })
+        });
+    }
+
+    pub(crate) fn set_stencil_reference_value(
+        &self,
+        reference_value: u32,
+        read_mask: u32,
+        write_mask: u32,
+    ) {
+        self.with_context_3d(|ctx| {
+            ctx.process_command(Context3DCommand::SetStencilReferenceValue {
+                reference_value,
+                read_mask,
+                write_mask,
+            })
+        });
+    }
+
     pub(crate) fn create_cube_texture(
         &self,
         size: u32,
@@ -215,6 +215,28 @@ impl<'gc> EventObject<'gc> {
             )
             .unwrap() // we don't expect to break here
     }
+
+    pub fn sample_data_event(
+        activation: &mut Activation<'_, 'gc>,
+        position: f64,
+        data: Object<'gc>,
+    ) -> Object<'gc> {
+        let sample_data_cls = activation.avm2().classes().sampledataevent;
+        sample_data_cls
+            .construct(
+                activation,
+                &[
+                    "sampleData".into(),
+                    //bubbles
+                    false.into(),
+                    //cancelable
+                    false.into(),
+                    position.into(),
+                    data.into(),
+                ],
+            )
+            .unwrap() // we don't expect to break here
+    }
 }
 
 impl<'gc> TObject<'gc> for EventObject<'gc> {
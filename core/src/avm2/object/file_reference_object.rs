@@ -0,0 +1,114 @@
+use crate::avm2::object::script_object::ScriptObjectData;
+use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::{Activation, Error};
+use crate::backend::ui::FileDialogResult;
+use crate::loader::Handle;
+use gc_arena::barrier::unlock;
+use gc_arena::lock::{Lock, RefLock};
+use gc_arena::{Collect, Gc, GcWeak, Mutation};
+use std::cell::{Cell, Ref, RefCell, RefMut};
+use std::fmt;
+
+/// A class instance allocator that allocates FileReference objects.
+pub fn file_reference_allocator<'gc>(
+    class: ClassObject<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let base = ScriptObjectData::new(class).into();
+
+    Ok(FileReferenceObject(Gc::new(
+        activation.context.gc(),
+        FileReferenceObjectData {
+            base,
+            file: RefCell::new(None),
+            data: Lock::new(None),
+            loader: Cell::new(None),
+        },
+    ))
+    .into())
+}
+
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct FileReferenceObject<'gc>(pub Gc<'gc, FileReferenceObjectData<'gc>>);
+
+#[derive(Clone, Collect, Copy, Debug)]
+#[collect(no_drop)]
+pub struct FileReferenceObjectWeak<'gc>(pub GcWeak<'gc, FileReferenceObjectData<'gc>>);
+
+impl<'gc> TObject<'gc> for FileReferenceObject<'gc> {
+    fn base(&self) -> Ref<ScriptObjectData<'gc>> {
+        self.0.base.borrow()
+    }
+
+    fn base_mut(&self, mc: &Mutation<'gc>) -> RefMut<ScriptObjectData<'gc>> {
+        unlock!(Gc::write(mc, self.0), FileReferenceObjectData, base).borrow_mut()
+    }
+
+    fn as_ptr(&self) -> *const ObjectPtr {
+        Gc::as_ptr(self.0) as *const ObjectPtr
+    }
+
+    fn value_of(&self, _mc: &Mutation<'gc>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_file_reference(&self) -> Option<FileReferenceObject<'gc>> {
+        Some(*self)
+    }
+}
+
+impl<'gc> FileReferenceObject<'gc> {
+    pub fn has_file(&self) -> bool {
+        self.0.file.borrow().is_some()
+    }
+
+    /// Calls `f` with the file that was picked for this `FileReference`, if any.
+    pub fn with_file<R>(&self, f: impl FnOnce(&dyn FileDialogResult) -> R) -> Option<R> {
+        self.0.file.borrow().as_deref().map(f)
+    }
+
+    pub fn set_file(&self, file: Option<Box<dyn FileDialogResult>>) {
+        *self.0.file.borrow_mut() = file;
+    }
+
+    /// The `ByteArray` holding the contents of the file, once it has been loaded.
+    pub fn data(&self) -> Option<Object<'gc>> {
+        self.0.data.get()
+    }
+
+    pub fn set_data(&self, mc: &Mutation<'gc>, data: Option<Object<'gc>>) {
+        unlock!(Gc::write(mc, self.0), FileReferenceObjectData, data).set(data);
+    }
+
+    /// The loader running the dialog or transfer that this `FileReference` is busy with.
+    pub fn loader(&self) -> Option<Handle> {
+        self.0.loader.get()
+    }
+
+    pub fn set_loader(&self, loader: Option<Handle>) -> Option<Handle> {
+        self.0.loader.replace(loader)
+    }
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct FileReferenceObjectData<'gc> {
+    /// Base script object
+    base: RefLock<ScriptObjectData<'gc>>,
+
+    #[collect(require_static)]
+    file: RefCell<Option<Box<dyn FileDialogResult>>>,
+
+    data: Lock<Option<Object<'gc>>>,
+
+    #[collect(require_static)]
+    loader: Cell<Option<Handle>>,
+}
+
+impl fmt::Debug for FileReferenceObject<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FileReferenceObject")
+    }
+}
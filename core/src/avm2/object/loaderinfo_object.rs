@@ -83,6 +83,10 @@ pub struct LoaderInfoObjectData<'gc> {
     /// The loaded stream that this gets its info from.
     loaded_stream: Option<LoaderStream<'gc>>,
 
+    /// How much of the movie has been downloaded, while it's still too
+    /// incomplete to be parsed.
+    bytes_downloaded: Option<usize>,
+
     loader: Option<Object<'gc>>,
 
     /// Whether or not we've fired our 'init' event
@@ -116,6 +120,7 @@ impl<'gc> LoaderInfoObject<'gc> {
             LoaderInfoObjectData {
                 base,
                 loaded_stream,
+                bytes_downloaded: None,
                 loader,
                 init_event_fired: false,
                 complete_event_fired: false,
@@ -160,6 +165,7 @@ impl<'gc> LoaderInfoObject<'gc> {
             LoaderInfoObjectData {
                 base,
                 loaded_stream: Some(LoaderStream::NotYetLoaded(movie, root_clip, is_stage)),
+                bytes_downloaded: None,
                 loader,
                 init_event_fired: false,
                 complete_event_fired: false,
@@ -262,7 +268,21 @@ impl<'gc> LoaderInfoObject<'gc> {
     }
 
     pub fn set_loader_stream(&self, stream: LoaderStream<'gc>, mc: &Mutation<'gc>) {
-        self.0.write(mc).loaded_stream = Some(stream);
+        let mut write = self.0.write(mc);
+        write.loaded_stream = Some(stream);
+        write.bytes_downloaded = None;
+    }
+
+    /// How much of the movie has been downloaded, if it's still downloading.
+    pub fn bytes_downloaded(&self) -> Option<usize> {
+        self.0.read().bytes_downloaded
+    }
+
+    /// Report how much of the movie has been downloaded, before it can be parsed.
+    ///
+    /// This is reset by the next call to `set_loader_stream`.
+    pub fn set_bytes_downloaded(&self, bytes_downloaded: usize, mc: &Mutation<'gc>) {
+        self.0.write(mc).bytes_downloaded = Some(bytes_downloaded);
     }
 
     pub fn unload(&self, activation: &mut Activation<'_, 'gc>) {
@@ -29,6 +29,7 @@ pub fn sound_allocator<'gc>(
             sound_data: SoundData::NotLoaded {
                 queued_plays: Vec::new(),
             },
+            extract_position: 0,
         },
     ))
     .into())
@@ -58,6 +59,9 @@ pub struct SoundObjectData<'gc> {
 
     /// The sound this object holds.
     sound_data: SoundData<'gc>,
+
+    /// The sample frame (at 44.1kHz) that the next call to `extract` continues from.
+    extract_position: u32,
 }
 
 #[derive(Collect)]
@@ -92,6 +96,15 @@ impl<'gc> SoundObject<'gc> {
         }
     }
 
+    /// The sample frame that `Sound.extract` continues from when no start position is given.
+    pub fn extract_position(self) -> u32 {
+        self.0.read().extract_position
+    }
+
+    pub fn set_extract_position(self, mc: &Mutation<'gc>, position: u32) {
+        self.0.write(mc).extract_position = position;
+    }
+
     /// Returns `true` if a `SoundChannel` should be returned back to the AVM2 caller.
     pub fn play(
         self,
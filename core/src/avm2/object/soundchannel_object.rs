@@ -111,6 +111,13 @@ impl<'gc> SoundChannelObject<'gc> {
         write.position
     }
 
+    /// Set the position of the sound in milliseconds.
+    ///
+    /// This is used to report the final position of a sound that has finished playing.
+    pub fn set_position(self, gc_context: &Mutation<'gc>, position: f64) {
+        self.0.write(gc_context).position = position;
+    }
+
     pub fn instance(self) -> Option<SoundInstanceHandle> {
         match &self.0.read().sound_channel_data {
             SoundChannelData::NotLoaded { .. } => None,
@@ -0,0 +1,172 @@
+//! Object representation for VideoTexture objects
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::script_object::ScriptObjectData;
+use crate::avm2::object::{EventObject, Object, ObjectPtr, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::{Avm2, Error};
+use crate::context::UpdateContext;
+use crate::streams::NetStream;
+use gc_arena::barrier::unlock;
+use gc_arena::lock::{Lock, RefLock};
+use gc_arena::{Collect, Gc, GcWeak, Mutation};
+use ruffle_render::backend::{Context3DTextureFormat, Texture};
+use ruffle_render::bitmap::BitmapInfo;
+use std::cell::{Ref, RefCell, RefMut};
+use std::rc::Rc;
+
+use super::Context3DObject;
+
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct VideoTextureObject<'gc>(pub Gc<'gc, VideoTextureObjectData<'gc>>);
+
+#[derive(Clone, Collect, Copy, Debug)]
+#[collect(no_drop)]
+pub struct VideoTextureObjectWeak<'gc>(pub GcWeak<'gc, VideoTextureObjectData<'gc>>);
+
+impl<'gc> VideoTextureObject<'gc> {
+    pub fn from_context(
+        activation: &mut Activation<'_, 'gc>,
+        context3d: Context3DObject<'gc>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let class = activation.avm2().classes().videotexture;
+
+        let this: Object<'gc> = VideoTextureObject(Gc::new(
+            activation.gc(),
+            VideoTextureObjectData {
+                base: RefLock::new(ScriptObjectData::new(class)),
+                context3d,
+                stream: Lock::new(None),
+                frame: RefCell::new(None),
+            },
+        ))
+        .into();
+        this.install_instance_slots(activation.gc());
+
+        class.call_native_init(this.into(), &[], activation)?;
+
+        Ok(this)
+    }
+
+    /// The texture holding the most recent frame, if one has been received yet.
+    pub fn handle(&self) -> Option<Rc<dyn Texture>> {
+        self.0
+            .frame
+            .borrow()
+            .as_ref()
+            .map(|frame| frame.texture.clone())
+    }
+
+    pub fn video_size(&self) -> (u32, u32) {
+        self.0
+            .frame
+            .borrow()
+            .as_ref()
+            .map_or((0, 0), |frame| (frame.width, frame.height))
+    }
+
+    pub fn attach_net_stream(self, mc: &Mutation<'gc>, stream: Option<NetStream<'gc>>) {
+        if let Some(old_stream) = self.0.stream.get() {
+            old_stream.detach_video_texture(mc, self);
+        }
+        unlock!(Gc::write(mc, self.0), VideoTextureObjectData, stream).set(stream);
+        if let Some(stream) = stream {
+            stream.attach_video_texture(mc, self);
+        }
+    }
+
+    /// Copies a newly decoded video frame into this texture, and fires `textureReady`.
+    ///
+    /// The backing texture is re-created whenever the size of the video changes.
+    /// Samplers that were bound to the old texture keep showing the old frame
+    /// until `setTextureAt` is called again.
+    pub fn update_frame(self, context: &mut UpdateContext<'_, 'gc>, bitmap: BitmapInfo) {
+        let width = bitmap.width as u32;
+        let height = bitmap.height as u32;
+
+        let texture = {
+            let mut frame = self.0.frame.borrow_mut();
+            match &*frame {
+                Some(frame) if frame.width == width && frame.height == height => {
+                    frame.texture.clone()
+                }
+                _ => {
+                    let texture = match self.0.context3d.create_texture_handle(
+                        width,
+                        height,
+                        Context3DTextureFormat::Bgra,
+                    ) {
+                        Ok(texture) => texture,
+                        Err(e) => {
+                            tracing::error!("Failed to create VideoTexture backing texture: {e}");
+                            return;
+                        }
+                    };
+                    *frame = Some(VideoFrame {
+                        texture: texture.clone(),
+                        width,
+                        height,
+                    });
+                    texture
+                }
+            }
+        };
+
+        self.0
+            .context3d
+            .copy_bitmap_handle_to_texture(bitmap.handle, texture);
+
+        let texture_ready_evt = EventObject::bare_default_event(context, "textureReady");
+        Avm2::dispatch_event(context, texture_ready_evt, self.into());
+    }
+}
+
+struct VideoFrame {
+    texture: Rc<dyn Texture>,
+    width: u32,
+    height: u32,
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct VideoTextureObjectData<'gc> {
+    /// Base script object
+    base: RefLock<ScriptObjectData<'gc>>,
+
+    context3d: Context3DObject<'gc>,
+
+    /// The `NetStream` providing frames for this texture.
+    stream: Lock<Option<NetStream<'gc>>>,
+
+    #[collect(require_static)]
+    frame: RefCell<Option<VideoFrame>>,
+}
+
+impl<'gc> TObject<'gc> for VideoTextureObject<'gc> {
+    fn base(&self) -> Ref<ScriptObjectData<'gc>> {
+        self.0.base.borrow()
+    }
+
+    fn base_mut(&self, mc: &Mutation<'gc>) -> RefMut<ScriptObjectData<'gc>> {
+        unlock!(Gc::write(mc, self.0), VideoTextureObjectData, base).borrow_mut()
+    }
+
+    fn as_ptr(&self) -> *const ObjectPtr {
+        Gc::as_ptr(self.0) as *const ObjectPtr
+    }
+
+    fn value_of(&self, _mc: &Mutation<'gc>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_video_texture(&self) -> Option<VideoTextureObject<'gc>> {
+        Some(*self)
+    }
+}
+
+impl std::fmt::Debug for VideoTextureObject<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "VideoTexture")
+    }
+}
@@ -1,4 +1,5 @@
 pub mod audio;
+pub mod local_connection;
 pub mod log;
 pub mod navigator;
 pub mod storage;
@@ -1,6 +1,11 @@
 use crate::{
     avm1::SoundObject,
-    avm2::{Avm2, EventObject as Avm2EventObject, SoundChannelObject},
+    avm2::{
+        bytearray::{ByteArrayStorage, Endian},
+        object::ByteArrayObject,
+        Activation as Avm2Activation, Avm2, EventObject as Avm2EventObject, Object as Avm2Object,
+        SoundChannelObject, TObject as _,
+    },
     buffer::Substream,
     context::UpdateContext,
     display_object::{self, DisplayObject, MovieClip, TDisplayObject},
@@ -131,6 +136,30 @@ pub trait AudioBackend: Downcast {
         stream_info: &SoundStreamInfo,
     ) -> Result<SoundInstanceHandle, DecodeError>;
 
+    /// Starts playing a sound whose audio is generated on demand, such as by
+    /// the `sampleData` event of an AVM2 `Sound`.
+    ///
+    /// Audio is supplied to the sound with `append_dynamic_sound_data`.
+    fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, DecodeError>;
+
+    /// Appends 44.1KHz stereo sample frames to a sound started with
+    /// `start_dynamic_sound`.
+    ///
+    /// If `is_final` is set, no more audio will be supplied, and the sound
+    /// ends once all appended frames have played.
+    fn append_dynamic_sound_data(
+        &mut self,
+        instance: SoundInstanceHandle,
+        frames: &[[f32; 2]],
+        is_final: bool,
+    );
+
+    /// Get the number of sample frames that were appended to a dynamic sound,
+    /// but have not been played yet.
+    /// Returns `None` if the sound is not/no longer playing, or if the
+    /// backend does not consume audio.
+    fn dynamic_sound_buffered_frames(&self, instance: SoundInstanceHandle) -> Option<usize>;
+
     /// Stops a playing sound instance.
     /// No-op if the sound is not playing.
     fn stop_sound(&mut self, sound: SoundInstanceHandle);
@@ -151,6 +180,17 @@ pub trait AudioBackend: Downcast {
     /// This is specifically measured in compressed bytes.
     fn get_sound_size(&self, sound: SoundHandle) -> Option<u32>;
 
+    /// Decode raw sample frames from a given sound.
+    ///
+    /// Frames are returned as stereo 44.1kHz samples, starting at `start_frame` and
+    /// containing at most `num_frames` frames. Returns `None` if the sound cannot be decoded.
+    fn extract_sound_data(
+        &self,
+        sound: SoundHandle,
+        start_frame: u32,
+        num_frames: u32,
+    ) -> Option<Vec<[f32; 2]>>;
+
     /// Get the sound format that a given sound was added with.
     fn get_sound_format(&self, sound: SoundHandle) -> Option<&swf::SoundFormat>;
 
@@ -290,6 +330,22 @@ impl AudioBackend for NullAudioBackend {
         Ok(SoundInstanceHandle::from_raw_parts(0, 0))
     }
 
+    fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, DecodeError> {
+        Ok(SoundInstanceHandle::from_raw_parts(0, 0))
+    }
+
+    fn append_dynamic_sound_data(
+        &mut self,
+        _instance: SoundInstanceHandle,
+        _frames: &[[f32; 2]],
+        _is_final: bool,
+    ) {
+    }
+
+    fn dynamic_sound_buffered_frames(&self, _instance: SoundInstanceHandle) -> Option<usize> {
+        None
+    }
+
     fn stop_sound(&mut self, _sound: SoundInstanceHandle) {}
 
     fn stop_all_sounds(&mut self) {}
@@ -311,6 +367,15 @@ impl AudioBackend for NullAudioBackend {
         }
     }
 
+    fn extract_sound_data(
+        &self,
+        _sound: SoundHandle,
+        _start_frame: u32,
+        _num_frames: u32,
+    ) -> Option<Vec<[f32; 2]>> {
+        None
+    }
+
     fn get_sound_format(&self, sound: SoundHandle) -> Option<&swf::SoundFormat> {
         self.sounds.get(sound).map(|s| &s.format)
     }
@@ -376,6 +441,19 @@ impl<'gc> AudioManager<'gc> {
     /// The player will adjust animation speed to stay within this many seconds of the audio track.
     pub const STREAM_DEFAULT_SYNC_THRESHOLD: f64 = 0.2;
 
+    /// The minimum number of sample frames a `sampleData` event handler must supply.
+    ///
+    /// Supplying fewer frames than this ends the sound once they have played.
+    pub const MIN_SAMPLE_DATA_FRAMES: usize = 2048;
+
+    /// The maximum number of sample frames a `sampleData` event handler may supply.
+    ///
+    /// Like Flash Player, any frames beyond this are dropped.
+    pub const MAX_SAMPLE_DATA_FRAMES: usize = 8192;
+
+    /// The sample rate of audio supplied by `sampleData` event handlers.
+    pub const SAMPLE_DATA_RATE: f64 = 44100.0;
+
     pub fn new() -> Self {
         Self {
             sounds: Vec::with_capacity(Self::MAX_SOUNDS),
@@ -425,6 +503,13 @@ impl<'gc> AudioManager<'gc> {
                 }
 
                 if let Some(object) = sound.avm2_object {
+                    // Generated sounds end after the last sample frame supplied to them.
+                    if let Some(source) = &sound.dynamic_source {
+                        object.set_position(
+                            context.gc_context,
+                            source.position * 1000.0 / Self::SAMPLE_DATA_RATE,
+                        );
+                    }
                     event_targets.push(object);
                 }
 
@@ -441,6 +526,152 @@ impl<'gc> AudioManager<'gc> {
         context.audio_manager.update_sound_transforms(context.audio);
     }
 
+    /// Request more audio for sounds generated by AVM2 `sampleData` events.
+    ///
+    /// Events are dispatched until enough audio is buffered to last until the
+    /// audio backend next consumes audio, which should be called once per tick.
+    pub fn update_dynamic_sounds(context: &mut UpdateContext<'_, 'gc>) {
+        let target_frames = context
+            .audio
+            .position_resolution()
+            .map(|duration| (duration.as_secs_f64() * Self::SAMPLE_DATA_RATE).ceil() as usize)
+            .unwrap_or_default()
+            .max(Self::MIN_SAMPLE_DATA_FRAMES);
+
+        // Event handlers may start or stop sounds, so don't hold on to the list.
+        let sources: Vec<_> = context
+            .audio_manager
+            .sounds
+            .iter()
+            .filter_map(|sound| {
+                let source = sound.dynamic_source.as_ref()?;
+                (!source.is_final).then_some((sound.instance, source.sound))
+            })
+            .collect();
+
+        for (instance, sound_object) in sources {
+            let Some(mut buffered_frames) = context.audio.dynamic_sound_buffered_frames(instance)
+            else {
+                continue;
+            };
+
+            while buffered_frames < target_frames {
+                // A previous event handler may have stopped this sound.
+                let Some(position) = context
+                    .audio_manager
+                    .dynamic_source_mut(instance)
+                    .map(|source| source.position)
+                else {
+                    break;
+                };
+
+                let frames = Self::request_sample_data(context, sound_object, position);
+                let is_final = frames.len() < Self::MIN_SAMPLE_DATA_FRAMES;
+                context
+                    .audio
+                    .append_dynamic_sound_data(instance, &frames, is_final);
+                if let Some(source) = context.audio_manager.dynamic_source_mut(instance) {
+                    source.position += frames.len() as f64;
+                    source.is_final = is_final;
+                }
+
+                if is_final {
+                    break;
+                }
+                buffered_frames += frames.len();
+            }
+        }
+    }
+
+    /// Dispatch a `sampleData` event to an AVM2 `Sound`, and return the
+    /// sample frames that the event handlers wrote to the event's `ByteArray`.
+    fn request_sample_data(
+        context: &mut UpdateContext<'_, 'gc>,
+        sound_object: Avm2Object<'gc>,
+        position: f64,
+    ) -> Vec<[f32; 2]> {
+        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+        let data = match ByteArrayObject::from_storage(&mut activation, ByteArrayStorage::new()) {
+            Ok(data) => data,
+            Err(e) => {
+                tracing::error!("Unable to create sampleData ByteArray: {:?}", e);
+                return Vec::new();
+            }
+        };
+        let event = Avm2EventObject::sample_data_event(&mut activation, position, data);
+        Avm2::dispatch_event(&mut activation.context, event, sound_object);
+
+        let Some(bytearray) = data.as_bytearray() else {
+            return Vec::new();
+        };
+        let read_f32: fn([u8; 4]) -> f32 = match bytearray.endian() {
+            Endian::Big => f32::from_be_bytes,
+            Endian::Little => f32::from_le_bytes,
+        };
+
+        // Sample data is written as interleaved stereo 32-bit floats.
+        let frames = bytearray.bytes().chunks_exact(8);
+        if frames.len() > Self::MAX_SAMPLE_DATA_FRAMES {
+            tracing::warn!(
+                "sampleData event supplied {} sample frames, only using the first {}",
+                frames.len(),
+                Self::MAX_SAMPLE_DATA_FRAMES
+            );
+        }
+        frames
+            .take(Self::MAX_SAMPLE_DATA_FRAMES)
+            .map(|frame| {
+                [
+                    read_f32([frame[0], frame[1], frame[2], frame[3]]),
+                    read_f32([frame[4], frame[5], frame[6], frame[7]]),
+                ]
+            })
+            .collect()
+    }
+
+    /// Starts a sound whose audio is generated by the `sampleData` events of
+    /// the given AVM2 `Sound`, the first of which is at `start_position` sample frames.
+    pub fn start_dynamic_sound(
+        &mut self,
+        audio: &mut dyn AudioBackend,
+        sound_object: Avm2Object<'gc>,
+        start_position: f64,
+    ) -> Option<SoundInstanceHandle> {
+        if self.sounds.len() < Self::MAX_SOUNDS {
+            let handle = audio.start_dynamic_sound().ok()?;
+            let instance = SoundInstance {
+                sound: None,
+                instance: handle,
+                display_object: None,
+                transform: display_object::SoundTransform::default(),
+                avm1_object: None,
+                avm2_object: None,
+                stream_start_frame: None,
+                dynamic_source: Some(DynamicSoundSource {
+                    sound: sound_object,
+                    position: start_position,
+                    is_final: false,
+                }),
+            };
+            audio.set_sound_transform(handle, self.transform_for_sound(&instance));
+            self.sounds.push(instance);
+            Some(handle)
+        } else {
+            None
+        }
+    }
+
+    fn dynamic_source_mut(
+        &mut self,
+        instance: SoundInstanceHandle,
+    ) -> Option<&mut DynamicSoundSource<'gc>> {
+        self.sounds
+            .iter_mut()
+            .find(|other| other.instance == instance)?
+            .dynamic_source
+            .as_mut()
+    }
+
     pub fn start_sound(
         &mut self,
         audio: &mut dyn AudioBackend,
@@ -459,6 +690,7 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object,
                 avm2_object: None,
                 stream_start_frame: None,
+                dynamic_source: None,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -556,6 +788,7 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object: None,
                 avm2_object: None,
                 stream_start_frame: Some(clip_frame),
+                dynamic_source: None,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -582,6 +815,7 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object: None,
                 avm2_object: None,
                 stream_start_frame: None,
+                dynamic_source: None,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -765,6 +999,23 @@ pub struct SoundInstance<'gc> {
     avm2_object: Option<SoundChannelObject<'gc>>,
 
     stream_start_frame: Option<u16>,
+
+    /// The source of this sound's audio, if it is generated by ActionScript.
+    dynamic_source: Option<DynamicSoundSource<'gc>>,
+}
+
+/// The state of a sound whose audio is generated by AVM2 `sampleData` events.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+struct DynamicSoundSource<'gc> {
+    /// The AVM2 `Sound` object that `sampleData` events are dispatched to.
+    sound: Avm2Object<'gc>,
+
+    /// The number of sample frames generated so far.
+    position: f64,
+
+    /// Whether an event handler supplied too few sample frames, ending the sound.
+    is_final: bool,
 }
 
 /// A sound transform for a playing sound, for use by audio backends.
@@ -1,18 +1,26 @@
 //! Audio decoders.
 
+#[cfg(feature = "aac")]
+mod aac;
 mod adpcm;
 #[cfg(feature = "mp3")]
 mod mp3;
 #[cfg(feature = "nellymoser")]
 mod nellymoser;
 mod pcm;
+#[cfg(feature = "speex")]
+mod speex;
 
+#[cfg(feature = "aac")]
+pub use aac::AacDecoder;
 pub use adpcm::AdpcmDecoder;
 #[cfg(feature = "mp3")]
 pub use mp3::{mp3_metadata, Mp3Decoder};
 #[cfg(feature = "nellymoser")]
 pub use nellymoser::NellymoserDecoder;
 pub use pcm::PcmDecoder;
+#[cfg(feature = "speex")]
+pub use speex::SpeexDecoder;
 
 use crate::backend::audio::{SoundStreamInfo, SoundStreamWrapping};
 use crate::buffer::{Slice, SliceCursor, Substream, SubstreamChunksIter};
@@ -30,6 +38,14 @@ pub enum Error {
     #[error("Couldn't decode ADPCM")]
     InvalidAdpcm(#[from] adpcm::Error),
 
+    #[cfg(feature = "aac")]
+    #[error("Couldn't decode AAC")]
+    InvalidAac(#[from] aac::Error),
+
+    #[cfg(feature = "speex")]
+    #[error("Couldn't decode Speex")]
+    InvalidSpeex(#[from] speex::Error),
+
     #[error("Unhandled compression {0:?}")]
     UnhandledCompression(AudioCompression),
 
@@ -80,6 +96,8 @@ pub fn make_decoder<R: 'static + Read + Send + Sync>(
         AudioCompression::Nellymoser => {
             Box::new(NellymoserDecoder::new(data, format.sample_rate.into()))
         }
+        #[cfg(feature = "speex")]
+        AudioCompression::Speex => Box::new(SpeexDecoder::new(data)?),
         _ => return Err(Error::UnhandledCompression(format.compression)),
     };
     Ok(decoder)
@@ -491,12 +509,12 @@ pub fn make_substream_decoder(
     stream_info: &SoundStreamInfo,
     data_stream: Substream,
 ) -> Result<Box<dyn Decoder + Send>, Error> {
-    let decoder: Box<dyn Decoder + Send> =
-        if stream_info.stream_format.compression == AudioCompression::Adpcm {
-            Box::new(AdpcmSubstreamDecoder::new(stream_info, data_stream)?)
-        } else {
-            Box::new(StandardSubstreamDecoder::new(stream_info, data_stream)?)
-        };
+    let decoder: Box<dyn Decoder + Send> = match stream_info.stream_format.compression {
+        AudioCompression::Adpcm => Box::new(AdpcmSubstreamDecoder::new(stream_info, data_stream)?),
+        #[cfg(feature = "aac")]
+        AudioCompression::Aac => Box::new(AacSubstreamDecoder::new(stream_info, data_stream)?),
+        _ => Box::new(StandardSubstreamDecoder::new(stream_info, data_stream)?),
+    };
     Ok(decoder)
 }
 
@@ -600,3 +618,74 @@ impl Iterator for AdpcmSubstreamDecoder {
         }
     }
 }
+
+/// Raw AAC frames are not self-delimiting, so each chunk of the `Substream`
+/// is decoded as a single packet.
+///
+/// The first chunk must contain the `AudioSpecificConfig` used to set up the
+/// decoder, which FLV stores in the AAC sequence header.
+#[cfg(feature = "aac")]
+pub struct AacSubstreamDecoder {
+    tag_reader: SubstreamTagReader,
+    decoder: AacDecoder,
+
+    /// The interleaved samples of the most recently decoded packet.
+    samples: Vec<i16>,
+
+    /// The index of the next sample frame to return from `samples`.
+    cur_sample_frame: usize,
+}
+
+#[cfg(feature = "aac")]
+impl AacSubstreamDecoder {
+    fn new(stream_info: &SoundStreamInfo, data_stream: Substream) -> Result<Self, Error> {
+        let empty_buffer = data_stream.buffer().to_empty_slice();
+        let mut tag_reader = SubstreamTagReader::new(stream_info, data_stream);
+        let audio_specific_config = tag_reader.next().unwrap_or(empty_buffer);
+        let decoder = AacDecoder::new(&audio_specific_config.data())?;
+        Ok(Self {
+            tag_reader,
+            decoder,
+            samples: Vec::new(),
+            cur_sample_frame: 0,
+        })
+    }
+}
+
+#[cfg(feature = "aac")]
+impl Decoder for AacSubstreamDecoder {
+    fn num_channels(&self) -> u8 {
+        self.decoder.num_channels()
+    }
+    fn sample_rate(&self) -> u16 {
+        self.decoder.sample_rate()
+    }
+}
+
+#[cfg(feature = "aac")]
+impl Iterator for AacSubstreamDecoder {
+    type Item = [i16; 2];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let num_channels = usize::from(self.decoder.num_channels());
+        while (self.cur_sample_frame + 1) * num_channels > self.samples.len() {
+            let audio_data = self.tag_reader.next()?;
+            self.samples.clear();
+            self.cur_sample_frame = 0;
+            match self.decoder.decode_packet(&audio_data.data()) {
+                Ok(samples) => self.samples.extend_from_slice(samples),
+                // Skip corrupt packets rather than ending the stream.
+                Err(e) => tracing::warn!("Couldn't decode AAC packet: {}", e),
+            }
+        }
+
+        let i = self.cur_sample_frame * num_channels;
+        self.cur_sample_frame += 1;
+        if num_channels == 1 {
+            Some([self.samples[i], self.samples[i]])
+        } else {
+            Some([self.samples[i], self.samples[i + 1]])
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use symphonia::core::{audio, codecs, errors, formats::Packet};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Couldn't decode AAC frame")]
+    FrameDecode(#[from] errors::Error),
+
+    #[error("Missing AAC sequence header")]
+    MissingSequenceHeader,
+
+    #[error("Invalid sample rate")]
+    InvalidSampleRate,
+
+    #[error("Invalid channels")]
+    InvalidChannels,
+}
+
+/// Decoder for raw AAC frames, such as the ones contained in FLV audio tags.
+///
+/// Unlike other decoders, this is fed one packet at a time, as raw AAC frames
+/// are not self-delimiting.
+pub struct AacDecoder {
+    decoder: Box<dyn codecs::Decoder>,
+    sample_buf: audio::SampleBuffer<i16>,
+    sample_rate: u16,
+    num_channels: u8,
+}
+
+impl AacDecoder {
+    // AAC frames contain 1024 samples, or 2048 with SBR.
+    const SAMPLE_BUFFER_DURATION: u64 = 2048;
+
+    /// Creates a decoder from an `AudioSpecificConfig`, which FLV stores in
+    /// the AAC sequence header.
+    pub fn new(audio_specific_config: &[u8]) -> Result<Self, Error> {
+        if audio_specific_config.len() < 2 {
+            return Err(Error::MissingSequenceHeader);
+        }
+
+        let mut codec_params = codecs::CodecParameters::new();
+        codec_params
+            .for_codec(codecs::CODEC_TYPE_AAC)
+            .with_extra_data(audio_specific_config.into());
+        let decoder = symphonia::default::get_codecs().make(&codec_params, &Default::default())?;
+
+        let codec_params = decoder.codec_params();
+        let sample_rate = codec_params.sample_rate.ok_or(Error::InvalidSampleRate)?;
+        let channels = codec_params.channels.ok_or(Error::InvalidChannels)?;
+        Ok(Self {
+            sample_buf: audio::SampleBuffer::new(
+                Self::SAMPLE_BUFFER_DURATION,
+                audio::SignalSpec::new(sample_rate, channels),
+            ),
+            num_channels: channels
+                .count()
+                .try_into()
+                .map_err(|_| Error::InvalidChannels)?,
+            sample_rate: sample_rate
+                .try_into()
+                .map_err(|_| Error::InvalidSampleRate)?,
+            decoder,
+        })
+    }
+
+    /// The number of channels of the decoded audio.
+    pub fn num_channels(&self) -> u8 {
+        self.num_channels
+    }
+
+    /// The sample rate of the decoded audio.
+    pub fn sample_rate(&self) -> u16 {
+        self.sample_rate
+    }
+
+    /// Decodes a single raw AAC frame, returning its interleaved samples.
+    pub fn decode_packet(&mut self, data: &[u8]) -> Result<&[i16], Error> {
+        let packet = Packet::new_from_slice(0, 0, 0, data);
+        let decoded = self.decoder.decode(&packet)?;
+        if self.sample_buf.capacity() < decoded.capacity() {
+            // Ensure our buffer has enough space for the decoded samples.
+            self.sample_buf = audio::SampleBuffer::new(decoded.capacity() as u64, *decoded.spec());
+        }
+        self.sample_buf.copy_interleaved_ref(decoded);
+        Ok(self.sample_buf.samples())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The `AudioSpecificConfig` of 22050Hz mono AAC-LC.
+    const CONFIG: &[u8] = &[0x13, 0x88];
+
+    /// Four raw frames of a 440Hz sine wave, encoded with the above config.
+    /// The encoder delay means the sine only begins in the last frame.
+    const SINE: &[&[u8]] = &[
+        &[
+            0x01, 0x40, 0x42, 0x80, 0xA3, 0x7A, 0x38, 0x85, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D,
+            0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D,
+            0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D,
+            0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D,
+            0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D,
+            0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D,
+            0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2D, 0x2F,
+        ],
+        &[
+            0x01, 0x2A, 0x97, 0x98, 0x3C, 0xBC, 0x36, 0xD4, 0x23, 0xE2, 0xE1, 0x30, 0x8B, 0xE3,
+            0xAD, 0x65, 0x5D, 0x5E, 0xFD, 0x7F, 0x1F, 0xF3, 0x74, 0x5C, 0x3E, 0xDF, 0xC7, 0xFC,
+            0xEB, 0x7A, 0xE9, 0x15, 0x92, 0xD6, 0x45, 0x5A, 0x02, 0xAE, 0x01, 0x03, 0x5D, 0x02,
+            0x24, 0xB9, 0x8F, 0xF3, 0xFB, 0x2B, 0xF0, 0x1F, 0x84, 0xCC, 0x1F, 0x11, 0xB1, 0x36,
+            0x8B, 0xD1, 0xAC, 0xB7, 0xEF, 0xA5, 0x29, 0x4A, 0x2A, 0x3D, 0xE2, 0x28, 0xB4, 0xBA,
+            0x1D, 0x2D, 0xD2, 0x4D, 0x9F, 0x9D, 0x1F, 0x8E, 0x00, 0xCB, 0x1F, 0xDB, 0x70, 0x9D,
+            0x8A, 0x54, 0x77, 0x28, 0x2D, 0x15, 0x7C, 0xD5, 0x2B, 0xCE, 0x36, 0x8D, 0xAB, 0x05,
+            0xAC, 0x16, 0xB0, 0x5A, 0xE0, 0x18, 0xB3, 0x2F, 0xCF, 0x8A, 0xEE, 0x4A, 0x99, 0x09,
+            0x88, 0xDC,
+        ],
+        &[
+            0x01, 0x16, 0xF2, 0xA9, 0x16, 0x4A, 0x33, 0x10, 0x42, 0x04, 0x10, 0x82, 0x55, 0x33,
+            0x19, 0xDF, 0x7E, 0xFE, 0xBF, 0x9E, 0xBC, 0xF1, 0xC4, 0x52, 0xAF, 0x7F, 0x4B, 0x64,
+            0xA3, 0x07, 0x0F, 0x85, 0x6A, 0xD5, 0xAA, 0x90, 0x19, 0x59, 0xB1, 0x5B, 0x56, 0xD5,
+            0x62, 0x2F, 0x23, 0x91, 0xFC, 0x9C, 0xFB, 0xF0, 0xB4, 0xED, 0xBA, 0x0F, 0xBA, 0x39,
+            0xBF, 0x8D, 0xDE, 0x5A, 0x3A, 0x9A, 0xE8, 0x56, 0x25, 0x47, 0xB1, 0x9D, 0x9D, 0x9D,
+            0xCA, 0x6C, 0x2A, 0x40, 0x92, 0xB3, 0x67, 0x58, 0xAC, 0xAA, 0x79, 0x50, 0x62, 0xB5,
+            0x91, 0x9D, 0xF2, 0x61, 0x20, 0xB5, 0x38, 0xE3, 0x8B, 0x32, 0x13, 0xD7, 0x86, 0x0F,
+            0x03, 0x30, 0xE0,
+        ],
+        &[
+            0x01, 0x3C, 0x32, 0x85, 0x36, 0x23, 0x50, 0x14, 0x44, 0x03, 0x10, 0x80, 0x84, 0x20,
+            0x11, 0x08, 0x7F, 0xF3, 0x7F, 0x93, 0xFD, 0x78, 0xC1, 0xFF, 0xDA, 0x82, 0x48, 0x80,
+            0x2F, 0xBE, 0x80, 0x4A, 0x08, 0x22, 0xCD, 0xE4, 0xEE, 0x6C, 0x20, 0x35, 0xB5, 0xA5,
+            0xAC, 0x03, 0xCC, 0x16, 0xA6, 0xEA, 0x2D, 0xE9, 0xAD, 0xC4, 0xA3, 0x60, 0x05, 0xE9,
+            0x58, 0xB3, 0x08, 0x52, 0xD2, 0xD2, 0xD2, 0xD2, 0xF0,
+        ],
+    ];
+
+    #[test]
+    fn decode_frames() {
+        let mut decoder = AacDecoder::new(CONFIG).unwrap();
+        assert_eq!(decoder.num_channels(), 1);
+        assert_eq!(decoder.sample_rate(), 22050);
+
+        let mut loudest = 0;
+        for frame in SINE {
+            let samples = decoder.decode_packet(frame).unwrap();
+            assert_eq!(samples.len(), 1024);
+            loudest = samples.iter().fold(loudest, |loudest, sample| {
+                loudest.max(sample.unsigned_abs())
+            });
+        }
+        assert!(loudest > 4000);
+    }
+
+    #[test]
+    fn missing_sequence_header() {
+        assert!(matches!(
+            AacDecoder::new(&[]),
+            Err(Error::MissingSequenceHeader)
+        ));
+    }
+}
@@ -0,0 +1,287 @@
+//! Speex decoding, using the Speex reference library.
+
+use super::{Decoder, SeekableDecoder};
+use speex_sys as ffi;
+use std::io::{Cursor, Read, Seek, SeekFrom};
+use std::os::raw::{c_char, c_int, c_void};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Couldn't initialize Speex decoder")]
+    InitializationFailed,
+}
+
+/// Creates a wideband decoder with perceptual enhancement enabled.
+///
+/// The returned state must be freed with `speex_decoder_destroy`.
+fn new_decoder_state() -> Result<*mut c_void, Error> {
+    // SAFETY: `speex_lib_get_mode` returns a pointer to a static mode
+    // definition.
+    let state = unsafe {
+        let mode = ffi::speex_lib_get_mode(ffi::SPEEX_MODEID_WB);
+        if mode.is_null() {
+            return Err(Error::InitializationFailed);
+        }
+        ffi::speex_decoder_init(mode)
+    };
+    if state.is_null() {
+        return Err(Error::InitializationFailed);
+    }
+
+    let mut enhancement: c_int = 1;
+    // SAFETY: `state` is a valid decoder, and the control request reads a
+    // single `c_int`.
+    unsafe {
+        ffi::speex_decoder_ctl(
+            state,
+            ffi::SPEEX_SET_ENH,
+            &mut enhancement as *mut c_int as *mut c_void,
+        );
+    }
+    Ok(state)
+}
+
+/// Decoder for Speex audio, as used by SWF and FLV files.
+///
+/// Flash only ever encodes Speex as 16kHz mono wideband audio, regardless of
+/// the sample rate declared by the container.
+pub struct SpeexDecoder<R: Read> {
+    inner: R,
+    state: *mut c_void,
+    bits: Box<ffi::SpeexBits>,
+    frame_size: usize,
+    frame: Vec<i16>,
+    cur_sample: usize,
+    packet: Vec<u8>,
+}
+
+// SAFETY: The decoder state and bit buffer are owned exclusively by this
+// struct and are only ever accessed through `&mut self`.
+unsafe impl<R: Read + Send> Send for SpeexDecoder<R> {}
+unsafe impl<R: Read + Sync> Sync for SpeexDecoder<R> {}
+
+impl<R: Read> SpeexDecoder<R> {
+    /// The sample rate of Speex audio in Flash.
+    const SAMPLE_RATE: u16 = 16000;
+
+    /// The largest amount of data read from the underlying stream at once.
+    ///
+    /// Each read of a streamed source yields a single audio packet, so this
+    /// only has to comfortably exceed the size of one.
+    const MAX_PACKET_SIZE: usize = 8192;
+
+    pub fn new(inner: R) -> Result<Self, Error> {
+        let state = new_decoder_state()?;
+
+        let mut frame_size: c_int = 0;
+        // SAFETY: `SpeexBits` is plain data, and is initialized below.
+        let mut bits: Box<ffi::SpeexBits> = Box::new(unsafe { std::mem::zeroed() });
+        // SAFETY: `state` is a valid decoder, and the control request writes
+        // a single `c_int`.
+        unsafe {
+            ffi::speex_decoder_ctl(
+                state,
+                ffi::SPEEX_GET_FRAME_SIZE,
+                &mut frame_size as *mut c_int as *mut c_void,
+            );
+            ffi::speex_bits_init(bits.as_mut());
+        }
+
+        let frame_size = frame_size.max(1) as usize;
+        Ok(Self {
+            inner,
+            state,
+            bits,
+            frame_size,
+            frame: vec![0; frame_size],
+            // Nothing is decoded yet, so the first call to `next` decodes a frame.
+            cur_sample: frame_size,
+            packet: vec![0; Self::MAX_PACKET_SIZE],
+        })
+    }
+
+    /// Reads the next packet from the underlying stream into the bit buffer.
+    ///
+    /// Returns `false` if the stream has ended.
+    fn read_packet(&mut self) -> bool {
+        let len = match self.inner.read(&mut self.packet) {
+            Ok(len) if len > 0 => len,
+            _ => return false,
+        };
+        // SAFETY: `packet` is valid for `len` bytes, which are copied into
+        // the bit buffer.
+        unsafe {
+            ffi::speex_bits_read_from(
+                self.bits.as_mut(),
+                self.packet.as_ptr() as *const c_char,
+                len as c_int,
+            );
+        }
+        true
+    }
+
+    /// Skips the padding that ends a packet, if the last frame left any.
+    ///
+    /// The encoder pads packets to a byte boundary with a zero bit followed
+    /// by ones, which the decoder does not consume itself.
+    fn skip_terminator(&mut self) {
+        // SAFETY: `bits` is an initialized bit buffer.
+        unsafe {
+            let padding = ffi::speex_bits_remaining(self.bits.as_mut()) % 8;
+            if padding > 0
+                && ffi::speex_bits_peek_unsigned(self.bits.as_mut(), padding)
+                    == (1 << (padding - 1)) - 1
+            {
+                ffi::speex_bits_advance(self.bits.as_mut(), padding);
+            }
+        }
+    }
+
+    /// Decodes the next frame into `self.frame`.
+    ///
+    /// Returns `false` if the stream has ended.
+    fn decode_frame(&mut self) -> bool {
+        loop {
+            // SAFETY: `bits` is an initialized bit buffer.
+            let has_frame = unsafe { ffi::speex_bits_remaining(self.bits.as_mut()) >= 5 };
+
+            if has_frame {
+                // SAFETY: `state` is a valid decoder and `frame` holds
+                // `frame_size` samples, as the decoder expects.
+                let result = unsafe {
+                    ffi::speex_decode_int(self.state, self.bits.as_mut(), self.frame.as_mut_ptr())
+                };
+                if result == 0 {
+                    self.skip_terminator();
+                    return true;
+                }
+                // A corrupt frame or terminator; drop the rest of the packet.
+            }
+
+            if !self.read_packet() {
+                return false;
+            }
+        }
+    }
+}
+
+impl<R: Read> Drop for SpeexDecoder<R> {
+    fn drop(&mut self) {
+        // SAFETY: Both were initialized in `new` and are not used again.
+        unsafe {
+            ffi::speex_bits_destroy(self.bits.as_mut());
+            ffi::speex_decoder_destroy(self.state);
+        }
+    }
+}
+
+impl<R: Read> Iterator for SpeexDecoder<R> {
+    type Item = [i16; 2];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cur_sample >= self.frame_size {
+            if !self.decode_frame() {
+                return None;
+            }
+            self.cur_sample = 0;
+        }
+
+        let sample = self.frame[self.cur_sample];
+        self.cur_sample += 1;
+        Some([sample, sample])
+    }
+}
+
+impl<R: Read + Send + Sync> Decoder for SpeexDecoder<R> {
+    #[inline]
+    fn num_channels(&self) -> u8 {
+        1
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> u16 {
+        Self::SAMPLE_RATE
+    }
+}
+
+impl<R: AsRef<[u8]> + Send + Sync> SeekableDecoder for SpeexDecoder<Cursor<R>> {
+    fn reset(&mut self) {
+        let _ = self.inner.seek(SeekFrom::Start(0));
+        // SAFETY: Reading an empty buffer resets the bit buffer.
+        unsafe {
+            ffi::speex_bits_read_from(self.bits.as_mut(), std::ptr::null(), 0);
+        }
+        // `SPEEX_RESET_STATE` leaves some of the narrowband decoder's history
+        // behind, so start over with a fresh decoder instead.
+        if let Ok(state) = new_decoder_state() {
+            // SAFETY: The old state is not used again.
+            unsafe { ffi::speex_decoder_destroy(self.state) };
+            self.state = state;
+        }
+        self.cur_sample = self.frame_size;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Five frames of a 440Hz sine wave, encoded by the Speex reference
+    /// encoder at quality 8 with one frame per packet.
+    const SINE: &[u8] = &[
+        0x30, 0x54, 0x98, 0x22, 0xD5, 0x00, 0x00, 0x68, 0xE8, 0xE8, 0xE8, 0xE8, 0xE8, 0xE8, 0xE8,
+        0x81, 0xF9, 0xF4, 0x74, 0x74, 0x60, 0x48, 0x91, 0x2C, 0xA1, 0x01, 0x76, 0xD6, 0xD0, 0xBA,
+        0xCB, 0x38, 0x68, 0x1D, 0x93, 0x40, 0x63, 0xA0, 0x2D, 0xFD, 0x00, 0xD8, 0x8D, 0xD2, 0x17,
+        0xDB, 0x3B, 0xF3, 0xAB, 0xAB, 0xAB, 0xAB, 0xAB, 0x0A, 0xBA, 0xBF, 0x3A, 0xBA, 0xB0, 0xAB,
+        0xAB, 0xAB, 0xAB, 0xAB, 0x0A, 0xBA, 0xBA, 0xBA, 0xBA, 0xB7, 0x30, 0x54, 0x98, 0x22, 0xD3,
+        0x03, 0x5D, 0x01, 0x26, 0x59, 0x6B, 0xB5, 0xE8, 0xAD, 0x06, 0x01, 0xAE, 0xE9, 0x22, 0xB7,
+        0x18, 0x74, 0x7C, 0x2C, 0xA0, 0x6E, 0x57, 0x2C, 0x84, 0x51, 0x93, 0xB1, 0x8B, 0x96, 0xD8,
+        0xD4, 0xCC, 0x96, 0x1F, 0x06, 0x29, 0xD6, 0x43, 0x09, 0x0D, 0x2B, 0x38, 0x60, 0xAB, 0xAB,
+        0xAB, 0xAB, 0xAB, 0x0A, 0xBA, 0xBA, 0xBA, 0xBA, 0xB0, 0xAB, 0xAB, 0xAB, 0xAB, 0xAB, 0x0A,
+        0xBA, 0xBA, 0xBA, 0xBA, 0xB7, 0x30, 0x54, 0x82, 0x20, 0xD3, 0xDC, 0x9C, 0xC6, 0x32, 0x50,
+        0xB7, 0x79, 0xB1, 0x70, 0xCD, 0x53, 0x9A, 0x8A, 0x52, 0x94, 0x74, 0x5C, 0x64, 0x54, 0x64,
+        0x29, 0xAD, 0x3A, 0xAF, 0xB1, 0x7A, 0xDC, 0x5B, 0x4D, 0x2C, 0x34, 0xE0, 0xA0, 0x62, 0x4D,
+        0x1D, 0x1E, 0x94, 0xA5, 0x15, 0x1B, 0x38, 0x60, 0xAB, 0xAB, 0xAB, 0xAB, 0xAB, 0x0A, 0xBA,
+        0xBA, 0xBA, 0xBA, 0xB0, 0xAB, 0xAB, 0xAB, 0xAB, 0xAB, 0x0A, 0xBA, 0xBA, 0xBA, 0xBA, 0xB7,
+        0x30, 0x54, 0x98, 0x22, 0xD3, 0xB9, 0x7D, 0x14, 0x91, 0x38, 0x78, 0xB5, 0xA8, 0x82, 0x78,
+        0xCA, 0xCC, 0xB9, 0xB2, 0x44, 0xBD, 0xA2, 0x18, 0x64, 0x3C, 0xE5, 0x76, 0x77, 0xA8, 0x00,
+        0xD5, 0x3E, 0x60, 0x90, 0x9B, 0x92, 0xBB, 0x25, 0xC6, 0x4D, 0x4D, 0x66, 0x2E, 0xC7, 0x0D,
+        0xFB, 0x38, 0x60, 0xAB, 0xAB, 0xAB, 0xAB, 0xAB, 0x0A, 0xBA, 0xBA, 0xBA, 0xBA, 0xB0, 0xAB,
+        0xAB, 0xAB, 0xAB, 0xAB, 0x0A, 0xBA, 0xBA, 0xBA, 0xBA, 0xB7, 0x30, 0x54, 0x98, 0x22, 0xD3,
+        0xB9, 0x71, 0x76, 0x6F, 0xC8, 0xEF, 0x18, 0x25, 0xA0, 0x7E, 0xD3, 0xF8, 0x5E, 0x63, 0x07,
+        0x9C, 0x27, 0x00, 0x66, 0x94, 0xA5, 0x38, 0x13, 0xA0, 0xE1, 0x94, 0xBB, 0xB5, 0x61, 0x7E,
+        0x92, 0x9C, 0x12, 0x51, 0xB4, 0xD3, 0x17, 0x19, 0x19, 0xE5, 0xAB, 0x38, 0x60, 0xAB, 0xAB,
+        0xAB, 0xAB, 0xAB, 0x0A, 0xBA, 0xBA, 0xBA, 0xBA, 0xB0, 0xAB, 0xAB, 0xAB, 0xAB, 0xAB, 0x0A,
+        0xBA, 0xBA, 0xBA, 0xBA, 0xB7,
+    ];
+
+    #[test]
+    fn decode_frames() {
+        let decoder = SpeexDecoder::new(Cursor::new(SINE)).unwrap();
+        assert_eq!(decoder.num_channels(), 1);
+        assert_eq!(decoder.sample_rate(), 16000);
+
+        let samples: Vec<_> = decoder.collect();
+        assert_eq!(samples.len(), 5 * 320);
+        assert!(samples.iter().all(|[left, right]| left == right));
+        assert!(samples.iter().any(|[sample, _]| sample.abs() > 4000));
+    }
+
+    #[test]
+    fn reset() {
+        let mut decoder = SpeexDecoder::new(Cursor::new(SINE)).unwrap();
+        let first: Vec<_> = decoder.by_ref().take(1000).collect();
+        decoder.reset();
+        let second: Vec<_> = decoder.take(1000).collect();
+        assert_eq!(first.len(), 1000);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn decode_empty() {
+        let mut decoder = SpeexDecoder::new(Cursor::new(&[][..])).unwrap();
+        assert_eq!(decoder.next(), None);
+    }
+}
@@ -4,6 +4,7 @@ use crate::backend::audio::{DecodeError, RegisterError};
 use crate::buffer::Substream;
 use crate::tag_utils::SwfSlice;
 use generational_arena::Arena;
+use std::collections::{HashMap, VecDeque};
 use std::io::Cursor;
 use std::sync::{Arc, Mutex, RwLock};
 use swf::AudioCompression;
@@ -70,6 +71,9 @@ pub struct AudioMixer {
 
     /// The last two windows of output samples.
     output_memory: Arc<RwLock<CircBuf>>,
+
+    /// The sample buffers of playing sounds that are generated on demand.
+    dynamic_sounds: HashMap<SoundInstanceHandle, Arc<Mutex<DynamicSoundBuffer>>>,
 }
 
 /// An audio stream.
@@ -137,6 +141,60 @@ impl<D: Decoder> dasp::signal::Signal for DecoderStream<D> {
     }
 }
 
+/// Sample frames supplied to a sound that is generated on demand.
+#[derive(Default)]
+struct DynamicSoundBuffer {
+    /// The sample frames that have not been played yet.
+    frames: VecDeque<[i16; 2]>,
+
+    /// Whether no more frames will be supplied to this sound.
+    is_final: bool,
+}
+
+/// A stream that plays sample frames supplied on demand, such as by the
+/// `sampleData` event of an AVM2 `Sound`.
+///
+/// If the buffer runs dry before it has been marked final, silence is played
+/// until more frames are supplied.
+struct DynamicSoundStream {
+    buffer: Arc<Mutex<DynamicSoundBuffer>>,
+    position: u32,
+    is_exhausted: bool,
+}
+
+impl Stream for DynamicSoundStream {
+    #[inline]
+    fn source_position(&self) -> u32 {
+        self.position
+    }
+
+    #[inline]
+    fn source_sample_rate(&self) -> u16 {
+        AudioMixer::DYNAMIC_SOUND_SAMPLE_RATE
+    }
+}
+
+impl dasp::signal::Signal for DynamicSoundStream {
+    type Frame = [i16; 2];
+
+    #[inline]
+    fn next(&mut self) -> [i16; 2] {
+        let mut buffer = self.buffer.lock().expect("Cannot be called reentrant");
+        if let Some(frame) = buffer.frames.pop_front() {
+            self.position += 1;
+            frame
+        } else {
+            self.is_exhausted = buffer.is_final;
+            Default::default()
+        }
+    }
+
+    #[inline]
+    fn is_exhausted(&self) -> bool {
+        self.is_exhausted
+    }
+}
+
 /// Contains the data and metadata for a sound in an SWF file.
 ///
 /// A sound is defined by the `DefineSound` SWF tags and contains the audio data for the sound.
@@ -236,6 +294,12 @@ impl SoundInstance {
 }
 
 impl AudioMixer {
+    /// The sample rate of sounds generated on demand.
+    pub const DYNAMIC_SOUND_SAMPLE_RATE: u16 = 44100;
+
+    /// The sample rate of audio data extracted from sounds.
+    pub const EXTRACT_SAMPLE_RATE: u32 = 44100;
+
     /// Creates a new `AudioMixer` with the given number of channels and sample rate.
     pub fn new(num_output_channels: u8, output_sample_rate: u32) -> Self {
         Self {
@@ -245,6 +309,7 @@ impl AudioMixer {
             num_output_channels,
             output_sample_rate,
             output_memory: Arc::new(RwLock::new(CircBuf::new())),
+            dynamic_sounds: HashMap::new(),
         }
     }
 
@@ -326,13 +391,20 @@ impl AudioMixer {
                 data,
                 format.sample_rate.into(),
             )),
+            #[cfg(feature = "speex")]
+            AudioCompression::Speex => Box::new(decoders::SpeexDecoder::new(data)?),
             _ => return Err(decoders::Error::UnhandledCompression(format.compression)),
         };
         Ok(decoder)
     }
 
     /// Transforms a `Stream` into a new `Stream` that matches the output sample rate.
-    fn make_resampler(&self, mut stream: impl Stream) -> impl Stream {
+    fn make_resampler(&self, stream: impl Stream) -> impl Stream {
+        Self::make_resampler_with_rate(stream, self.output_sample_rate)
+    }
+
+    /// Creates a `Stream` that resamples `stream` to the given sample rate.
+    fn make_resampler_with_rate(mut stream: impl Stream, output_sample_rate: u32) -> impl Stream {
         // TODO: Allow interpolator to be user-configurable?
         let left = stream.next();
         let right = stream.next();
@@ -342,7 +414,7 @@ impl AudioMixer {
             stream,
             interpolator,
             sample_rate,
-            self.output_sample_rate.into(),
+            output_sample_rate.into(),
         ))
     }
 
@@ -624,6 +696,76 @@ impl AudioMixer {
         Ok(handle)
     }
 
+    /// Starts a sound whose sample frames are supplied on demand with
+    /// `AudioMixer::append_dynamic_sound_data`.
+    pub fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, DecodeError> {
+        let buffer = Arc::new(Mutex::new(DynamicSoundBuffer::default()));
+        let stream = DynamicSoundStream {
+            buffer: Arc::clone(&buffer),
+            position: 0,
+            is_exhausted: false,
+        };
+        let stream = Box::new(self.make_resampler(stream));
+
+        let mut sound_instances = self
+            .sound_instances
+            .lock()
+            .expect("Cannot be called reentrant");
+        let handle = sound_instances.insert(SoundInstance::new_stream(stream));
+
+        // Forget the buffers of any dynamic sounds that have finished playing.
+        self.dynamic_sounds
+            .retain(|instance, _| sound_instances.contains(*instance));
+        self.dynamic_sounds.insert(handle, buffer);
+        Ok(handle)
+    }
+
+    /// Appends 44.1KHz stereo sample frames to a sound started with
+    /// `AudioMixer::start_dynamic_sound`.
+    ///
+    /// If `is_final` is set, the sound ends once all of its frames have been played.
+    pub fn append_dynamic_sound_data(
+        &mut self,
+        instance: SoundInstanceHandle,
+        frames: &[[f32; 2]],
+        is_final: bool,
+    ) {
+        use dasp::Sample;
+
+        if let Some(buffer) = self.dynamic_sounds.get(&instance) {
+            let mut buffer = buffer.lock().expect("Cannot be called reentrant");
+            buffer.frames.extend(
+                frames
+                    .iter()
+                    .map(|&[left, right]| [left.to_sample::<i16>(), right.to_sample::<i16>()]),
+            );
+            buffer.is_final |= is_final;
+        }
+    }
+
+    /// Returns the number of sample frames that were appended to a dynamic sound, but have not
+    /// been played yet.
+    ///
+    /// Returns `None` if the sound is no longer playing.
+    pub fn dynamic_sound_buffered_frames(&self, instance: SoundInstanceHandle) -> Option<usize> {
+        let is_playing = self
+            .sound_instances
+            .lock()
+            .expect("Cannot be called reentrant")
+            .contains(instance);
+        if !is_playing {
+            return None;
+        }
+
+        self.dynamic_sounds.get(&instance).map(|buffer| {
+            buffer
+                .lock()
+                .expect("Cannot be called reentrant")
+                .frames
+                .len()
+        })
+    }
+
     /// Stops a playing sound instance.
     pub fn stop_sound(&mut self, sound: SoundInstanceHandle) {
         let mut sound_instances = self
@@ -631,6 +773,7 @@ impl AudioMixer {
             .lock()
             .expect("Cannot be called reentrant");
         sound_instances.remove(sound);
+        self.dynamic_sounds.remove(&sound);
     }
 
     pub fn stop_all_sounds(&mut self) {
@@ -646,6 +789,7 @@ impl AudioMixer {
             sound_instances.remove(i);
         }
         sound_instances.clear();
+        self.dynamic_sounds.clear();
     }
 
     /// Returns the position of a playing sound in milliseconds.
@@ -691,6 +835,55 @@ impl AudioMixer {
         self.sounds.get(sound).map(|s| s.data.len() as u32)
     }
 
+    /// Decodes up to `num_frames` frames of the given sound, starting at `start_frame`.
+    ///
+    /// Frames are counted at 44.1kHz, regardless of the sample rate of the sound itself.
+    /// Returns `None` if the sound is not registered or could not be decoded.
+    pub fn extract_sound_data(
+        &self,
+        sound: SoundHandle,
+        start_frame: u32,
+        num_frames: u32,
+    ) -> Option<Vec<[f32; 2]>> {
+        use dasp::Sample;
+
+        let sound = self.sounds.get(sound)?;
+        let sample_rate = u64::from(sound.format.sample_rate);
+        if sample_rate == 0 {
+            return None;
+        }
+        let total_frames = (u64::from(sound.num_sample_frames)
+            * u64::from(Self::EXTRACT_SAMPLE_RATE)
+            / sample_rate) as u32;
+        let num_frames = num_frames.min(total_frames.saturating_sub(start_frame));
+        if num_frames == 0 {
+            return Some(Vec::new());
+        }
+
+        let data = Cursor::new(ArcAsRef(Arc::clone(&sound.data)));
+        let decoder = Self::make_seekable_decoder(&sound.format, data).ok()?;
+        let settings = swf::SoundInfo {
+            event: swf::SoundEvent::Start,
+            in_sample: Some(start_frame),
+            out_sample: None,
+            num_loops: 1,
+            envelope: None,
+        };
+        let stream = EventSoundStream::new_with_settings(
+            decoder,
+            &settings,
+            sound.num_sample_frames,
+            sound.skip_sample_frames,
+        );
+        let stream = Self::make_resampler_with_rate(stream, Self::EXTRACT_SAMPLE_RATE);
+        Some(
+            stream
+                .take(num_frames as usize)
+                .map(|[left, right]| [left.to_sample::<f32>(), right.to_sample::<f32>()])
+                .collect(),
+        )
+    }
+
     pub fn get_sound_format(&self, sound: SoundHandle) -> Option<&swf::SoundFormat> {
         self.sounds.get(sound).map(|s| &s.format)
     }
@@ -1117,6 +1310,27 @@ macro_rules! impl_audio_mixer_backend {
             self.$mixer.start_substream(stream_data, stream_info)
         }
 
+        #[inline]
+        fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, DecodeError> {
+            self.$mixer.start_dynamic_sound()
+        }
+
+        #[inline]
+        fn append_dynamic_sound_data(
+            &mut self,
+            instance: SoundInstanceHandle,
+            frames: &[[f32; 2]],
+            is_final: bool,
+        ) {
+            self.$mixer
+                .append_dynamic_sound_data(instance, frames, is_final)
+        }
+
+        #[inline]
+        fn dynamic_sound_buffered_frames(&self, instance: SoundInstanceHandle) -> Option<usize> {
+            self.$mixer.dynamic_sound_buffered_frames(instance)
+        }
+
         #[inline]
         fn stop_sound(&mut self, sound: SoundInstanceHandle) {
             self.$mixer.stop_sound(sound)
@@ -1142,6 +1356,17 @@ macro_rules! impl_audio_mixer_backend {
             self.$mixer.get_sound_size(sound)
         }
 
+        #[inline]
+        fn extract_sound_data(
+            &self,
+            sound: SoundHandle,
+            start_frame: u32,
+            num_frames: u32,
+        ) -> Option<Vec<[f32; 2]>> {
+            self.$mixer
+                .extract_sound_data(sound, start_frame, num_frames)
+        }
+
         #[inline]
         fn get_sound_format(&self, sound: SoundHandle) -> Option<&swf::SoundFormat> {
             self.$mixer.get_sound_format(sound)
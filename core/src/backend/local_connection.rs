@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// A message sent with `LocalConnection.send`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocalConnectionMessage {
+    /// The fully qualified name of the connection the message is sent to.
+    pub connection_name: String,
+
+    /// The name of the method to call on the receiving connection.
+    pub method_name: String,
+
+    /// The domain of the movie that sent the message.
+    pub sender_domain: String,
+
+    /// The arguments of the call, encoded as an AMF LSO with one element per argument.
+    pub arguments: Vec<u8>,
+}
+
+/// Delivers `LocalConnection` messages between players.
+///
+/// Every player has its own backend, and the backend decides which other players it can
+/// reach, such as every player in the process or every player on the machine.
+pub trait LocalConnectionBackend {
+    /// Claims a connection name for this player.
+    ///
+    /// Returns `false` if the name is already claimed by any player that can be reached.
+    fn connect(&mut self, name: &str) -> bool;
+
+    /// Releases a connection name that was claimed with `connect`.
+    fn close(&mut self, name: &str);
+
+    /// Sends a message to the player that has claimed its connection name.
+    ///
+    /// Returns `false` if nobody is listening on that name.
+    fn send(&mut self, message: LocalConnectionMessage) -> bool;
+
+    /// Takes every message that was sent to a name claimed by this player.
+    fn receive(&mut self) -> Vec<LocalConnectionMessage>;
+}
+
+#[derive(Default)]
+struct BrokerState {
+    next_endpoint: u64,
+
+    /// The endpoint that each connection name is claimed by.
+    connections: HashMap<String, u64>,
+
+    /// Messages waiting to be received by each endpoint.
+    inboxes: HashMap<u64, Vec<LocalConnectionMessage>>,
+}
+
+/// Routes `LocalConnection` messages between players in the same process.
+#[derive(Clone, Default)]
+pub struct LocalConnectionBroker(Arc<Mutex<BrokerState>>);
+
+impl LocalConnectionBroker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The broker shared by every player in this process.
+    pub fn global() -> Self {
+        static GLOBAL: OnceLock<LocalConnectionBroker> = OnceLock::new();
+        GLOBAL.get_or_init(Self::new).clone()
+    }
+}
+
+/// A `LocalConnectionBackend` that reaches every other player using the same broker.
+pub struct InProcessLocalConnectionBackend {
+    broker: LocalConnectionBroker,
+    endpoint: u64,
+}
+
+impl InProcessLocalConnectionBackend {
+    pub fn new(broker: &LocalConnectionBroker) -> Self {
+        let mut state = broker.0.lock().expect("Broker should not be poisoned");
+        let endpoint = state.next_endpoint;
+        state.next_endpoint += 1;
+        state.inboxes.insert(endpoint, vec![]);
+        drop(state);
+
+        Self {
+            broker: broker.clone(),
+            endpoint,
+        }
+    }
+}
+
+impl Default for InProcessLocalConnectionBackend {
+    fn default() -> Self {
+        Self::new(&LocalConnectionBroker::global())
+    }
+}
+
+impl LocalConnectionBackend for InProcessLocalConnectionBackend {
+    fn connect(&mut self, name: &str) -> bool {
+        let mut state = self.broker.0.lock().expect("Broker should not be poisoned");
+        if state.connections.contains_key(name) {
+            return false;
+        }
+        state.connections.insert(name.to_string(), self.endpoint);
+        true
+    }
+
+    fn close(&mut self, name: &str) {
+        let mut state = self.broker.0.lock().expect("Broker should not be poisoned");
+        if state.connections.get(name) == Some(&self.endpoint) {
+            state.connections.remove(name);
+        }
+    }
+
+    fn send(&mut self, message: LocalConnectionMessage) -> bool {
+        let mut state = self.broker.0.lock().expect("Broker should not be poisoned");
+        let Some(&endpoint) = state.connections.get(&message.connection_name) else {
+            return false;
+        };
+        match state.inboxes.get_mut(&endpoint) {
+            Some(inbox) => {
+                inbox.push(message);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn receive(&mut self) -> Vec<LocalConnectionMessage> {
+        let mut state = self.broker.0.lock().expect("Broker should not be poisoned");
+        state
+            .inboxes
+            .get_mut(&self.endpoint)
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for InProcessLocalConnectionBackend {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.broker.0.lock() {
+            state
+                .connections
+                .retain(|_, endpoint| *endpoint != self.endpoint);
+            state.inboxes.remove(&self.endpoint);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(connection_name: &str) -> LocalConnectionMessage {
+        LocalConnectionMessage {
+            connection_name: connection_name.to_string(),
+            method_name: "ping".to_string(),
+            sender_domain: "localhost".to_string(),
+            arguments: vec![],
+        }
+    }
+
+    #[test]
+    fn routes_messages_between_players() {
+        let broker = LocalConnectionBroker::new();
+        let mut sender = InProcessLocalConnectionBackend::new(&broker);
+        let mut receiver = InProcessLocalConnectionBackend::new(&broker);
+
+        assert!(!sender.send(message("_lobby")));
+        assert!(receiver.connect("_lobby"));
+        assert!(sender.send(message("_lobby")));
+
+        assert_eq!(sender.receive(), vec![]);
+        assert_eq!(receiver.receive(), vec![message("_lobby")]);
+        assert_eq!(receiver.receive(), vec![]);
+    }
+
+    #[test]
+    fn names_are_claimed_once() {
+        let broker = LocalConnectionBroker::new();
+        let mut first = InProcessLocalConnectionBackend::new(&broker);
+        let mut second = InProcessLocalConnectionBackend::new(&broker);
+
+        assert!(first.connect("_lobby"));
+        assert!(!second.connect("_lobby"));
+
+        // Only the player that claimed a name can release it.
+        second.close("_lobby");
+        assert!(!second.connect("_lobby"));
+
+        first.close("_lobby");
+        assert!(second.connect("_lobby"));
+
+        drop(second);
+        assert!(first.connect("_lobby"));
+    }
+}
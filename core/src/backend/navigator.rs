@@ -12,6 +12,7 @@ use std::future::Future;
 use std::path::{Path, PathBuf};
 use std::pin::Pin;
 use std::sync::mpsc::Sender;
+use std::task::{Context, Poll};
 use std::time::Duration;
 use swf::avm1::types::SendVarsMethod;
 use url::{ParseError, Url};
@@ -176,14 +177,102 @@ pub struct SuccessResponse {
     /// The final URL obtained after any redirects.
     pub url: String,
 
-    /// The contents of the response body.
-    pub body: Vec<u8>,
+    /// The contents of the response body, which may still be arriving.
+    pub body: ResponseBody,
 
     /// The status code of the response.
     pub status: u16,
 
     /// The field to indicate if the request has been redirected.
     pub redirected: bool,
+
+    /// The headers of the response, in the order they were received.
+    /// This is empty for anything other than HTTP responses.
+    pub headers: Vec<(String, String)>,
+}
+
+/// A source of body chunks that arrive over time, such as a network connection.
+pub trait ChunkStream {
+    /// Polls for the next chunk of the body, which is `None` once the body is complete.
+    fn poll_chunk(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Vec<u8>, Error>>>;
+}
+
+/// A body that is already available in full.
+struct BufferedChunk(Option<Vec<u8>>);
+
+impl ChunkStream for BufferedChunk {
+    fn poll_chunk(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Vec<u8>, Error>>> {
+        Poll::Ready(self.0.take().map(Ok))
+    }
+}
+
+/// The body of a response, which can be read in chunks as it arrives.
+pub struct ResponseBody {
+    expected_length: Option<u64>,
+    chunks: Pin<Box<dyn ChunkStream>>,
+}
+
+impl ResponseBody {
+    /// Creates a body that is already available in full.
+    pub fn from_bytes(body: Vec<u8>) -> Self {
+        Self {
+            expected_length: Some(body.len() as u64),
+            chunks: Box::pin(BufferedChunk(Some(body))),
+        }
+    }
+
+    /// Creates a body that arrives over time.
+    ///
+    /// `expected_length` is the value of the `Content-Length` header, if there was one.
+    pub fn from_stream(expected_length: Option<u64>, chunks: impl ChunkStream + 'static) -> Self {
+        Self {
+            expected_length,
+            chunks: Box::pin(chunks),
+        }
+    }
+
+    /// The length that the body is expected to have once complete, if known.
+    pub fn expected_length(&self) -> Option<u64> {
+        self.expected_length
+    }
+
+    /// Waits for the next chunk of the body, or `None` once the body is complete.
+    pub async fn next_chunk(&mut self) -> Result<Option<Vec<u8>>, Error> {
+        std::future::poll_fn(|cx| self.chunks.as_mut().poll_chunk(cx))
+            .await
+            .transpose()
+    }
+
+    /// Waits for the rest of the body, and returns all of it.
+    pub async fn read_to_end(&mut self) -> Result<Vec<u8>, Error> {
+        let mut body = Vec::new();
+        while let Some(chunk) = self.next_chunk().await? {
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body)
+    }
+}
+
+impl SuccessResponse {
+    /// Waits for the whole body of a fetch, returning the response along with the body.
+    pub async fn buffered(
+        fetch: OwnedFuture<SuccessResponse, ErrorResponse>,
+    ) -> Result<(SuccessResponse, Vec<u8>), ErrorResponse> {
+        let mut response = fetch.await?;
+        match response.body.read_to_end().await {
+            Ok(body) => Ok((response, body)),
+            Err(error) => Err(ErrorResponse {
+                url: response.url,
+                error,
+            }),
+        }
+    }
 }
 
 /// A response to a non-successful fetch request.
@@ -566,9 +655,10 @@ pub fn fetch_path<NavigatorType: NavigatorBackend>(
         };
         Ok(SuccessResponse {
             url: url.to_string(),
-            body,
+            body: ResponseBody::from_bytes(body),
             status: 0,
             redirected: false,
+            headers: vec![],
         })
     })
 }
@@ -1,12 +1,31 @@
+use crate::backend::navigator::OwnedFuture;
 use crate::events::{KeyCode, PlayerEvent, TextControlCode};
+use chrono::{DateTime, Utc};
 use downcast_rs::Downcast;
 use fluent_templates::loader::langid;
 pub use fluent_templates::LanguageIdentifier;
 use std::borrow::Cow;
 use std::collections::HashSet;
+use std::future::Future;
+use std::pin::Pin;
 use url::Url;
 
 pub type FullscreenError = Cow<'static, str>;
+pub type FileDialogError = Cow<'static, str>;
+
+/// The files picked in a file dialog, which is empty if the user cancelled it.
+pub type FileDialogFuture = Pin<Box<dyn Future<Output = Vec<Box<dyn FileDialogResult>>> + 'static>>;
+
+/// Why a file dialog couldn't be displayed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileDialogUnavailable {
+    /// This backend can't display file dialogs at all.
+    Unsupported,
+
+    /// Another file dialog is still open.
+    AlreadyOpen,
+}
+
 pub static US_ENGLISH: LanguageIdentifier = langid!("en-US");
 
 pub trait UiBackend: Downcast {
@@ -39,9 +58,85 @@ pub trait UiBackend: Downcast {
     fn language(&self) -> &LanguageIdentifier;
 
     fn display_unsupported_video(&self, url: Url);
+
+    /// Displays a dialog for the user to pick files to open, limited to those matching `filters`
+    /// if there are any.
+    ///
+    /// Fails if a dialog couldn't be displayed, such as when another one is still open.
+    fn display_file_open_dialog(
+        &mut self,
+        filters: Vec<FileFilter>,
+        multiple: bool,
+    ) -> Result<FileDialogFuture, FileDialogUnavailable>;
+
+    /// Displays a dialog for the user to pick where to save a file, suggesting `file_name`.
+    ///
+    /// Fails if a dialog couldn't be displayed, such as when another one is still open.
+    fn display_file_save_dialog(
+        &mut self,
+        file_name: String,
+    ) -> Result<FileDialogFuture, FileDialogUnavailable>;
 }
 impl_downcast!(UiBackend);
 
+/// A filter limiting the files that can be picked in a file open dialog.
+/// Equivalent to AS3 `FileFilter`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileFilter {
+    /// The description shown to the user, such as "Images".
+    pub description: String,
+
+    /// The patterns that files must match, separated by semicolons, such as `*.jpg;*.png`.
+    pub extensions: String,
+
+    /// The Mac file types that files must have, separated by semicolons, if any.
+    pub mac_type: Option<String>,
+}
+
+impl FileFilter {
+    /// The extensions allowed by this filter, without their leading wildcard and dot.
+    ///
+    /// Patterns that allow any extension, like `*.*`, are skipped.
+    pub fn extension_list(&self) -> impl Iterator<Item = &str> {
+        self.extensions
+            .split(';')
+            .map(|pattern| {
+                pattern
+                    .trim()
+                    .trim_start_matches('*')
+                    .trim_start_matches('.')
+            })
+            .filter(|extension| !extension.is_empty() && *extension != "*")
+    }
+}
+
+/// A file picked by the user in a file dialog.
+pub trait FileDialogResult: Downcast {
+    /// The name of the file, without any directories.
+    fn file_name(&self) -> String;
+
+    /// The size of the file in bytes, if it exists.
+    fn size(&self) -> Option<u64>;
+
+    fn creation_time(&self) -> Option<DateTime<Utc>>;
+
+    fn modification_time(&self) -> Option<DateTime<Utc>>;
+
+    /// The extension of the file, without the leading dot.
+    fn extension(&self) -> Option<String> {
+        let file_name = self.file_name();
+        let (_, extension) = file_name.rsplit_once('.')?;
+        Some(extension.to_string())
+    }
+
+    /// Reads the contents of the file.
+    fn read(&self) -> OwnedFuture<Vec<u8>, FileDialogError>;
+
+    /// Replaces the contents of the file.
+    fn write(&self, data: Vec<u8>) -> OwnedFuture<(), FileDialogError>;
+}
+impl_downcast!(FileDialogResult);
+
 /// A mouse cursor icon displayed by the Flash Player.
 /// Communicated from the core to the UI backend via `UiBackend::set_mouse_cursor`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -180,6 +275,21 @@ impl UiBackend for NullUiBackend {
     fn language(&self) -> &LanguageIdentifier {
         &US_ENGLISH
     }
+
+    fn display_file_open_dialog(
+        &mut self,
+        _filters: Vec<FileFilter>,
+        _multiple: bool,
+    ) -> Result<FileDialogFuture, FileDialogUnavailable> {
+        Err(FileDialogUnavailable::Unsupported)
+    }
+
+    fn display_file_save_dialog(
+        &mut self,
+        _file_name: String,
+    ) -> Result<FileDialogFuture, FileDialogUnavailable> {
+        Err(FileDialogUnavailable::Unsupported)
+    }
 }
 
 impl Default for NullUiBackend {
@@ -0,0 +1,667 @@
+//! Software implementations of every bitmap filter.
+//!
+//! These are used by `BitmapData.applyFilter` whenever the render backend can't apply a filter
+//! itself, and mirror the semantics of the filter shaders in the wgpu backend.
+
+use crate::bitmap::bitmap_data::Color;
+use ruffle_render::filters::{
+    gradient_lut, DisplacementMapFilter, DisplacementMapFilterMode, Filter,
+};
+use swf::{
+    BevelFilter, BlurFilter, ColorMatrixFilter, ConvolutionFilter, DropShadowFilter, GlowFilter,
+    GradientFilter, GradientRecord,
+};
+
+/// A premultiplied RGBA pixel, with every channel ranging from 0 to 1.
+type Pixel = [f32; 4];
+
+const TRANSPARENT: Pixel = [0.0; 4];
+
+/// An image that filters operate on.
+///
+/// Pixels outside of the image are treated as transparent black.
+#[derive(Clone)]
+pub struct FilterImage {
+    width: u32,
+    height: u32,
+    pixels: Vec<Pixel>,
+}
+
+impl FilterImage {
+    /// Creates an image from a function returning the (premultiplied) color of each pixel.
+    pub fn from_fn(width: u32, height: u32, mut f: impl FnMut(u32, u32) -> Color) -> Self {
+        let mut pixels = Vec::with_capacity(width as usize * height as usize);
+        for y in 0..height {
+            for x in 0..width {
+                let color = f(x, y);
+                pixels.push([
+                    f32::from(color.red()) / 255.0,
+                    f32::from(color.green()) / 255.0,
+                    f32::from(color.blue()) / 255.0,
+                    f32::from(color.alpha()) / 255.0,
+                ]);
+            }
+        }
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    fn from_pixels(width: u32, height: u32, mut f: impl FnMut(i32, i32) -> Pixel) -> Self {
+        let mut pixels = Vec::with_capacity(width as usize * height as usize);
+        for y in 0..height as i32 {
+            for x in 0..width as i32 {
+                pixels.push(f(x, y));
+            }
+        }
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the premultiplied color of the given pixel, which must lie inside the image.
+    pub fn color(&self, x: u32, y: u32) -> Color {
+        let pixel = self.pixels[(y * self.width + x) as usize];
+        let to_u8 = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+        Color::argb(
+            to_u8(pixel[3]),
+            to_u8(pixel[0]),
+            to_u8(pixel[1]),
+            to_u8(pixel[2]),
+        )
+    }
+
+    fn contains(&self, x: i32, y: i32) -> bool {
+        x >= 0 && y >= 0 && x < self.width as i32 && y < self.height as i32
+    }
+
+    fn get(&self, x: i32, y: i32) -> Pixel {
+        if self.contains(x, y) {
+            self.pixels[(y as u32 * self.width + x as u32) as usize]
+        } else {
+            TRANSPARENT
+        }
+    }
+
+    fn get_clamped(&self, x: i32, y: i32) -> Pixel {
+        self.get(
+            x.clamp(0, self.width as i32 - 1),
+            y.clamp(0, self.height as i32 - 1),
+        )
+    }
+
+    fn alpha(&self, x: i32, y: i32) -> f32 {
+        self.get(x, y)[3]
+    }
+}
+
+/// Applies `filter` to the whole of `source`, returning an image of the same size.
+///
+/// `displacement_map` is the `mapBitmap` of a `DisplacementMapFilter`, as the filter itself
+/// only references it by a render backend handle.
+pub fn apply_filter(
+    source: &FilterImage,
+    filter: &Filter,
+    displacement_map: Option<&FilterImage>,
+) -> FilterImage {
+    match filter {
+        Filter::BlurFilter(filter) => blur(source, filter),
+        Filter::GlowFilter(filter) => glow(source, filter, (0, 0)),
+        Filter::DropShadowFilter(filter) => drop_shadow(source, filter),
+        Filter::BevelFilter(filter) => bevel(source, filter),
+        Filter::GradientGlowFilter(filter) => gradient_glow(source, filter),
+        Filter::GradientBevelFilter(filter) => gradient_bevel(source, filter),
+        Filter::ConvolutionFilter(filter) => convolution(source, filter),
+        Filter::ColorMatrixFilter(filter) => color_matrix(source, filter),
+        Filter::DisplacementMapFilter(filter) => displacement_map(source, filter, displacement_map),
+        Filter::ShaderFilter(_) => {
+            tracing::warn!("ShaderFilter can't be applied without a render backend");
+            source.clone()
+        }
+    }
+}
+
+fn premultiply(color: &swf::Color) -> Pixel {
+    let a = f32::from(color.a) / 255.0;
+    [
+        f32::from(color.r) / 255.0 * a,
+        f32::from(color.g) / 255.0 * a,
+        f32::from(color.b) / 255.0 * a,
+        a,
+    ]
+}
+
+fn unmultiply(pixel: Pixel) -> Pixel {
+    if pixel[3] == 0.0 {
+        return TRANSPARENT;
+    }
+    [
+        pixel[0] / pixel[3],
+        pixel[1] / pixel[3],
+        pixel[2] / pixel[3],
+        pixel[3],
+    ]
+}
+
+fn scale(pixel: Pixel, factor: f32) -> Pixel {
+    pixel.map(|c| c * factor)
+}
+
+fn add(a: Pixel, b: Pixel) -> Pixel {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2], a[3] + b[3]]
+}
+
+/// Converts a filter's angle and distance to a whole pixel offset.
+fn pixel_offset(angle: f32, distance: f32) -> (i32, i32) {
+    (
+        (angle.cos() * distance).round() as i32,
+        (angle.sin() * distance).round() as i32,
+    )
+}
+
+/// A fractional box blur, applied horizontally and then vertically once for every pass.
+fn blur(source: &FilterImage, filter: &BlurFilter) -> FilterImage {
+    let mut image = source.clone();
+    for _ in 0..filter.num_passes() {
+        for horizontal in [true, false] {
+            let strength = if horizontal {
+                filter.blur_x.to_f32()
+            } else {
+                filter.blur_y.to_f32()
+            };
+            // Full width of the kernel (left edge to right edge)
+            let full_size = strength.min(255.0);
+            if full_size <= 1.0 {
+                // A width of 1 or less is a noop (it'd just sample itself and nothing else)
+                continue;
+            }
+            image = blur_pass(&image, full_size, horizontal);
+        }
+    }
+    image
+}
+
+fn blur_pass(image: &FilterImage, full_size: f32, horizontal: bool) -> FilterImage {
+    // See the wgpu blur filter for an explanation of these values.
+    // Every pixel within `m` of the center has a weight of 1, and the ones just outside of that
+    // have a weight of `alpha`, so that all of the weights add up to `full_size`.
+    let radius = (full_size - 1.0) / 2.0;
+    let m = radius.ceil() - 1.0;
+    let alpha = ((radius - m) * 255.0).floor() / 255.0;
+    let m = m as i32;
+
+    FilterImage::from_pixels(image.width, image.height, |x, y| {
+        let mut total = TRANSPARENT;
+        for k in -(m + 1)..=(m + 1) {
+            let weight = if k.abs() <= m { 1.0 } else { alpha };
+            let pixel = if horizontal {
+                image.get(x + k, y)
+            } else {
+                image.get(x, y + k)
+            };
+            total = add(total, scale(pixel, weight));
+        }
+        // This rounding imitates the fixed-point computations of FP.
+        total.map(|c| (c * 255.0 / full_size + 1.0e-3).floor() / 255.0)
+    })
+}
+
+/// Looks up the blurred alpha `offset` pixels away from `(x, y)`.
+fn blurred_alpha(blurred: &FilterImage, x: i32, y: i32, offset: (i32, i32)) -> f32 {
+    blurred.alpha(x + offset.0, y + offset.1)
+}
+
+fn glow(source: &FilterImage, filter: &GlowFilter, blur_offset: (i32, i32)) -> FilterImage {
+    let blurred = blur(source, &filter.inner_blur_filter());
+    let inner = filter.is_inner();
+    let knockout = filter.is_knockout();
+    let composite_source = filter.composite_source();
+    let strength = filter.strength.to_f32();
+    let color = [
+        f32::from(filter.color.r) / 255.0,
+        f32::from(filter.color.g) / 255.0,
+        f32::from(filter.color.b) / 255.0,
+        1.0,
+    ];
+    let color_alpha = f32::from(filter.color.a) / 255.0;
+
+    FilterImage::from_pixels(source.width, source.height, |x, y| {
+        let blur = blurred_alpha(&blurred, x, y, blur_offset);
+        let dest = source.get(x, y);
+        if inner {
+            let alpha = color_alpha * ((1.0 - blur) * strength).clamp(0.0, 1.0);
+            if composite_source && !knockout {
+                add(scale(color, alpha * dest[3]), scale(dest, 1.0 - alpha))
+            } else {
+                // Yes, it's intentional that the !composite_source case differs from outer glows.
+                scale(color, alpha * dest[3])
+            }
+        } else {
+            let alpha = color_alpha * (blur * strength).clamp(0.0, 1.0);
+            if knockout {
+                scale(color, alpha * (1.0 - dest[3]))
+            } else if composite_source {
+                add(scale(color, alpha * (1.0 - dest[3])), dest)
+            } else {
+                scale(color, alpha)
+            }
+        }
+    })
+}
+
+fn drop_shadow(source: &FilterImage, filter: &DropShadowFilter) -> FilterImage {
+    // A drop shadow is just a glow with an offset.
+    let (x, y) = pixel_offset(filter.angle.to_f32(), filter.distance.to_f32());
+    glow(source, &filter.inner_glow_filter(), (-x, -y))
+}
+
+/// Composites a bevel of the given (premultiplied) `glow` color over `dest`.
+fn composite_bevel(glow: Pixel, dest: Pixel, inner: bool, outer: bool, knockout: bool) -> Pixel {
+    if inner && outer {
+        if knockout {
+            glow
+        } else {
+            add(scale(dest, 1.0 - glow[3]), glow)
+        }
+    } else if inner {
+        if knockout {
+            scale(glow, dest[3])
+        } else {
+            add(scale(glow, dest[3]), scale(dest, 1.0 - glow[3]))
+        }
+    } else if knockout {
+        scale(glow, 1.0 - dest[3])
+    } else {
+        add(dest, scale(glow, 1.0 - dest[3]))
+    }
+}
+
+fn bevel(source: &FilterImage, filter: &BevelFilter) -> FilterImage {
+    let blurred = blur(source, &filter.inner_blur_filter());
+    let (offset_x, offset_y) = pixel_offset(filter.angle.to_f32(), filter.distance.to_f32());
+    let outer = !filter.is_inner() || filter.is_on_top();
+    let inner = filter.is_inner() || filter.is_on_top();
+    let knockout = filter.is_knockout();
+    let strength = filter.strength.to_f32();
+    let highlight_color = premultiply(&filter.highlight_color);
+    let shadow_color = premultiply(&filter.shadow_color);
+
+    FilterImage::from_pixels(source.width, source.height, |x, y| {
+        let left = blurred_alpha(&blurred, x, y, (offset_x, offset_y));
+        let right = blurred_alpha(&blurred, x, y, (-offset_x, -offset_y));
+        let highlight_alpha = ((left - right) * strength).clamp(0.0, 1.0);
+        let shadow_alpha = ((right - left) * strength).clamp(0.0, 1.0);
+        let glow = add(
+            scale(highlight_color, highlight_alpha),
+            scale(shadow_color, shadow_alpha),
+        );
+        composite_bevel(glow, source.get(x, y), inner, outer, knockout)
+    })
+}
+
+/// Builds a lookup table of the premultiplied gradient color at every ratio.
+fn gradient_colors(records: &[GradientRecord]) -> Vec<Pixel> {
+    gradient_lut(records).iter().map(premultiply).collect()
+}
+
+fn gradient_color(colors: &[Pixel], t: f32) -> Pixel {
+    colors[(t.clamp(0.0, 1.0) * 255.0).round() as usize]
+}
+
+fn gradient_glow(source: &FilterImage, filter: &GradientFilter) -> FilterImage {
+    let blurred = blur(source, &filter.inner_blur_filter());
+    let (offset_x, offset_y) = pixel_offset(filter.angle.to_f32(), filter.distance.to_f32());
+    let colors = gradient_colors(&filter.colors);
+    let outer = !filter.is_inner() || filter.is_on_top();
+    let inner = filter.is_inner() || filter.is_on_top();
+    let knockout = filter.is_knockout();
+    let composite_source = filter.composite_source();
+    let strength = filter.strength.to_f32();
+
+    FilterImage::from_pixels(source.width, source.height, |x, y| {
+        let blur = blurred_alpha(&blurred, x, y, (-offset_x, -offset_y));
+        let dest = source.get(x, y);
+        if inner && outer {
+            let glow = gradient_color(&colors, blur * strength);
+            if knockout {
+                glow
+            } else {
+                add(scale(dest, 1.0 - glow[3]), glow)
+            }
+        } else if inner {
+            // The ratios of an inner glow run from the inside of the shape towards its edges
+            let glow = gradient_color(&colors, (1.0 - blur) * strength);
+            if composite_source && !knockout {
+                add(scale(glow, dest[3]), scale(dest, 1.0 - glow[3]))
+            } else {
+                scale(glow, dest[3])
+            }
+        } else {
+            let glow = gradient_color(&colors, blur * strength);
+            if knockout {
+                scale(glow, 1.0 - dest[3])
+            } else if composite_source {
+                add(scale(glow, 1.0 - dest[3]), dest)
+            } else {
+                glow
+            }
+        }
+    })
+}
+
+fn gradient_bevel(source: &FilterImage, filter: &GradientFilter) -> FilterImage {
+    let blurred = blur(source, &filter.inner_blur_filter());
+    let (offset_x, offset_y) = pixel_offset(filter.angle.to_f32(), filter.distance.to_f32());
+    let colors = gradient_colors(&filter.colors);
+    let outer = !filter.is_inner() || filter.is_on_top();
+    let inner = filter.is_inner() || filter.is_on_top();
+    let knockout = filter.is_knockout();
+    let strength = filter.strength.to_f32();
+
+    FilterImage::from_pixels(source.width, source.height, |x, y| {
+        let left = blurred_alpha(&blurred, x, y, (offset_x, offset_y));
+        let right = blurred_alpha(&blurred, x, y, (-offset_x, -offset_y));
+        // Ratio 0 is the highlight side and ratio 255 the shadow side, with the flat areas in the middle
+        let glow = gradient_color(&colors, 0.5 - (left - right) * strength * 0.5);
+        composite_bevel(glow, source.get(x, y), inner, outer, knockout)
+    })
+}
+
+fn convolution(source: &FilterImage, filter: &ConvolutionFilter) -> FilterImage {
+    let matrix_x = filter.num_matrix_cols as i32;
+    let matrix_y = filter.num_matrix_rows as i32;
+    if (matrix_x * matrix_y) as usize > filter.matrix.len() {
+        return source.clone();
+    }
+    let matrix: Vec<f32> = filter.matrix.iter().map(|value| value.to_f32()).collect();
+    // Flash treats a divisor of 0 as 1.
+    let divisor = filter.divisor.to_f32();
+    let divisor = if divisor == 0.0 { 1.0 } else { divisor };
+    let bias = filter.bias.to_f32() / 255.0;
+    let default_color = [
+        f32::from(filter.default_color.r) / 255.0,
+        f32::from(filter.default_color.g) / 255.0,
+        f32::from(filter.default_color.b) / 255.0,
+        f32::from(filter.default_color.a) / 255.0,
+    ];
+    let clamp = filter.is_clamped();
+    let preserve_alpha = filter.is_preserve_alpha();
+
+    // Samples the unmultiplied source color, handling the edges of the image.
+    let load = |x: i32, y: i32| {
+        if source.contains(x, y) {
+            unmultiply(source.get(x, y))
+        } else if clamp {
+            unmultiply(source.get_clamped(x, y))
+        } else {
+            default_color
+        }
+    };
+
+    FilterImage::from_pixels(source.width, source.height, |x, y| {
+        let mut total = TRANSPARENT;
+        for my in 0..matrix_y {
+            for mx in 0..matrix_x {
+                let weight = matrix[(my * matrix_x + mx) as usize];
+                let pixel = load(x + mx - matrix_x / 2, y + my - matrix_y / 2);
+                total = add(total, scale(pixel, weight));
+            }
+        }
+        let mut color = total.map(|c| (c / divisor + bias).clamp(0.0, 1.0));
+        if preserve_alpha {
+            color[3] = load(x, y)[3];
+        }
+        [
+            color[0] * color[3],
+            color[1] * color[3],
+            color[2] * color[3],
+            color[3],
+        ]
+    })
+}
+
+fn color_matrix(source: &FilterImage, filter: &ColorMatrixFilter) -> FilterImage {
+    let m = &filter.matrix;
+    FilterImage::from_pixels(source.width, source.height, |x, y| {
+        let src = unmultiply(source.get(x, y));
+        let channel = |row: usize| {
+            let row = &m[row * 5..row * 5 + 5];
+            (row[0] * src[0] + row[1] * src[1] + row[2] * src[2] + row[3] * src[3] + row[4] / 255.0)
+                .clamp(0.0, 1.0)
+        };
+        let alpha = channel(3);
+        [
+            channel(0) * alpha,
+            channel(1) * alpha,
+            channel(2) * alpha,
+            alpha,
+        ]
+    })
+}
+
+fn displacement_map(
+    source: &FilterImage,
+    filter: &DisplacementMapFilter,
+    map: Option<&FilterImage>,
+) -> FilterImage {
+    let Some(map) = map else {
+        return source.clone();
+    };
+    let width = source.width as i32;
+    let height = source.height as i32;
+    let color = premultiply(&filter.color);
+    let scale_x = filter.scale_x * filter.viewscale_x;
+    let scale_y = filter.scale_y * filter.viewscale_y;
+
+    // Returns the displacement for the given channel of a map pixel, centered around 128.
+    let component = |pixel: Pixel, channel: u8| {
+        let value = match channel {
+            1 => pixel[0],
+            2 => pixel[1],
+            4 => pixel[2],
+            8 => pixel[3],
+            _ => return 0.0,
+        };
+        value * 255.0 - 128.0
+    };
+
+    FilterImage::from_pixels(source.width, source.height, |x, y| {
+        let center_x = x as f32 + 0.5;
+        let center_y = y as f32 + 0.5;
+        let map_x = ((center_x - filter.map_point.0 as f32) / filter.viewscale_x).floor() as i32;
+        let map_y = ((center_y - filter.map_point.1 as f32) / filter.viewscale_y).floor() as i32;
+        let (offset_x, offset_y) = if map.contains(map_x, map_y) {
+            let pixel = map.get(map_x, map_y);
+            (
+                component(pixel, filter.component_x) * scale_x / 256.0,
+                component(pixel, filter.component_y) * scale_y / 256.0,
+            )
+        } else {
+            (0.0, 0.0)
+        };
+        let displaced_x = (center_x + offset_x).floor() as i32;
+        let displaced_y = (center_y + offset_y).floor() as i32;
+
+        if source.contains(displaced_x, displaced_y) {
+            return source.get(displaced_x, displaced_y);
+        }
+        match filter.mode {
+            DisplacementMapFilterMode::Wrap => source.get(
+                displaced_x.rem_euclid(width),
+                displaced_y.rem_euclid(height),
+            ),
+            DisplacementMapFilterMode::Clamp => source.get_clamped(displaced_x, displaced_y),
+            DisplacementMapFilterMode::Ignore => source.get(x, y),
+            DisplacementMapFilterMode::Color => color,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_filter, FilterImage};
+    use crate::bitmap::bitmap_data::Color;
+    use ruffle_render::filters::{DisplacementMapFilter, DisplacementMapFilterMode, Filter};
+    use swf::{
+        BlurFilter, BlurFilterFlags, ColorMatrixFilter, ConvolutionFilter, ConvolutionFilterFlags,
+        Fixed16,
+    };
+
+    const RED: u32 = 0xFFFF0000;
+    const GREEN: u32 = 0xFF00FF00;
+    const BLUE: u32 = 0xFF0000FF;
+    const WHITE: u32 = 0xFFFFFFFF;
+
+    /// Builds a single row image from premultiplied ARGB colors.
+    fn image(pixels: &[u32]) -> FilterImage {
+        FilterImage::from_fn(pixels.len() as u32, 1, |x, _| {
+            Color::from(pixels[x as usize])
+        })
+    }
+
+    fn pixels(image: &FilterImage) -> Vec<u32> {
+        (0..image.width())
+            .map(|x| u32::from(image.color(x, 0)))
+            .collect()
+    }
+
+    #[test]
+    fn blur() {
+        let filter = Filter::BlurFilter(BlurFilter {
+            blur_x: Fixed16::from_f32(3.0),
+            blur_y: Fixed16::ZERO,
+            flags: BlurFilterFlags::from_passes(1),
+        });
+        let result = apply_filter(&image(&[0, WHITE, 0]), &filter, None);
+
+        // Every pixel is the average of three pixels, exactly one of which is white.
+        assert_eq!(pixels(&result), [0x55555555; 3]);
+    }
+
+    #[test]
+    fn convolution() {
+        let mut convolution = ConvolutionFilter {
+            num_matrix_rows: 1,
+            num_matrix_cols: 3,
+            matrix: vec![Fixed16::ONE; 3],
+            divisor: Fixed16::from_f32(3.0),
+            bias: Fixed16::ZERO,
+            default_color: swf::Color::from_rgba(0),
+            flags: ConvolutionFilterFlags::empty(),
+        };
+        let source = image(&[WHITE; 3]);
+
+        // Without clamping, the default color is used past the edges of the image.
+        let result = apply_filter(
+            &source,
+            &Filter::ConvolutionFilter(convolution.clone()),
+            None,
+        );
+        assert_eq!(pixels(&result), [0xAA717171, WHITE, 0xAA717171]);
+
+        convolution.flags = ConvolutionFilterFlags::CLAMP;
+        let result = apply_filter(&source, &Filter::ConvolutionFilter(convolution), None);
+        assert_eq!(pixels(&result), [WHITE; 3]);
+    }
+
+    #[test]
+    fn convolution_with_too_few_values() {
+        let filter = Filter::ConvolutionFilter(ConvolutionFilter {
+            num_matrix_rows: 3,
+            num_matrix_cols: 3,
+            matrix: vec![Fixed16::ONE; 3],
+            divisor: Fixed16::ONE,
+            bias: Fixed16::ZERO,
+            default_color: swf::Color::from_rgba(0),
+            flags: ConvolutionFilterFlags::empty(),
+        });
+        let result = apply_filter(&image(&[RED, GREEN, BLUE]), &filter, None);
+
+        assert_eq!(pixels(&result), [RED, GREEN, BLUE]);
+    }
+
+    #[test]
+    fn color_matrix() {
+        // Swaps the red and blue channels.
+        #[rustfmt::skip]
+        let matrix = [
+            0.0, 0.0, 1.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0, 0.0,
+            1.0, 0.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, 0.0, 1.0, 0.0,
+        ];
+        let filter = Filter::ColorMatrixFilter(ColorMatrixFilter { matrix });
+        let result = apply_filter(&image(&[0xFF102030]), &filter, None);
+
+        assert_eq!(pixels(&result), [0xFF302010]);
+    }
+
+    #[test]
+    fn color_matrix_offsets() {
+        // Offsets apply even to transparent pixels.
+        #[rustfmt::skip]
+        let matrix = [
+            0.0, 0.0, 0.0, 0.0, 255.0,
+            0.0, 0.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, 0.0, 0.0, 255.0,
+        ];
+        let filter = Filter::ColorMatrixFilter(ColorMatrixFilter { matrix });
+        let result = apply_filter(&image(&[0]), &filter, None);
+
+        assert_eq!(pixels(&result), [RED]);
+    }
+
+    #[test]
+    fn displacement_map() {
+        // A full red channel moves every pixel one to the left.
+        let map = image(&[RED; 3]);
+        let source = image(&[RED, GREEN, BLUE]);
+        let filter = |mode| {
+            Filter::DisplacementMapFilter(DisplacementMapFilter {
+                color: swf::Color::from_rgba(0xFFFFFF00),
+                component_x: 1,
+                mode,
+                scale_x: 2.0,
+                ..Default::default()
+            })
+        };
+
+        let result = apply_filter(
+            &source,
+            &filter(DisplacementMapFilterMode::Wrap),
+            Some(&map),
+        );
+        assert_eq!(pixels(&result), [GREEN, BLUE, RED]);
+
+        let result = apply_filter(
+            &source,
+            &filter(DisplacementMapFilterMode::Clamp),
+            Some(&map),
+        );
+        assert_eq!(pixels(&result), [GREEN, BLUE, BLUE]);
+
+        let result = apply_filter(
+            &source,
+            &filter(DisplacementMapFilterMode::Color),
+            Some(&map),
+        );
+        assert_eq!(pixels(&result), [GREEN, BLUE, 0xFFFFFF00]);
+
+        // Without a map, nothing is displaced.
+        let result = apply_filter(&source, &filter(DisplacementMapFilterMode::Wrap), None);
+        assert_eq!(pixels(&result), [RED, GREEN, BLUE]);
+    }
+}
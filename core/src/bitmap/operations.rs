@@ -6,11 +6,12 @@ use crate::bitmap::bitmap_data::{
     BitmapData, BitmapDataDrawError, BitmapDataWrapper, ChannelOptions, Color, IBitmapDrawable,
     LehmerRng, ThresholdOperation,
 };
+use crate::bitmap::filters::{self, FilterImage};
 use crate::bitmap::turbulence::Turbulence;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::TDisplayObject;
 use gc_arena::Mutation;
-use ruffle_render::bitmap::{PixelRegion, PixelSnapping};
+use ruffle_render::bitmap::{BitmapHandle, PixelRegion, PixelSnapping};
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::filters::Filter;
 use ruffle_render::matrix::Matrix;
@@ -1164,6 +1165,7 @@ pub fn copy_pixels_with_alpha_source<'gc>(
     write.set_cpu_dirty(context.gc_context, dirty_region);
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn apply_filter<'gc>(
     context: &mut UpdateContext<'_, 'gc>,
     target: BitmapDataWrapper<'gc>,
@@ -1172,7 +1174,13 @@ pub fn apply_filter<'gc>(
     source_size: (u32, u32),
     dest_point: (u32, u32),
     filter: Filter,
+    displacement_map: Option<BitmapDataWrapper<'gc>>,
 ) {
+    let source_size = filter_source_size(&filter, source, source_point, source_size);
+    if source_size.0 == 0 || source_size.1 == 0 {
+        return;
+    }
+
     if !context.renderer.is_filter_supported(&filter) {
         let mut source_region = PixelRegion::for_whole_size(source.width(), source.height());
         let mut dest_region = PixelRegion::for_whole_size(target.width(), target.height());
@@ -1187,14 +1195,16 @@ pub fn apply_filter<'gc>(
             return;
         }
 
-        // Until we support these filters, treat this like a copy
-        copy_on_cpu(
+        apply_filter_on_cpu(
             context.gc_context,
-            source,
             target,
+            source,
+            source_point,
+            source_size,
             source_region,
             dest_region,
-            false,
+            &filter,
+            displacement_map,
         );
         return;
     }
@@ -1221,6 +1231,112 @@ pub fn apply_filter<'gc>(
     }
 }
 
+/// Trims the parts of the filter's `sourceRect` that lie so far past the source bitmap that the
+/// filter can't reach any of its pixels from them.
+///
+/// Those parts are transparent and nothing from them is copied to the target, but leaving them
+/// in would allocate an image as large as the whole rectangle, however big script makes it.
+fn filter_source_size(
+    filter: &Filter,
+    source: BitmapDataWrapper<'_>,
+    source_point: (u32, u32),
+    source_size: (u32, u32),
+) -> (u32, u32) {
+    let source_rect = Rectangle {
+        x_min: Twips::ZERO,
+        y_min: Twips::ZERO,
+        x_max: Twips::from_pixels(source.width() as f64),
+        y_max: Twips::from_pixels(source.height() as f64),
+    };
+    let dest_rect = filter.calculate_dest_rect(source_rect.clone());
+    let margin = |before: Twips, after: Twips| before.max(after).to_pixels().max(0.0).ceil() as u32;
+    let mut margin_x = margin(
+        source_rect.x_min - dest_rect.x_min,
+        dest_rect.x_max - source_rect.x_max,
+    );
+    let mut margin_y = margin(
+        source_rect.y_min - dest_rect.y_min,
+        dest_rect.y_max - source_rect.y_max,
+    );
+
+    // Some filters sample further away than their output grows.
+    let (extra_x, extra_y) = match filter {
+        Filter::ConvolutionFilter(filter) => (
+            u32::from(filter.num_matrix_cols),
+            u32::from(filter.num_matrix_rows),
+        ),
+        Filter::DisplacementMapFilter(filter) => (
+            (filter.scale_x * filter.viewscale_x).abs() as u32,
+            (filter.scale_y * filter.viewscale_y).abs() as u32,
+        ),
+        _ => (0, 0),
+    };
+    // The margin is capped at the size of the bitmap itself, so that the filtered image stays
+    // bounded however far the filter's parameters reach.
+    margin_x = margin_x.saturating_add(extra_x).min(source.width());
+    margin_y = margin_y.saturating_add(extra_y).min(source.height());
+
+    // One more pixel keeps the edges of the image transparent, as filters that clamp to the
+    // edges of their input would otherwise see the source bitmap there.
+    let end_x = source.width() + margin_x + 1;
+    let end_y = source.height() + margin_y + 1;
+    (
+        source_size.0.min(end_x.saturating_sub(source_point.0)),
+        source_size.1.min(end_y.saturating_sub(source_point.1)),
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn apply_filter_on_cpu<'gc>(
+    gc_context: &Mutation<'gc>,
+    target: BitmapDataWrapper<'gc>,
+    source: BitmapDataWrapper<'gc>,
+    source_point: (u32, u32),
+    source_size: (u32, u32),
+    source_region: PixelRegion,
+    dest_region: PixelRegion,
+    filter: &Filter,
+    displacement_map: Option<BitmapDataWrapper<'gc>>,
+) {
+    // The filter sees the whole source rectangle, with anything outside of the source being transparent.
+    let source_image = {
+        let source = source.sync();
+        let source = source.read();
+        FilterImage::from_fn(source_size.0, source_size.1, |x, y| {
+            match (source_point.0.checked_add(x), source_point.1.checked_add(y)) {
+                (Some(x), Some(y)) if x < source.width() && y < source.height() => {
+                    source.get_pixel32_raw(x, y)
+                }
+                _ => Color::default(),
+            }
+        })
+    };
+    let map_image = displacement_map.map(|map| {
+        let map = map.sync();
+        let map = map.read();
+        FilterImage::from_fn(map.width(), map.height(), |x, y| map.get_pixel32_raw(x, y))
+    });
+
+    let result = filters::apply_filter(&source_image, filter, map_image.as_ref());
+
+    let target = target.sync();
+    let mut write = target.write(gc_context);
+    let opaque = !write.transparency();
+    for y in 0..dest_region.height() {
+        for x in 0..dest_region.width() {
+            let mut color = result.color(
+                source_region.x_min - source_point.0 + x,
+                source_region.y_min - source_point.1 + y,
+            );
+            if opaque {
+                color = color.with_alpha(255);
+            }
+            write.set_pixel32_raw(dest_region.x_min + x, dest_region.y_min + y, color);
+        }
+    }
+    write.set_cpu_dirty(gc_context, dest_region);
+}
+
 #[allow(clippy::too_many_arguments)]
 fn copy_on_cpu<'gc>(
     context: &Mutation<'gc>,
@@ -1554,6 +1670,39 @@ pub fn draw<'gc>(
     }
 }
 
+/// Replaces the contents of `target` with a bitmap that only exists as a render handle,
+/// such as the bitmap of a fill recorded in a `Drawing`.
+pub fn copy_from_bitmap_handle<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    target: BitmapDataWrapper<'gc>,
+    source: BitmapHandle,
+) -> Result<(), BitmapDataDrawError> {
+    let dirty_region = PixelRegion::for_whole_size(target.width(), target.height());
+    if dirty_region.width() == 0 || dirty_region.height() == 0 {
+        return Ok(());
+    }
+
+    let mut commands = CommandList::new();
+    commands.render_bitmap(source, Transform::default(), false, PixelSnapping::Never);
+
+    let handle = target.bitmap_handle(context.gc_context, context.renderer);
+    let (target, _) = target.overwrite_cpu_pixels_from_gpu(context.gc_context);
+    let mut write = target.write(context.gc_context);
+
+    let image =
+        context
+            .renderer
+            .render_offscreen(handle, commands, context.stage.quality(), dirty_region);
+
+    match image {
+        Some(sync_handle) => {
+            write.set_gpu_dirty(context.gc_context, sync_handle, dirty_region);
+            Ok(())
+        }
+        None => Err(BitmapDataDrawError::Unimplemented),
+    }
+}
+
 pub fn get_vector(
     target: BitmapDataWrapper,
     x: i32,
@@ -1632,6 +1781,21 @@ pub fn get_pixels_as_byte_array<'gc>(
     height: i32,
 ) -> Result<ByteArrayStorage, Error<'gc>> {
     let mut result = ByteArrayStorage::new();
+    copy_pixels_to_byte_array(target, x, y, width, height, &mut result)
+        .map_err(|e| e.to_avm(activation))?;
+    Ok(result)
+}
+
+/// Writes the unmultiplied ARGB pixels of the given region into `bytearray`,
+/// starting at its current position.
+pub fn copy_pixels_to_byte_array(
+    target: BitmapDataWrapper,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+    bytearray: &mut ByteArrayStorage,
+) -> Result<(), ByteArrayError> {
     let mut region = PixelRegion::for_region_i32(x, y, width, height);
     region.clamp(target.width(), target.height());
 
@@ -1639,13 +1803,89 @@ pub fn get_pixels_as_byte_array<'gc>(
     for y in region.y_min..region.y_max {
         for x in region.x_min..region.x_max {
             let color = read.get_pixel32_raw(x, y);
-            result
-                .write_unsigned_int(color.to_un_multiplied_alpha().into())
-                .map_err(|e| e.to_avm(activation))?;
+            bytearray.write_unsigned_int(color.to_un_multiplied_alpha().into())?;
         }
     }
 
-    Ok(result)
+    Ok(())
+}
+
+/// Counts how often each value of each (unmultiplied) channel occurs within the given region,
+/// in the order red, green, blue, alpha.
+pub fn histogram(
+    target: BitmapDataWrapper,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+) -> [[f64; 256]; 4] {
+    let mut result = [[0.0; 256]; 4];
+    let mut region = PixelRegion::for_region_i32(x, y, width, height);
+    region.clamp(target.width(), target.height());
+
+    let read = target.read_area(region);
+    for y in region.y_min..region.y_max {
+        for x in region.x_min..region.x_max {
+            let color = read.get_pixel32_raw(x, y).to_un_multiplied_alpha();
+            result[0][color.red() as usize] += 1.0;
+            result[1][color.green() as usize] += 1.0;
+            result[2][color.blue() as usize] += 1.0;
+            result[3][color.alpha() as usize] += 1.0;
+        }
+    }
+
+    result
+}
+
+/// The file formats that `BitmapData.encode` can produce.
+#[derive(Clone, Copy, Debug)]
+pub enum BitmapEncoding {
+    Png { fast_compression: bool },
+    Jpeg { quality: u8 },
+}
+
+pub fn encode(
+    target: BitmapDataWrapper,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+    encoding: BitmapEncoding,
+) -> Result<Vec<u8>, ruffle_render::error::Error> {
+    let mut region = PixelRegion::for_region_i32(x, y, width, height);
+    region.clamp(target.width(), target.height());
+    if region.width() == 0 || region.height() == 0 {
+        return Err(ruffle_render::error::Error::InvalidSize);
+    }
+
+    let read = target.read_area(region);
+    let mut rgba = Vec::with_capacity(region.width() as usize * region.height() as usize * 4);
+    for y in region.y_min..region.y_max {
+        for x in region.x_min..region.x_max {
+            let color = read.get_pixel32_raw(x, y).to_un_multiplied_alpha();
+            rgba.extend([color.red(), color.green(), color.blue(), color.alpha()]);
+        }
+    }
+
+    match encoding {
+        BitmapEncoding::Png { fast_compression } => ruffle_render::utils::encode_png(
+            region.width(),
+            region.height(),
+            &rgba,
+            read.transparency(),
+            fast_compression,
+        ),
+        BitmapEncoding::Jpeg { quality } => {
+            // JPEG can't store dimensions larger than 65535 pixels.
+            let (Ok(width), Ok(height)) = (
+                u16::try_from(region.width()),
+                u16::try_from(region.height()),
+            ) else {
+                return Err(ruffle_render::error::Error::InvalidSize);
+            };
+            ruffle_render::utils::encode_jpeg(width, height, &rgba, quality)
+        }
+    }
 }
 
 pub fn set_pixels_from_byte_array<'gc>(
@@ -51,3 +51,22 @@ pub enum NetworkingAccessMode {
     #[serde(rename = "none")]
     None,
 }
+
+/// How strictly the player enforces the Flash Player security sandbox.
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename = "securityMode")]
+pub enum SecurityMode {
+    /// Movies may access any content and load data from anywhere.
+    ///
+    /// This is the most compatible mode, as content that was written for a sandbox
+    /// that no longer exists (such as a site that has gone offline) keeps working.
+    #[default]
+    #[serde(rename = "permissive")]
+    Permissive,
+
+    /// Cross-domain access has to be allowed by `Security.allowDomain` or by a
+    /// `crossdomain.xml` policy file, like in Flash Player.
+    #[serde(rename = "strict")]
+    Strict,
+}
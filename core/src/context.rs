@@ -6,6 +6,7 @@ use crate::avm1::{Object as Avm1Object, Value as Avm1Value};
 use crate::avm2::{Avm2, Object as Avm2Object, SoundChannelObject};
 use crate::backend::{
     audio::{AudioBackend, AudioManager, SoundHandle, SoundInstanceHandle},
+    local_connection::LocalConnectionBackend,
     log::LogBackend,
     navigator::NavigatorBackend,
     storage::StorageBackend,
@@ -18,8 +19,10 @@ use crate::focus_tracker::FocusTracker;
 use crate::frame_lifecycle::FramePhase;
 use crate::library::Library;
 use crate::loader::LoadManager;
+use crate::local_connection::LocalConnections;
 use crate::player::Player;
 use crate::prelude::*;
+use crate::security::SecurityManager;
 use crate::socket::Sockets;
 use crate::streams::StreamManager;
 use crate::string::AvmStringInterner;
@@ -119,6 +122,9 @@ pub struct UpdateContext<'a, 'gc> {
     /// The storage backend, used for storing persistent state
     pub storage: &'a mut dyn StorageBackend,
 
+    /// The LocalConnection backend, used to exchange messages with other players.
+    pub local_connection: &'a mut dyn LocalConnectionBackend,
+
     /// The logging backend, used for trace output capturing.
     ///
     /// **DO NOT** use this field directly, use the `avm_trace` method instead.
@@ -163,6 +169,9 @@ pub struct UpdateContext<'a, 'gc> {
     /// The system properties
     pub system: &'a mut SystemProperties,
 
+    /// The permissions that domains have granted each other.
+    pub security: &'a mut SecurityManager,
+
     /// The current instance ID. Used to generate default `instanceN` names.
     pub instance_counter: &'a mut i32,
 
@@ -227,6 +236,9 @@ pub struct UpdateContext<'a, 'gc> {
 
     pub sockets: &'a mut Sockets<'gc>,
 
+    /// The LocalConnection objects that are connected in this player.
+    pub local_connections: &'a mut LocalConnections<'gc>,
+
     /// Dynamic root for allowing handles to GC objects to exist outside of the GC.
     pub dynamic_root: gc_arena::DynamicRootSet<'gc>,
 }
@@ -268,6 +280,15 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             .start_sound(self.audio, sound, settings, owner, avm1_object)
     }
 
+    pub fn start_dynamic_sound(
+        &mut self,
+        sound_object: crate::avm2::Object<'gc>,
+        start_position: f64,
+    ) -> Option<SoundInstanceHandle> {
+        self.audio_manager
+            .start_dynamic_sound(self.audio, sound_object, start_position)
+    }
+
     pub fn attach_avm2_sound_channel(
         &mut self,
         instance: SoundInstanceHandle,
@@ -363,6 +384,7 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             ui: self.ui,
             video: self.video,
             storage: self.storage,
+            local_connection: self.local_connection,
             rng: self.rng,
             stage: self.stage,
             mouse_over_object: self.mouse_over_object,
@@ -373,6 +395,7 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             player: self.player.clone(),
             load_manager: self.load_manager,
             system: self.system,
+            security: self.security,
             instance_counter: self.instance_counter,
             avm1_shared_objects: self.avm1_shared_objects,
             avm2_shared_objects: self.avm2_shared_objects,
@@ -394,6 +417,7 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             frame_phase: self.frame_phase,
             stream_manager: self.stream_manager,
             sockets: self.sockets,
+            local_connections: self.local_connections,
             dynamic_root: self.dynamic_root,
         }
     }
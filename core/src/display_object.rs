@@ -55,6 +55,7 @@ use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, PixelSnapping};
 use ruffle_render::blend::ExtendedBlendMode;
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::filters::Filter;
+use ruffle_render::matrix3d::Matrix3D;
 pub use stage::{Stage, StageAlign, StageDisplayState, StageScaleMode, WindowMode};
 pub use text::Text;
 pub use video::Video;
@@ -182,6 +183,57 @@ impl BitmapCache {
     }
 }
 
+/// The 3D properties of a display object that its 2D matrix can't hold.
+#[derive(Clone, Debug)]
+pub struct Transform3D {
+    z: f64,
+    rotation_x: Degrees,
+    rotation_y: Degrees,
+    scale_z: f64,
+}
+
+impl Default for Transform3D {
+    fn default() -> Self {
+        Self {
+            z: 0.0,
+            rotation_x: Degrees::from(0.0),
+            rotation_y: Degrees::from(0.0),
+            scale_z: 1.0,
+        }
+    }
+}
+
+/// The perspective projection set on a display object by `transform.perspectiveProjection`.
+/// It applies to every 3D descendant that doesn't have a closer projection.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PerspectiveProjection {
+    /// The field of view in degrees, between 0 and 180 (exclusive).
+    pub field_of_view: f64,
+
+    /// The point the projection converges towards, in the owning object's local space.
+    pub center: Point<Twips>,
+}
+
+impl PerspectiveProjection {
+    /// The projection that Flash Player gives the root of a movie on a stage of the given size.
+    pub fn default_for_stage(stage_width: f64, stage_height: f64) -> Self {
+        Self {
+            field_of_view: 55.0,
+            center: Point::from_pixels(stage_width / 2.0, stage_height / 2.0),
+        }
+    }
+
+    /// Flash Player derives the focal length from the field of view and the stage width.
+    pub fn focal_length(&self, stage_width: f64) -> f64 {
+        stage_width / 2.0 / (self.field_of_view / 2.0).to_radians().tan()
+    }
+
+    /// Calculates the field of view that results in the given focal length.
+    pub fn field_of_view_for(focal_length: f64, stage_width: f64) -> f64 {
+        (stage_width / 2.0 / focal_length).atan().to_degrees() * 2.0
+    }
+}
+
 #[derive(Clone, Collect)]
 #[collect(no_drop)]
 pub struct DisplayObjectBase<'gc> {
@@ -256,6 +308,16 @@ pub struct DisplayObjectBase<'gc> {
     /// None means not cached, Some means cached.
     #[collect(require_static)]
     cache: Option<BitmapCache>,
+
+    /// The 3D part of this object's transform.
+    /// This is `None` until a 3D property such as `z` or `rotationX` is set.
+    #[collect(require_static)]
+    transform_3d: Option<Box<Transform3D>>,
+
+    /// The perspective projection applied to this object's 3D descendants,
+    /// as set by `transform.perspectiveProjection`.
+    #[collect(require_static)]
+    perspective_projection: Option<PerspectiveProjection>,
 }
 
 impl<'gc> Default for DisplayObjectBase<'gc> {
@@ -283,6 +345,8 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             scroll_rect: None,
             next_scroll_rect: Default::default(),
             cache: None,
+            transform_3d: None,
+            perspective_projection: None,
         }
     }
 }
@@ -488,6 +552,141 @@ impl<'gc> DisplayObjectBase<'gc> {
         changed
     }
 
+    fn transform_3d_mut(&mut self) -> &mut Transform3D {
+        self.set_transformed_by_script(true);
+        self.transform_3d.get_or_insert_with(Default::default)
+    }
+
+    fn z(&self) -> f64 {
+        self.transform_3d.as_ref().map_or(0.0, |t| t.z)
+    }
+
+    fn set_z(&mut self, z: f64) -> bool {
+        let transform_3d = self.transform_3d_mut();
+        let changed = transform_3d.z != z;
+        transform_3d.z = z;
+        changed
+    }
+
+    fn rotation_x(&self) -> Degrees {
+        self.transform_3d
+            .as_ref()
+            .map_or(Degrees::from(0.0), |t| t.rotation_x)
+    }
+
+    fn set_rotation_x(&mut self, degrees: Degrees) -> bool {
+        let transform_3d = self.transform_3d_mut();
+        let changed = transform_3d.rotation_x != degrees;
+        transform_3d.rotation_x = degrees;
+        changed
+    }
+
+    fn rotation_y(&self) -> Degrees {
+        self.transform_3d
+            .as_ref()
+            .map_or(Degrees::from(0.0), |t| t.rotation_y)
+    }
+
+    fn set_rotation_y(&mut self, degrees: Degrees) -> bool {
+        let transform_3d = self.transform_3d_mut();
+        let changed = transform_3d.rotation_y != degrees;
+        transform_3d.rotation_y = degrees;
+        changed
+    }
+
+    fn scale_z(&self) -> f64 {
+        self.transform_3d.as_ref().map_or(1.0, |t| t.scale_z)
+    }
+
+    fn set_scale_z(&mut self, value: f64) -> bool {
+        let transform_3d = self.transform_3d_mut();
+        let changed = transform_3d.scale_z != value;
+        transform_3d.scale_z = value;
+        changed
+    }
+
+    /// Whether this object has a 3D transform, in which case `transform.matrix` is `null`
+    /// and `transform.matrix3D` is used instead.
+    pub fn has_transform_3d(&self) -> bool {
+        self.transform_3d.is_some()
+    }
+
+    /// The full 3D transform of this object, or `None` if it only has a 2D transform.
+    ///
+    /// `x`, `y`, `rotationZ`, `scaleX` and `scaleY` stay stored in the 2D matrix, so 2D code
+    /// keeps working with 3D objects. Any skew of that matrix is lost.
+    pub fn matrix_3d(&mut self) -> Option<Matrix3D> {
+        let (z, rotation_x, rotation_y, scale_z) = self
+            .transform_3d
+            .as_ref()
+            .map(|t| (t.z, t.rotation_x, t.rotation_y, t.scale_z))?;
+        self.cache_scale_rotation();
+        Some(Matrix3D::from_components(
+            [self.x().to_pixels(), self.y().to_pixels(), z],
+            [
+                rotation_x.into_radians(),
+                rotation_y.into_radians(),
+                self.rotation.into_radians(),
+            ],
+            [self.scale_x.unit(), self.scale_y.unit(), scale_z],
+        ))
+    }
+
+    /// Sets the full 3D transform of this object, as done by `transform.matrix3D`.
+    /// `None` turns this back into a 2D object, keeping its current 2D matrix.
+    pub fn set_matrix_3d(&mut self, matrix: Option<Matrix3D>) {
+        self.set_transformed_by_script(true);
+        let Some(matrix) = matrix else {
+            self.transform_3d = None;
+            return;
+        };
+
+        let ([x, y, z], [rotation_x, rotation_y, rotation_z], [scale_x, scale_y, scale_z]) =
+            matrix.decompose();
+        self.transform_3d = Some(Box::new(Transform3D {
+            z,
+            rotation_x: Degrees::from_radians(rotation_x),
+            rotation_y: Degrees::from_radians(rotation_y),
+            scale_z,
+        }));
+
+        let (sin, cos) = rotation_z.sin_cos();
+        self.transform.matrix = Matrix {
+            a: (scale_x * cos) as f32,
+            b: (scale_x * sin) as f32,
+            c: (scale_y * -sin) as f32,
+            d: (scale_y * cos) as f32,
+            tx: Twips::from_pixels(x),
+            ty: Twips::from_pixels(y),
+        };
+        self.rotation = Degrees::from_radians(rotation_z);
+        self.scale_x = Percent::from_unit(scale_x);
+        self.scale_y = Percent::from_unit(scale_y);
+        self.skew = 0.0;
+        self.set_scale_rotation_cached(true);
+    }
+
+    /// The 3D transform of this object if it needs to be rendered with a projection,
+    /// which is not the case when it is still flat in the XY plane.
+    fn projected_matrix_3d(&mut self) -> Option<Matrix3D> {
+        let transform_3d = self.transform_3d.as_ref()?;
+        if transform_3d.z == 0.0
+            && f64::from(transform_3d.rotation_x) == 0.0
+            && f64::from(transform_3d.rotation_y) == 0.0
+        {
+            return None;
+        }
+        self.matrix_3d()
+    }
+
+    pub fn perspective_projection(&self) -> Option<PerspectiveProjection> {
+        self.perspective_projection
+    }
+
+    pub fn set_perspective_projection(&mut self, projection: Option<PerspectiveProjection>) {
+        self.perspective_projection = projection;
+    }
+
     fn name(&self) -> Option<AvmString<'gc>> {
         self.name
     }
@@ -761,7 +960,120 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
     if this.maskee().is_some() {
         return;
     }
+    let matrix_3d = this.base_mut(context.gc_context).projected_matrix_3d();
+    if let Some(matrix_3d) = matrix_3d {
+        render_projected(this, context, matrix_3d);
+        return;
+    }
     context.transform_stack.push(this.base().transform());
+    render_transformed(this, context);
+    context.transform_stack.pop();
+}
+
+/// Renders an object with a 3D transform.
+///
+/// Like in Flash Player, the object and its children are drawn flat in its own local space,
+/// which is then projected onto the parent as a whole.
+fn render_projected<'gc>(
+    this: DisplayObject<'gc>,
+    context: &mut RenderContext<'_, 'gc>,
+    matrix_3d: Matrix3D,
+) {
+    let view_matrix = context.stage.view_matrix();
+    let bounds = this.render_bounds_with_transform(&Matrix::IDENTITY, true, &view_matrix);
+    if !bounds.is_valid() {
+        return;
+    }
+
+    // Draw at the scale of the stage, so that the contents stay sharp when the player is scaled.
+    let scale_x = view_matrix.a.abs();
+    let scale_y = view_matrix.d.abs();
+    let width = (bounds.width().to_pixels() * f64::from(scale_x)).ceil();
+    let height = (bounds.height().to_pixels() * f64::from(scale_y)).ceil();
+    if width <= 0.0 || height <= 0.0 || width > u16::MAX as f64 || height > u16::MAX as f64 {
+        return;
+    }
+
+    let mut transform_stack = TransformStack::new();
+    transform_stack.push(&Transform {
+        matrix: Matrix::scale(scale_x, scale_y) * Matrix::translate(-bounds.x_min, -bounds.y_min),
+        color_transform: Default::default(),
+    });
+    let mut projected_context = RenderContext {
+        renderer: context.renderer,
+        commands: CommandList::new(),
+        cache_draws: context.cache_draws,
+        gc_context: context.gc_context,
+        library: context.library,
+        transform_stack: &mut transform_stack,
+        is_offscreen: true,
+        use_bitmap_cache: context.use_bitmap_cache,
+        stage: context.stage,
+    };
+    render_transformed(this, &mut projected_context);
+    let commands = projected_context.commands;
+
+    let parent_transform = context.transform_stack.transform();
+    let projection = this
+        .parent()
+        .map(|parent| projection_matrix(parent, context.stage))
+        .unwrap_or(Matrix3D::IDENTITY);
+    let matrix = Matrix3D::from(parent_transform.matrix)
+        * projection
+        * matrix_3d
+        * Matrix3D::translation(bounds.x_min.to_pixels(), bounds.y_min.to_pixels(), 0.0)
+        * Matrix3D::scale(1.0 / f64::from(scale_x), 1.0 / f64::from(scale_y), 1.0);
+    context.commands.render_projected(
+        commands,
+        width as u32,
+        height as u32,
+        matrix,
+        parent_transform.color_transform * *this.base().color_transform(),
+    );
+}
+
+/// Builds the perspective projection that applies to the 3D children of `parent`,
+/// in the local space of `parent`.
+///
+/// This comes from the closest ancestor with a `perspectiveProjection`, falling back to
+/// the default projection of the root movie.
+fn projection_matrix<'gc>(parent: DisplayObject<'gc>, stage: Stage<'gc>) -> Matrix3D {
+    let (stage_width, stage_height) = stage.stage_size();
+    let (stage_width, stage_height) = (f64::from(stage_width), f64::from(stage_height));
+
+    let mut node = Some(parent);
+    let (owner, projection) = loop {
+        match node {
+            Some(obj) => {
+                if let Some(projection) = obj.base().perspective_projection() {
+                    break (Some(obj), projection);
+                }
+                node = obj.parent();
+            }
+            None => {
+                break (
+                    stage.root_clip(),
+                    PerspectiveProjection::default_for_stage(stage_width, stage_height),
+                );
+            }
+        }
+    };
+
+    let center = match owner {
+        Some(owner) if !DisplayObject::ptr_eq(owner, parent) => parent
+            .global_to_local(owner.local_to_global(projection.center))
+            .unwrap_or(projection.center),
+        _ => projection.center,
+    };
+    Matrix3D::perspective(
+        projection.focal_length(stage_width),
+        center.x.to_pixels(),
+        center.y.to_pixels(),
+    )
+}
+
+/// Renders an object whose own transform is already on the transform stack.
+fn render_transformed<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_, 'gc>) {
     let blend_mode = this.blend_mode();
     let original_commands = if blend_mode != ExtendedBlendMode::Normal {
         Some(std::mem::take(&mut context.commands))
@@ -945,8 +1257,6 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
         };
         context.commands.blend(sub_commands, render_blend_mode);
     }
-
-    context.transform_stack.pop();
 }
 
 /// This applies the **standard** method of `mask` and `scrollRect`.
@@ -1343,6 +1653,82 @@ pub trait TDisplayObject<'gc>:
         }
     }
 
+    /// The `z` position in pixels of this display object in local space.
+    /// Returned by the AVM2 `z` property.
+    fn z(&self) -> f64 {
+        self.base().z()
+    }
+
+    /// Sets the `z` position in pixels of this display object in local space.
+    /// Set by the AVM2 `z` property, which turns this into a 3D object.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_z(&self, gc_context: &Mutation<'gc>, z: f64) {
+        if self.base_mut(gc_context).set_z(z) {
+            if let Some(parent) = self.parent() {
+                parent.invalidate_cached_bitmap(gc_context);
+            }
+        }
+    }
+
+    /// The rotation in degrees of this display object around its X axis.
+    /// Returned by the AVM2 `rotationX` property.
+    fn rotation_x(&self) -> Degrees {
+        self.base().rotation_x()
+    }
+
+    /// Sets the rotation in degrees of this display object around its X axis.
+    /// Set by the AVM2 `rotationX` property, which turns this into a 3D object.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_rotation_x(&self, gc_context: &Mutation<'gc>, degrees: Degrees) {
+        if self.base_mut(gc_context).set_rotation_x(degrees) {
+            if let Some(parent) = self.parent() {
+                parent.invalidate_cached_bitmap(gc_context);
+            }
+        }
+    }
+
+    /// The rotation in degrees of this display object around its Y axis.
+    /// Returned by the AVM2 `rotationY` property.
+    fn rotation_y(&self) -> Degrees {
+        self.base().rotation_y()
+    }
+
+    /// Sets the rotation in degrees of this display object around its Y axis.
+    /// Set by the AVM2 `rotationY` property, which turns this into a 3D object.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_rotation_y(&self, gc_context: &Mutation<'gc>, degrees: Degrees) {
+        if self.base_mut(gc_context).set_rotation_y(degrees) {
+            if let Some(parent) = self.parent() {
+                parent.invalidate_cached_bitmap(gc_context);
+            }
+        }
+    }
+
+    /// Sets the rotation in degrees of this display object around its Z axis.
+    /// This is the same as `rotation`, except that it turns this into a 3D object.
+    /// Set by the AVM2 `rotationZ` property.
+    fn set_rotation_z(&self, gc_context: &Mutation<'gc>, degrees: Degrees) {
+        self.base_mut(gc_context).transform_3d_mut();
+        self.set_rotation(gc_context, degrees);
+    }
+
+    /// The Z axis scale for this display object in local space.
+    /// Returned by the AVM2 `scaleZ` property.
+    fn scale_z(&self) -> f64 {
+        self.base().scale_z()
+    }
+
+    /// Sets the Z axis scale for this display object in local space.
+    /// Set by the AVM2 `scaleZ` property, which turns this into a 3D object.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_scale_z(&self, gc_context: &Mutation<'gc>, value: f64) {
+        if self.base_mut(gc_context).set_scale_z(value) {
+            if let Some(parent) = self.parent() {
+                parent.invalidate_cached_bitmap(gc_context);
+            }
+        }
+    }
+
     /// Gets the pixel width of the AABB containing this display object in local space.
     /// Returned by the ActionScript `_width`/`width` properties.
     fn width(&self) -> f64 {
@@ -8,6 +8,7 @@ use crate::avm2::{
     QName as Avm2QName, StageObject as Avm2StageObject, TObject as Avm2TObject, Value as Avm2Value,
 };
 use crate::backend::audio::{SoundHandle, SoundInstanceHandle};
+use crate::backend::navigator::Request;
 use crate::backend::ui::MouseCursor;
 use crate::frame_lifecycle::run_inner_goto_frame;
 use bitflags::bitflags;
@@ -46,7 +47,8 @@ use std::cmp::max;
 use std::collections::HashMap;
 use std::sync::Arc;
 use swf::extensions::ReadSwfExt;
-use swf::{ClipEventFlag, FontFlag, FrameLabelData, SwfStr};
+use swf::{ClipEventFlag, FrameLabelData};
+use url::Url;
 
 use super::interactive::Avm2MousePick;
 
@@ -456,11 +458,16 @@ impl<'gc> MovieClip<'gc> {
 
         {
             let read = self.0.read();
-            if read.static_data.preload_progress.read().next_preload_chunk
-                >= read.static_data.swf.len() as u64
-            {
+            let progress = read.static_data.preload_progress.read();
+            if progress.next_preload_chunk >= read.static_data.swf.len() as u64 {
                 return true;
             }
+
+            // Later tags may reference imported characters, so nothing else
+            // can be preloaded until the asset library has been bound.
+            if progress.awaiting_import {
+                return false;
+            }
         }
 
         // TODO: Re-creating static data because preload step occurs after construction.
@@ -651,6 +658,8 @@ impl<'gc> MovieClip<'gc> {
                     .0
                     .write(context.gc_context)
                     .export_assets(context, reader),
+                TagCode::ImportAssets => return self.import_assets(context, reader, 1),
+                TagCode::ImportAssets2 => return self.import_assets(context, reader, 2),
                 TagCode::FrameLabel => self.0.write(context.gc_context).frame_label(
                     reader,
                     cur_frame,
@@ -779,6 +788,65 @@ impl<'gc> MovieClip<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn import_assets(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        reader: &mut SwfStream<'_>,
+        version: u8,
+    ) -> DecodeResult {
+        let (url, imports) = if version == 1 {
+            reader.read_import_assets()?
+        } else {
+            reader.read_import_assets_2()?
+        };
+
+        // The library URL is relative to the importing movie, not the player.
+        let movie = self.movie();
+        let url = url.decode(reader.encoding());
+        let url = Url::parse(movie.url())
+            .and_then(|base| base.join(&url))
+            .map(|url| url.to_string())
+            .unwrap_or_else(|_| url.into_owned());
+        let imports = imports
+            .into_iter()
+            .map(|import| {
+                (
+                    import.id,
+                    import.name.decode(reader.encoding()).into_owned(),
+                )
+            })
+            .collect();
+
+        let future = context.load_manager.load_asset_library(
+            context.player.clone(),
+            self,
+            Request::get(url),
+            imports,
+        );
+        context.navigator.spawn_future(future);
+
+        self.0
+            .read()
+            .static_data
+            .preload_progress
+            .write(context.gc_context)
+            .awaiting_import = true;
+
+        Ok(ControlFlow::Exit)
+    }
+
+    /// Resume preloading after the library for an `ImportAssets` tag has
+    /// been loaded and its symbols bound into this movie's library.
+    pub fn finish_asset_import(self, gc_context: &Mutation<'gc>) {
+        self.0
+            .read()
+            .static_data
+            .preload_progress
+            .write(gc_context)
+            .awaiting_import = false;
+    }
+
     #[inline]
     fn do_abc(
         self,
@@ -3846,21 +3914,12 @@ impl<'gc, 'a> MovieClipData<'gc> {
         context: &mut UpdateContext<'_, 'gc>,
         reader: &mut SwfStream<'a>,
     ) -> Result<(), Error> {
-        tracing::warn!("DefineFont4 tag (TLF text) is not implemented");
         let font = reader.read_define_font_4()?;
         let font_id = font.id;
-        let font_object = Font::from_swf_tag(
+        let font_object = Font::from_font4_tag(
             context.gc_context,
             context.renderer,
-            swf::Font {
-                version: 4,
-                id: font.id,
-                name: SwfStr::from_bytes(b"<Unknown Font4>"),
-                language: swf::Language::Unknown,
-                layout: None,
-                glyphs: Vec::new(),
-                flags: FontFlag::empty(),
-            },
+            font,
             reader.encoding(),
         );
         context
@@ -4391,6 +4450,9 @@ struct PreloadProgress {
 
     /// The symbol we are currently asynchronously preloading.
     cur_preload_symbol: Option<CharacterId>,
+
+    /// Whether preloading is blocked on an `ImportAssets` library load.
+    awaiting_import: bool,
 }
 
 impl Default for PreloadProgress {
@@ -4400,6 +4462,7 @@ impl Default for PreloadProgress {
             cur_preload_frame: 1,
             last_frame_start_pos: 0,
             cur_preload_symbol: None,
+            awaiting_import: false,
         }
     }
 }
@@ -322,6 +322,8 @@ impl<'gc> Video<'gc> {
                         codec: streamdef.codec,
                         data: &read.movie.data()[*slice_start..*slice_end],
                         frame_id,
+                        timestamp: 0,
+                        composition_time_offset: 0,
                     };
                     context
                         .video
@@ -329,7 +331,7 @@ impl<'gc> Video<'gc> {
                 }
                 None => {
                     if let Some((_old_id, old_frame)) = read.decoded_frame.clone() {
-                        Ok(old_frame)
+                        Ok(Some(old_frame))
                     } else {
                         Err(Error::SeekingBeforeDecoding(frame_id))
                     }
@@ -342,7 +344,10 @@ impl<'gc> Video<'gc> {
         drop(read);
 
         match res {
-            Ok(bitmap) => self.0.write(context.gc_context).decoded_frame = Some((frame_id, bitmap)),
+            Ok(Some(bitmap)) => {
+                self.0.write(context.gc_context).decoded_frame = Some((frame_id, bitmap))
+            }
+            Ok(None) => {}
             Err(e) => tracing::error!("Got error when seeking to video frame {}: {}", frame_id, e),
         }
     }
@@ -417,6 +422,8 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
                                 codec: streamdef.codec,
                                 data: &movie.data()[*frame_start..*frame_end],
                                 frame_id: *frame_id,
+                                timestamp: 0,
+                                composition_time_offset: 0,
                             },
                         );
 
@@ -128,6 +128,16 @@ impl GlyphSource {
     }
 }
 
+/// The kind of font data that a `Font` was built from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FontType {
+    /// A font embedded with `DefineFont`, `DefineFont2` or `DefineFont3`.
+    Embedded,
+
+    /// A CFF/OpenType font embedded with `DefineFont4`, as used by TLF text.
+    EmbeddedCFF,
+}
+
 #[derive(Debug, Clone, Collect, Copy)]
 #[collect(no_drop)]
 pub struct Font<'gc>(Gc<'gc, FontData>);
@@ -156,6 +166,9 @@ struct FontData {
     /// The identity of the font.
     #[collect(require_static)]
     descriptor: FontDescriptor,
+
+    /// The kind of font data this font was built from.
+    font_type: FontType,
 }
 
 impl<'gc> Font<'gc> {
@@ -227,6 +240,168 @@ impl<'gc> Font<'gc> {
                 descent,
                 leading,
                 descriptor,
+                font_type: FontType::Embedded,
+            },
+        ))
+    }
+
+    /// Construct a font from the CFF/OpenType data embedded in a `DefineFont4`
+    /// tag.
+    ///
+    /// If the tag carries no font data, or the data cannot be parsed, the
+    /// resulting font has no glyphs and will be rendered as a device font.
+    pub fn from_font4_tag(
+        gc_context: &Mutation<'gc>,
+        renderer: &mut dyn RenderBackend,
+        tag: swf::Font4,
+        encoding: &'static swf::Encoding,
+    ) -> Font<'gc> {
+        let descriptor = FontDescriptor::from_parts(
+            &tag.name.to_string_lossy(encoding),
+            tag.is_bold,
+            tag.is_italic,
+        );
+
+        let face = match tag.data.map(|data| ttf_parser::Face::parse(data, 0)) {
+            Some(Ok(face)) => Some(face),
+            Some(Err(e)) => {
+                tracing::warn!(
+                    "Unable to parse DefineFont4 data for font {:?}: {}",
+                    descriptor.class(),
+                    e
+                );
+                None
+            }
+            None => None,
+        };
+
+        let Some(face) = face else {
+            return Font(Gc::new(
+                gc_context,
+                FontData {
+                    glyphs: GlyphSource::Empty,
+                    scale: 1024.0,
+                    ascent: 0,
+                    descent: 0,
+                    leading: 0,
+                    descriptor,
+                    font_type: FontType::EmbeddedCFF,
+                },
+            ));
+        };
+
+        // Collect every Unicode code point mapped by the font. Glyphs are only
+        // built for glyph IDs that are reachable from a code point.
+        let mut mapped_code_points = Vec::new();
+        if let Some(cmap) = face.tables().cmap {
+            for subtable in cmap.subtables {
+                if !subtable.is_unicode() {
+                    continue;
+                }
+
+                subtable.codepoints(|code_point| {
+                    // TODO: Properly handle UTF-16/out-of-bounds code points.
+                    if let (Ok(code), Some(glyph_id)) =
+                        (u16::try_from(code_point), subtable.glyph_index(code_point))
+                    {
+                        mapped_code_points.push((code, glyph_id));
+                    }
+                });
+            }
+        }
+
+        let mut glyphs: Vec<Glyph> = Vec::new();
+        let mut code_point_to_glyph = fnv::FnvHashMap::default();
+        let mut glyph_id_to_code_points: fnv::FnvHashMap<u16, Vec<u16>> =
+            fnv::FnvHashMap::default();
+        let mut glyph_id_to_index = fnv::FnvHashMap::default();
+        for (code, glyph_id) in mapped_code_points {
+            if code_point_to_glyph.contains_key(&code) {
+                continue;
+            }
+
+            let index = *glyph_id_to_index.entry(glyph_id.0).or_insert_with(|| {
+                let mut outline = GlyphOutline::default();
+                face.outline_glyph(glyph_id, &mut outline);
+                let advance = face.glyph_hor_advance(glyph_id).unwrap_or_default();
+
+                let glyph = Glyph {
+                    shape_handle: None.into(),
+                    advance: Twips::new(advance.into()),
+                    shape: GlyphShape::Swf(RefCell::new(SwfGlyphOrShape::Glyph(swf::Glyph {
+                        shape_records: outline.records,
+                        code,
+                        advance: advance.try_into().unwrap_or(i16::MAX),
+                        bounds: None,
+                    }))),
+                };
+
+                // Eager-load ASCII characters.
+                if code < 128 {
+                    glyph.shape_handle(renderer);
+                }
+
+                glyphs.push(glyph);
+                glyphs.len() - 1
+            });
+
+            code_point_to_glyph.insert(code, index);
+            glyph_id_to_code_points
+                .entry(glyph_id.0)
+                .or_default()
+                .push(code);
+        }
+
+        // Only simple pair kerning from the legacy `kern` table is supported.
+        let mut kerning_pairs = fnv::FnvHashMap::default();
+        if let Some(kern) = face.tables().kern {
+            for subtable in kern.subtables {
+                if !subtable.horizontal || subtable.variable {
+                    continue;
+                }
+
+                if let ttf_parser::kern::Format::Format0(pairs) = subtable.format {
+                    for pair in pairs.pairs {
+                        let (Some(lefts), Some(rights)) = (
+                            glyph_id_to_code_points.get(&pair.left().0),
+                            glyph_id_to_code_points.get(&pair.right().0),
+                        ) else {
+                            continue;
+                        };
+
+                        for left in lefts {
+                            for right in rights {
+                                kerning_pairs
+                                    .entry((*left, *right))
+                                    .or_insert_with(|| Twips::new(pair.value.into()));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Font(Gc::new(
+            gc_context,
+            FontData {
+                glyphs: if glyphs.is_empty() {
+                    GlyphSource::Empty
+                } else {
+                    GlyphSource::Memory {
+                        glyphs,
+                        code_point_to_glyph,
+                        kerning_pairs,
+                    }
+                },
+
+                // OpenType outlines are stored in font units, which we use
+                // directly as twips.
+                scale: face.units_per_em().into(),
+                ascent: face.ascender(),
+                descent: face.descender().saturating_neg(),
+                leading: face.line_gap(),
+                descriptor,
+                font_type: FontType::EmbeddedCFF,
             },
         ))
     }
@@ -467,6 +642,103 @@ impl<'gc> Font<'gc> {
     pub fn descriptor(&self) -> &FontDescriptor {
         &self.0.descriptor
     }
+
+    /// Returns the kind of font data this font was built from.
+    pub fn font_type(&self) -> FontType {
+        self.0.font_type
+    }
+}
+
+/// Converts OpenType glyph outlines into SWF shape records.
+///
+/// Font units are used directly as twips. The Y axis is flipped, as
+/// OpenType outlines point upwards from the baseline.
+#[derive(Default)]
+struct GlyphOutline {
+    records: Vec<swf::ShapeRecord>,
+    contour_start: Point<Twips>,
+    cursor: Point<Twips>,
+}
+
+impl GlyphOutline {
+    fn point(x: f32, y: f32) -> Point<Twips> {
+        Point::new(Twips::new(x.round() as i32), Twips::new(-y.round() as i32))
+    }
+}
+
+impl ttf_parser::OutlineBuilder for GlyphOutline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let to = Self::point(x, y);
+        // Only the first contour needs to select the glyph fill.
+        let fill_style_1 = self.records.is_empty().then_some(1);
+        self.records.push(swf::ShapeRecord::StyleChange(Box::new(
+            swf::StyleChangeData {
+                move_to: Some(to),
+                fill_style_0: None,
+                fill_style_1,
+                line_style: None,
+                new_styles: None,
+            },
+        )));
+        self.contour_start = to;
+        self.cursor = to;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let to = Self::point(x, y);
+        self.records.push(swf::ShapeRecord::StraightEdge {
+            delta: to - self.cursor,
+        });
+        self.cursor = to;
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let control = Self::point(x1, y1);
+        let anchor = Self::point(x, y);
+        self.records.push(swf::ShapeRecord::CurvedEdge {
+            control_delta: control - self.cursor,
+            anchor_delta: anchor - control,
+        });
+        self.cursor = anchor;
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        // SWF shapes only support quadratic curves, so approximate the cubic
+        // curve by splitting it in half and fitting a quadratic to each half.
+        let to_f32 = |p: Point<Twips>| (p.x.get() as f32, -p.y.get() as f32);
+        let p0 = to_f32(self.cursor);
+        let (p1, p2, p3) = ((x1, y1), (x2, y2), (x, y));
+        let mid = |a: (f32, f32), b: (f32, f32)| ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0);
+
+        let p01 = mid(p0, p1);
+        let p12 = mid(p1, p2);
+        let p23 = mid(p2, p3);
+        let p012 = mid(p01, p12);
+        let p123 = mid(p12, p23);
+        let split = mid(p012, p123);
+
+        let quad_control = |a: (f32, f32), b: (f32, f32), c: (f32, f32), d: (f32, f32)| {
+            (
+                (3.0 * (b.0 + c.0) - a.0 - d.0) / 4.0,
+                (3.0 * (b.1 + c.1) - a.1 - d.1) / 4.0,
+            )
+        };
+        let c0 = quad_control(p0, p01, p012, split);
+        let c1 = quad_control(split, p123, p23, p3);
+
+        self.quad_to(c0.0, c0.1, split.0, split.1);
+        self.quad_to(c1.0, c1.1, p3.0, p3.1);
+    }
+
+    fn close(&mut self) {
+        if self.cursor != self.contour_start {
+            let start = self.contour_start;
+            self.records.push(swf::ShapeRecord::StraightEdge {
+                delta: start - self.cursor,
+            });
+            self.cursor = start;
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -461,13 +461,10 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         span: &TextSpan,
         is_device_font: bool,
     ) -> Option<Font<'gc>> {
-        let library = context.library.library_for_movie_mut(self.movie.clone());
-
         // If this text field is set to use device fonts, fallback to using our embedded Noto Sans.
         // Note that the SWF can still contain a DefineFont tag with no glyphs/layout info in this case (see #451).
         // In an ideal world, device fonts would search for a matching font on the system and render it in some way.
-        if let Some(font) = library
-            .get_font_by_name(&span.font.to_utf8_lossy(), span.bold, span.italic)
+        if let Some(font) = Self::find_font(context, self.movie.clone(), span)
             .filter(|f| !is_device_font && f.has_glyphs())
             .or_else(|| context.library.device_font())
         {
@@ -478,6 +475,27 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         None
     }
 
+    /// Find the embedded font a span refers to.
+    ///
+    /// Fonts defined by the movie take precedence over fonts registered
+    /// globally with `Font.registerFont`.
+    fn find_font(
+        context: &mut UpdateContext<'_, 'gc>,
+        movie: Arc<SwfMovie>,
+        span: &TextSpan,
+    ) -> Option<Font<'gc>> {
+        let name = span.font.to_utf8_lossy();
+        context
+            .library
+            .library_for_movie_mut(movie)
+            .get_font_by_name(&name, span.bold, span.italic)
+            .or_else(|| {
+                context
+                    .library
+                    .get_global_font_by_name(&name, span.bold, span.italic)
+            })
+    }
+
     /// Append text to the current line of the ongoing layout operation.
     ///
     /// The text given may or may not be separated into fragments, depending on
@@ -527,10 +545,7 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         span: &TextSpan,
         is_device_font: bool,
     ) {
-        let library = context.library.library_for_movie_mut(self.movie.clone());
-
-        if let Some(bullet_font) = library
-            .get_font_by_name(&span.font.to_utf8_lossy(), span.bold, span.italic)
+        if let Some(bullet_font) = Self::find_font(context, self.movie.clone(), span)
             .filter(|f| !is_device_font && f.has_glyphs())
             .or_else(|| context.library.device_font())
             .or(self.font)
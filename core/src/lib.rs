@@ -37,10 +37,12 @@ mod html;
 mod library;
 pub mod limits;
 pub mod loader;
+mod local_connection;
 mod locale;
 pub mod pixel_bender;
 mod player;
 mod prelude;
+mod security;
 pub mod socket;
 mod streams;
 pub mod string;
@@ -394,6 +394,10 @@ pub struct Library<'gc> {
     /// A list of the symbols associated with specific AVM2 constructor
     /// prototypes.
     avm2_class_registry: Avm2ClassRegistry<'gc>,
+
+    /// Fonts registered with `Font.registerFont`, alongside the class they
+    /// were registered with. These are available to every movie.
+    registered_fonts: Vec<(Avm2ClassObject<'gc>, Font<'gc>)>,
 }
 
 unsafe impl<'gc> gc_arena::Collect for Library<'gc> {
@@ -404,6 +408,7 @@ unsafe impl<'gc> gc_arena::Collect for Library<'gc> {
         }
         self.device_font.trace(cc);
         self.avm2_class_registry.trace(cc);
+        self.registered_fonts.trace(cc);
     }
 }
 
@@ -413,6 +418,7 @@ impl<'gc> Library<'gc> {
             movie_libraries: PtrWeakKeyHashMap::new(),
             device_font: None,
             avm2_class_registry: Default::default(),
+            registered_fonts: Vec::new(),
         }
     }
 
@@ -451,4 +457,43 @@ impl<'gc> Library<'gc> {
     pub fn avm2_class_registry_mut(&mut self) -> &mut Avm2ClassRegistry<'gc> {
         &mut self.avm2_class_registry
     }
+
+    /// Make a font available to every movie, as done by `Font.registerFont`.
+    ///
+    /// Registering the same class more than once has no effect.
+    pub fn register_global_font(&mut self, class: Avm2ClassObject<'gc>, font: Font<'gc>) {
+        if !self
+            .registered_fonts
+            .iter()
+            .any(|(registered, _)| *registered == class)
+        {
+            self.registered_fonts.push((class, font));
+        }
+    }
+
+    /// Returns the classes of all fonts registered with `Font.registerFont`,
+    /// in the order they were registered.
+    pub fn global_font_classes(&self) -> impl Iterator<Item = Avm2ClassObject<'gc>> + '_ {
+        self.registered_fonts.iter().map(|(class, _)| *class)
+    }
+
+    /// Find a font registered with `Font.registerFont` by name and style.
+    pub fn get_global_font_by_name(
+        &self,
+        name: &str,
+        is_bold: bool,
+        is_italic: bool,
+    ) -> Option<Font<'gc>> {
+        let descriptor = FontDescriptor::from_parts(name, is_bold, is_italic);
+        self.registered_fonts
+            .iter()
+            .map(|(_, font)| *font)
+            .find(|font| *font.descriptor() == descriptor)
+            .or_else(|| {
+                self.registered_fonts
+                    .iter()
+                    .map(|(_, font)| *font)
+                    .find(|font| font.descriptor().class() == name)
+            })
+    }
 }
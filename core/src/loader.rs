@@ -1,18 +1,23 @@
 //! Management of async loaders
 
+use crate::avm1::globals::file_reference::FileReferenceObject as Avm1FileReferenceObject;
 use crate::avm1::ExecutionReason;
 use crate::avm1::{Activation, ActivationIdentifier};
 use crate::avm1::{Attribute, Avm1};
 use crate::avm1::{Object, SoundObject, TObject, Value};
 use crate::avm2::bytearray::ByteArrayStorage;
 use crate::avm2::object::{
-    BitmapDataObject, ByteArrayObject, EventObject as Avm2EventObject, LoaderStream, TObject as _,
+    BitmapDataObject, ByteArrayObject, EventObject as Avm2EventObject, FileReferenceObject,
+    LoaderStream, TObject as _,
 };
 use crate::avm2::{
-    Activation as Avm2Activation, Avm2, Domain as Avm2Domain, Object as Avm2Object,
-    Value as Avm2Value,
+    Activation as Avm2Activation, ArrayObject as Avm2ArrayObject, ArrayStorage as Avm2ArrayStorage,
+    Avm2, Domain as Avm2Domain, Multiname, Object as Avm2Object, Value as Avm2Value,
 };
-use crate::backend::navigator::{OwnedFuture, Request};
+use crate::backend::navigator::{
+    ErrorResponse, OwnedFuture, Request, ResponseBody, SuccessResponse,
+};
+use crate::backend::ui::{FileDialogFuture, FileDialogResult};
 use crate::bitmap::bitmap_data::Color;
 use crate::bitmap::bitmap_data::{BitmapData, BitmapDataWrapper};
 use crate::context::{ActionQueue, ActionType, UpdateContext};
@@ -23,6 +28,7 @@ use crate::events::ClipEvent;
 use crate::frame_lifecycle::catchup_display_object_to_frame;
 use crate::limits::ExecutionLimit;
 use crate::player::Player;
+use crate::security::{DataAccess, SecurityViolation};
 use crate::streams::NetStream;
 use crate::string::AvmString;
 use crate::tag_utils::SwfMovie;
@@ -35,6 +41,7 @@ use std::fmt;
 use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
 use swf::read::{extract_swz, read_compression_type};
+use swf::CharacterId;
 use thiserror::Error;
 use url::{form_urlencoded, ParseError, Url};
 
@@ -156,6 +163,12 @@ pub enum Error {
     #[error("Other Loader spawned as Movie unloader")]
     NotMovieUnloader,
 
+    #[error("Non-asset-library loader spawned as asset library loader")]
+    NotAssetLibraryLoader,
+
+    #[error("Non-FileReference loader spawned as FileReference loader")]
+    NotFileReferenceLoader,
+
     #[error("HTTP Status is not OK: {0} redirected: {1}")]
     HttpNotOk(String, u16, bool),
 
@@ -228,7 +241,9 @@ impl<'gc> LoadManager<'gc> {
             | Loader::SoundAvm1 { self_handle, .. }
             | Loader::SoundAvm2 { self_handle, .. }
             | Loader::NetStream { self_handle, .. }
-            | Loader::MovieUnloader { self_handle, .. } => *self_handle = Some(handle),
+            | Loader::MovieUnloader { self_handle, .. }
+            | Loader::AssetLibrary { self_handle, .. }
+            | Loader::FileReference { self_handle, .. } => *self_handle = Some(handle),
         }
         handle
     }
@@ -377,6 +392,7 @@ impl<'gc> LoadManager<'gc> {
         target_object: Avm2Object<'gc>,
         request: Request,
         data_format: DataFormat,
+        requester_url: String,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::LoadURLLoader {
             self_handle: None,
@@ -384,7 +400,7 @@ impl<'gc> LoadManager<'gc> {
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
-        loader.load_url_loader(player, request, data_format)
+        loader.load_url_loader(player, request, data_format, requester_url)
     }
 
     /// Kick off an AVM1 audio load.
@@ -439,9 +455,121 @@ impl<'gc> LoadManager<'gc> {
         loader.stream_loader(player, request)
     }
 
+    /// Kick off a runtime shared library load for an `ImportAssets` tag.
+    ///
+    /// Once the library has been fetched and preloaded, the requested
+    /// exports are bound into the importing movie's library and the importer
+    /// is allowed to continue preloading.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_asset_library(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        importer: MovieClip<'gc>,
+        request: Request,
+        imports: Vec<(CharacterId, String)>,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::AssetLibrary {
+            self_handle: None,
+            importer,
+            imports,
+            library: None,
+        };
+        let handle = self.add_loader(loader);
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.asset_library_loader(player, request)
+    }
+
     /// Process tags on all loaders in the Parsing phase.
     ///
     /// Returns true if *all* loaders finished preloading.
+    /// Adds a loader for a dialog or transfer of a `FileReference`, cancelling
+    /// the one that it was previously busy with.
+    fn add_file_reference_loader(&mut self, target_object: FileReference<'gc>) -> Handle {
+        let loader = Loader::FileReference {
+            self_handle: None,
+            target_object,
+        };
+        let handle = self.add_loader(loader);
+        if let Some(previous) = target_object.set_loader(Some(handle)) {
+            self.remove_loader(previous);
+        }
+        handle
+    }
+
+    /// Kick off a file dialog for picking the file of a `FileReference`, or
+    /// the files of a `FileReferenceList`.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn browse_file_reference(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: FileReference<'gc>,
+        dialog: FileDialogFuture,
+    ) -> OwnedFuture<(), Error> {
+        let handle = self.add_file_reference_loader(target_object);
+        Loader::file_browse_loader(handle, player, dialog)
+    }
+
+    /// Kick off reading the file picked for an AVM2 `FileReference` into its
+    /// `data` property.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_file_reference(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: FileReferenceObject<'gc>,
+    ) -> OwnedFuture<(), Error> {
+        let handle = self.add_file_reference_loader(FileReference::Avm2(target_object));
+        Loader::file_load_loader(handle, player)
+    }
+
+    /// Kick off saving data to the file that the user picks in a save dialog.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn save_file_reference(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: FileReference<'gc>,
+        dialog: FileDialogFuture,
+        data: Vec<u8>,
+    ) -> OwnedFuture<(), Error> {
+        let handle = self.add_file_reference_loader(target_object);
+        Loader::file_save_loader(handle, player, dialog, data)
+    }
+
+    /// Kick off uploading the file picked for a `FileReference` to the URL of
+    /// `request`, along with the given form fields.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn upload_file_reference(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: FileReference<'gc>,
+        request: Request,
+        fields: Vec<(String, String)>,
+        field_name: String,
+        requester_url: String,
+    ) -> OwnedFuture<(), Error> {
+        let handle = self.add_file_reference_loader(target_object);
+        Loader::file_upload_loader(handle, player, request, fields, field_name, requester_url)
+    }
+
+    /// Kick off downloading a file to where the user picks in a save dialog.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn download_file_reference(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: FileReference<'gc>,
+        dialog: FileDialogFuture,
+        request: Request,
+        requester_url: String,
+    ) -> OwnedFuture<(), Error> {
+        let handle = self.add_file_reference_loader(target_object);
+        Loader::file_download_loader(handle, player, dialog, request, requester_url)
+    }
+
     pub fn preload_tick(context: &mut UpdateContext<'_, 'gc>, limit: &mut ExecutionLimit) -> bool {
         let mut did_finish = true;
         let handles: Vec<_> = context.load_manager.0.iter().map(|(h, _)| h).collect();
@@ -475,6 +603,8 @@ impl<'gc> Default for LoadManager<'gc> {
 pub enum LoaderStatus {
     /// The movie hasn't been loaded yet.
     Pending,
+    /// The movie is still downloading, and its progress has been reported.
+    Downloading,
     /// The movie is currently being parsed (e.g. mc.preload)
     Parsing,
     /// The movie loaded successfully.
@@ -612,6 +742,39 @@ pub enum Loader<'gc> {
         /// The target MovieClip to unload.
         target_clip: DisplayObject<'gc>,
     },
+
+    /// Loader that is loading a runtime shared library for `ImportAssets`.
+    AssetLibrary {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<Handle>,
+
+        /// The root clip of the movie that is importing assets.
+        ///
+        /// Its preload is blocked until this loader binds the imports.
+        importer: MovieClip<'gc>,
+
+        /// The character IDs to bind in the importing movie, along with the
+        /// export names they are looked up by in the library.
+        #[collect(require_static)]
+        imports: Vec<(CharacterId, String)>,
+
+        /// The root clip of the library movie.
+        ///
+        /// This is only available once the library has been fetched. It is
+        /// never placed on the stage and only exists to preload the library.
+        library: Option<MovieClip<'gc>>,
+    },
+
+    /// Loader that is running a file dialog or file transfer for a `FileReference`.
+    FileReference {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<Handle>,
+
+        /// The object to report the progress of the dialog or transfer to.
+        target_object: FileReference<'gc>,
+    },
 }
 
 impl<'gc> Loader<'gc> {
@@ -693,7 +856,7 @@ impl<'gc> Loader<'gc> {
         Box::pin(async move {
             let fetch = player.lock().unwrap().navigator().fetch(request);
 
-            let response = fetch.await.map_err(|error| {
+            let (response, body) = SuccessResponse::buffered(fetch).await.map_err(|error| {
                 player
                     .lock()
                     .unwrap()
@@ -715,7 +878,7 @@ impl<'gc> Loader<'gc> {
                 .map(|u| u.to_string())
                 .unwrap_or(swf_url);
 
-            let mut movie = SwfMovie::from_data(&response.body, spoofed_or_swf_url, None)?;
+            let mut movie = SwfMovie::from_data(&body, spoofed_or_swf_url, None)?;
             on_metadata(movie.header());
             movie.append_parameters(parameters);
             player.lock().unwrap().set_root_movie(movie);
@@ -777,19 +940,47 @@ impl<'gc> Loader<'gc> {
                 Loader::movie_loader_start(handle, uc)
             })?;
 
-            match fetch.await {
-                Ok(response) if replacing_root_movie => {
-                    ContentType::sniff(&response.body).expect(ContentType::Swf)?;
+            let response = match fetch.await {
+                Ok(mut response) => {
+                    let mut body = Vec::new();
+                    let read = read_chunks(&mut response.body, |chunk, loaded_len, total_len| {
+                        body.extend_from_slice(&chunk);
+
+                        // The last chunk is reported by the movie's preloading instead.
+                        if replacing_root_movie || (total_len > 0 && loaded_len >= total_len) {
+                            return Ok(());
+                        }
+                        player.lock().unwrap().update(|uc| {
+                            Loader::movie_loader_download_progress(
+                                handle, uc, loaded_len, total_len,
+                            )
+                        })
+                    })
+                    .await?;
+                    match read {
+                        Ok(()) => Ok((response, body)),
+                        Err(error) => Err(ErrorResponse {
+                            url: response.url,
+                            error,
+                        }),
+                    }
+                }
+                Err(response) => Err(response),
+            };
+
+            match response {
+                Ok((response, body)) if replacing_root_movie => {
+                    ContentType::sniff(&body).expect(ContentType::Swf)?;
 
-                    let movie = SwfMovie::from_data(&response.body, response.url, loader_url)?;
+                    let movie = SwfMovie::from_data(&body, response.url, loader_url)?;
                     player.lock().unwrap().set_root_movie(movie);
                     return Ok(());
                 }
-                Ok(response) => {
+                Ok((response, body)) => {
                     Loader::movie_loader_data(
                         handle,
                         player,
-                        &response.body,
+                        &body,
                         response.url,
                         response.status,
                         response.redirected,
@@ -897,7 +1088,9 @@ impl<'gc> Loader<'gc> {
         Box::pin(async move {
             let fetch = player.lock().unwrap().navigator().fetch(request);
 
-            let response = fetch.await.map_err(|e| e.error)?;
+            let (_, body) = SuccessResponse::buffered(fetch)
+                .await
+                .map_err(|e| e.error)?;
 
             // Fire the load handler.
             player.lock().unwrap().update(|uc| {
@@ -913,7 +1106,7 @@ impl<'gc> Loader<'gc> {
                     ActivationIdentifier::root("[Form Loader]"),
                 );
 
-                for (k, v) in form_urlencoded::parse(&response.body) {
+                for (k, v) in form_urlencoded::parse(&body) {
                     let k = AvmString::new_utf8(activation.context.gc_context, k);
                     let v = AvmString::new_utf8(activation.context.gc_context, v);
                     that.set(k, v.into(), &mut activation)?;
@@ -960,7 +1153,7 @@ impl<'gc> Loader<'gc> {
         Box::pin(async move {
             let fetch = player.lock().unwrap().navigator().fetch(request);
 
-            let data = fetch.await;
+            let data = SuccessResponse::buffered(fetch).await;
 
             // Fire the load handler.
             player.lock().unwrap().update(|uc| {
@@ -975,8 +1168,8 @@ impl<'gc> Loader<'gc> {
                     Activation::from_stub(uc.reborrow(), ActivationIdentifier::root("[Loader]"));
 
                 match data {
-                    Ok(response) => {
-                        let length = response.body.len();
+                    Ok((response, body)) => {
+                        let length = body.len();
 
                         // Set the properties used by the getBytesTotal and getBytesLoaded methods.
                         that.set("_bytesTotal", length.into(), &mut activation)?;
@@ -998,7 +1191,7 @@ impl<'gc> Loader<'gc> {
                         } else {
                             AvmString::new_utf8(
                                 activation.context.gc_context,
-                                UTF_8.decode(&response.body).0,
+                                UTF_8.decode(&body).0,
                             )
                             .into()
                         };
@@ -1047,6 +1240,7 @@ impl<'gc> Loader<'gc> {
         player: Weak<Mutex<Player>>,
         request: Request,
         data_format: DataFormat,
+        requester_url: String,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
             Loader::LoadURLLoader { self_handle, .. } => {
@@ -1060,164 +1254,257 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
+            if let Err(violation) = check_data_access(&player, &requester_url, request.url()).await
+            {
+                return player
+                    .lock()
+                    .unwrap()
+                    .update(|uc| Loader::url_loader_security_error(uc, handle, violation));
+            }
+
             let fetch = player.lock().unwrap().navigator().fetch(request);
-            let response = fetch.await;
+            let mut response = match fetch.await {
+                Ok(response) => response,
+                Err(response) => {
+                    return player
+                        .lock()
+                        .unwrap()
+                        .update(|uc| Loader::url_loader_error(uc, handle, data_format, response));
+                }
+            };
 
-            player.lock().unwrap().update(|uc| {
-                let loader = uc.load_manager.get_loader(handle);
-                let target = match loader {
-                    Some(&Loader::LoadURLLoader { target_object, .. }) => target_object,
-                    // We would have already returned after the previous 'update' call
-                    _ => unreachable!(),
-                };
+            // 'bytesTotal' stays at 0 if the server didn't tell us the length.
+            let expected_len = response.body.expected_length().unwrap_or(0) as usize;
 
+            player.lock().unwrap().update(|uc| -> Result<(), Error> {
+                let target = Loader::url_loader_target(uc, handle)?;
                 let mut activation = Avm2Activation::from_nothing(uc.reborrow());
 
-                fn set_data<'a, 'gc: 'a>(
-                    body: Vec<u8>,
-                    activation: &mut Avm2Activation<'a, 'gc>,
-                    target: Avm2Object<'gc>,
-                    data_format: DataFormat,
-                ) {
-                    let data_object = match data_format {
-                        DataFormat::Binary => {
-                            let storage = ByteArrayStorage::from_vec(body);
-                            let bytearray =
-                                ByteArrayObject::from_storage(activation, storage).unwrap();
-                            bytearray.into()
-                        }
-                        DataFormat::Text => Avm2Value::String(AvmString::new_utf8_bytes(
-                            activation.context.gc_context,
-                            &body,
-                        )),
-                        DataFormat::Variables => {
-                            tracing::warn!(
-                                "Support for URLLoaderDataFormat.VARIABLES not yet implemented"
-                            );
-                            Avm2Value::Undefined
-                        }
-                    };
+                // Binary data is also exposed while it's still arriving, which is
+                // what `URLStream` reads from.
+                let stream = match data_format {
+                    DataFormat::Binary => {
+                        ByteArrayObject::from_storage(&mut activation, ByteArrayStorage::new())
+                            .map_err(|e| Error::Avm2Error(e.to_string()))?
+                            .into()
+                    }
+                    DataFormat::Text | DataFormat::Variables => Avm2Value::Null,
+                };
+                let stream_name = Multiname::new(activation.avm2().flash_net_internal, "_stream");
+                target
+                    .set_property(&stream_name, stream, &mut activation)
+                    .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                target
+                    .set_public_property("bytesLoaded", 0.into(), &mut activation)
+                    .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                target
+                    .set_public_property("bytesTotal", expected_len.into(), &mut activation)
+                    .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+                // The "open" event is fired once the server responds, so it's
+                // not fired if an IO error occurs opening the connection
+                // (e.g. if a file does not exist on disk).
+                let open_evt = Avm2EventObject::bare_default_event(&mut activation.context, "open");
+                Avm2::dispatch_event(&mut activation.context, open_evt, target);
+                Ok(())
+            })?;
+
+            let mut body = Vec::new();
+            let read = read_chunks(&mut response.body, |chunk, loaded_len, total_len| {
+                body.extend_from_slice(&chunk);
+                player.lock().unwrap().update(|uc| -> Result<(), Error> {
+                    let target = Loader::url_loader_target(uc, handle)?;
+                    let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+
+                    let stream_name =
+                        Multiname::new(activation.avm2().flash_net_internal, "_stream");
+                    let stream = target
+                        .get_property(&stream_name, &mut activation)
+                        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                    if let Some(mut stream) = stream
+                        .as_object()
+                        .and_then(|o| o.as_bytearray_mut(activation.context.gc_context))
+                    {
+                        // Append without moving the read position.
+                        let end = stream.len();
+                        stream
+                            .write_at(&chunk, end)
+                            .map_err(|_| Error::FetchError("Response is too large".to_string()))?;
+                    }
 
                     target
-                        .set_public_property("data", data_object, activation)
-                        .unwrap();
-                }
+                        .set_public_property("bytesLoaded", loaded_len.into(), &mut activation)
+                        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                    target
+                        .set_public_property("bytesTotal", total_len.into(), &mut activation)
+                        .map_err(|e| Error::Avm2Error(e.to_string()))?;
 
-                match response {
-                    Ok(response) => {
-                        let total_len = response.body.len();
-
-                        // FIXME - the "open" event should be fired earlier, just before
-                        // we start to fetch the data.
-                        // However, the "open" event should not be fired if an IO error
-                        // occurs opening the connection (e.g. if a file does not exist on disk).
-                        // We currently have no way of detecting this, so we settle for firing
-                        // the event after the entire fetch is complete. This causes there
-                        // to a longer delay between the initial load triggered by the script
-                        // and the "load" event firing, but it ensures that we match
-                        // the Flash behavior w.r.t when an event is fired vs not fired.
-                        let open_evt =
-                            Avm2EventObject::bare_default_event(&mut activation.context, "open");
-                        Avm2::dispatch_event(&mut activation.context, open_evt, target);
-                        set_data(response.body, &mut activation, target, data_format);
-
-                        // FIXME - we should fire "progress" events as we receive data, not
-                        // just at the end
-                        let progress_evt = activation
-                            .avm2()
-                            .classes()
-                            .progressevent
-                            .construct(
-                                &mut activation,
-                                &[
-                                    "progress".into(),
-                                    false.into(),
-                                    false.into(),
-                                    total_len.into(),
-                                    total_len.into(),
-                                ],
-                            )
-                            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                    let progress_evt = activation
+                        .avm2()
+                        .classes()
+                        .progressevent
+                        .construct(
+                            &mut activation,
+                            &[
+                                "progress".into(),
+                                false.into(),
+                                false.into(),
+                                loaded_len.into(),
+                                total_len.into(),
+                            ],
+                        )
+                        .map_err(|e| Error::Avm2Error(e.to_string()))?;
 
-                        Avm2::dispatch_event(&mut activation.context, progress_evt, target);
+                    Avm2::dispatch_event(&mut activation.context, progress_evt, target);
+                    Ok(())
+                })
+            })
+            .await?;
+            if let Err(error) = read {
+                let response = ErrorResponse {
+                    url: response.url,
+                    error,
+                };
+                return player
+                    .lock()
+                    .unwrap()
+                    .update(|uc| Loader::url_loader_error(uc, handle, data_format, response));
+            }
 
-                        let http_status_evt = activation
-                            .avm2()
-                            .classes()
-                            .httpstatusevent
-                            .construct(
-                                &mut activation,
-                                &[
-                                    "httpStatus".into(),
-                                    false.into(),
-                                    false.into(),
-                                    response.status.into(),
-                                    response.redirected.into(),
-                                ],
-                            )
-                            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+            player.lock().unwrap().update(|uc| {
+                let target = Loader::url_loader_target(uc, handle)?;
+                let mut activation = Avm2Activation::from_nothing(uc.reborrow());
 
-                        Avm2::dispatch_event(&mut activation.context, http_status_evt, target);
+                target
+                    .set_public_property("bytesTotal", body.len().into(), &mut activation)
+                    .map_err(|e| Error::Avm2Error(e.to_string()))?;
 
-                        let complete_evt = Avm2EventObject::bare_default_event(
-                            &mut activation.context,
-                            "complete",
-                        );
-                        Avm2::dispatch_event(uc, complete_evt, target);
+                let data = match data_format {
+                    // The stream already holds the whole body.
+                    DataFormat::Binary => {
+                        let stream_name =
+                            Multiname::new(activation.avm2().flash_net_internal, "_stream");
+                        target
+                            .get_property(&stream_name, &mut activation)
+                            .map_err(|e| Error::Avm2Error(e.to_string()))?
                     }
-                    Err(response) => {
-                        // Testing with Flash shoes that the 'data' property is cleared
-                        // when an error occurs
+                    DataFormat::Text | DataFormat::Variables => {
+                        url_loader_data(&mut activation, body, data_format)
+                    }
+                };
+                target
+                    .set_public_property("data", data, &mut activation)
+                    .map_err(|e| Error::Avm2Error(e.to_string()))?;
 
-                        set_data(Vec::new(), &mut activation, target, data_format);
+                // Local files have no response URL, just like in Flash.
+                let response_url = (response.status != 0).then_some(response.url.as_str());
+                let http_status_evt = http_status_event(
+                    &mut activation,
+                    response.status,
+                    response.redirected,
+                    response_url,
+                    &response.headers,
+                )?;
+                Avm2::dispatch_event(&mut activation.context, http_status_evt, target);
 
-                        let (status_code, redirected) =
-                            if let Error::HttpNotOk(_, status_code, redirected) = response.error {
-                                (status_code, redirected)
-                            } else {
-                                (0, false)
-                            };
-                        let http_status_evt = activation
-                            .avm2()
-                            .classes()
-                            .httpstatusevent
-                            .construct(
-                                &mut activation,
-                                &[
-                                    "httpStatus".into(),
-                                    false.into(),
-                                    false.into(),
-                                    status_code.into(),
-                                    redirected.into(),
-                                ],
-                            )
-                            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                let complete_evt =
+                    Avm2EventObject::bare_default_event(&mut activation.context, "complete");
+                Avm2::dispatch_event(uc, complete_evt, target);
 
-                        Avm2::dispatch_event(&mut activation.context, http_status_evt, target);
+                Ok(())
+            })
+        })
+    }
 
-                        // FIXME - Match the exact error message generated by Flash
+    /// Returns the `URLLoader` that a LoadURLLoader loader is loading into.
+    fn url_loader_target(
+        uc: &UpdateContext<'_, 'gc>,
+        handle: Handle,
+    ) -> Result<Avm2Object<'gc>, Error> {
+        match uc.load_manager.get_loader(handle) {
+            Some(&Loader::LoadURLLoader { target_object, .. }) => Ok(target_object),
+            None => Err(Error::Cancelled),
+            _ => Err(Error::NotLoadDataLoader),
+        }
+    }
 
-                        let io_error_evt_cls = activation.avm2().classes().ioerrorevent;
-                        let io_error_evt = io_error_evt_cls
-                            .construct(
-                                &mut activation,
-                                &[
-                                    "ioError".into(),
-                                    false.into(),
-                                    false.into(),
-                                    "Error #2032: Stream Error".into(),
-                                    2032.into(),
-                                ],
-                            )
-                            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+    /// Report a failed LoadURLLoader load to script code.
+    fn url_loader_error(
+        uc: &mut UpdateContext<'_, 'gc>,
+        handle: Handle,
+        data_format: DataFormat,
+        response: ErrorResponse,
+    ) -> Result<(), Error> {
+        let target = Loader::url_loader_target(uc, handle)?;
+        let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+
+        // Testing with Flash shoes that the 'data' property is cleared
+        // when an error occurs
+        let data = url_loader_data(&mut activation, Vec::new(), data_format);
+        target
+            .set_public_property("data", data, &mut activation)
+            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+        let (status_code, redirected) =
+            if let Error::HttpNotOk(_, status_code, redirected) = response.error {
+                (status_code, redirected)
+            } else {
+                (0, false)
+            };
+        let http_status_evt =
+            http_status_event(&mut activation, status_code, redirected, None, &[])?;
+        Avm2::dispatch_event(&mut activation.context, http_status_evt, target);
+
+        // FIXME - Match the exact error message generated by Flash
+
+        let io_error_evt_cls = activation.avm2().classes().ioerrorevent;
+        let io_error_evt = io_error_evt_cls
+            .construct(
+                &mut activation,
+                &[
+                    "ioError".into(),
+                    false.into(),
+                    false.into(),
+                    "Error #2032: Stream Error".into(),
+                    2032.into(),
+                ],
+            )
+            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+        Avm2::dispatch_event(uc, io_error_evt, target);
 
-                        Avm2::dispatch_event(uc, io_error_evt, target);
-                    }
-                }
+        Ok(())
+    }
 
-                Ok(())
-            })
-        })
+    /// Report a LoadURLLoader load that was blocked by the security sandbox to script code.
+    fn url_loader_security_error(
+        uc: &mut UpdateContext<'_, 'gc>,
+        handle: Handle,
+        violation: SecurityViolation,
+    ) -> Result<(), Error> {
+        let target = Loader::url_loader_target(uc, handle)?;
+        let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+
+        let text = AvmString::new_utf8(activation.context.gc_context, violation.message);
+        let security_error_evt = activation
+            .avm2()
+            .classes()
+            .securityerrorevent
+            .construct(
+                &mut activation,
+                &[
+                    "securityError".into(),
+                    false.into(),
+                    false.into(),
+                    text.into(),
+                    violation.code.into(),
+                ],
+            )
+            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+        Avm2::dispatch_event(uc, security_error_evt, target);
+
+        Ok(())
     }
 
     /// Creates a future for a Sound load call.
@@ -1240,7 +1527,7 @@ impl<'gc> Loader<'gc> {
 
         Box::pin(async move {
             let fetch = player.lock().unwrap().navigator().fetch(request);
-            let data = fetch.await;
+            let data = SuccessResponse::buffered(fetch).await;
 
             // Fire the load handler.
             player.lock().unwrap().update(|uc| {
@@ -1253,8 +1540,8 @@ impl<'gc> Loader<'gc> {
 
                 let success = data
                     .map_err(|e| e.error)
-                    .and_then(|data| {
-                        let handle = uc.audio.register_mp3(&data.body)?;
+                    .and_then(|(_, body)| {
+                        let handle = uc.audio.register_mp3(&body)?;
                         sound_object.set_sound(uc.gc_context, Some(handle));
                         let duration = uc
                             .audio
@@ -1303,7 +1590,7 @@ impl<'gc> Loader<'gc> {
 
         Box::pin(async move {
             let fetch = player.lock().unwrap().navigator().fetch(request);
-            let response = fetch.await;
+            let response = SuccessResponse::buffered(fetch).await;
 
             player.lock().unwrap().update(|uc| {
                 let loader = uc.load_manager.get_loader(handle);
@@ -1314,8 +1601,8 @@ impl<'gc> Loader<'gc> {
                 };
 
                 match response {
-                    Ok(response) => {
-                        let handle = uc.audio.register_mp3(&response.body)?;
+                    Ok((_, body)) => {
+                        let handle = uc.audio.register_mp3(&body)?;
                         if let Err(e) = sound_object
                             .as_sound_object()
                             .expect("Not a sound object")
@@ -1380,55 +1667,546 @@ impl<'gc> Loader<'gc> {
 
         Box::pin(async move {
             let fetch = player.lock().unwrap().navigator().fetch(request);
-            let response = fetch.await;
+            let mut response = match fetch.await {
+                Ok(response) => response,
+                Err(response) => {
+                    return player.lock().unwrap().update(|uc| {
+                        Loader::stream_loader_target(uc, handle)?.report_error(uc, response.error);
+                        Ok(())
+                    });
+                }
+            };
 
             player.lock().unwrap().update(|uc| {
-                let loader = uc.load_manager.get_loader(handle);
-                let stream = match loader {
-                    Some(&Loader::NetStream { target_stream, .. }) => target_stream,
-                    None => return Err(Error::Cancelled),
-                    _ => return Err(Error::NotNetStreamLoader),
-                };
+                Loader::stream_loader_target(uc, handle)?.reset_buffer(uc);
+                Ok(())
+            })?;
 
-                match response {
-                    Ok(mut response) => {
-                        stream.reset_buffer(uc);
-                        stream.load_buffer(uc, &mut response.body);
-                    }
-                    Err(response) => {
-                        stream.report_error(response.error);
-                    }
-                }
+            // Hand the data to the stream as it arrives, so that it can start
+            // playing before the download is complete.
+            let read = read_chunks(&mut response.body, |mut chunk, _, _| {
+                player.lock().unwrap().update(|uc| {
+                    Loader::stream_loader_target(uc, handle)?.load_buffer(uc, &mut chunk);
+                    Ok(())
+                })
+            })
+            .await?;
 
+            player.lock().unwrap().update(|uc| {
+                let stream = Loader::stream_loader_target(uc, handle)?;
+                match read {
+                    Ok(()) => stream.finish_buffer(uc),
+                    Err(error) => stream.report_error(uc, error),
+                }
                 Ok(())
             })
         })
     }
 
-    /// Report a movie loader start event to script code.
-    fn movie_loader_start(handle: Index, uc: &mut UpdateContext<'_, 'gc>) -> Result<(), Error> {
-        let me = uc.load_manager.get_loader_mut(handle);
-        if me.is_none() {
-            return Err(Error::Cancelled);
+    /// Returns the stream that a NetStream loader is loading into.
+    fn stream_loader_target(
+        uc: &UpdateContext<'_, 'gc>,
+        handle: Handle,
+    ) -> Result<NetStream<'gc>, Error> {
+        match uc.load_manager.get_loader(handle) {
+            Some(&Loader::NetStream { target_stream, .. }) => Ok(target_stream),
+            None => Err(Error::Cancelled),
+            _ => Err(Error::NotNetStreamLoader),
         }
+    }
 
-        let me = me.unwrap();
-
-        let (clip, vm_data) = match me {
-            Loader::Movie {
-                target_clip,
-                vm_data,
-                ..
-            } => (*target_clip, *vm_data),
-            _ => unreachable!(),
+    /// Creates a future for a runtime shared library load.
+    fn asset_library_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::AssetLibrary { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotAssetLibraryLoader) }),
         };
 
-        match vm_data {
-            MovieLoaderVMData::Avm1 { broadcaster } => {
-                if let Some(broadcaster) = broadcaster {
-                    Avm1::run_stack_frame_for_method(
-                        clip,
-                        broadcaster,
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let response = SuccessResponse::buffered(fetch).await;
+
+            player.lock().unwrap().update(|uc| {
+                let importer = match uc.load_manager.get_loader(handle) {
+                    Some(&Loader::AssetLibrary { importer, .. }) => importer,
+                    None => return Err(Error::Cancelled),
+                    _ => return Err(Error::NotAssetLibraryLoader),
+                };
+
+                let movie = response.map_err(|e| e.error).and_then(|(response, body)| {
+                    ContentType::sniff(&body).expect(ContentType::Swf)?;
+                    Ok(SwfMovie::from_data(&body, response.url, None)?)
+                });
+
+                let movie = match movie {
+                    Ok(movie) => Arc::new(movie),
+                    Err(e) => {
+                        // Flash keeps playing the importing movie; any imported
+                        // symbols are simply missing.
+                        tracing::error!("Error loading imported asset library: {}", e);
+                        uc.load_manager.remove_loader(handle);
+                        importer.finish_asset_import(uc.gc_context);
+                        return Err(e);
+                    }
+                };
+
+                // The library shares the AVM2 domain of the importer, in case it
+                // contains any `DoAbc` tags of its own.
+                if let Some(domain) = uc
+                    .library
+                    .library_for_movie(importer.movie())
+                    .and_then(|l| l.try_avm2_domain())
+                {
+                    uc.library
+                        .library_for_movie_mut(movie.clone())
+                        .set_avm2_domain(domain);
+                }
+
+                let library_clip = MovieClip::new_with_data(
+                    uc.gc_context,
+                    0,
+                    movie.clone().into(),
+                    movie.num_frames(),
+                );
+
+                if let Some(Loader::AssetLibrary { library, .. }) =
+                    uc.load_manager.get_loader_mut(handle)
+                {
+                    *library = Some(library_clip);
+                }
+
+                Loader::asset_library_preload(handle, uc)
+            })
+        })
+    }
+
+    /// Preload a fetched asset library and bind its exports into the
+    /// importing movie.
+    ///
+    /// Libraries are preloaded in one go, as the importing movie cannot make
+    /// any progress until they are done. If the library is itself waiting on
+    /// an import of its own, this returns early; that import will resume the
+    /// library once it finishes.
+    fn asset_library_preload(handle: Handle, uc: &mut UpdateContext<'_, 'gc>) -> Result<(), Error> {
+        let library_clip = match uc.load_manager.get_loader(handle) {
+            Some(&Loader::AssetLibrary {
+                library: Some(library),
+                ..
+            }) => library,
+            Some(Loader::AssetLibrary { .. }) => return Ok(()),
+            None => return Err(Error::Cancelled),
+            _ => return Err(Error::NotAssetLibraryLoader),
+        };
+
+        if !library_clip.preload(uc, &mut ExecutionLimit::none()) {
+            return Ok(());
+        }
+
+        let (importer, imports) = match uc.load_manager.get_loader_mut(handle) {
+            Some(Loader::AssetLibrary {
+                importer, imports, ..
+            }) => (*importer, std::mem::take(imports)),
+            _ => unreachable!(),
+        };
+        uc.load_manager.remove_loader(handle);
+
+        let library_movie = library_clip.movie();
+        for (id, name) in imports {
+            let name = AvmString::new_utf8(uc.gc_context, name);
+            let character = uc
+                .library
+                .library_for_movie(library_movie.clone())
+                .and_then(|l| l.character_by_export_name(name))
+                .cloned();
+
+            if let Some(character) = character {
+                // Imported symbols are attachable under the name they were
+                // imported with, just like local exports.
+                let library = uc.library.library_for_movie_mut(importer.movie());
+                library.register_character(id, character);
+                library.register_export(id, name);
+            } else {
+                tracing::warn!(
+                    "Can't import {}: {} doesn't export it",
+                    name,
+                    library_movie.url()
+                );
+            }
+        }
+
+        importer.finish_asset_import(uc.gc_context);
+
+        // If the importer is itself an asset library, it was blocked on us.
+        let parent = uc
+            .load_manager
+            .0
+            .iter()
+            .find_map(|(parent, loader)| match loader {
+                Loader::AssetLibrary {
+                    library: Some(library),
+                    ..
+                } if DisplayObject::ptr_eq((*library).into(), importer.into()) => Some(parent),
+                _ => None,
+            });
+        if let Some(parent) = parent {
+            Loader::asset_library_preload(parent, uc)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the object that a FileReference loader reports to.
+    fn file_reference_target(
+        uc: &UpdateContext<'_, 'gc>,
+        handle: Handle,
+    ) -> Result<FileReference<'gc>, Error> {
+        match uc.load_manager.get_loader(handle) {
+            Some(&Loader::FileReference { target_object, .. }) => Ok(target_object),
+            None => Err(Error::Cancelled),
+            _ => Err(Error::NotFileReferenceLoader),
+        }
+    }
+
+    /// Removes a FileReference loader that has nothing left to do, and returns
+    /// the object that it reports to.
+    fn file_reference_finish(
+        uc: &mut UpdateContext<'_, 'gc>,
+        handle: Handle,
+    ) -> Result<FileReference<'gc>, Error> {
+        let target = Loader::file_reference_target(uc, handle)?;
+        uc.load_manager.remove_loader(handle);
+        if target.loader() == Some(handle) {
+            target.set_loader(None);
+        }
+        Ok(target)
+    }
+
+    /// Creates a future for a FileReference browse call.
+    fn file_browse_loader(
+        handle: Handle,
+        player: Weak<Mutex<Player>>,
+        dialog: FileDialogFuture,
+    ) -> OwnedFuture<(), Error> {
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let files = dialog.await;
+
+            player.lock().unwrap().update(|uc| {
+                let target = Loader::file_reference_finish(uc, handle)?;
+                if files.is_empty() {
+                    return target.dispatch(uc, FileReferenceEvent::Cancel);
+                }
+
+                target.select(uc, files)?;
+                target.dispatch(uc, FileReferenceEvent::Select)
+            })
+        })
+    }
+
+    /// Creates a future for a FileReference load call.
+    fn file_load_loader(handle: Handle, player: Weak<Mutex<Player>>) -> OwnedFuture<(), Error> {
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let read = player.lock().unwrap().update(|uc| {
+                let target = Loader::file_reference_target(uc, handle)?;
+                let read = target
+                    .with_file(|file| file.read())
+                    .ok_or(Error::Cancelled)?;
+                target.dispatch(uc, FileReferenceEvent::Open)?;
+                Ok(read)
+            })?;
+
+            let data = read.await;
+
+            player.lock().unwrap().update(|uc| {
+                let target = Loader::file_reference_finish(uc, handle)?;
+                let data = match data {
+                    Ok(data) => data,
+                    Err(error) => {
+                        tracing::warn!("Couldn't read file: {error}");
+                        return target.dispatch(uc, FileReferenceEvent::IoError);
+                    }
+                };
+
+                let length = data.len();
+                target.dispatch(
+                    uc,
+                    FileReferenceEvent::Progress {
+                        loaded: length,
+                        total: length,
+                    },
+                )?;
+                target.set_data(uc, data)?;
+                target.dispatch(uc, FileReferenceEvent::Complete)
+            })
+        })
+    }
+
+    /// Creates a future for a FileReference save call.
+    fn file_save_loader(
+        handle: Handle,
+        player: Weak<Mutex<Player>>,
+        dialog: FileDialogFuture,
+        data: Vec<u8>,
+    ) -> OwnedFuture<(), Error> {
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let files = dialog.await;
+            let length = data.len();
+
+            let write = player.lock().unwrap().update(|uc| {
+                let target = Loader::file_reference_target(uc, handle)?;
+                if files.is_empty() {
+                    Loader::file_reference_finish(uc, handle)?;
+                    target.dispatch(uc, FileReferenceEvent::Cancel)?;
+                    return Ok(None);
+                }
+
+                target.select(uc, files)?;
+                target.dispatch(uc, FileReferenceEvent::Select)?;
+
+                // The movie may have cancelled the save when it heard about the file.
+                let target = Loader::file_reference_target(uc, handle)?;
+                target.dispatch(uc, FileReferenceEvent::Open)?;
+                Ok(target.with_file(|file| file.write(data)))
+            })?;
+            let Some(write) = write else {
+                return Ok(());
+            };
+
+            let result = write.await;
+
+            player.lock().unwrap().update(|uc| {
+                let target = Loader::file_reference_finish(uc, handle)?;
+                if let Err(error) = result {
+                    tracing::warn!("Couldn't save file: {error}");
+                    return target.dispatch(uc, FileReferenceEvent::IoError);
+                }
+
+                target.dispatch(
+                    uc,
+                    FileReferenceEvent::Progress {
+                        loaded: length,
+                        total: length,
+                    },
+                )?;
+                target.dispatch(uc, FileReferenceEvent::Complete)
+            })
+        })
+    }
+
+    /// Creates a future for a FileReference upload call.
+    fn file_upload_loader(
+        handle: Handle,
+        player: Weak<Mutex<Player>>,
+        request: Request,
+        fields: Vec<(String, String)>,
+        field_name: String,
+        requester_url: String,
+    ) -> OwnedFuture<(), Error> {
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            if let Err(violation) = check_data_access(&player, &requester_url, request.url()).await
+            {
+                return player.lock().unwrap().update(|uc| {
+                    let target = Loader::file_reference_finish(uc, handle)?;
+                    target.dispatch(uc, FileReferenceEvent::SecurityError(violation))
+                });
+            }
+
+            let (file_name, read) = player.lock().unwrap().update(|uc| {
+                let target = Loader::file_reference_target(uc, handle)?;
+                let file = target
+                    .with_file(|file| (file.file_name(), file.read()))
+                    .ok_or(Error::Cancelled)?;
+                target.dispatch(uc, FileReferenceEvent::Open)?;
+                Ok(file)
+            })?;
+
+            let contents = match read.await {
+                Ok(contents) => contents,
+                Err(error) => {
+                    tracing::warn!("Couldn't read file to upload: {error}");
+                    return player.lock().unwrap().update(|uc| {
+                        let target = Loader::file_reference_finish(uc, handle)?;
+                        target.dispatch(uc, FileReferenceEvent::IoError)
+                    });
+                }
+            };
+
+            let length = contents.len();
+            let body = multipart_form_data(&fields, &field_name, &file_name, &contents);
+            let mut upload = Request::post(request.url().to_string(), Some(body));
+            upload.set_headers(request.headers().clone());
+
+            let fetch = player.lock().unwrap().navigator().fetch(upload);
+            let response = SuccessResponse::buffered(fetch).await;
+
+            player.lock().unwrap().update(|uc| {
+                let target = Loader::file_reference_finish(uc, handle)?;
+                match response {
+                    Ok((_, body)) => {
+                        target.dispatch(
+                            uc,
+                            FileReferenceEvent::Progress {
+                                loaded: length,
+                                total: length,
+                            },
+                        )?;
+                        target.dispatch(uc, FileReferenceEvent::Complete)?;
+                        target.dispatch(uc, FileReferenceEvent::UploadCompleteData(body))
+                    }
+                    Err(response) => {
+                        if let Error::HttpNotOk(_, status, _) = response.error {
+                            target.dispatch(uc, FileReferenceEvent::HttpError(status))
+                        } else {
+                            target.dispatch(uc, FileReferenceEvent::IoError)
+                        }
+                    }
+                }
+            })
+        })
+    }
+
+    /// Creates a future for a FileReference download call.
+    fn file_download_loader(
+        handle: Handle,
+        player: Weak<Mutex<Player>>,
+        dialog: FileDialogFuture,
+        request: Request,
+        requester_url: String,
+    ) -> OwnedFuture<(), Error> {
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let files = dialog.await;
+
+            let picked = player.lock().unwrap().update(|uc| {
+                let target = Loader::file_reference_target(uc, handle)?;
+                if files.is_empty() {
+                    Loader::file_reference_finish(uc, handle)?;
+                    target.dispatch(uc, FileReferenceEvent::Cancel)?;
+                    return Ok(false);
+                }
+
+                target.select(uc, files)?;
+                target.dispatch(uc, FileReferenceEvent::Select)?;
+                Ok(true)
+            })?;
+            if !picked {
+                return Ok(());
+            }
+
+            if let Err(violation) = check_data_access(&player, &requester_url, request.url()).await
+            {
+                return player.lock().unwrap().update(|uc| {
+                    let target = Loader::file_reference_finish(uc, handle)?;
+                    target.dispatch(uc, FileReferenceEvent::SecurityError(violation))
+                });
+            }
+
+            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let mut response = match fetch.await {
+                Ok(response) => response,
+                Err(response) => {
+                    tracing::warn!("Couldn't download {}: {}", response.url, response.error);
+                    return player.lock().unwrap().update(|uc| {
+                        let target = Loader::file_reference_finish(uc, handle)?;
+                        target.dispatch(uc, FileReferenceEvent::IoError)
+                    });
+                }
+            };
+
+            player.lock().unwrap().update(|uc| {
+                let target = Loader::file_reference_target(uc, handle)?;
+                target.dispatch(uc, FileReferenceEvent::Open)
+            })?;
+
+            let mut body = Vec::new();
+            let read = read_chunks(&mut response.body, |chunk, loaded, total| {
+                body.extend_from_slice(&chunk);
+                player.lock().unwrap().update(|uc| {
+                    let target = Loader::file_reference_target(uc, handle)?;
+                    target.dispatch(uc, FileReferenceEvent::Progress { loaded, total })
+                })
+            })
+            .await?;
+            if let Err(error) = read {
+                tracing::warn!("Couldn't download {}: {}", response.url, error);
+                return player.lock().unwrap().update(|uc| {
+                    let target = Loader::file_reference_finish(uc, handle)?;
+                    target.dispatch(uc, FileReferenceEvent::IoError)
+                });
+            }
+
+            let write = player.lock().unwrap().update(|uc| {
+                let target = Loader::file_reference_target(uc, handle)?;
+                target
+                    .with_file(|file| file.write(body))
+                    .ok_or(Error::Cancelled)
+            })?;
+
+            let result = write.await;
+
+            player.lock().unwrap().update(|uc| {
+                let target = Loader::file_reference_finish(uc, handle)?;
+                if let Err(error) = result {
+                    tracing::warn!("Couldn't save downloaded file: {error}");
+                    return target.dispatch(uc, FileReferenceEvent::IoError);
+                }
+                target.dispatch(uc, FileReferenceEvent::Complete)
+            })
+        })
+    }
+
+    /// Report a movie loader start event to script code.
+    fn movie_loader_start(handle: Index, uc: &mut UpdateContext<'_, 'gc>) -> Result<(), Error> {
+        let me = uc.load_manager.get_loader_mut(handle);
+        if me.is_none() {
+            return Err(Error::Cancelled);
+        }
+
+        let me = me.unwrap();
+
+        let (clip, vm_data) = match me {
+            Loader::Movie {
+                target_clip,
+                vm_data,
+                ..
+            } => (*target_clip, *vm_data),
+            _ => unreachable!(),
+        };
+
+        match vm_data {
+            MovieLoaderVMData::Avm1 { broadcaster } => {
+                if let Some(broadcaster) = broadcaster {
+                    Avm1::run_stack_frame_for_method(
+                        clip,
+                        broadcaster,
                         uc,
                         "broadcastMessage".into(),
                         &["onLoadStart".into(), clip.object()],
@@ -1509,14 +2287,16 @@ impl<'gc> Loader<'gc> {
                 ContentType::Unknown => Arc::new(SwfMovie::error_movie(url.clone())),
             };
 
-            match activation.context.load_manager.get_loader_mut(handle) {
+            let progress_reported = match activation.context.load_manager.get_loader_mut(handle) {
                 Some(Loader::Movie {
                     movie: old,
                     loader_status,
                     ..
                 }) => {
+                    let progress_reported = *loader_status == LoaderStatus::Downloading;
                     *loader_status = LoaderStatus::Parsing;
-                    *old = Some(movie.clone())
+                    *old = Some(movie.clone());
+                    progress_reported
                 }
                 _ => unreachable!(),
             };
@@ -1541,7 +2321,11 @@ impl<'gc> Loader<'gc> {
                 // Flash always fires an initial 'progress' event with
                 // bytesLoaded=0 and bytesTotal set to the proper value.
                 // This only seems to happen for an AVM2 event handler
-                Loader::movie_loader_progress(handle, &mut activation.context, 0, length)?;
+                // If the movie arrived in several chunks, that was already
+                // fired once the download started.
+                if !progress_reported {
+                    Loader::movie_loader_progress(handle, &mut activation.context, 0, length)?;
+                }
 
                 // Update the LoaderStream - we now have a real SWF movie and a real target clip
                 // This is intentionally set *after* the first 'progress' event, to match Flash's behavior
@@ -1685,6 +2469,59 @@ impl<'gc> Loader<'gc> {
         }) //TODO: content sniffing errors need to be reported somehow
     }
 
+    /// Report the progress of a movie that is still downloading to script code.
+    ///
+    /// The movie can't be parsed until it has fully arrived, so until then
+    /// `LoaderInfo` only exposes the downloaded and total lengths.
+    ///
+    /// TODO: Play the movie while it's still downloading, like Flash does. This
+    /// needs `SwfMovie` to grow as chunks arrive, as the slices of movie data held
+    /// by characters and clips currently assume that it's complete, and the
+    /// preloader would have to stop at the last complete tag instead of the end.
+    fn movie_loader_download_progress(
+        handle: Handle,
+        uc: &mut UpdateContext<'_, 'gc>,
+        loaded_len: usize,
+        total_len: usize,
+    ) -> Result<(), Error> {
+        let (clip, vm_data, first_progress) = match uc.load_manager.get_loader_mut(handle) {
+            Some(Loader::Movie {
+                target_clip,
+                vm_data,
+                loader_status,
+                ..
+            }) => {
+                let first_progress = *loader_status == LoaderStatus::Pending;
+                *loader_status = LoaderStatus::Downloading;
+                (*target_clip, *vm_data, first_progress)
+            }
+            None => return Err(Error::Cancelled),
+            _ => unreachable!(),
+        };
+
+        if let MovieLoaderVMData::Avm2 { loader_info, .. } = vm_data {
+            let loader_info = loader_info.as_loader_info_object().unwrap();
+            let fake_movie = Arc::new(SwfMovie::empty_fake_compressed_len(
+                uc.swf.version(),
+                total_len,
+            ));
+            loader_info.set_loader_stream(
+                LoaderStream::NotYetLoaded(fake_movie, Some(clip), false),
+                uc.gc_context,
+            );
+
+            // Like with a movie that arrives all at once, the first 'progress'
+            // event has bytesLoaded=0.
+            if first_progress {
+                loader_info.set_bytes_downloaded(0, uc.gc_context);
+                Loader::movie_loader_progress(handle, uc, 0, total_len)?;
+            }
+            loader_info.set_bytes_downloaded(loaded_len, uc.gc_context);
+        }
+
+        Loader::movie_loader_progress(handle, uc, loaded_len, total_len)
+    }
+
     /// Report a movie loader progress event to script code.
     ///
     /// The current and total length are always reported as compressed lengths.
@@ -2085,6 +2922,7 @@ impl<'gc> Loader<'gc> {
 
         match loader_status {
             LoaderStatus::Pending => false,
+            LoaderStatus::Downloading => false,
             LoaderStatus::Parsing => false,
             LoaderStatus::Failed => true,
             LoaderStatus::Succeeded => {
@@ -2108,3 +2946,564 @@ impl<'gc> Loader<'gc> {
         }
     }
 }
+
+/// The object that a FileReference loader reports to.
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub enum FileReference<'gc> {
+    /// An AVM1 `FileReference`.
+    Avm1(Object<'gc>),
+
+    /// An AVM2 `FileReference`.
+    Avm2(FileReferenceObject<'gc>),
+
+    /// An AVM2 `FileReferenceList`, which is given a new `FileReference` for each picked file.
+    Avm2List(Avm2Object<'gc>),
+}
+
+/// An event that a FileReference loader reports to script code.
+pub enum FileReferenceEvent {
+    Select,
+    Cancel,
+    Open,
+    Progress { loaded: usize, total: usize },
+    Complete,
+    UploadCompleteData(Vec<u8>),
+    HttpError(u16),
+    IoError,
+    SecurityError(SecurityViolation),
+}
+
+impl<'gc> FileReference<'gc> {
+    fn avm1_object(self) -> Option<Avm1FileReferenceObject<'gc>> {
+        match self {
+            FileReference::Avm1(object) => Avm1FileReferenceObject::cast(object.into()),
+            _ => None,
+        }
+    }
+
+    /// The loader that this object is busy with, if any.
+    pub fn loader(self) -> Option<Handle> {
+        match self {
+            FileReference::Avm1(_) => self.avm1_object().and_then(|object| object.loader()),
+            FileReference::Avm2(object) => object.loader(),
+            FileReference::Avm2List(_) => None,
+        }
+    }
+
+    /// Sets the loader that this object is busy with, returning the previous one.
+    pub fn set_loader(self, loader: Option<Handle>) -> Option<Handle> {
+        match self {
+            FileReference::Avm1(_) => self
+                .avm1_object()
+                .and_then(|object| object.set_loader(loader)),
+            FileReference::Avm2(object) => object.set_loader(loader),
+            FileReference::Avm2List(_) => None,
+        }
+    }
+
+    /// Calls `f` with the file that was picked for this object, if any.
+    fn with_file<R>(self, f: impl FnOnce(&dyn FileDialogResult) -> R) -> Option<R> {
+        match self {
+            FileReference::Avm1(_) => self.avm1_object().and_then(|object| object.with_file(f)),
+            FileReference::Avm2(object) => object.with_file(f),
+            FileReference::Avm2List(_) => None,
+        }
+    }
+
+    /// Stores the files picked in a dialog, replacing anything that was loaded before.
+    fn select(
+        self,
+        uc: &mut UpdateContext<'_, 'gc>,
+        files: Vec<Box<dyn FileDialogResult>>,
+    ) -> Result<(), Error> {
+        match self {
+            FileReference::Avm1(_) => {
+                if let Some(object) = self.avm1_object() {
+                    object.set_file(files.into_iter().next());
+                }
+            }
+            FileReference::Avm2(object) => {
+                object.set_file(files.into_iter().next());
+                object.set_data(uc.gc_context, None);
+            }
+            FileReference::Avm2List(object) => {
+                let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+                let mut file_list = Vec::with_capacity(files.len());
+                for file in files {
+                    let file_reference = activation
+                        .avm2()
+                        .classes()
+                        .filereference
+                        .construct(&mut activation, &[])
+                        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                    if let Some(file_reference) = file_reference.as_file_reference() {
+                        file_reference.set_file(Some(file));
+                    }
+                    file_list.push(Some(file_reference.into()));
+                }
+                let file_list = Avm2ArrayObject::from_storage(
+                    &mut activation,
+                    Avm2ArrayStorage::from_storage(file_list),
+                )
+                .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+                let file_list_name =
+                    Multiname::new(activation.avm2().flash_net_internal, "_fileList");
+                object
+                    .set_property(&file_list_name, file_list.into(), &mut activation)
+                    .map_err(|e| Error::Avm2Error(e.to_string()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Exposes the contents of a loaded file to script code.
+    fn set_data(self, uc: &mut UpdateContext<'_, 'gc>, data: Vec<u8>) -> Result<(), Error> {
+        if let FileReference::Avm2(object) = self {
+            let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+            let bytearray =
+                ByteArrayObject::from_storage(&mut activation, ByteArrayStorage::from_vec(data))
+                    .map_err(|e| Error::Avm2Error(e.to_string()))?;
+            object.set_data(activation.context.gc_context, Some(bytearray));
+        }
+        Ok(())
+    }
+
+    /// Reports an event to the listeners of this object.
+    fn dispatch(
+        self,
+        uc: &mut UpdateContext<'_, 'gc>,
+        event: FileReferenceEvent,
+    ) -> Result<(), Error> {
+        match self {
+            FileReference::Avm1(object) => {
+                let mut activation =
+                    Activation::from_stub(uc.reborrow(), ActivationIdentifier::root("[Loader]"));
+                let this = Value::Object(object);
+                let args = match event {
+                    FileReferenceEvent::Select => vec!["onSelect".into(), this],
+                    FileReferenceEvent::Cancel => vec!["onCancel".into(), this],
+                    FileReferenceEvent::Open => vec!["onOpen".into(), this],
+                    FileReferenceEvent::Progress { loaded, total } => {
+                        vec!["onProgress".into(), this, loaded.into(), total.into()]
+                    }
+                    FileReferenceEvent::Complete => vec!["onComplete".into(), this],
+                    FileReferenceEvent::UploadCompleteData(body) => {
+                        let data = AvmString::new_utf8(
+                            activation.context.gc_context,
+                            UTF_8.decode(&body).0,
+                        );
+                        vec!["onUploadCompleteData".into(), this, data.into()]
+                    }
+                    FileReferenceEvent::HttpError(status) => {
+                        vec!["onHTTPError".into(), this, status.into()]
+                    }
+                    FileReferenceEvent::IoError => vec!["onIOError".into(), this],
+                    FileReferenceEvent::SecurityError(violation) => {
+                        let message =
+                            AvmString::new_utf8(activation.context.gc_context, violation.message);
+                        vec!["onSecurityError".into(), this, message.into()]
+                    }
+                };
+                let _ = object.call_method(
+                    "broadcastMessage".into(),
+                    &args,
+                    &mut activation,
+                    ExecutionReason::Special,
+                );
+            }
+            FileReference::Avm2(object) => {
+                Self::dispatch_avm2(uc, object.into(), event)?;
+            }
+            FileReference::Avm2List(object) => {
+                Self::dispatch_avm2(uc, object, event)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn dispatch_avm2(
+        uc: &mut UpdateContext<'_, 'gc>,
+        target: Avm2Object<'gc>,
+        event: FileReferenceEvent,
+    ) -> Result<(), Error> {
+        let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+        let event = match event {
+            FileReferenceEvent::Select => {
+                Avm2EventObject::bare_default_event(&mut activation.context, "select")
+            }
+            FileReferenceEvent::Cancel => {
+                Avm2EventObject::bare_default_event(&mut activation.context, "cancel")
+            }
+            FileReferenceEvent::Open => {
+                Avm2EventObject::bare_default_event(&mut activation.context, "open")
+            }
+            FileReferenceEvent::Progress { loaded, total } => activation
+                .avm2()
+                .classes()
+                .progressevent
+                .construct(
+                    &mut activation,
+                    &[
+                        "progress".into(),
+                        false.into(),
+                        false.into(),
+                        loaded.into(),
+                        total.into(),
+                    ],
+                )
+                .map_err(|e| Error::Avm2Error(e.to_string()))?,
+            FileReferenceEvent::Complete => {
+                Avm2EventObject::bare_default_event(&mut activation.context, "complete")
+            }
+            FileReferenceEvent::UploadCompleteData(body) => {
+                let data =
+                    AvmString::new_utf8(activation.context.gc_context, UTF_8.decode(&body).0);
+                activation
+                    .avm2()
+                    .classes()
+                    .dataevent
+                    .construct(
+                        &mut activation,
+                        &[
+                            "uploadCompleteData".into(),
+                            false.into(),
+                            false.into(),
+                            data.into(),
+                        ],
+                    )
+                    .map_err(|e| Error::Avm2Error(e.to_string()))?
+            }
+            FileReferenceEvent::HttpError(status) => {
+                let http_status_evt = http_status_event(&mut activation, status, false, None, &[])?;
+                Avm2::dispatch_event(&mut activation.context, http_status_evt, target);
+                Self::io_error_event(&mut activation)?
+            }
+            FileReferenceEvent::IoError => Self::io_error_event(&mut activation)?,
+            FileReferenceEvent::SecurityError(violation) => {
+                let text = AvmString::new_utf8(activation.context.gc_context, violation.message);
+                activation
+                    .avm2()
+                    .classes()
+                    .securityerrorevent
+                    .construct(
+                        &mut activation,
+                        &[
+                            "securityError".into(),
+                            false.into(),
+                            false.into(),
+                            text.into(),
+                            violation.code.into(),
+                        ],
+                    )
+                    .map_err(|e| Error::Avm2Error(e.to_string()))?
+            }
+        };
+
+        Avm2::dispatch_event(&mut activation.context, event, target);
+
+        Ok(())
+    }
+
+    fn io_error_event(activation: &mut Avm2Activation<'_, 'gc>) -> Result<Avm2Object<'gc>, Error> {
+        activation
+            .avm2()
+            .classes()
+            .ioerrorevent
+            .construct(
+                activation,
+                &[
+                    "ioError".into(),
+                    false.into(),
+                    false.into(),
+                    "Error #2038: File I/O Error.".into(),
+                    2038.into(),
+                ],
+            )
+            .map_err(|e| Error::Avm2Error(e.to_string()))
+    }
+}
+
+/// The name that Flash suggests when saving the file at `url`, which is the last segment of its path.
+pub fn file_name_from_url(url: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    path.rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Whether `name` can be suggested as the name of a saved file.
+pub fn is_valid_file_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.chars().any(|c| {
+            matches!(
+                c,
+                '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '%'
+            )
+        })
+}
+
+/// Builds the `multipart/form-data` body of a FileReference upload, returning it
+/// along with its content type.
+///
+/// The parts are in the same order that Flash sends them in.
+fn multipart_form_data(
+    fields: &[(String, String)],
+    file_field: &str,
+    file_name: &str,
+    file_data: &[u8],
+) -> (Vec<u8>, String) {
+    let mut boundary = "----------RuffleUploadBoundary".to_string();
+    let mut attempt = 0;
+    while contains_bytes(file_data, boundary.as_bytes())
+        || fields
+            .iter()
+            .any(|(name, value)| name.contains(&boundary) || value.contains(&boundary))
+    {
+        attempt += 1;
+        boundary = format!("----------RuffleUploadBoundary{attempt}");
+    }
+
+    let mut body = Vec::new();
+    let mut add_field = |name: &str, value: &str| {
+        body.extend_from_slice(
+            format!(
+                "--{boundary}\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n"
+            )
+            .as_bytes(),
+        );
+    };
+    add_field("Filename", file_name);
+    for (name, value) in fields {
+        add_field(name, value);
+    }
+    body.extend_from_slice(
+        format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"{file_field}\"; filename=\"{file_name}\"\r\nContent-Type: application/octet-stream\r\n\r\n"
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(file_data);
+    body.extend_from_slice(b"\r\n");
+    body.extend_from_slice(
+        format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"Upload\"\r\n\r\nSubmit Query\r\n--{boundary}--\r\n"
+        )
+        .as_bytes(),
+    );
+
+    (body, format!("multipart/form-data; boundary={boundary}"))
+}
+
+fn contains_bytes(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+/// Waits for the policy files that decide whether a movie loaded from `requester_url`
+/// can load data from `url`, and returns the violation if it can't.
+async fn check_data_access(
+    player: &Arc<Mutex<Player>>,
+    requester_url: &str,
+    url: &str,
+) -> Result<(), SecurityViolation> {
+    loop {
+        let access = player.lock().unwrap().check_data_access(requester_url, url);
+        let policy_url = match access {
+            DataAccess::Allowed => return Ok(()),
+            DataAccess::Denied(violation) => return Err(violation),
+            DataAccess::NeedsPolicyFile(policy_url) => policy_url,
+        };
+
+        let fetch = player
+            .lock()
+            .unwrap()
+            .navigator()
+            .fetch(Request::get(policy_url.clone()));
+        let policy = SuccessResponse::buffered(fetch).await.ok();
+        let content_type = policy.as_ref().and_then(|(response, _)| {
+            response
+                .headers
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+                .map(|(_, value)| value.as_str())
+        });
+        player.lock().unwrap().security_mut().add_policy_file(
+            policy_url,
+            policy.as_ref().map(|(_, body)| body.as_slice()),
+            content_type,
+        );
+    }
+}
+
+/// Converts the body of a LoadURLLoader load into the value of `URLLoader.data`.
+fn url_loader_data<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc>,
+    body: Vec<u8>,
+    data_format: DataFormat,
+) -> Avm2Value<'gc> {
+    match data_format {
+        DataFormat::Binary => {
+            let storage = ByteArrayStorage::from_vec(body);
+            let bytearray = ByteArrayObject::from_storage(activation, storage).unwrap();
+            bytearray.into()
+        }
+        DataFormat::Text => Avm2Value::String(AvmString::new_utf8_bytes(
+            activation.context.gc_context,
+            &body,
+        )),
+        DataFormat::Variables => {
+            tracing::warn!("Support for URLLoaderDataFormat.VARIABLES not yet implemented");
+            Avm2Value::Undefined
+        }
+    }
+}
+
+/// Reads a response body as it arrives, handing each chunk to `on_chunk` along with the
+/// number of bytes loaded so far and the expected total, which is 0 if the length is unknown.
+///
+/// An error returned by `on_chunk` (such as the loader being cancelled) stops reading and is
+/// passed on as the outer error. An error reading the body is returned as the inner error, so
+/// that the caller can report it to the loader's target.
+async fn read_chunks(
+    body: &mut ResponseBody,
+    mut on_chunk: impl FnMut(Vec<u8>, usize, usize) -> Result<(), Error>,
+) -> Result<Result<(), Error>, Error> {
+    let expected_len = body.expected_length().unwrap_or(0) as usize;
+    let mut loaded_len = 0;
+    loop {
+        match body.next_chunk().await {
+            Ok(Some(chunk)) => {
+                loaded_len += chunk.len();
+                let total_len = if expected_len > 0 {
+                    expected_len.max(loaded_len)
+                } else {
+                    0
+                };
+                on_chunk(chunk, loaded_len, total_len)?;
+            }
+            Ok(None) => return Ok(Ok(())),
+            Err(error) => return Ok(Err(error)),
+        }
+    }
+}
+
+/// Creates an `HTTPStatusEvent`, along with the URL and headers of the response if there was one.
+fn http_status_event<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc>,
+    status: u16,
+    redirected: bool,
+    response_url: Option<&str>,
+    headers: &[(String, String)],
+) -> Result<Avm2Object<'gc>, Error> {
+    let http_status_evt = activation
+        .avm2()
+        .classes()
+        .httpstatusevent
+        .construct(
+            activation,
+            &[
+                "httpStatus".into(),
+                false.into(),
+                false.into(),
+                status.into(),
+                redirected.into(),
+            ],
+        )
+        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+    let mut response_headers = Vec::with_capacity(headers.len());
+    for (name, value) in headers {
+        let name = AvmString::new_utf8(activation.context.gc_context, name);
+        let value = AvmString::new_utf8(activation.context.gc_context, value);
+        let header = activation
+            .avm2()
+            .classes()
+            .urlrequestheader
+            .construct(activation, &[name.into(), value.into()])
+            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+        response_headers.push(header.into());
+    }
+    let response_headers =
+        Avm2ArrayObject::from_storage(activation, Avm2ArrayStorage::from_args(&response_headers))
+            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+    http_status_evt
+        .set_public_property("responseHeaders", response_headers.into(), activation)
+        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+
+    if let Some(response_url) = response_url {
+        let response_url = AvmString::new_utf8(activation.context.gc_context, response_url);
+        http_status_evt
+            .set_public_property("responseURL", response_url.into(), activation)
+            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+    }
+
+    Ok(http_status_evt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggests_file_names_from_urls() {
+        assert_eq!(
+            file_name_from_url("http://example.com/files/report.pdf?v=2#page=3"),
+            "report.pdf"
+        );
+        assert_eq!(file_name_from_url("data/level1.xml"), "level1.xml");
+        assert_eq!(file_name_from_url("http://example.com/"), "");
+    }
+
+    #[test]
+    fn validates_file_names() {
+        assert!(is_valid_file_name("save game.sol"));
+        assert!(!is_valid_file_name(""));
+        assert!(!is_valid_file_name("../passwd"));
+        assert!(!is_valid_file_name("what?.txt"));
+        assert!(!is_valid_file_name("100%.txt"));
+    }
+
+    #[test]
+    fn builds_multipart_uploads() {
+        let fields = vec![("user".to_string(), "alice".to_string())];
+        let (body, content_type) = multipart_form_data(&fields, "Filedata", "a.txt", b"hello");
+
+        assert_eq!(
+            content_type,
+            "multipart/form-data; boundary=----------RuffleUploadBoundary"
+        );
+        assert_eq!(
+            String::from_utf8(body).unwrap(),
+            "------------RuffleUploadBoundary\r\n\
+             Content-Disposition: form-data; name=\"Filename\"\r\n\r\n\
+             a.txt\r\n\
+             ------------RuffleUploadBoundary\r\n\
+             Content-Disposition: form-data; name=\"user\"\r\n\r\n\
+             alice\r\n\
+             ------------RuffleUploadBoundary\r\n\
+             Content-Disposition: form-data; name=\"Filedata\"; filename=\"a.txt\"\r\n\
+             Content-Type: application/octet-stream\r\n\r\n\
+             hello\r\n\
+             ------------RuffleUploadBoundary\r\n\
+             Content-Disposition: form-data; name=\"Upload\"\r\n\r\n\
+             Submit Query\r\n\
+             ------------RuffleUploadBoundary--\r\n"
+        );
+    }
+
+    #[test]
+    fn picks_a_boundary_missing_from_the_file() {
+        let (body, content_type) =
+            multipart_form_data(&[], "Filedata", "a.txt", b"----------RuffleUploadBoundary");
+
+        assert_eq!(
+            content_type,
+            "multipart/form-data; boundary=----------RuffleUploadBoundary1"
+        );
+        assert!(body.ends_with(b"------------RuffleUploadBoundary1--\r\n"));
+    }
+}
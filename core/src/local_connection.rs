@@ -0,0 +1,337 @@
+//! Management of `LocalConnection` objects, and the messages sent between them.
+
+use crate::avm1::globals::local_connection as avm1_local_connection;
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier, ExecutionReason, Object as Avm1Object,
+    ScriptObject as Avm1ScriptObject, TObject as Avm1TObject, Value as Avm1Value,
+};
+use crate::avm2::globals::flash::net::local_connection as avm2_local_connection;
+use crate::avm2::{
+    Activation as Avm2Activation, Avm2, Error as Avm2Error, Multiname, Object as Avm2Object,
+    TObject as Avm2TObject, Value as Avm2Value,
+};
+use crate::backend::local_connection::{LocalConnectionBackend, LocalConnectionMessage};
+use crate::context::UpdateContext;
+use crate::string::AvmString;
+use gc_arena::Collect;
+use std::collections::HashMap;
+
+/// The largest that the AMF encoding of the arguments of a message can be.
+pub const MAX_ARGUMENTS_SIZE: usize = 40 * 1024;
+
+#[derive(Copy, Clone, Collect)]
+#[collect(no_drop)]
+pub enum LocalConnectionKind<'gc> {
+    Avm1(Avm1Object<'gc>),
+    Avm2(Avm2Object<'gc>),
+}
+
+impl<'gc> PartialEq for LocalConnectionKind<'gc> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Avm1(a), Self::Avm1(b)) => Avm1Object::ptr_eq(*a, *b),
+            (Self::Avm2(a), Self::Avm2(b)) => Avm2Object::ptr_eq(*a, *b),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct LocalConnection<'gc> {
+    target: LocalConnectionKind<'gc>,
+
+    /// The domain of the movie that connected, which can always send messages to it.
+    domain: String,
+}
+
+/// Manages the `LocalConnection` objects of a player.
+///
+/// Messages are exchanged through the `LocalConnectionBackend`, which may deliver them
+/// to this player or to another one.
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct LocalConnections<'gc> {
+    /// The connected objects, by fully qualified connection name.
+    connections: HashMap<String, LocalConnection<'gc>>,
+
+    /// Senders that are yet to be told whether their message was received.
+    statuses: Vec<(LocalConnectionKind<'gc>, bool)>,
+}
+
+impl<'gc> LocalConnections<'gc> {
+    pub fn empty() -> Self {
+        Self {
+            connections: HashMap::new(),
+            statuses: Vec::new(),
+        }
+    }
+
+    /// The domain of a movie, as seen by `LocalConnection`.
+    ///
+    /// Returns `None` if the URL of the movie can't be parsed.
+    pub fn domain(url: &str) -> Option<String> {
+        let url = url::Url::parse(url).ok()?;
+        Some(match url.domain() {
+            Some(domain) if url.scheme() != "file" => domain.to_string(),
+            _ => "localhost".to_string(),
+        })
+    }
+
+    /// The name that identifies a connection between players.
+    ///
+    /// Names starting with an underscore are shared by every domain, and names that
+    /// already contain a domain are used as-is. Other names are only visible to the same domain.
+    pub fn qualified_name(domain: &str, name: &str) -> String {
+        if name.starts_with('_') || name.contains(':') {
+            name.to_string()
+        } else {
+            format!("{domain}:{name}")
+        }
+    }
+
+    pub fn is_connected(&self, target: LocalConnectionKind<'gc>) -> bool {
+        self.connections
+            .values()
+            .any(|connection| connection.target == target)
+    }
+
+    /// Starts listening for messages sent to `name`.
+    ///
+    /// Returns `false` if the object is already connected, or if the name is in use.
+    pub fn connect(
+        &mut self,
+        backend: &mut dyn LocalConnectionBackend,
+        target: LocalConnectionKind<'gc>,
+        domain: &str,
+        name: &str,
+    ) -> bool {
+        if self.is_connected(target) {
+            return false;
+        }
+
+        let name = Self::qualified_name(domain, name);
+        if !backend.connect(&name) {
+            return false;
+        }
+
+        self.connections.insert(
+            name,
+            LocalConnection {
+                target,
+                domain: domain.to_string(),
+            },
+        );
+        true
+    }
+
+    /// Stops listening for messages.
+    ///
+    /// Returns `false` if the object wasn't connected.
+    pub fn close(
+        &mut self,
+        backend: &mut dyn LocalConnectionBackend,
+        target: LocalConnectionKind<'gc>,
+    ) -> bool {
+        let name = self
+            .connections
+            .iter()
+            .find(|(_, connection)| connection.target == target)
+            .map(|(name, _)| name.clone());
+
+        if let Some(name) = name {
+            self.connections.remove(&name);
+            backend.close(&name);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Sends a message, and remembers to tell `sender` whether it was received.
+    pub fn send(
+        &mut self,
+        backend: &mut dyn LocalConnectionBackend,
+        sender: LocalConnectionKind<'gc>,
+        domain: &str,
+        name: &str,
+        method_name: String,
+        arguments: Vec<u8>,
+    ) {
+        let received = backend.send(LocalConnectionMessage {
+            connection_name: Self::qualified_name(domain, name),
+            method_name,
+            sender_domain: domain.to_string(),
+            arguments,
+        });
+        self.statuses.push((sender, received));
+    }
+
+    /// Dispatches the statuses of sent messages, and calls the methods of received messages.
+    pub fn update_connections(context: &mut UpdateContext<'_, 'gc>) {
+        for (sender, received) in std::mem::take(&mut context.local_connections.statuses) {
+            let level = if received { "status" } else { "error" };
+            match sender {
+                LocalConnectionKind::Avm1(object) => Self::avm1_status(context, object, level),
+                LocalConnectionKind::Avm2(object) => Self::avm2_status(context, object, level),
+            }
+        }
+
+        for message in context.local_connection.receive() {
+            // The connection may have been closed since the message was sent.
+            let Some(connection) = context
+                .local_connections
+                .connections
+                .get(&message.connection_name)
+            else {
+                continue;
+            };
+
+            let target = connection.target;
+            let domain = connection.domain.clone();
+            match target {
+                LocalConnectionKind::Avm1(object) => {
+                    Self::avm1_receive(context, object, &domain, &message)
+                }
+                LocalConnectionKind::Avm2(object) => {
+                    let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                    if let Err(e) = Self::avm2_receive(&mut activation, object, &domain, &message) {
+                        tracing::error!("Unhandled AVM2 error in LocalConnection callback: {e:?}");
+                    }
+                }
+            }
+        }
+    }
+
+    fn avm1_status(
+        context: &mut UpdateContext<'_, 'gc>,
+        object: Avm1Object<'gc>,
+        level: &'static str,
+    ) {
+        let Some(root) = context.stage.root_clip() else {
+            return;
+        };
+        let mut activation = Avm1Activation::from_nothing(
+            context.reborrow(),
+            ActivationIdentifier::root("[LocalConnection Status]"),
+            root,
+        );
+        let info_object = Avm1ScriptObject::new(
+            activation.context.gc_context,
+            Some(activation.context.avm1.prototypes().object),
+        );
+        let _ = info_object.set("level", Avm1Value::String(level.into()), &mut activation);
+
+        if let Err(e) = object.call_method(
+            "onStatus".into(),
+            &[info_object.into()],
+            &mut activation,
+            ExecutionReason::Special,
+        ) {
+            tracing::error!("Unhandled AVM1 error in LocalConnection onStatus: {}", e);
+        }
+    }
+
+    fn avm2_status(
+        context: &mut UpdateContext<'_, 'gc>,
+        object: Avm2Object<'gc>,
+        level: &'static str,
+    ) {
+        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+        let event = activation.avm2().classes().statusevent.construct(
+            &mut activation,
+            &[
+                "status".into(),
+                false.into(),
+                false.into(),
+                Avm2Value::Null,
+                level.into(),
+            ],
+        );
+
+        match event {
+            Ok(event) => Avm2::dispatch_event(&mut activation.context, event, object),
+            Err(e) => tracing::error!("Couldn't create LocalConnection status event: {e:?}"),
+        }
+    }
+
+    fn avm1_receive(
+        context: &mut UpdateContext<'_, 'gc>,
+        object: Avm1Object<'gc>,
+        domain: &str,
+        message: &LocalConnectionMessage,
+    ) {
+        let Some(root) = context.stage.root_clip() else {
+            return;
+        };
+        let mut activation = Avm1Activation::from_nothing(
+            context.reborrow(),
+            ActivationIdentifier::root("[LocalConnection]"),
+            root,
+        );
+
+        // Other domains have to be allowed by the `allowDomain` callback.
+        if message.sender_domain != domain {
+            let sender_domain =
+                AvmString::new_utf8(activation.context.gc_context, &message.sender_domain);
+            let allowed = object
+                .call_method(
+                    "allowDomain".into(),
+                    &[sender_domain.into()],
+                    &mut activation,
+                    ExecutionReason::Special,
+                )
+                .map(|allowed| allowed.as_bool(activation.swf_version()))
+                .unwrap_or(false);
+            if !allowed {
+                return;
+            }
+        }
+
+        if avm1_local_connection::RESERVED_METHODS.contains(&message.method_name.as_str()) {
+            return;
+        }
+
+        let arguments =
+            avm1_local_connection::deserialize_arguments(&mut activation, &message.arguments);
+        let method_name = AvmString::new_utf8(activation.context.gc_context, &message.method_name);
+        if let Err(e) = object.call_method(
+            method_name,
+            &arguments,
+            &mut activation,
+            ExecutionReason::Special,
+        ) {
+            tracing::error!("Unhandled AVM1 error in LocalConnection callback: {}", e);
+        }
+    }
+
+    fn avm2_receive(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        object: Avm2Object<'gc>,
+        domain: &str,
+        message: &LocalConnectionMessage,
+    ) -> Result<(), Avm2Error<'gc>> {
+        // Other domains have to be allowed with `allowDomain`.
+        if message.sender_domain != domain {
+            let sender_domain =
+                AvmString::new_utf8(activation.context.gc_context, &message.sender_domain);
+            let is_domain_allowed =
+                Multiname::new(activation.avm2().flash_net_internal, "isDomainAllowed");
+            let allowed = object
+                .call_property(&is_domain_allowed, &[sender_domain.into()], activation)?
+                .coerce_to_boolean();
+            if !allowed {
+                return Ok(());
+            }
+        }
+
+        let arguments =
+            avm2_local_connection::deserialize_arguments(activation, &message.arguments)?;
+        let client = object
+            .get_public_property("client", activation)?
+            .coerce_to_object(activation)?;
+        let method_name = AvmString::new_utf8(activation.context.gc_context, &message.method_name);
+        client.call_public_property(method_name, &arguments, activation)?;
+        Ok(())
+    }
+}
@@ -12,13 +12,14 @@ use crate::avm2::{
 };
 use crate::backend::{
     audio::{AudioBackend, AudioManager},
+    local_connection::{InProcessLocalConnectionBackend, LocalConnectionBackend},
     log::LogBackend,
     navigator::{NavigatorBackend, Request},
     storage::StorageBackend,
     ui::{InputManager, MouseCursor, UiBackend},
 };
 use crate::compatibility_rules::CompatibilityRules;
-use crate::config::Letterbox;
+use crate::config::{Letterbox, SecurityMode};
 use crate::context::GcContext;
 use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
 use crate::context_menu::{
@@ -38,8 +39,10 @@ use crate::frame_lifecycle::{run_all_phases_avm2, FramePhase};
 use crate::library::Library;
 use crate::limits::ExecutionLimit;
 use crate::loader::{LoadBehavior, LoadManager};
+use crate::local_connection::LocalConnections;
 use crate::locale::get_current_date_time;
 use crate::prelude::*;
+use crate::security::{DataAccess, SecurityManager};
 use crate::socket::Sockets;
 use crate::streams::StreamManager;
 use crate::string::{AvmString, AvmStringInterner};
@@ -163,6 +166,8 @@ struct GcRootData<'gc> {
 
     sockets: Sockets<'gc>,
 
+    local_connections: LocalConnections<'gc>,
+
     /// Dynamic root for allowing handles to GC objects to exist outside of the GC.
     dynamic_root: DynamicRootSet<'gc>,
 }
@@ -191,6 +196,7 @@ impl<'gc> GcRootData<'gc> {
         &mut AudioManager<'gc>,
         &mut StreamManager<'gc>,
         &mut Sockets<'gc>,
+        &mut LocalConnections<'gc>,
         DynamicRootSet<'gc>,
     ) {
         (
@@ -211,6 +217,7 @@ impl<'gc> GcRootData<'gc> {
             &mut self.audio_manager,
             &mut self.stream_manager,
             &mut self.sockets,
+            &mut self.local_connections,
             self.dynamic_root,
         )
     }
@@ -222,6 +229,7 @@ type Audio = Box<dyn AudioBackend>;
 type Navigator = Box<dyn NavigatorBackend>;
 type Renderer = Box<dyn RenderBackend>;
 type Storage = Box<dyn StorageBackend>;
+type LocalConnection = Box<dyn LocalConnectionBackend>;
 type Log = Box<dyn LogBackend>;
 type Ui = Box<dyn UiBackend>;
 type Video = Box<dyn VideoBackend>;
@@ -248,6 +256,7 @@ pub struct Player {
     audio: Audio,
     navigator: Navigator,
     storage: Storage,
+    local_connection: LocalConnection,
     log: Log,
     ui: Ui,
     video: Video,
@@ -287,6 +296,9 @@ pub struct Player {
 
     system: SystemProperties,
 
+    /// The permissions that domains have granted each other, and the policy files loaded so far.
+    security: SecurityManager,
+
     /// The current instance ID. Used to generate default `instanceN` names.
     instance_counter: i32,
 
@@ -551,10 +563,12 @@ impl Player {
             });
 
             self.update_sockets();
+            self.update_local_connections();
             self.update_timers(dt);
             self.update(|context| {
                 StreamManager::tick(context, dt);
             });
+            self.update_dynamic_sounds();
             self.audio.tick();
         }
     }
@@ -1650,6 +1664,9 @@ impl Player {
                 Avm1::run_frame(context);
             }
             AudioManager::update_sounds(context);
+
+            // Senders hear back about their messages by the end of the frame.
+            LocalConnections::update_connections(context);
         });
 
         self.needs_render = true;
@@ -1732,6 +1749,20 @@ impl Player {
         &self.navigator
     }
 
+    pub fn security_mut(&mut self) -> &mut SecurityManager {
+        &mut self.security
+    }
+
+    /// Checks whether a movie loaded from `requester_url` can load data from `url`.
+    pub fn check_data_access(&self, requester_url: &str, url: &str) -> DataAccess {
+        let url = match self.navigator.resolve_url(url) {
+            Ok(url) => url.to_string(),
+            Err(_) => url.to_string(),
+        };
+        self.security
+            .data_access(&self.system.sandbox_type, requester_url, &url)
+    }
+
     // The frame rate of the current movie in FPS.
     pub fn frame_rate(&self) -> f64 {
         self.frame_rate
@@ -1891,6 +1922,7 @@ impl Player {
                 audio_manager,
                 stream_manager,
                 sockets,
+                local_connections,
                 dynamic_root,
             ) = root_data.update_context_params();
 
@@ -1915,8 +1947,10 @@ impl Player {
                 player: self.self_reference.clone(),
                 load_manager,
                 system: &mut self.system,
+                security: &mut self.security,
                 instance_counter: &mut self.instance_counter,
                 storage: self.storage.deref_mut(),
+                local_connection: self.local_connection.deref_mut(),
                 log: self.log.deref_mut(),
                 video: self.video.deref_mut(),
                 avm1_shared_objects,
@@ -1942,6 +1976,7 @@ impl Player {
                 stub_tracker: &mut self.stub_tracker,
                 stream_manager,
                 sockets,
+                local_connections,
                 dynamic_root,
             };
 
@@ -2072,6 +2107,14 @@ impl Player {
             self.mutate_with_update_context(|context| Timers::update_timers(context, dt));
     }
 
+    /// Request more audio for sounds generated by ActionScript.
+    /// This should be called before the audio backend consumes audio.
+    pub fn update_dynamic_sounds(&mut self) {
+        self.update(|context| {
+            AudioManager::update_dynamic_sounds(context);
+        });
+    }
+
     /// Update connected Sockets.
     pub fn update_sockets(&mut self) {
         self.mutate_with_update_context(|context| {
@@ -2079,6 +2122,13 @@ impl Player {
         })
     }
 
+    /// Deliver LocalConnection messages sent to this player, and their statuses to the senders.
+    pub fn update_local_connections(&mut self) {
+        self.update(|context| {
+            LocalConnections::update_connections(context);
+        })
+    }
+
     /// Returns whether this player consumes mouse wheel events.
     /// Used by web to prevent scrolling.
     pub fn should_prevent_scrolling(&mut self) -> bool {
@@ -2142,6 +2192,7 @@ pub struct PlayerBuilder {
     navigator: Option<Navigator>,
     renderer: Option<Renderer>,
     storage: Option<Storage>,
+    local_connection: Option<LocalConnection>,
     ui: Option<Ui>,
     video: Option<Video>,
 
@@ -2163,6 +2214,7 @@ pub struct PlayerBuilder {
     player_version: Option<u8>,
     quality: StageQuality,
     sandbox_type: SandboxType,
+    security_mode: SecurityMode,
     frame_rate: Option<f64>,
     external_interface_providers: Vec<Box<dyn ExternalInterfaceProvider>>,
     fs_command_provider: Box<dyn FsCommandProvider>,
@@ -2183,6 +2235,7 @@ impl PlayerBuilder {
             navigator: None,
             renderer: None,
             storage: None,
+            local_connection: None,
             ui: None,
             video: None,
 
@@ -2208,6 +2261,7 @@ impl PlayerBuilder {
             player_version: None,
             quality: StageQuality::High,
             sandbox_type: SandboxType::LocalTrusted,
+            security_mode: SecurityMode::Permissive,
             frame_rate: None,
             external_interface_providers: vec![],
             fs_command_provider: Box::new(NullFsCommandProvider),
@@ -2256,6 +2310,18 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets the LocalConnection backend of the player.
+    ///
+    /// By default, a player can exchange messages with every other player in the process.
+    #[inline]
+    pub fn with_local_connection(
+        mut self,
+        local_connection: impl 'static + LocalConnectionBackend,
+    ) -> Self {
+        self.local_connection = Some(Box::new(local_connection));
+        self
+    }
+
     /// Sets the UI backend of the player.
     #[inline]
     pub fn with_ui(mut self, ui: impl 'static + UiBackend) -> Self {
@@ -2363,6 +2429,12 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets how strictly the security sandbox is enforced (default is `SecurityMode::Permissive`).
+    pub fn with_security_mode(mut self, security_mode: SecurityMode) -> Self {
+        self.security_mode = security_mode;
+        self
+    }
+
     /// Sets and locks the player's frame rate. If None is provided, this has no effect.
     pub fn with_frame_rate(mut self, frame_rate: Option<f64>) -> Self {
         self.frame_rate = frame_rate;
@@ -2424,6 +2496,7 @@ impl PlayerBuilder {
                     unbound_text_fields: Vec::new(),
                     stream_manager: StreamManager::new(),
                     sockets: Sockets::empty(),
+                    local_connections: LocalConnections::empty(),
                     dynamic_root,
                 },
             ),
@@ -2453,6 +2526,9 @@ impl PlayerBuilder {
         let storage = self
             .storage
             .unwrap_or_else(|| Box::new(storage::MemoryStorageBackend::new()));
+        let local_connection = self
+            .local_connection
+            .unwrap_or_else(|| Box::new(InProcessLocalConnectionBackend::default()));
         let ui = self
             .ui
             .unwrap_or_else(|| Box::new(ui::NullUiBackend::new()));
@@ -2474,6 +2550,7 @@ impl PlayerBuilder {
                 navigator,
                 renderer,
                 storage,
+                local_connection,
                 ui,
                 video,
 
@@ -2503,6 +2580,7 @@ impl PlayerBuilder {
                 // Misc. state
                 rng: SmallRng::seed_from_u64(get_current_date_time().timestamp_millis() as u64),
                 system: SystemProperties::new(self.sandbox_type),
+                security: SecurityManager::new(self.security_mode),
                 transform_stack: TransformStack::new(),
                 instance_counter: 0,
                 player_version,
@@ -0,0 +1,785 @@
+//! The security sandbox, which decides what content a movie is allowed to access.
+//!
+//! Every movie belongs to the security domain it was loaded from. Movies can always access
+//! content from their own domain. Other domains have to grant access, either by calling
+//! `Security.allowDomain` or by serving a `crossdomain.xml` policy file.
+//!
+//! Only some cross-domain access is checked so far: loading data, connecting sockets,
+//! reading pixels with `BitmapData.draw`, and reaching another movie through AVM2's
+//! `LoaderInfo.content`. Movies can otherwise still script each other, such as AVM1
+//! movies reaching into `_level`s or clips loaded from other domains.
+
+use crate::avm1::globals::system::SandboxType;
+use crate::config::SecurityMode;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use url::Url;
+
+/// The MIME type that policy files are served with, when a server cares about it.
+pub const POLICY_FILE_CONTENT_TYPE: &str = "text/x-cross-domain-policy";
+
+/// The port where socket servers are first asked for a policy file.
+pub const MASTER_SOCKET_POLICY_PORT: u16 = 843;
+
+/// Where a movie, or any other resource, was loaded from.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SecurityDomain {
+    /// The local filesystem, or a URL that can't be parsed.
+    Local,
+
+    /// A server on the network.
+    Remote {
+        /// The scheme, host and port of the server, such as `https://example.com:8080`.
+        origin: String,
+
+        /// The lowercase host name of the server.
+        host: String,
+
+        /// Whether the server was reached over HTTPS.
+        secure: bool,
+    },
+}
+
+impl SecurityDomain {
+    pub fn from_url(url: &str) -> Self {
+        let Ok(url) = Url::parse(url) else {
+            return Self::Local;
+        };
+        match url.host_str() {
+            Some(host) if url.scheme() != "file" => Self::Remote {
+                origin: url.origin().ascii_serialization(),
+                host: host.to_ascii_lowercase(),
+                secure: url.scheme() == "https",
+            },
+            _ => Self::Local,
+        }
+    }
+
+    fn is_secure(&self) -> bool {
+        matches!(self, Self::Remote { secure: true, .. })
+    }
+
+    /// Whether a domain pattern, as given to `Security.allowDomain` or found in a policy file,
+    /// matches this domain.
+    ///
+    /// Patterns are either `*`, a host name, or a host name prefixed by `*.` which also
+    /// matches every subdomain.
+    fn matches(&self, pattern: &str) -> bool {
+        if pattern == "*" {
+            return true;
+        }
+        let Self::Remote { host, .. } = self else {
+            return false;
+        };
+        match pattern.strip_prefix("*.") {
+            Some(suffix) => {
+                host == suffix
+                    || host
+                        .strip_suffix(suffix)
+                        .is_some_and(|prefix| prefix.ends_with('.'))
+            }
+            None => host == pattern,
+        }
+    }
+
+    /// The URL of the master policy file of this domain, which governs every other policy file.
+    fn master_policy_url(&self) -> Option<String> {
+        match self {
+            Self::Local => None,
+            Self::Remote { origin, .. } => Some(format!("{origin}/crossdomain.xml")),
+        }
+    }
+}
+
+/// Turns an argument of `Security.allowDomain` into a domain pattern.
+///
+/// Flash Player accepts the URL of a movie in place of its domain.
+fn domain_pattern(domain: &str) -> String {
+    let domain = domain.trim();
+    let host = match Url::parse(domain) {
+        Ok(url) if domain.contains("://") => url.host_str().unwrap_or_default().to_string(),
+        _ => domain.to_string(),
+    };
+    host.to_ascii_lowercase()
+}
+
+/// Which policy files a server honors, as declared by its master policy file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MetaPolicy {
+    /// No policy file is honored, not even the master policy file.
+    None,
+
+    /// Only the master policy file, at `/crossdomain.xml`, is honored.
+    #[default]
+    MasterOnly,
+
+    /// Policy files are honored if they are served as `text/x-cross-domain-policy`.
+    ByContentType,
+
+    /// Every policy file is honored.
+    All,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct AllowAccessFrom {
+    domain: String,
+
+    /// Whether movies loaded over HTTP are refused when the policy file was served over HTTPS.
+    secure: bool,
+
+    /// The ports that sockets may connect to. Socket policy files without any allow nothing.
+    to_ports: Vec<RangeInclusive<u16>>,
+}
+
+/// A parsed `crossdomain.xml` policy file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PolicyFile {
+    meta_policy: Option<MetaPolicy>,
+    allow_access_from: Vec<AllowAccessFrom>,
+}
+
+impl PolicyFile {
+    /// Parses a policy file.
+    ///
+    /// Returns `None` if the data isn't well-formed XML with a `cross-domain-policy` root.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let mut reader = Reader::from_reader(data);
+        reader.expand_empty_elements(true);
+        reader.trim_text(true);
+
+        let mut policy = None;
+        let mut depth = 0;
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(element)) => {
+                    match depth {
+                        0 if element.name().into_inner() == b"cross-domain-policy" => {
+                            policy = Some(Self::default());
+                        }
+                        0 => return None,
+                        1 => policy.as_mut()?.parse_directive(&element),
+                        _ => {}
+                    }
+                    depth += 1;
+                }
+                Ok(Event::End(_)) => depth -= 1,
+                // Unclosed elements make the file malformed.
+                Ok(Event::Eof) if depth != 0 => return None,
+                Ok(Event::Eof) => break,
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!("Error while parsing policy file: {}", e);
+                    return None;
+                }
+            }
+        }
+        policy
+    }
+
+    fn parse_directive(&mut self, element: &BytesStart<'_>) {
+        let attribute = |name: &[u8]| {
+            element.attributes().flatten().find_map(|attribute| {
+                (attribute.key.into_inner() == name).then(|| {
+                    String::from_utf8_lossy(&attribute.value)
+                        .trim()
+                        .to_ascii_lowercase()
+                })
+            })
+        };
+
+        match element.name().into_inner() {
+            b"site-control" => {
+                self.meta_policy = match attribute(b"permitted-cross-domain-policies").as_deref() {
+                    Some("none") => Some(MetaPolicy::None),
+                    Some("master-only") => Some(MetaPolicy::MasterOnly),
+                    Some("by-content-type") => Some(MetaPolicy::ByContentType),
+                    Some("all") => Some(MetaPolicy::All),
+                    _ => self.meta_policy,
+                };
+            }
+            b"allow-access-from" => {
+                if let Some(domain) = attribute(b"domain") {
+                    let secure = attribute(b"secure").as_deref() != Some("false");
+                    let to_ports = attribute(b"to-ports")
+                        .map(|to_ports| parse_port_ranges(&to_ports))
+                        .unwrap_or_default();
+                    self.allow_access_from.push(AllowAccessFrom {
+                        domain,
+                        secure,
+                        to_ports,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The meta-policy that this file declares, if it's the master policy file of its server.
+    pub fn meta_policy(&self) -> MetaPolicy {
+        self.meta_policy.unwrap_or_default()
+    }
+
+    /// Whether this policy file, served by `location`, lets movies from `requester` load data.
+    pub fn allows(&self, requester: &SecurityDomain, location: &SecurityDomain) -> bool {
+        self.allow_access_from.iter().any(|entry| {
+            requester.matches(&entry.domain)
+                && (!entry.secure || !location.is_secure() || requester.is_secure())
+        })
+    }
+
+    /// Whether this socket policy file lets movies from `requester` connect to `port`.
+    pub fn allows_socket(&self, requester: &SecurityDomain, port: u16) -> bool {
+        self.allow_access_from.iter().any(|entry| {
+            requester.matches(&entry.domain)
+                && entry.to_ports.iter().any(|ports| ports.contains(&port))
+        })
+    }
+}
+
+/// Parses the `to-ports` attribute of a socket policy file, such as `*`, `507,516` or `516-523`.
+///
+/// Entries that aren't valid ports are ignored.
+fn parse_port_ranges(to_ports: &str) -> Vec<RangeInclusive<u16>> {
+    to_ports
+        .split(',')
+        .filter_map(|entry| match entry.trim() {
+            "*" => Some(0..=u16::MAX),
+            entry => match entry.split_once('-') {
+                Some((start, end)) => Some(start.trim().parse().ok()?..=end.trim().parse().ok()?),
+                None => {
+                    let port = entry.parse().ok()?;
+                    Some(port..=port)
+                }
+            },
+        })
+        .collect()
+}
+
+/// Whether a policy file at `policy_url` applies to `target`.
+///
+/// A policy file only covers its own directory and the directories below it.
+fn policy_covers(policy_url: &Url, target: &Url) -> bool {
+    let directory = &policy_url.path()[..=policy_url.path().rfind('/').unwrap_or(0)];
+    policy_url.origin() == target.origin() && target.path().starts_with(directory)
+}
+
+/// A security sandbox violation, reported as a `SecurityError` or a `SecurityErrorEvent`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SecurityViolation {
+    pub code: u32,
+    pub message: String,
+}
+
+impl SecurityViolation {
+    fn cannot_load_data(requester: &str, target: &str) -> Self {
+        Self {
+            code: 2048,
+            message: format!(
+                "Error #2048: Security sandbox violation: {requester} cannot load data from {target}."
+            ),
+        }
+    }
+
+    fn cannot_access_local_resource(requester: &str, target: &str) -> Self {
+        Self {
+            code: 2148,
+            message: format!(
+                "Error #2148: SWF file {requester} cannot access local resource {target}. Only local-with-filesystem and trusted local SWF files may access local resources."
+            ),
+        }
+    }
+}
+
+/// Whether a movie can load data from a URL.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DataAccess {
+    Allowed,
+
+    /// The policy file at this URL has to be loaded, with `SecurityManager::add_policy_file`,
+    /// before access can be decided.
+    NeedsPolicyFile(String),
+
+    Denied(SecurityViolation),
+}
+
+#[derive(Clone, Debug)]
+struct LoadedPolicyFile {
+    policy: PolicyFile,
+
+    /// Whether the policy file was served as `text/x-cross-domain-policy`.
+    has_policy_content_type: bool,
+}
+
+#[derive(Clone, Debug)]
+struct AllowedDomain {
+    pattern: String,
+
+    /// Whether movies loaded over HTTP are allowed to access movies loaded over HTTPS.
+    insecure: bool,
+}
+
+/// Tracks the permissions that domains have granted each other, and enforces them.
+pub struct SecurityManager {
+    mode: SecurityMode,
+
+    /// The domains that movies from each domain have allowed with `Security.allowDomain`.
+    allowed_domains: HashMap<SecurityDomain, Vec<AllowedDomain>>,
+
+    /// The policy files registered with `Security.loadPolicyFile`.
+    policy_file_urls: Vec<String>,
+
+    /// The policy files that have been requested, by URL.
+    /// `None` if a policy file couldn't be loaded or parsed.
+    policy_files: HashMap<String, Option<LoadedPolicyFile>>,
+}
+
+impl SecurityManager {
+    pub fn new(mode: SecurityMode) -> Self {
+        Self {
+            mode,
+            allowed_domains: HashMap::new(),
+            policy_file_urls: Vec::new(),
+            policy_files: HashMap::new(),
+        }
+    }
+
+    pub fn mode(&self) -> SecurityMode {
+        self.mode
+    }
+
+    /// Lets movies from `domains` access movies from the domain of `grantor_url`.
+    ///
+    /// Unless `insecure` is set, movies loaded over HTTP still can't access HTTPS movies.
+    pub fn allow_domain(&mut self, grantor_url: &str, domains: &[String], insecure: bool) {
+        let grants = self
+            .allowed_domains
+            .entry(SecurityDomain::from_url(grantor_url))
+            .or_default();
+        grants.extend(domains.iter().map(|domain| AllowedDomain {
+            pattern: domain_pattern(domain),
+            insecure,
+        }));
+    }
+
+    /// Whether movies from `grantee_url` have been allowed to access movies from `grantor_url`.
+    pub fn is_domain_allowed(&self, grantor_url: &str, grantee_url: &str) -> bool {
+        let grantor = SecurityDomain::from_url(grantor_url);
+        let grantee = SecurityDomain::from_url(grantee_url);
+        self.mode == SecurityMode::Permissive
+            || grantor == grantee
+            || self.allowed_domains.get(&grantor).is_some_and(|grants| {
+                grants.iter().any(|grant| {
+                    grantee.matches(&grant.pattern)
+                        && (grant.insecure || !grantor.is_secure() || grantee.is_secure())
+                })
+            })
+    }
+
+    /// Whether a movie from `accessor_url` can script, or read the pixels of, content
+    /// loaded from `target_url`.
+    ///
+    /// This is allowed by `Security.allowDomain`, or by a policy file that was already loaded.
+    pub fn can_access(&self, accessor_url: &str, target_url: &str) -> bool {
+        if self.is_domain_allowed(target_url, accessor_url) {
+            return true;
+        }
+        let Ok(target) = Url::parse(target_url) else {
+            return false;
+        };
+        self.policy_file_access(accessor_url, &target) == DataAccess::Allowed
+    }
+
+    /// Registers a policy file with `Security.loadPolicyFile`.
+    ///
+    /// It's only loaded once it's needed to decide whether a movie can load data,
+    /// or connect a socket in the case of `xmlsocket://` URLs.
+    pub fn load_policy_file(&mut self, url: String) {
+        if !self.policy_file_urls.contains(&url) {
+            self.policy_file_urls.push(url);
+        }
+    }
+
+    /// Stores a policy file that was requested through `DataAccess::NeedsPolicyFile`.
+    ///
+    /// `data` is `None` if the policy file couldn't be loaded.
+    pub fn add_policy_file(
+        &mut self,
+        url: String,
+        data: Option<&[u8]>,
+        content_type: Option<&str>,
+    ) {
+        let policy = data
+            .and_then(PolicyFile::parse)
+            .map(|policy| LoadedPolicyFile {
+                policy,
+                has_policy_content_type: content_type.is_some_and(|content_type| {
+                    content_type.split(';').next().is_some_and(|mime| {
+                        mime.trim().eq_ignore_ascii_case(POLICY_FILE_CONTENT_TYPE)
+                    })
+                }),
+            });
+        if policy.is_none() {
+            tracing::warn!("Failed to load policy file from {}", url);
+        }
+        self.policy_files.insert(url, policy);
+    }
+
+    /// Whether a movie from `requester_url`, running in `sandbox_type`, can load data from `target_url`.
+    pub fn data_access(
+        &self,
+        sandbox_type: &SandboxType,
+        requester_url: &str,
+        target_url: &str,
+    ) -> DataAccess {
+        if self.mode == SecurityMode::Permissive {
+            return DataAccess::Allowed;
+        }
+        // Invalid URLs will fail to load anyway.
+        let Ok(target) = Url::parse(target_url) else {
+            return DataAccess::Allowed;
+        };
+
+        let requester = SecurityDomain::from_url(requester_url);
+        let target_domain = SecurityDomain::from_url(target.as_str());
+        match (sandbox_type, &target_domain) {
+            (SandboxType::LocalTrusted, _) => DataAccess::Allowed,
+            (SandboxType::LocalWithFile, SecurityDomain::Local) => DataAccess::Allowed,
+            (SandboxType::LocalWithFile, SecurityDomain::Remote { .. }) => DataAccess::Denied(
+                SecurityViolation::cannot_load_data(requester_url, target.as_str()),
+            ),
+            (_, SecurityDomain::Local) => DataAccess::Denied(
+                SecurityViolation::cannot_access_local_resource(requester_url, target.as_str()),
+            ),
+            _ if requester == target_domain => DataAccess::Allowed,
+            _ => self.policy_file_access(requester_url, &target),
+        }
+    }
+
+    /// Whether a movie from `requester_url`, running in `sandbox_type`, can connect a socket
+    /// to `port` on `host`.
+    ///
+    /// Socket policy files are identified by `xmlsocket://` URLs. The master policy file on
+    /// port 843 is checked first, then any registered with `Security.loadPolicyFile`,
+    /// and finally the one served by the port that the socket connects to.
+    pub fn socket_access(
+        &self,
+        sandbox_type: &SandboxType,
+        requester_url: &str,
+        host: &str,
+        port: u16,
+    ) -> DataAccess {
+        let denied = || {
+            DataAccess::Denied(SecurityViolation::cannot_load_data(
+                requester_url,
+                &format!("{host}:{port}"),
+            ))
+        };
+        match (self.mode, sandbox_type) {
+            (SecurityMode::Permissive, _) | (_, SandboxType::LocalTrusted) => {
+                return DataAccess::Allowed
+            }
+            (_, SandboxType::LocalWithFile) => return denied(),
+            _ => {}
+        }
+
+        let requester = SecurityDomain::from_url(requester_url);
+        let master_url = format!("xmlsocket://{host}:{MASTER_SOCKET_POLICY_PORT}");
+        let target_url = format!("xmlsocket://{host}:{port}");
+        let registered_urls = self.policy_file_urls.iter().filter(|url| {
+            Url::parse(url).is_ok_and(|url| {
+                url.scheme() == "xmlsocket" && url.host_str().is_some_and(|h| h == host)
+            })
+        });
+
+        let mut candidates = vec![master_url.clone()];
+        candidates.extend(registered_urls.cloned());
+        candidates.push(target_url);
+        candidates.dedup();
+
+        for url in candidates {
+            let policy = match self.policy_files.get(&url) {
+                Some(policy) => policy.as_ref(),
+                None => return DataAccess::NeedsPolicyFile(url),
+            };
+            if let Some(loaded) = policy {
+                let meta_policy = loaded.policy.meta_policy;
+                if meta_policy == Some(MetaPolicy::None) {
+                    return denied();
+                }
+                if loaded.policy.allows_socket(&requester, port) {
+                    return DataAccess::Allowed;
+                }
+                // Unlike HTTP servers, socket servers honor every policy file by default.
+                if url == master_url && meta_policy == Some(MetaPolicy::MasterOnly) {
+                    return denied();
+                }
+            }
+        }
+        denied()
+    }
+
+    /// Checks the policy files of the server of `target`, starting with its master policy file.
+    fn policy_file_access(&self, requester_url: &str, target: &Url) -> DataAccess {
+        let requester = &SecurityDomain::from_url(requester_url);
+        let target_domain = SecurityDomain::from_url(target.as_str());
+        let denied = || {
+            DataAccess::Denied(SecurityViolation::cannot_load_data(
+                requester_url,
+                target.as_str(),
+            ))
+        };
+        let Some(master_url) = target_domain.master_policy_url() else {
+            return denied();
+        };
+
+        let master = match self.policy_files.get(&master_url) {
+            Some(master) => master.as_ref(),
+            None => return DataAccess::NeedsPolicyFile(master_url),
+        };
+        let meta_policy = master
+            .map(|master| master.policy.meta_policy())
+            .unwrap_or_default();
+        if meta_policy == MetaPolicy::None {
+            return denied();
+        }
+        if master.is_some_and(|master| master.policy.allows(requester, &target_domain)) {
+            return DataAccess::Allowed;
+        }
+        if meta_policy == MetaPolicy::MasterOnly {
+            return denied();
+        }
+
+        for url in &self.policy_file_urls {
+            let Ok(policy_url) = Url::parse(url) else {
+                continue;
+            };
+            if *url == master_url || !policy_covers(&policy_url, target) {
+                continue;
+            }
+            match self.policy_files.get(url) {
+                None => return DataAccess::NeedsPolicyFile(url.clone()),
+                Some(Some(loaded))
+                    if (meta_policy == MetaPolicy::All || loaded.has_policy_content_type)
+                        && loaded.policy.allows(requester, &target_domain) =>
+                {
+                    return DataAccess::Allowed;
+                }
+                Some(_) => {}
+            }
+        }
+        denied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REQUESTER: &str = "http://movies.example.org/game.swf";
+    const TARGET: &str = "http://data.example.com/scores.txt";
+    const MASTER_POLICY: &str = "http://data.example.com/crossdomain.xml";
+
+    fn strict() -> SecurityManager {
+        SecurityManager::new(SecurityMode::Strict)
+    }
+
+    #[test]
+    fn parses_policy_files() {
+        let policy = PolicyFile::parse(
+            br#"<?xml version="1.0"?>
+            <!DOCTYPE cross-domain-policy SYSTEM "http://www.adobe.com/xml/dtds/cross-domain-policy.dtd">
+            <cross-domain-policy>
+                <site-control permitted-cross-domain-policies="all"/>
+                <allow-access-from domain="*.Example.org" />
+                <allow-access-from domain="insecure.example.net" secure="false" />
+            </cross-domain-policy>"#,
+        )
+        .unwrap();
+
+        assert_eq!(policy.meta_policy(), MetaPolicy::All);
+        let remote = SecurityDomain::from_url("https://example.com/");
+        let secure = SecurityDomain::from_url("https://sub.example.org/");
+        let insecure = SecurityDomain::from_url("http://example.org/");
+        assert!(policy.allows(&secure, &remote));
+        assert!(!policy.allows(&insecure, &remote));
+        assert!(policy.allows(
+            &SecurityDomain::from_url("http://insecure.example.net/"),
+            &remote
+        ));
+        assert!(!policy.allows(&SecurityDomain::from_url("http://badexample.org/"), &remote));
+        assert!(!policy.allows(&SecurityDomain::Local, &remote));
+
+        assert_eq!(PolicyFile::parse(b"<html></html>"), None);
+        assert_eq!(PolicyFile::parse(b"<cross-domain-policy>"), None);
+    }
+
+    #[test]
+    fn permissive_mode_allows_everything() {
+        let security = SecurityManager::new(SecurityMode::Permissive);
+        assert_eq!(
+            security.data_access(&SandboxType::Remote, REQUESTER, TARGET),
+            DataAccess::Allowed
+        );
+        assert!(security.can_access(REQUESTER, TARGET));
+    }
+
+    #[test]
+    fn remote_data_needs_a_policy_file() {
+        let mut security = strict();
+        assert_eq!(
+            security.data_access(
+                &SandboxType::Remote,
+                REQUESTER,
+                "http://movies.example.org/a"
+            ),
+            DataAccess::Allowed
+        );
+        assert_eq!(
+            security.data_access(&SandboxType::Remote, REQUESTER, TARGET),
+            DataAccess::NeedsPolicyFile(MASTER_POLICY.to_string())
+        );
+
+        security.add_policy_file(MASTER_POLICY.to_string(), None, None);
+        assert!(matches!(
+            security.data_access(&SandboxType::Remote, REQUESTER, TARGET),
+            DataAccess::Denied(SecurityViolation { code: 2048, .. })
+        ));
+
+        security.add_policy_file(
+            MASTER_POLICY.to_string(),
+            Some(br#"<cross-domain-policy><allow-access-from domain="*.example.org"/></cross-domain-policy>"#),
+            None,
+        );
+        assert_eq!(
+            security.data_access(&SandboxType::Remote, REQUESTER, TARGET),
+            DataAccess::Allowed
+        );
+    }
+
+    #[test]
+    fn meta_policy_governs_other_policy_files() {
+        let mut security = strict();
+        let policy_url = "http://data.example.com/api/policy.xml";
+        security.load_policy_file(policy_url.to_string());
+        security.add_policy_file(
+            MASTER_POLICY.to_string(),
+            Some(br#"<cross-domain-policy><site-control permitted-cross-domain-policies="by-content-type"/></cross-domain-policy>"#),
+            None,
+        );
+
+        // The policy file doesn't cover the root of the server.
+        assert!(matches!(
+            security.data_access(&SandboxType::Remote, REQUESTER, TARGET),
+            DataAccess::Denied(_)
+        ));
+
+        let target = "http://data.example.com/api/scores.txt";
+        assert_eq!(
+            security.data_access(&SandboxType::Remote, REQUESTER, target),
+            DataAccess::NeedsPolicyFile(policy_url.to_string())
+        );
+        let policy =
+            br#"<cross-domain-policy><allow-access-from domain="*"/></cross-domain-policy>"#;
+        security.add_policy_file(policy_url.to_string(), Some(policy), Some("text/xml"));
+        assert!(matches!(
+            security.data_access(&SandboxType::Remote, REQUESTER, target),
+            DataAccess::Denied(_)
+        ));
+        security.add_policy_file(
+            policy_url.to_string(),
+            Some(policy),
+            Some("text/x-cross-domain-policy; charset=utf-8"),
+        );
+        assert_eq!(
+            security.data_access(&SandboxType::Remote, REQUESTER, target),
+            DataAccess::Allowed
+        );
+    }
+
+    #[test]
+    fn local_sandboxes() {
+        let security = strict();
+        let local = "file:///home/user/game.swf";
+        assert_eq!(
+            security.data_access(
+                &SandboxType::LocalWithFile,
+                local,
+                "file:///home/user/a.txt"
+            ),
+            DataAccess::Allowed
+        );
+        assert!(matches!(
+            security.data_access(&SandboxType::LocalWithFile, local, TARGET),
+            DataAccess::Denied(SecurityViolation { code: 2048, .. })
+        ));
+        assert!(matches!(
+            security.data_access(&SandboxType::Remote, REQUESTER, "file:///etc/passwd"),
+            DataAccess::Denied(SecurityViolation { code: 2148, .. })
+        ));
+        assert_eq!(
+            security.data_access(&SandboxType::LocalTrusted, local, TARGET),
+            DataAccess::Allowed
+        );
+    }
+
+    #[test]
+    fn socket_policy_files() {
+        let mut security = strict();
+        let master = "xmlsocket://game.example.com:843";
+        let target = "xmlsocket://game.example.com:5000";
+        assert_eq!(
+            security.socket_access(&SandboxType::Remote, REQUESTER, "game.example.com", 5000),
+            DataAccess::NeedsPolicyFile(master.to_string())
+        );
+
+        security.add_policy_file(master.to_string(), None, None);
+        assert_eq!(
+            security.socket_access(&SandboxType::Remote, REQUESTER, "game.example.com", 5000),
+            DataAccess::NeedsPolicyFile(target.to_string())
+        );
+
+        security.add_policy_file(
+            target.to_string(),
+            Some(br#"<cross-domain-policy><allow-access-from domain="*.example.org" to-ports="80,4000-5000"/></cross-domain-policy>"#),
+            None,
+        );
+        assert_eq!(
+            security.socket_access(&SandboxType::Remote, REQUESTER, "game.example.com", 5000),
+            DataAccess::Allowed
+        );
+        assert!(matches!(
+            security.socket_access(&SandboxType::Remote, REQUESTER, "game.example.com", 5001),
+            DataAccess::NeedsPolicyFile(_)
+        ));
+        assert!(matches!(
+            security.socket_access(
+                &SandboxType::LocalWithFile,
+                REQUESTER,
+                "game.example.com",
+                5000
+            ),
+            DataAccess::Denied(SecurityViolation { code: 2048, .. })
+        ));
+    }
+
+    #[test]
+    fn allow_domain_grants_access() {
+        let mut security = strict();
+        let secure_target = "https://data.example.com/content.swf";
+        assert!(!security.can_access(REQUESTER, secure_target));
+
+        security.allow_domain(
+            secure_target,
+            &["http://movies.example.org/other.swf".to_string()],
+            false,
+        );
+        assert!(!security.can_access(REQUESTER, secure_target));
+        assert!(security.can_access("https://movies.example.org/game.swf", secure_target));
+
+        security.allow_domain(secure_target, &["*".to_string()], true);
+        assert!(security.can_access(REQUESTER, secure_target));
+        assert!(!security.can_access(secure_target, REQUESTER));
+    }
+}
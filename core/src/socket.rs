@@ -7,18 +7,20 @@ use crate::{
         object::SocketObject, Activation as Avm2Activation, Avm2, EventObject,
         TObject as Avm2TObject,
     },
-    backend::navigator::NavigatorBackend,
     context::UpdateContext,
+    security::{DataAccess, SecurityViolation},
     string::AvmString,
 };
-use async_channel::{unbounded, Sender as AsyncSender};
+use async_channel::{unbounded, Receiver as AsyncReceiver, Sender as AsyncSender};
 use gc_arena::Collect;
 use generational_arena::{Arena, Index};
+use instant::Instant;
 use std::{
     cell::RefCell,
     sync::mpsc::{channel, Receiver, Sender},
     time::Duration,
 };
+use url::Url;
 
 pub type SocketHandle = Index;
 
@@ -29,22 +31,61 @@ enum SocketKind<'gc> {
     Avm1(Avm1Object<'gc>),
 }
 
+/// What a client sends to ask a server for its socket policy file.
+const POLICY_FILE_REQUEST: &[u8] = b"<policy-file-request/>\0";
+
+/// How long a server has to answer a policy file request.
+const POLICY_FILE_TIMEOUT: Duration = Duration::from_secs(3);
+
 #[derive(Collect)]
 #[collect(no_drop)]
 struct Socket<'gc> {
     target: SocketKind<'gc>,
     sender: RefCell<AsyncSender<Vec<u8>>>,
+
+    /// The connection that is yet to be made, while policy files are being requested.
+    #[collect(require_static)]
+    pending: Option<PendingConnection>,
 }
 
 impl<'gc> Socket<'gc> {
-    fn new(target: SocketKind<'gc>, sender: AsyncSender<Vec<u8>>) -> Self {
+    fn new(
+        target: SocketKind<'gc>,
+        sender: AsyncSender<Vec<u8>>,
+        pending: PendingConnection,
+    ) -> Self {
         Self {
             target,
             sender: RefCell::new(sender),
+            pending: Some(pending),
         }
     }
 }
 
+struct PendingConnection {
+    /// The URL of the movie that is connecting, which policy files have to allow.
+    requester_url: String,
+    host: String,
+    port: u16,
+    timeout: Duration,
+    receiver: AsyncReceiver<Vec<u8>>,
+}
+
+/// A connection to a server that was asked for its socket policy file.
+struct PolicyFileRequest {
+    /// The sockets that are waiting for the policy file.
+    sockets: Vec<SocketHandle>,
+
+    /// The `xmlsocket://` URL of the policy file.
+    url: String,
+
+    /// Dropping this closes the connection.
+    _sender: AsyncSender<Vec<u8>>,
+
+    response: Vec<u8>,
+    started: Instant,
+}
+
 #[derive(Debug)]
 pub enum ConnectionState {
     Connected,
@@ -60,11 +101,22 @@ pub enum SocketAction {
 }
 
 /// Manages the collection of Sockets.
+///
+/// When the security sandbox is enforced, a socket only connects once a socket policy file
+/// allows it. Policy files are requested over connections of their own, whose actions are
+/// sent to a separate channel.
 pub struct Sockets<'gc> {
     sockets: Arena<Socket<'gc>>,
 
     receiver: Receiver<SocketAction>,
     sender: Sender<SocketAction>,
+
+    policy_file_requests: Arena<PolicyFileRequest>,
+    policy_receiver: Receiver<SocketAction>,
+    policy_sender: Sender<SocketAction>,
+
+    /// Sockets that the security sandbox didn't allow to connect.
+    violations: Vec<(SocketHandle, SecurityViolation)>,
 }
 
 unsafe impl<'gc> Collect for Sockets<'gc> {
@@ -78,46 +130,50 @@ unsafe impl<'gc> Collect for Sockets<'gc> {
 impl<'gc> Sockets<'gc> {
     pub fn empty() -> Self {
         let (sender, receiver) = channel();
+        let (policy_sender, policy_receiver) = channel();
 
         Self {
             sockets: Arena::new(),
             receiver,
             sender,
+            policy_file_requests: Arena::new(),
+            policy_receiver,
+            policy_sender,
+            violations: Vec::new(),
         }
     }
 
     pub fn connect_avm2(
-        &mut self,
-        backend: &mut dyn NavigatorBackend,
+        context: &mut UpdateContext<'_, 'gc>,
+        requester_url: String,
         target: SocketObject<'gc>,
         host: String,
         port: u16,
     ) {
         let (sender, receiver) = unbounded();
 
-        let socket = Socket::new(SocketKind::Avm2(target), sender);
-        let handle = self.sockets.insert(socket);
-
-        // NOTE: This call will send SocketAction::Connect to sender with connection status.
-        backend.connect_socket(
+        let pending = PendingConnection {
+            requester_url,
             host,
             port,
-            Duration::from_millis(target.timeout().into()),
-            handle,
+            timeout: Duration::from_millis(target.timeout().into()),
             receiver,
-            self.sender.clone(),
-        );
+        };
+        let socket = Socket::new(SocketKind::Avm2(target), sender, pending);
+        let handle = context.sockets.sockets.insert(socket);
+
+        Self::continue_connecting(context, handle);
 
         if let Some(existing_handle) = target.set_handle(handle) {
             // As written in the AS3 docs, we are supposed to close the existing connection,
             // when a new one is created.
-            self.close(existing_handle)
+            context.sockets.close(existing_handle)
         }
     }
 
     pub fn connect_avm1(
-        &mut self,
-        backend: &mut dyn NavigatorBackend,
+        context: &mut UpdateContext<'_, 'gc>,
+        requester_url: String,
         target: Avm1Object<'gc>,
         host: String,
         port: u16,
@@ -129,23 +185,222 @@ impl<'gc> Sockets<'gc> {
             None => return,
         };
 
-        let socket = Socket::new(SocketKind::Avm1(target), sender);
-        let handle = self.sockets.insert(socket);
-
-        // NOTE: This call will send SocketAction::Connect to sender with connection status.
-        backend.connect_socket(
+        let pending = PendingConnection {
+            requester_url,
             host,
             port,
-            Duration::from_millis(xml_socket.timeout().into()),
-            handle,
+            timeout: Duration::from_millis(xml_socket.timeout().into()),
             receiver,
-            self.sender.clone(),
-        );
+        };
+        let socket = Socket::new(SocketKind::Avm1(target), sender, pending);
+        let handle = context.sockets.sockets.insert(socket);
+
+        Self::continue_connecting(context, handle);
 
         if let Some(existing_handle) = xml_socket.set_handle(handle) {
             // NOTE: AS2 docs don't specify what happens when connect is called with open connection,
             //       but we will close the existing connection anyway.
-            self.close(existing_handle)
+            context.sockets.close(existing_handle)
+        }
+    }
+
+    /// Connects a pending socket if the security sandbox allows it, or otherwise requests
+    /// the next policy file that could allow it.
+    fn continue_connecting(context: &mut UpdateContext<'_, 'gc>, handle: SocketHandle) {
+        loop {
+            let Some(pending) = context
+                .sockets
+                .sockets
+                .get(handle)
+                .and_then(|socket| socket.pending.as_ref())
+            else {
+                return;
+            };
+
+            let access = context.security.socket_access(
+                &context.system.sandbox_type,
+                &pending.requester_url,
+                &pending.host,
+                pending.port,
+            );
+            match access {
+                DataAccess::Allowed => {
+                    let pending = context.sockets.sockets[handle]
+                        .pending
+                        .take()
+                        .expect("socket should be pending");
+
+                    // NOTE: This call will send SocketAction::Connect to sender with connection status.
+                    context.navigator.connect_socket(
+                        pending.host,
+                        pending.port,
+                        pending.timeout,
+                        handle,
+                        pending.receiver,
+                        context.sockets.sender.clone(),
+                    );
+                    return;
+                }
+                DataAccess::NeedsPolicyFile(url) => {
+                    let address = Url::parse(&url)
+                        .ok()
+                        .and_then(|url| Some((url.host_str()?.to_string(), url.port()?)));
+                    let Some((host, port)) = address else {
+                        context.security.add_policy_file(url, None, None);
+                        continue;
+                    };
+
+                    // Sockets that need the same policy file share one request for it.
+                    if let Some((_, request)) = context
+                        .sockets
+                        .policy_file_requests
+                        .iter_mut()
+                        .find(|(_, request)| request.url == url)
+                    {
+                        request.sockets.push(handle);
+                        return;
+                    }
+
+                    let (sender, receiver) = unbounded();
+                    // This is sent once the connection is made.
+                    let _ = sender.send_blocking(POLICY_FILE_REQUEST.to_vec());
+                    let request = context
+                        .sockets
+                        .policy_file_requests
+                        .insert(PolicyFileRequest {
+                            sockets: vec![handle],
+                            url,
+                            _sender: sender,
+                            response: Vec::new(),
+                            started: Instant::now(),
+                        });
+                    context.navigator.connect_socket(
+                        host,
+                        port,
+                        POLICY_FILE_TIMEOUT,
+                        request,
+                        receiver,
+                        context.sockets.policy_sender.clone(),
+                    );
+                    return;
+                }
+                DataAccess::Denied(violation) => {
+                    context.sockets.sockets[handle].pending = None;
+                    context.sockets.violations.push((handle, violation));
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Stores the response to a policy file request, and lets its sockets continue connecting.
+    fn finish_policy_file_request(context: &mut UpdateContext<'_, 'gc>, request: SocketHandle) {
+        // Dropping the request closes its connection.
+        let Some(request) = context.sockets.policy_file_requests.remove(request) else {
+            return;
+        };
+
+        // The policy file ends with a null byte.
+        let policy = request
+            .response
+            .split(|&b| b == 0)
+            .next()
+            .filter(|policy| !policy.is_empty());
+        context.security.add_policy_file(request.url, policy, None);
+
+        for socket in request.sockets {
+            Self::continue_connecting(context, socket);
+        }
+    }
+
+    /// Handles the responses of servers that were asked for their policy file.
+    fn update_policy_file_requests(context: &mut UpdateContext<'_, 'gc>) {
+        let mut actions = vec![];
+
+        while let Ok(action) = context.sockets.policy_receiver.try_recv() {
+            actions.push(action)
+        }
+
+        for action in actions {
+            match action {
+                SocketAction::Connect(_, ConnectionState::Connected) => {}
+                SocketAction::Connect(request, _) | SocketAction::Close(request) => {
+                    Self::finish_policy_file_request(context, request)
+                }
+                SocketAction::Data(request, data) => {
+                    let Some(policy_file_request) =
+                        context.sockets.policy_file_requests.get_mut(request)
+                    else {
+                        continue;
+                    };
+                    policy_file_request.response.extend(data);
+                    if policy_file_request.response.contains(&0) {
+                        Self::finish_policy_file_request(context, request);
+                    }
+                }
+            }
+        }
+
+        // Servers that don't answer are treated as having no policy file.
+        let timed_out = context
+            .sockets
+            .policy_file_requests
+            .iter()
+            .filter(|(_, request)| request.started.elapsed() >= POLICY_FILE_TIMEOUT)
+            .map(|(request, _)| request)
+            .collect::<Vec<_>>();
+        for request in timed_out {
+            Self::finish_policy_file_request(context, request);
+        }
+
+        for (handle, violation) in std::mem::take(&mut context.sockets.violations) {
+            let target = match context.sockets.sockets.remove(handle) {
+                Some(socket) => socket.target,
+                // Socket must have been closed before we could send event.
+                None => continue,
+            };
+
+            match target {
+                SocketKind::Avm2(target) => {
+                    let mut activation = Avm2Activation::from_nothing(context.reborrow());
+
+                    let message = AvmString::new_utf8(activation.gc(), violation.message);
+                    let security_error_evt = activation
+                        .avm2()
+                        .classes()
+                        .securityerrorevent
+                        .construct(
+                            &mut activation,
+                            &[
+                                "securityError".into(),
+                                false.into(),
+                                false.into(),
+                                message.into(),
+                                violation.code.into(),
+                            ],
+                        )
+                        .expect("SecurityErrorEvent should be constructed");
+
+                    Avm2::dispatch_event(
+                        &mut activation.context,
+                        security_error_evt,
+                        target.into(),
+                    );
+                }
+                SocketKind::Avm1(target) => {
+                    let mut activation = Avm1Activation::from_stub(
+                        context.reborrow(),
+                        ActivationIdentifier::root("[XMLSocket]"),
+                    );
+
+                    let _ = target.call_method(
+                        "onConnect".into(),
+                        &[false.into()],
+                        &mut activation,
+                        ExecutionReason::Special,
+                    );
+                }
+            }
         }
     }
 
@@ -160,7 +415,7 @@ impl<'gc> Sockets<'gc> {
     }
 
     pub fn close(&mut self, handle: SocketHandle) {
-        if let Some(Socket { sender, target }) = self.sockets.remove(handle) {
+        if let Some(Socket { sender, target, .. }) = self.sockets.remove(handle) {
             drop(sender); // NOTE: By dropping the sender, the reading task will close automatically.
 
             // Clear the buffers if the connection was closed.
@@ -180,6 +435,8 @@ impl<'gc> Sockets<'gc> {
     }
 
     pub fn update_sockets(context: &mut UpdateContext<'_, 'gc>) {
+        Self::update_policy_file_requests(context);
+
         let mut actions = vec![];
 
         while let Ok(action) = context.sockets.receiver.try_recv() {
@@ -1,11 +1,14 @@
 //! NetStream implementation
 
+mod mp4;
+
+use self::mp4::{Mp4Codec, Mp4Error, Mp4Movie, Mp4Track};
 use crate::avm1::{
     Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier,
     ExecutionReason as Avm1ExecutionReason, FlvValueAvm1Ext, ScriptObject as Avm1ScriptObject,
     TObject as Avm1TObject, Value as Avm1Value,
 };
-use crate::avm2::object::TObject as Avm2TObject;
+use crate::avm2::object::{TObject as Avm2TObject, VideoTextureObject};
 use crate::avm2::{
     Activation as Avm2Activation, Avm2, Error as Avm2Error, EventObject as Avm2EventObject,
     FlvValueAvm2Ext, Object as Avm2Object,
@@ -22,9 +25,10 @@ use crate::string::AvmString;
 use crate::vminterface::AvmObject;
 use flv_rs::{
     AudioData as FlvAudioData, AudioDataType as FlvAudioDataType, Error as FlvError, FlvReader,
-    Header as FlvHeader, ScriptData as FlvScriptData, SoundFormat as FlvSoundFormat,
-    SoundRate as FlvSoundRate, SoundSize as FlvSoundSize, SoundType as FlvSoundType, Tag as FlvTag,
-    TagData as FlvTagData, Value as FlvValue, VideoData as FlvVideoData,
+    FrameType as FlvFrameType, Header as FlvHeader, ScriptData as FlvScriptData,
+    SoundFormat as FlvSoundFormat, SoundRate as FlvSoundRate, SoundSize as FlvSoundSize,
+    SoundType as FlvSoundType, Tag as FlvTag, TagData as FlvTagData, TypeFlags as FlvTypeFlags,
+    Value as FlvValue, Variable as FlvVariable, VideoData as FlvVideoData,
     VideoPacket as FlvVideoPacket,
 };
 use gc_arena::{Collect, GcCell, Mutation};
@@ -129,7 +133,8 @@ impl<'gc> StreamManager<'gc> {
     pub fn tick(context: &mut UpdateContext<'_, 'gc>, dt: f64) {
         let streams = context.stream_manager.playing_streams.clone();
         for stream in streams {
-            stream.tick(context, dt)
+            stream.tick(context, dt);
+            stream.update_video_textures(context);
         }
     }
 }
@@ -177,6 +182,30 @@ pub enum NetStreamType {
         /// frame IDs ourselves for various API related purposes.
         frame_id: u32,
     },
+
+    /// The stream is an MP4 or F4V.
+    Mp4 {
+        movie: Mp4Movie,
+
+        /// The video track's stream instance, if it has a supported codec.
+        video_stream: Option<VideoStreamHandle>,
+
+        /// The index of the next video sample to decode.
+        next_video_sample: usize,
+
+        /// The index of the next audio sample to stream.
+        next_audio_sample: usize,
+    },
+}
+
+/// A position in the stream that playback can be restarted from.
+#[derive(Clone, Copy, Debug)]
+struct SeekPoint {
+    /// The time of the seek point, in milliseconds.
+    time: f64,
+
+    /// The buffer position of the tag at the seek point.
+    offset: usize,
 }
 
 #[derive(Clone, Debug, Collect)]
@@ -207,6 +236,46 @@ pub struct NetStreamData<'gc> {
     /// The current seek offset in the stream.
     stream_time: f64,
 
+    /// How much media, in seconds, must be buffered before playback starts.
+    ///
+    /// This corresponds to `NetStream.bufferTime`.
+    buffer_time: f64,
+
+    /// Whether playback is waiting for enough media to be buffered.
+    ///
+    /// `NetStream.Buffer.Full` is fired when this is cleared, and
+    /// `NetStream.Buffer.Empty` when playback runs out of data and it is set
+    /// again.
+    is_buffering: bool,
+
+    /// Whether all of the stream's data has been loaded into the buffer.
+    is_loaded: bool,
+
+    /// Whether playback was paused by the user.
+    ///
+    /// Streams that have reached the end of their data stop playing without
+    /// being paused, so that seeking back into them resumes playback.
+    is_paused: bool,
+
+    /// The buffer position up to which tags have been scanned for seek
+    /// points.
+    scan_offset: usize,
+
+    /// The timestamp of the last tag in the buffer, in milliseconds.
+    buffered_time: f64,
+
+    /// Seek points found by scanning the buffer, in order of time.
+    #[collect(require_static)]
+    seek_points: Vec<SeekPoint>,
+
+    /// Seek points listed by the `keyframes` object of the stream metadata,
+    /// in order of time.
+    ///
+    /// These are not guaranteed to be accurate and are checked against the
+    /// buffer before use.
+    #[collect(require_static)]
+    metadata_seek_points: Vec<SeekPoint>,
+
     /// The last decoded bitmap.
     ///
     /// Any `Video`s on the stage will display the bitmap here when attached to
@@ -214,12 +283,23 @@ pub struct NetStreamData<'gc> {
     #[collect(require_static)]
     last_decoded_bitmap: Option<BitmapInfo>,
 
+    /// Whether `last_decoded_bitmap` has changed since it was last sent to
+    /// `video_textures`.
+    has_new_frame: bool,
+
+    /// The Stage3D `VideoTexture`s that display this stream.
+    video_textures: Vec<VideoTextureObject<'gc>>,
+
     /// The AVM side of this stream.
     avm_object: Option<AvmObject<'gc>>,
 
     /// The AVM2 client object, which corresponds to `NetStream.client`.
     avm2_client: Option<Avm2Object<'gc>>,
 
+    /// The most recent metadata object passed to the AVM2 client's
+    /// `onMetaData`, which `NetStream.info` reports.
+    avm2_metadata: Option<Avm2Object<'gc>>,
+
     /// The URL of the requested FLV if one exists.
     url: Option<String>,
 
@@ -228,6 +308,13 @@ pub struct NetStreamData<'gc> {
     #[collect(require_static)]
     audio_stream: Option<(Substream, SoundStreamInfo)>,
 
+    /// The most recent AAC sequence header seen in the stream.
+    ///
+    /// This must be the first chunk of every AAC audio substream, as it
+    /// configures the decoder for the raw AAC frames that follow.
+    #[collect(require_static)]
+    aac_sequence_header: Option<Slice>,
+
     /// The currently playing sound stream
     #[collect(require_static)]
     sound_instance: Option<SoundInstanceHandle>,
@@ -248,11 +335,23 @@ impl<'gc> NetStream<'gc> {
                 preload_offset: 0,
                 stream_type: None,
                 stream_time: 0.0,
+                buffer_time: 0.1,
+                is_buffering: true,
+                is_loaded: false,
+                is_paused: false,
+                scan_offset: 0,
+                buffered_time: 0.0,
+                seek_points: Vec::new(),
+                metadata_seek_points: Vec::new(),
                 last_decoded_bitmap: None,
+                has_new_frame: false,
+                video_textures: Vec::new(),
                 avm_object,
                 avm2_client: None,
+                avm2_metadata: None,
                 url: None,
                 audio_stream: None,
+                aac_sequence_header: None,
                 sound_instance: None,
                 attached_to: None,
             },
@@ -267,6 +366,10 @@ impl<'gc> NetStream<'gc> {
         self.0.read().avm2_client
     }
 
+    pub fn avm2_metadata(self) -> Option<Avm2Object<'gc>> {
+        self.0.read().avm2_metadata
+    }
+
     pub fn set_avm_object(self, gc_context: &Mutation<'gc>, avm_object: AvmObject<'gc>) {
         self.0.write(gc_context).avm_object = Some(avm_object);
     }
@@ -296,8 +399,16 @@ impl<'gc> NetStream<'gc> {
         write.preload_offset = 0;
         write.stream_type = None;
         write.stream_time = 0.0;
+        write.is_buffering = true;
+        write.is_loaded = false;
+        write.scan_offset = 0;
+        write.buffered_time = 0.0;
+        write.seek_points.clear();
+        write.metadata_seek_points.clear();
         write.audio_stream = None;
+        write.aac_sequence_header = None;
         write.sound_instance = None;
+        write.avm2_metadata = None;
     }
 
     /// Append data to the `NetStream`'s current internal buffer.
@@ -310,17 +421,110 @@ impl<'gc> NetStream<'gc> {
     /// that all data is appended in the correct order and that data from
     /// separate streams is not mixed together.
     pub fn load_buffer(self, context: &mut UpdateContext<'_, 'gc>, data: &mut Vec<u8>) {
-        self.0.write(context.gc_context).buffer.append(data);
+        let mut write = self.0.write(context.gc_context);
+        write.buffer.append(data);
+        Self::scan_seek_points(&mut write);
+    }
+
+    /// Indicate that all of the stream's data has been loaded.
+    ///
+    /// Until this is called, running out of data is treated as a buffer
+    /// underrun rather than the end of the stream.
+    pub fn finish_buffer(self, context: &mut UpdateContext<'_, 'gc>) {
+        self.0.write(context.gc_context).is_loaded = true;
+    }
 
-        // NOTE: The onMetaData event triggers before this event in Flash due to its streaming behavior.
+    pub fn report_error(self, context: &mut UpdateContext<'_, 'gc>, error: Error) {
+        tracing::error!("Could not load NetStream: {}", error);
+        StreamManager::ensure_paused(context, self);
         self.trigger_status_event(
             context,
-            &[("code", "NetStream.Buffer.Full"), ("level", "status")],
+            &[
+                ("code", "NetStream.Play.StreamNotFound"),
+                ("level", "error"),
+            ],
         );
     }
 
-    pub fn report_error(self, _error: Error) {
-        //TODO: Report an `asyncError` to AVM1 or 2.
+    /// Scan newly loaded tags for seek points.
+    ///
+    /// Keyframes are seek points in streams with video; otherwise, every
+    /// audio tag is. The first tag of the stream is always a seek point, so
+    /// that seeking to the start works even if it is neither.
+    fn scan_seek_points(write: &mut NetStreamData<'gc>) {
+        let slice = write.buffer.to_full_slice();
+        let buffer = slice.data();
+        let Ok(header) = FlvHeader::parse(&mut FlvReader::from_source(&*buffer)) else {
+            return;
+        };
+        let has_video = header.type_flags.contains(FlvTypeFlags::HAS_VIDEO);
+
+        let mut reader = FlvReader::from_parts(&*buffer, write.scan_offset);
+        if write.scan_offset == 0 {
+            // Skip the header to get to the first tag.
+            let _ = FlvHeader::parse(&mut reader);
+        }
+
+        loop {
+            let offset = reader.stream_position().expect("valid position") as usize;
+            let Ok(tag) = FlvTag::parse(&mut reader) else {
+                break;
+            };
+            let time = f64::from(tag.timestamp);
+            let is_seek_point = write.seek_points.is_empty()
+                || match tag.data {
+                    FlvTagData::Video(FlvVideoData { frame_type, .. }) => {
+                        frame_type == FlvFrameType::Keyframe
+                    }
+                    FlvTagData::Audio(_) => !has_video,
+                    _ => false,
+                };
+            if is_seek_point
+                && write
+                    .seek_points
+                    .last()
+                    .map_or(true, |last| last.time < time)
+            {
+                write.seek_points.push(SeekPoint { time, offset });
+            }
+
+            write.buffered_time = write.buffered_time.max(time);
+            write.scan_offset = reader.stream_position().expect("valid position") as usize;
+        }
+    }
+
+    /// Find the seek point to restart playback from in order to seek to the
+    /// given time, in milliseconds.
+    ///
+    /// This is the last seek point at or before the given time.
+    fn find_seek_point(write: &NetStreamData<'gc>, time: f64) -> Option<SeekPoint> {
+        let slice = write.buffer.to_full_slice();
+        let buffer = slice.data();
+
+        // Metadata seek points are only used if they actually point to a tag
+        // with the expected timestamp.
+        let metadata_seek_point = write
+            .metadata_seek_points
+            .iter()
+            .rev()
+            .find(|point| point.time <= time)
+            .filter(|point| {
+                let mut reader = FlvReader::from_parts(&*buffer, point.offset);
+                matches!(
+                    FlvTag::parse(&mut reader),
+                    Ok(tag) if (f64::from(tag.timestamp) - point.time).abs() < 1.0
+                )
+            });
+
+        metadata_seek_point.copied().or_else(|| {
+            write
+                .seek_points
+                .iter()
+                .rev()
+                .find(|point| point.time <= time)
+                .or_else(|| write.seek_points.first())
+                .copied()
+        })
     }
 
     pub fn bytes_loaded(self) -> usize {
@@ -365,17 +569,117 @@ impl<'gc> NetStream<'gc> {
 
     /// Pause stream playback.
     pub fn pause(self, context: &mut UpdateContext<'_, 'gc>) {
+        self.0.write(context.gc_context).is_paused = true;
         StreamManager::ensure_paused(context, self);
     }
 
     /// Resume stream playback.
     pub fn resume(self, context: &mut UpdateContext<'_, 'gc>) {
+        self.0.write(context.gc_context).is_paused = false;
         StreamManager::ensure_playing(context, self);
     }
 
     /// Resume stream playback if paused, pause otherwise.
     pub fn toggle_paused(self, context: &mut UpdateContext<'_, 'gc>) {
-        StreamManager::toggle_paused(context, self);
+        if self.0.read().is_paused {
+            self.resume(context);
+        } else {
+            self.pause(context);
+        }
+    }
+
+    /// Seek to the given time in the stream, in seconds.
+    ///
+    /// Playback restarts from the closest seek point before the requested
+    /// time. Seeking is only possible within the data that has already been
+    /// loaded.
+    pub fn seek(self, context: &mut UpdateContext<'_, 'gc>, offset: f64) {
+        let mut write = self.0.write(context.gc_context);
+        let time = (offset * 1000.0).max(0.0);
+
+        let seek_point = match &write.stream_type {
+            Some(NetStreamType::Flv { .. }) if time <= write.buffered_time || write.is_loaded => {
+                Self::find_seek_point(&write, time)
+            }
+            Some(NetStreamType::Mp4 { movie, .. }) => Self::mp4_seek_point(movie, time),
+            _ => None,
+        };
+        let Some(seek_point) = seek_point else {
+            drop(write);
+            self.trigger_status_event(
+                context,
+                &[("code", "NetStream.Seek.InvalidTime"), ("level", "error")],
+            );
+            return;
+        };
+
+        if let Some(instance) = write.sound_instance {
+            // See `reset_buffer` for why the sound is stopped twice.
+            context.audio.stop_sound(instance);
+            context.audio_manager.stop_sound(context.audio, instance);
+        }
+        write.audio_stream = None;
+        write.sound_instance = None;
+
+        write.offset = seek_point.offset;
+        write.stream_time = seek_point.time;
+        write.is_buffering = true;
+        match &mut write.stream_type {
+            Some(NetStreamType::Flv { frame_id, .. }) => {
+                // Skip a frame ID so that decoders notice the discontinuity.
+                *frame_id += 1;
+            }
+            Some(NetStreamType::Mp4 {
+                movie,
+                next_video_sample,
+                next_audio_sample,
+                ..
+            }) => {
+                let sample_index = |track: &Option<Mp4Track>| {
+                    track.as_ref().map_or(0, |track| {
+                        track
+                            .samples
+                            .partition_point(|sample| sample.time < seek_point.time)
+                    })
+                };
+                *next_video_sample = sample_index(&movie.video);
+                *next_audio_sample = sample_index(&movie.audio);
+            }
+            None => {}
+        }
+        let is_paused = write.is_paused;
+        drop(write);
+
+        self.trigger_status_event(
+            context,
+            &[("code", "NetStream.Seek.Notify"), ("level", "status")],
+        );
+
+        if !is_paused {
+            StreamManager::ensure_playing(context, self);
+        }
+    }
+
+    /// The current playback position, in seconds.
+    pub fn time(self) -> f64 {
+        self.0.read().stream_time / 1000.0
+    }
+
+    /// The amount of media, in seconds, that is buffered ahead of the
+    /// current playback position.
+    pub fn buffer_length(self) -> f64 {
+        let read = self.0.read();
+        (read.buffered_time - read.stream_time).max(0.0) / 1000.0
+    }
+
+    /// The amount of media, in seconds, that must be buffered before
+    /// playback starts.
+    pub fn buffer_time(self) -> f64 {
+        self.0.read().buffer_time
+    }
+
+    pub fn set_buffer_time(self, gc_context: &Mutation<'gc>, buffer_time: f64) {
+        self.0.write(gc_context).buffer_time = buffer_time.max(0.0);
     }
 
     /// Indicates that this `NetStream`'s audio was detached from a `MovieClip` (AVM1)
@@ -410,6 +714,53 @@ impl<'gc> NetStream<'gc> {
         write.attached_to = Some(clip);
     }
 
+    /// Indicates that this `NetStream` was attached to a Stage3D `VideoTexture`.
+    pub fn attach_video_texture(
+        self,
+        gc_context: &Mutation<'gc>,
+        texture: VideoTextureObject<'gc>,
+    ) {
+        let mut write = self.0.write(gc_context);
+        if !write
+            .video_textures
+            .iter()
+            .any(|t| Avm2Object::ptr_eq(*t, texture))
+        {
+            write.video_textures.push(texture);
+            // Make sure the new texture receives the current frame.
+            write.has_new_frame = write.last_decoded_bitmap.is_some();
+        }
+    }
+
+    /// Indicates that this `NetStream` was detached from a Stage3D `VideoTexture`.
+    pub fn detach_video_texture(
+        self,
+        gc_context: &Mutation<'gc>,
+        texture: VideoTextureObject<'gc>,
+    ) {
+        self.0
+            .write(gc_context)
+            .video_textures
+            .retain(|t| !Avm2Object::ptr_eq(*t, texture));
+    }
+
+    /// Send the most recently decoded frame to any attached `VideoTexture`s.
+    fn update_video_textures(self, context: &mut UpdateContext<'_, 'gc>) {
+        let mut write = self.0.write(context.gc_context);
+        if !std::mem::take(&mut write.has_new_frame) {
+            return;
+        }
+        let Some(bitmap) = write.last_decoded_bitmap.clone() else {
+            return;
+        };
+        let textures = write.video_textures.clone();
+        drop(write);
+
+        for texture in textures {
+            texture.update_frame(context, bitmap.clone());
+        }
+    }
+
     /// Process a parsed FLV audio tag.
     ///
     /// `write` must be an active borrow of the current `NetStream`. `slice`
@@ -421,10 +772,64 @@ impl<'gc> NetStream<'gc> {
         audio_data: FlvAudioData<'_>,
     ) -> Result<(), NetstreamError> {
         let data = match audio_data.data {
-            FlvAudioDataType::Raw(data)
-            | FlvAudioDataType::AacSequenceHeader(data)
-            | FlvAudioDataType::AacRaw(data) => slice.to_subslice(data),
+            FlvAudioDataType::Raw(data) | FlvAudioDataType::AacRaw(data) => slice.to_subslice(data),
+            FlvAudioDataType::AacSequenceHeader(data) => {
+                // The sequence header is not audio itself; it is prepended to
+                // each new AAC substream instead.
+                write.aac_sequence_header = Some(slice.to_subslice(data));
+                return Ok(());
+            }
         };
+        let swf_format = SoundFormat {
+            compression: match audio_data.format {
+                FlvSoundFormat::LinearPCMPlatformEndian => {
+                    AudioCompression::UncompressedUnknownEndian
+                }
+                FlvSoundFormat::Adpcm => AudioCompression::Adpcm,
+                FlvSoundFormat::MP3 => AudioCompression::Mp3,
+                FlvSoundFormat::LinearPCMLittleEndian => AudioCompression::Uncompressed,
+                FlvSoundFormat::Nellymoser16kHz => AudioCompression::Nellymoser16Khz,
+                FlvSoundFormat::Nellymoser8kHz => AudioCompression::Nellymoser8Khz,
+                FlvSoundFormat::Nellymoser => AudioCompression::Nellymoser,
+                FlvSoundFormat::G711ALawPCM => return Err(NetstreamError::UnknownCodec),
+                FlvSoundFormat::G711MuLawPCM => return Err(NetstreamError::UnknownCodec),
+                FlvSoundFormat::Aac => AudioCompression::Aac,
+                FlvSoundFormat::Speex => AudioCompression::Speex,
+                FlvSoundFormat::MP38kHz => AudioCompression::Mp3,
+                FlvSoundFormat::DeviceSpecific => return Err(NetstreamError::UnknownCodec),
+            },
+            sample_rate: match (audio_data.format, audio_data.rate) {
+                (FlvSoundFormat::MP38kHz, _) => 8_000,
+                (_, FlvSoundRate::R5_500) => 5_500,
+                (_, FlvSoundRate::R11_000) => 11_000,
+                (_, FlvSoundRate::R22_000) => 22_000,
+                (_, FlvSoundRate::R44_000) => 44_000,
+            },
+            is_stereo: match audio_data.sound_type {
+                FlvSoundType::Mono => false,
+                FlvSoundType::Stereo => true,
+            },
+            is_16_bit: match audio_data.size {
+                FlvSoundSize::Bits8 => false,
+                FlvSoundSize::Bits16 => true,
+            },
+        };
+
+        Self::append_audio_data(write, slice, data, swf_format)
+    }
+
+    /// Append a chunk of audio data to the current audio stream, starting a
+    /// new one in the given format if necessary.
+    ///
+    /// `write` must be an active borrow of the current `NetStream`. `slice`
+    /// must reference the underlying backing buffer.
+    fn append_audio_data(
+        write: &mut NetStreamData<'gc>,
+        slice: &Slice,
+        data: Slice,
+        swf_format: SoundFormat,
+    ) -> Result<(), NetstreamError> {
+        let aac_sequence_header = write.aac_sequence_header.clone();
         let substream = match &mut write.audio_stream {
             Some((substream, _sound_stream_info)) => {
                 if substream
@@ -446,41 +851,16 @@ impl<'gc> NetStream<'gc> {
             }
             audio_stream => {
                 // None
-                let substream = Substream::new(slice.buffer().clone());
-                let swf_format = SoundFormat {
-                    compression: match audio_data.format {
-                        FlvSoundFormat::LinearPCMPlatformEndian => {
-                            AudioCompression::UncompressedUnknownEndian
-                        }
-                        FlvSoundFormat::Adpcm => AudioCompression::Adpcm,
-                        FlvSoundFormat::MP3 => AudioCompression::Mp3,
-                        FlvSoundFormat::LinearPCMLittleEndian => AudioCompression::Uncompressed,
-                        FlvSoundFormat::Nellymoser16kHz => AudioCompression::Nellymoser16Khz,
-                        FlvSoundFormat::Nellymoser8kHz => AudioCompression::Nellymoser8Khz,
-                        FlvSoundFormat::Nellymoser => AudioCompression::Nellymoser,
-                        FlvSoundFormat::G711ALawPCM => return Err(NetstreamError::UnknownCodec),
-                        FlvSoundFormat::G711MuLawPCM => return Err(NetstreamError::UnknownCodec),
-                        FlvSoundFormat::Aac => return Err(NetstreamError::UnknownCodec),
-                        FlvSoundFormat::Speex => AudioCompression::Speex,
-                        FlvSoundFormat::MP38kHz => AudioCompression::Mp3,
-                        FlvSoundFormat::DeviceSpecific => return Err(NetstreamError::UnknownCodec),
-                    },
-                    sample_rate: match (audio_data.format, audio_data.rate) {
-                        (FlvSoundFormat::MP38kHz, _) => 8_000,
-                        (_, FlvSoundRate::R5_500) => 5_500,
-                        (_, FlvSoundRate::R11_000) => 11_000,
-                        (_, FlvSoundRate::R22_000) => 22_000,
-                        (_, FlvSoundRate::R44_000) => 44_000,
-                    },
-                    is_stereo: match audio_data.sound_type {
-                        FlvSoundType::Mono => false,
-                        FlvSoundType::Stereo => true,
-                    },
-                    is_16_bit: match audio_data.size {
-                        FlvSoundSize::Bits8 => false,
-                        FlvSoundSize::Bits16 => true,
-                    },
-                };
+                let mut substream = Substream::new(slice.buffer().clone());
+
+                if swf_format.compression == AudioCompression::Aac {
+                    // Raw AAC frames can't be decoded without the sequence
+                    // header, so drop any that arrive before it.
+                    let Some(aac_sequence_header) = aac_sequence_header else {
+                        return Ok(());
+                    };
+                    substream.append(aac_sequence_header)?;
+                }
 
                 let sound_stream_head = SoundStreamInfo {
                     wrapping: SoundStreamWrapping::Unwrapped,
@@ -575,6 +955,7 @@ impl<'gc> NetStream<'gc> {
         context: &mut UpdateContext<'_, 'gc>,
         write: &mut NetStreamData<'gc>,
         slice: &Slice,
+        timestamp: i32,
         video_data: FlvVideoData<'_>,
         tag_needs_preloading: bool,
     ) {
@@ -589,109 +970,64 @@ impl<'gc> NetStream<'gc> {
         let codec = VideoCodec::from_u8(video_data.codec_id as u8);
         let buffer = slice.data();
 
-        match (video_handle, codec, video_data.data) {
-            (maybe_video_handle, Some(codec), FlvVideoPacket::Data(mut data))
+        match (codec, video_data.data) {
+            (Some(codec), FlvVideoPacket::Data(data))
             | (
-                maybe_video_handle,
                 Some(codec),
                 FlvVideoPacket::Vp6Data {
                     hadjust: _,
                     vadjust: _,
-                    mut data,
+                    data,
                 },
-            ) => {
-                //Some movies don't actually have metadata, so let's register a
-                //dummy stream just in case. All the actual data in the registration
-                //is lies, of course.
-                let video_handle = match maybe_video_handle {
-                    Some(stream) => stream,
-                    None => {
-                        match context.video.register_video_stream(
-                            1,
-                            (8, 8),
-                            codec,
-                            VideoDeblocking::UseVideoPacketValue,
-                        ) {
-                            Ok(new_handle) => {
-                                match &mut write.stream_type {
-                                    Some(NetStreamType::Flv { video_stream, .. }) => {
-                                        *video_stream = Some(new_handle)
-                                    }
-                                    _ => unreachable!(),
-                                }
-
-                                new_handle
-                            }
-                            Err(e) => {
-                                tracing::error!(
-                                    "Got error when registring FLV video stream: {}",
-                                    e
-                                );
-                                return; //TODO: This originally breaks and halts tag processing
-                            }
-                        }
-                    }
-                };
-
-                if codec == VideoCodec::ScreenVideo || codec == VideoCodec::ScreenVideoV2 {
-                    // ScreenVideo streams consider the FLV
-                    // video data byte to be integral to their
-                    // own bitstream.
-                    let offset = data.as_ptr() as usize - buffer.as_ptr() as usize;
-                    let len = data.len();
-                    data = buffer
-                        .get(offset - 1..offset + len)
-                        .expect("screenvideo flvs have video data bytes");
-                }
-
-                // NOTE: Currently, no implementation of the decoder backend actually requires
-                if tag_needs_preloading {
-                    let encoded_frame = EncodedFrame {
-                        codec,
-                        data, //TODO: ScreenVideo's decoder wants the FLV header bytes
-                        frame_id,
-                    };
-
+            ) => Self::flv_video_frame(
+                context,
+                write,
+                video_handle,
+                codec,
+                buffer,
+                data,
+                frame_id,
+                timestamp,
+                0,
+                tag_needs_preloading,
+            ),
+            (
+                Some(codec),
+                FlvVideoPacket::AvcNalu {
+                    composition_time_offset,
+                    data,
+                },
+            ) => Self::flv_video_frame(
+                context,
+                write,
+                video_handle,
+                codec,
+                buffer,
+                data,
+                frame_id,
+                timestamp,
+                composition_time_offset,
+                tag_needs_preloading,
+            ),
+            (Some(codec), FlvVideoPacket::AvcSequenceHeader(data)) => {
+                if let Some(video_handle) =
+                    Self::flv_video_stream(context, write, video_handle, codec)
+                {
                     if let Err(e) = context
                         .video
-                        .preload_video_stream_frame(video_handle, encoded_frame)
+                        .configure_video_stream_decoder(video_handle, data)
                     {
-                        tracing::error!("Preloading video frame {} failed: {}", frame_id, e);
-                    }
-                }
-
-                let encoded_frame = EncodedFrame {
-                    codec,
-                    data, //TODO: ScreenVideo's decoder wants the FLV header bytes
-                    frame_id,
-                };
-
-                match context.video.decode_video_stream_frame(
-                    video_handle,
-                    encoded_frame,
-                    context.renderer,
-                ) {
-                    Ok(bitmap_info) => {
-                        write.last_decoded_bitmap = Some(bitmap_info);
-                    }
-                    Err(e) => {
-                        tracing::error!("Decoding video frame {} failed: {}", frame_id, e);
+                        tracing::error!("Configuring FLV video decoder failed: {}", e);
                     }
                 }
             }
-            (_, _, FlvVideoPacket::CommandFrame(_command)) => {
+            (_, FlvVideoPacket::CommandFrame(_command)) => {
                 tracing::warn!("Stub: FLV command frame processing")
             }
-            (_, _, FlvVideoPacket::AvcSequenceHeader(_data)) => {
-                tracing::warn!("Stub: FLV AVC/H.264 Sequence Header processing")
-            }
-            (_, _, FlvVideoPacket::AvcNalu { .. }) => {
-                tracing::warn!("Stub: FLV AVC/H.264 NALU processing")
-            }
-            (_, _, FlvVideoPacket::AvcEndOfSequence) => {
+            (_, FlvVideoPacket::AvcEndOfSequence) => {
                 tracing::warn!("Stub: FLV AVC/H.264 End of Sequence processing")
             }
-            (_, None, _) => {
+            (None, _) => {
                 tracing::error!(
                     "FLV video tag has invalid codec id {}",
                     video_data.codec_id as u8
@@ -707,6 +1043,117 @@ impl<'gc> NetStream<'gc> {
         };
     }
 
+    /// Get the video stream of an FLV, registering one if necessary.
+    ///
+    /// `write` must be an active borrow of the current `NetStream`.
+    fn flv_video_stream(
+        context: &mut UpdateContext<'_, 'gc>,
+        write: &mut NetStreamData<'gc>,
+        video_handle: Option<VideoStreamHandle>,
+        codec: VideoCodec,
+    ) -> Option<VideoStreamHandle> {
+        if let Some(video_handle) = video_handle {
+            return Some(video_handle);
+        }
+
+        //Some movies don't actually have metadata, so let's register a
+        //dummy stream just in case. All the actual data in the registration
+        //is lies, of course.
+        match context.video.register_video_stream(
+            1,
+            (8, 8),
+            codec,
+            VideoDeblocking::UseVideoPacketValue,
+        ) {
+            Ok(new_handle) => {
+                match &mut write.stream_type {
+                    Some(NetStreamType::Flv { video_stream, .. }) => {
+                        *video_stream = Some(new_handle)
+                    }
+                    _ => unreachable!(),
+                }
+
+                Some(new_handle)
+            }
+            Err(e) => {
+                tracing::error!("Got error when registring FLV video stream: {}", e);
+                None //TODO: This originally breaks and halts tag processing
+            }
+        }
+    }
+
+    /// Preload and decode a single frame of FLV video.
+    ///
+    /// `write` must be an active borrow of the current `NetStream`.
+    #[allow(clippy::too_many_arguments)]
+    fn flv_video_frame(
+        context: &mut UpdateContext<'_, 'gc>,
+        write: &mut NetStreamData<'gc>,
+        video_handle: Option<VideoStreamHandle>,
+        codec: VideoCodec,
+        buffer: &[u8],
+        mut data: &[u8],
+        frame_id: u32,
+        timestamp: i32,
+        composition_time_offset: i32,
+        tag_needs_preloading: bool,
+    ) {
+        let Some(video_handle) = Self::flv_video_stream(context, write, video_handle, codec) else {
+            return;
+        };
+
+        if codec == VideoCodec::ScreenVideo || codec == VideoCodec::ScreenVideoV2 {
+            // ScreenVideo streams consider the FLV
+            // video data byte to be integral to their
+            // own bitstream.
+            let offset = data.as_ptr() as usize - buffer.as_ptr() as usize;
+            let len = data.len();
+            data = buffer
+                .get(offset - 1..offset + len)
+                .expect("screenvideo flvs have video data bytes");
+        }
+
+        // NOTE: Currently, no implementation of the decoder backend actually requires
+        if tag_needs_preloading {
+            let encoded_frame = EncodedFrame {
+                codec,
+                data, //TODO: ScreenVideo's decoder wants the FLV header bytes
+                frame_id,
+                timestamp,
+                composition_time_offset,
+            };
+
+            if let Err(e) = context
+                .video
+                .preload_video_stream_frame(video_handle, encoded_frame)
+            {
+                tracing::error!("Preloading video frame {} failed: {}", frame_id, e);
+            }
+        }
+
+        let encoded_frame = EncodedFrame {
+            codec,
+            data, //TODO: ScreenVideo's decoder wants the FLV header bytes
+            frame_id,
+            timestamp,
+            composition_time_offset,
+        };
+
+        match context
+            .video
+            .decode_video_stream_frame(video_handle, encoded_frame, context.renderer)
+        {
+            Ok(Some(bitmap_info)) => {
+                write.last_decoded_bitmap = Some(bitmap_info);
+                write.has_new_frame = true;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                tracing::error!("Decoding video frame {} failed: {}", frame_id, e);
+            }
+        }
+    }
+
     /// Process a parsed FLV script tag.
     ///
     /// This function attempts to borrow the current `NetStream`, you must drop
@@ -731,6 +1178,7 @@ impl<'gc> NetStream<'gc> {
         let mut video_codec_id = None;
         let mut frame_rate = None;
         let mut duration = None;
+        let mut metadata_seek_points = None;
 
         for var in script_data.0 {
             if var.name == b"onMetaData" && !has_stream_already {
@@ -747,6 +1195,11 @@ impl<'gc> NetStream<'gc> {
                                 }
                                 (b"framerate", FlvValue::Number(val)) => frame_rate = Some(val),
                                 (b"duration", FlvValue::Number(val)) => duration = Some(val),
+                                (b"keyframes", FlvValue::Object(keyframes))
+                                | (b"keyframes", FlvValue::EcmaArray(keyframes)) => {
+                                    metadata_seek_points =
+                                        Some(Self::metadata_seek_points(keyframes))
+                                }
                                 _ => {}
                             }
                         }
@@ -763,6 +1216,10 @@ impl<'gc> NetStream<'gc> {
             write = self.0.write(context.gc_context);
         }
 
+        if let Some(metadata_seek_points) = metadata_seek_points {
+            write.metadata_seek_points = metadata_seek_points;
+        }
+
         if tag_needs_preloading {
             if let (
                 Some(width),
@@ -797,6 +1254,342 @@ impl<'gc> NetStream<'gc> {
         }
     }
 
+    /// Read the seek points listed in the `keyframes` object of an FLV's
+    /// metadata.
+    ///
+    /// This is not part of the FLV specification, but is written by most
+    /// tools that produce FLVs: `times` holds the time of each keyframe in
+    /// seconds and `filepositions` the position of its tag in the file.
+    fn metadata_seek_points(keyframes: Vec<FlvVariable<'_>>) -> Vec<SeekPoint> {
+        fn numbers(value: FlvValue<'_>) -> Vec<f64> {
+            match value {
+                FlvValue::StrictArray(values)
+                | FlvValue::EcmaArray(values)
+                | FlvValue::Object(values) => values
+                    .into_iter()
+                    .filter_map(|value| match value.data {
+                        FlvValue::Number(number) => Some(number),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            }
+        }
+
+        let mut times = Vec::new();
+        let mut file_positions = Vec::new();
+        for keyframe_var in keyframes {
+            match keyframe_var.name {
+                b"times" => times = numbers(keyframe_var.data),
+                b"filepositions" => file_positions = numbers(keyframe_var.data),
+                _ => {}
+            }
+        }
+
+        let mut seek_points: Vec<SeekPoint> = times
+            .into_iter()
+            .zip(file_positions)
+            .filter(|(_, position)| *position >= 4.0)
+            .map(|(time, position)| SeekPoint {
+                time: time * 1000.0,
+                // Positions point at the tag itself, while our tags start at
+                // the size of the previous tag that precedes it.
+                offset: position as usize - 4,
+            })
+            .collect();
+        seek_points.sort_by(|a, b| a.time.total_cmp(&b.time));
+        seek_points
+    }
+
+    /// Set up playback of a parsed MP4 movie.
+    ///
+    /// `write` must be an active borrow of the current `NetStream`. `slice`
+    /// must reference the underlying backing buffer.
+    fn mp4_start(
+        context: &mut UpdateContext<'_, 'gc>,
+        write: &mut NetStreamData<'gc>,
+        slice: &Slice,
+        movie: Mp4Movie,
+    ) {
+        let buffer = slice.data();
+
+        let video_stream = match &movie.video {
+            Some(track) if track.codec == Mp4Codec::Avc => {
+                match context.video.register_video_stream(
+                    track.samples.len() as u32,
+                    (track.width, track.height),
+                    VideoCodec::H264,
+                    VideoDeblocking::UseVideoPacketValue,
+                ) {
+                    Ok(video_stream) => {
+                        let config = track
+                            .decoder_config
+                            .clone()
+                            .and_then(|config| buffer.get(config));
+                        if let Some(config) = config {
+                            if let Err(e) = context
+                                .video
+                                .configure_video_stream_decoder(video_stream, config)
+                            {
+                                tracing::error!("Configuring MP4 video decoder failed: {}", e);
+                            }
+                        }
+                        Some(video_stream)
+                    }
+                    Err(e) => {
+                        tracing::error!("Got error when registring MP4 video stream: {}", e);
+                        None
+                    }
+                }
+            }
+            Some(track) => {
+                tracing::error!("MP4 video track has unsupported codec {:?}", track.codec);
+                None
+            }
+            None => None,
+        };
+
+        if let Some(track) = &movie.audio {
+            if track.codec == Mp4Codec::Aac {
+                // MP4 stores the AAC decoder configuration in the sample
+                // description, where FLV would use a sequence header.
+                write.aac_sequence_header = track
+                    .decoder_config
+                    .clone()
+                    .and_then(|config| buffer.get(config))
+                    .map(|config| slice.to_subslice(config));
+            }
+        }
+
+        write.stream_type = Some(NetStreamType::Mp4 {
+            movie,
+            video_stream,
+            next_video_sample: 0,
+            next_audio_sample: 0,
+        });
+    }
+
+    /// Build the `onMetaData` object for an MP4 movie, in the same form as
+    /// the metadata of an FLV.
+    fn mp4_metadata(movie: &Mp4Movie) -> FlvValue<'static> {
+        let mut metadata = vec![FlvVariable {
+            name: b"duration",
+            data: FlvValue::Number(movie.duration),
+        }];
+
+        if let Some(track) = &movie.video {
+            metadata.push(FlvVariable {
+                name: b"width",
+                data: FlvValue::Number(track.width.into()),
+            });
+            metadata.push(FlvVariable {
+                name: b"height",
+                data: FlvValue::Number(track.height.into()),
+            });
+            if movie.duration > 0.0 {
+                metadata.push(FlvVariable {
+                    name: b"framerate",
+                    data: FlvValue::Number(track.samples.len() as f64 / movie.duration),
+                });
+            }
+            if track.codec == Mp4Codec::Avc {
+                metadata.push(FlvVariable {
+                    name: b"videocodecid",
+                    data: FlvValue::String(b"avc1"),
+                });
+            }
+        }
+
+        if let Some(track) = &movie.audio {
+            metadata.push(FlvVariable {
+                name: b"audiosamplerate",
+                data: FlvValue::Number(track.sample_rate.into()),
+            });
+            metadata.push(FlvVariable {
+                name: b"audiochannels",
+                data: FlvValue::Number(track.channels.into()),
+            });
+            if track.codec == Mp4Codec::Aac {
+                metadata.push(FlvVariable {
+                    name: b"audiocodecid",
+                    data: FlvValue::String(b"mp4a"),
+                });
+            }
+        }
+
+        FlvValue::Object(metadata)
+    }
+
+    /// The format of the audio of an MP4, if it is supported.
+    fn mp4_sound_format(track: &Mp4Track) -> Option<SoundFormat> {
+        Some(SoundFormat {
+            compression: match track.codec {
+                Mp4Codec::Aac => AudioCompression::Aac,
+                Mp4Codec::Mp3 => AudioCompression::Mp3,
+                _ => return None,
+            },
+            sample_rate: track.sample_rate.try_into().unwrap_or(u16::MAX),
+            is_stereo: track.channels > 1,
+            is_16_bit: true,
+        })
+    }
+
+    /// Find the seek point of an MP4 to restart playback from in order to
+    /// seek to the given time, in milliseconds.
+    ///
+    /// Sync samples are seek points in movies with video; otherwise, every
+    /// audio sample is.
+    fn mp4_seek_point(movie: &Mp4Movie, time: f64) -> Option<SeekPoint> {
+        let samples = movie
+            .video
+            .as_ref()
+            .or(movie.audio.as_ref())
+            .map(|track| &track.samples[..])
+            .unwrap_or_default();
+
+        samples
+            .iter()
+            .rev()
+            .find(|sample| sample.is_sync && sample.time <= time)
+            .or_else(|| samples.iter().find(|sample| sample.is_sync))
+            .map(|sample| SeekPoint {
+                time: sample.time,
+                offset: sample.offset,
+            })
+    }
+
+    /// Decode and stream the samples of an MP4 up to the given time.
+    ///
+    /// Returns whether the end of the movie was reached and whether playback
+    /// ran out of loaded data, in that order.
+    ///
+    /// `write` must be an active borrow of the current `NetStream`. `slice`
+    /// must reference the underlying backing buffer.
+    fn mp4_tick(
+        context: &mut UpdateContext<'_, 'gc>,
+        write: &mut NetStreamData<'gc>,
+        slice: &Slice,
+        end_time: f64,
+    ) -> (bool, bool) {
+        let Some(NetStreamType::Mp4 {
+            movie,
+            video_stream,
+            mut next_video_sample,
+            mut next_audio_sample,
+        }) = write.stream_type.take()
+        else {
+            unreachable!()
+        };
+        let buffer = slice.data();
+        let mut buffer_empty = false;
+
+        if let Some(track) = &movie.video {
+            while let Some(sample) = track.samples.get(next_video_sample) {
+                if sample.time >= end_time {
+                    break;
+                }
+
+                let data = buffer.get(sample.offset..sample.offset + sample.size);
+                if data.is_none() && !write.is_loaded {
+                    // The rest of the movie is still downloading.
+                    write.buffered_time = sample.time;
+                    buffer_empty = true;
+                    break;
+                }
+
+                if let (Some(video_stream), Some(data)) = (video_stream, data) {
+                    let encoded_frame = EncodedFrame {
+                        codec: VideoCodec::H264,
+                        data,
+                        frame_id: next_video_sample as u32,
+                        timestamp: sample.time as i32,
+                        composition_time_offset: sample.composition_time_offset as i32,
+                    };
+
+                    match context.video.decode_video_stream_frame(
+                        video_stream,
+                        encoded_frame,
+                        context.renderer,
+                    ) {
+                        Ok(Some(bitmap_info)) => {
+                            write.last_decoded_bitmap = Some(bitmap_info);
+                            write.has_new_frame = true;
+                        }
+                        Ok(None) => {}
+                        Err(e) => {
+                            tracing::error!(
+                                "Decoding video frame {} failed: {}",
+                                next_video_sample,
+                                e
+                            );
+                        }
+                    }
+                }
+
+                next_video_sample += 1;
+            }
+        }
+
+        let audio = movie
+            .audio
+            .as_ref()
+            .and_then(|track| Some((track, Self::mp4_sound_format(track)?)));
+        if let Some((track, swf_format)) = audio {
+            // Like FLV audio tags, a few samples are streamed ahead of time
+            // to avoid audio underruns.
+            let mut lookahead_samples = track.samples[..next_audio_sample]
+                .iter()
+                .rev()
+                .take_while(|sample| sample.time >= end_time)
+                .count();
+
+            while let Some(sample) = track.samples.get(next_audio_sample) {
+                if sample.time >= end_time {
+                    if lookahead_samples >= 5 {
+                        break;
+                    }
+                    lookahead_samples += 1;
+                }
+
+                let data = buffer.get(sample.offset..sample.offset + sample.size);
+                if data.is_none() && !write.is_loaded {
+                    if sample.time < end_time {
+                        write.buffered_time = sample.time;
+                        buffer_empty = true;
+                    }
+                    break;
+                }
+
+                if let Some(data) = data {
+                    let data = slice.to_subslice(data);
+                    if let Err(e) = Self::append_audio_data(write, slice, data, swf_format.clone())
+                    {
+                        //TODO: Fire an error event at AS.
+                        tracing::error!("Error committing sound stream: {}", e);
+                    }
+                }
+
+                next_audio_sample += 1;
+            }
+        }
+
+        let video_ended = movie
+            .video
+            .as_ref()
+            .map_or(true, |track| next_video_sample >= track.samples.len());
+        let audio_ended = audio.map_or(true, |(track, _)| next_audio_sample >= track.samples.len());
+        let end_of_video = !buffer_empty && video_ended && audio_ended;
+
+        write.stream_type = Some(NetStreamType::Mp4 {
+            movie,
+            video_stream,
+            next_video_sample,
+            next_audio_sample,
+        });
+
+        (end_of_video, buffer_empty)
+    }
+
     pub fn tick(self, context: &mut UpdateContext<'_, 'gc>, dt: f64) {
         #![allow(clippy::explicit_auto_deref)] //Erroneous lint
         let mut write = self.0.write(context.gc_context);
@@ -829,13 +1622,50 @@ impl<'gc> NetStream<'gc> {
                         }
                         Err(FlvError::EndOfData) => return,
                         Err(e) => {
-                            //TODO: Fire an error event to AS & stop playing too
                             tracing::error!("FLV header parsing failed: {}", e);
                             write.preload_offset = 3;
+                            drop(write);
+                            self.trigger_status_event(
+                                context,
+                                &[
+                                    ("code", "NetStream.Play.FileStructureInvalid"),
+                                    ("level", "error"),
+                                ],
+                            );
+                            StreamManager::ensure_paused(context, self);
                             return;
                         }
                     }
                 }
+                Some(_) if mp4::is_mp4(&buffer) => match Mp4Movie::parse(&buffer) {
+                    Ok(movie) => {
+                        let metadata = Self::mp4_metadata(&movie);
+                        Self::mp4_start(context, &mut write, &slice, movie);
+
+                        let avm_object = write.avm_object;
+                        drop(write);
+                        let _ =
+                            self.handle_script_data(avm_object, context, b"onMetaData", metadata); // Any errors while trying to lookup or call AVM2 properties are silently swallowed.
+                        write = self.0.write(context.gc_context);
+                    }
+                    // The movie header may be at the end of the file.
+                    Err(Mp4Error::EndOfData) if !write.is_loaded => return,
+                    Err(e) => {
+                        tracing::error!("MP4 parsing failed: {}", e);
+                        write.preload_offset = 3;
+                        drop(write);
+                        self.trigger_status_event(
+                            context,
+                            &[
+                                ("code", "NetStream.Play.FileStructureInvalid"),
+                                ("level", "error"),
+                            ],
+                        );
+                        StreamManager::ensure_paused(context, self);
+                        return;
+                    }
+                },
+                Some(_) if buffer.len() < 8 && !write.is_loaded => return, //Data not yet loaded
                 Some(magic) => {
                     //Unrecognized signature
                     //TODO: Fire an error event to AS & stop playing too
@@ -864,8 +1694,26 @@ impl<'gc> NetStream<'gc> {
             }
         }
 
+        if write.is_buffering {
+            let buffered_length = write.buffered_time - write.stream_time;
+            if !write.is_loaded && buffered_length < write.buffer_time * 1000.0 {
+                // Wait for more data before (re)starting playback.
+                return;
+            }
+
+            write.is_buffering = false;
+            drop(write);
+            // NOTE: The onMetaData event triggers before this event in Flash due to its streaming behavior.
+            self.trigger_status_event(
+                context,
+                &[("code", "NetStream.Buffer.Full"), ("level", "status")],
+            );
+            write = self.0.write(context.gc_context);
+        }
+
         let end_time = write.stream_time + dt;
         let mut end_of_video = false;
+        let mut buffer_empty = false;
         let mut error = false;
         let mut max_lookahead_audio_tags = 5;
         let mut is_lookahead_tag = false;
@@ -882,8 +1730,12 @@ impl<'gc> NetStream<'gc> {
                     // error states or playback ending to trip until we run
                     // those tags "for realsies"
                     if !is_lookahead_tag && matches!(e, FlvError::EndOfData) {
-                        //TODO: Check expected total length for streaming / progressive download
-                        end_of_video = true;
+                        if write.is_loaded {
+                            end_of_video = true;
+                        } else {
+                            // The rest of the stream is still downloading.
+                            buffer_empty = true;
+                        }
                     } else if !is_lookahead_tag {
                         //Corrupt tag or out of data
                         tracing::error!("FLV tag parsing failed: {}", e);
@@ -918,6 +1770,7 @@ impl<'gc> NetStream<'gc> {
                         context,
                         &mut write,
                         &slice,
+                        tag.timestamp,
                         video_data,
                         tag_needs_preloading,
                     ),
@@ -940,9 +1793,19 @@ impl<'gc> NetStream<'gc> {
                     write.preload_offset = max(write.offset, write.preload_offset);
                 }
             }
+        } else if matches!(write.stream_type, Some(NetStreamType::Mp4 { .. })) {
+            (end_of_video, buffer_empty) = Self::mp4_tick(context, &mut write, &slice, end_time);
         }
 
-        write.stream_time = end_time;
+        write.stream_time = if buffer_empty {
+            // Playback can't get past the data that has been loaded so far.
+            end_time.min(write.buffered_time)
+        } else {
+            end_time
+        };
+        if buffer_empty {
+            write.is_buffering = true;
+        }
         if let Err(e) = self.commit_sound_stream(context, &mut write) {
             //TODO: Fire an error event at AS.
             tracing::error!("Error committing sound stream: {}", e);
@@ -962,12 +1825,25 @@ impl<'gc> NetStream<'gc> {
                 context,
                 &[("code", "NetStream.Buffer.Empty"), ("level", "status")],
             );
-            self.pause(context);
+            StreamManager::ensure_paused(context, self);
+        }
+
+        if buffer_empty {
+            self.trigger_status_event(
+                context,
+                &[("code", "NetStream.Buffer.Empty"), ("level", "status")],
+            );
         }
 
         if error {
-            //TODO: Fire an error event at AS.
-            self.pause(context);
+            self.trigger_status_event(
+                context,
+                &[
+                    ("code", "NetStream.Play.FileStructureInvalid"),
+                    ("level", "error"),
+                ],
+            );
+            StreamManager::ensure_paused(context, self);
         }
     }
 
@@ -1067,6 +1943,10 @@ impl<'gc> NetStream<'gc> {
                     .expect("Client should be initialized if script data is being accessed");
 
                 let data_object = variable_data.to_avm2_value(&mut activation);
+                if variable_name == b"onMetaData" {
+                    self.0.write(activation.context.gc_context).avm2_metadata =
+                        data_object.as_object();
+                }
 
                 client_object.call_public_property(
                     AvmString::new_utf8_bytes(activation.context.gc_context, variable_name),
@@ -0,0 +1,605 @@
+//! MP4/F4V (ISO base media file format) demuxing.
+//!
+//! Only the parts of the format needed to play back progressive downloads
+//! are supported: the `moov` box is parsed into a table of samples for the
+//! first video and audio track, whose data is then read out of the stream
+//! buffer as playback reaches it.
+
+use std::ops::Range;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum Mp4Error {
+    /// The `moov` box has not been loaded yet.
+    #[error("Movie header not yet loaded")]
+    EndOfData,
+
+    #[error("Invalid {0} box")]
+    InvalidBox(&'static str),
+}
+
+/// The codec of a track.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mp4Codec {
+    /// H.264 video, configured by an `AVCDecoderConfigurationRecord`.
+    Avc,
+
+    /// AAC audio, configured by an `AudioSpecificConfig`.
+    Aac,
+
+    Mp3,
+
+    Unknown([u8; 4]),
+}
+
+/// A single sample (a video frame or a packet of audio) of a track.
+#[derive(Clone, Copy, Debug)]
+pub struct Mp4Sample {
+    /// The position of the sample data in the file.
+    pub offset: usize,
+
+    pub size: usize,
+
+    /// The decoding time of the sample, in milliseconds.
+    pub time: f64,
+
+    /// The difference between the presentation and decoding time of the
+    /// sample, in milliseconds.
+    pub composition_time_offset: f64,
+
+    /// Whether decoding can start at this sample.
+    pub is_sync: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct Mp4Track {
+    pub codec: Mp4Codec,
+
+    /// The position of the decoder configuration in the file, if the codec
+    /// has one.
+    pub decoder_config: Option<Range<usize>>,
+
+    pub width: u16,
+    pub height: u16,
+    pub sample_rate: u32,
+    pub channels: u16,
+
+    /// The samples of the track, in decoding order.
+    pub samples: Vec<Mp4Sample>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Mp4Movie {
+    /// The duration of the movie, in seconds.
+    pub duration: f64,
+
+    pub video: Option<Mp4Track>,
+    pub audio: Option<Mp4Track>,
+}
+
+/// Check if the given data looks like the start of an MP4 file.
+pub fn is_mp4(data: &[u8]) -> bool {
+    matches!(
+        data.get(4..8),
+        Some(b"ftyp") | Some(b"moov") | Some(b"mdat") | Some(b"free") | Some(b"wide")
+    )
+}
+
+impl Mp4Movie {
+    /// Parse the movie header out of the start of an MP4 file.
+    ///
+    /// `data` is all of the file that has been loaded so far. If the `moov`
+    /// box hasn't been completely loaded, `Mp4Error::EndOfData` is returned.
+    pub fn parse(data: &[u8]) -> Result<Self, Mp4Error> {
+        for mp4_box in Boxes::new(data, 0..data.len()) {
+            let mp4_box = mp4_box?;
+            if &mp4_box.kind == b"moov" {
+                return Self::parse_moov(data, mp4_box.data);
+            }
+        }
+
+        Err(Mp4Error::EndOfData)
+    }
+
+    fn parse_moov(data: &[u8], moov: Range<usize>) -> Result<Self, Mp4Error> {
+        let mut duration = 0.0;
+        let mut video = None;
+        let mut audio = None;
+
+        for mp4_box in Boxes::new(data, moov) {
+            let mp4_box = mp4_box?;
+            match &mp4_box.kind {
+                b"mvhd" => {
+                    let mut reader = Reader::new(data, mp4_box.data, "mvhd");
+                    let version = reader.read_u8()?;
+                    reader.skip(3)?;
+                    let (timescale, length) = if version == 1 {
+                        reader.skip(16)?;
+                        (reader.read_u32()?, reader.read_u64()?)
+                    } else {
+                        reader.skip(8)?;
+                        (reader.read_u32()?, u64::from(reader.read_u32()?))
+                    };
+                    if timescale > 0 {
+                        duration = length as f64 / f64::from(timescale);
+                    }
+                }
+                b"trak" => {
+                    if let Some((handler, track)) = Self::parse_trak(data, mp4_box.data)? {
+                        match &handler {
+                            b"vide" if video.is_none() => video = Some(track),
+                            b"soun" if audio.is_none() => audio = Some(track),
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            duration,
+            video,
+            audio,
+        })
+    }
+
+    /// Parse a track, returning its handler type along with it.
+    fn parse_trak(
+        data: &[u8],
+        trak: Range<usize>,
+    ) -> Result<Option<([u8; 4], Mp4Track)>, Mp4Error> {
+        let mut width = 0;
+        let mut height = 0;
+        let mut mdia = None;
+
+        for mp4_box in Boxes::new(data, trak) {
+            let mp4_box = mp4_box?;
+            match &mp4_box.kind {
+                b"tkhd" => {
+                    let mut reader = Reader::new(data, mp4_box.data, "tkhd");
+                    let version = reader.read_u8()?;
+                    reader.skip(3)?;
+                    // Times, IDs, layer, volume and the transformation
+                    // matrix precede the 16.16 fixed point dimensions.
+                    let times_size = if version == 1 { 32 } else { 20 };
+                    reader.skip(times_size + 52)?;
+                    width = (reader.read_u32()? >> 16) as u16;
+                    height = (reader.read_u32()? >> 16) as u16;
+                }
+                b"mdia" => mdia = Some(mp4_box.data),
+                _ => {}
+            }
+        }
+
+        let Some(mdia) = mdia else {
+            return Ok(None);
+        };
+
+        let mut timescale = 0;
+        let mut handler = [0; 4];
+        let mut stbl = None;
+        for mp4_box in Boxes::new(data, mdia) {
+            let mp4_box = mp4_box?;
+            match &mp4_box.kind {
+                b"mdhd" => {
+                    let mut reader = Reader::new(data, mp4_box.data, "mdhd");
+                    let version = reader.read_u8()?;
+                    reader.skip(3)?;
+                    reader.skip(if version == 1 { 16 } else { 8 })?;
+                    timescale = reader.read_u32()?;
+                }
+                b"hdlr" => {
+                    let mut reader = Reader::new(data, mp4_box.data, "hdlr");
+                    reader.skip(8)?;
+                    handler = reader.read_kind()?;
+                }
+                b"minf" => {
+                    for mp4_box in Boxes::new(data, mp4_box.data) {
+                        let mp4_box = mp4_box?;
+                        if &mp4_box.kind == b"stbl" {
+                            stbl = Some(mp4_box.data);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let (Some(stbl), true) = (stbl, timescale > 0) else {
+            return Ok(None);
+        };
+
+        let mut track = parse_sample_table(data, stbl, f64::from(timescale))?;
+        if track.width == 0 && track.height == 0 {
+            track.width = width;
+            track.height = height;
+        }
+        Ok(Some((handler, track)))
+    }
+}
+
+/// Parse the sample table of a track, whose times are in units of the given
+/// timescale.
+fn parse_sample_table(
+    data: &[u8],
+    stbl: Range<usize>,
+    timescale: f64,
+) -> Result<Mp4Track, Mp4Error> {
+    let mut track = Mp4Track {
+        codec: Mp4Codec::Unknown([0; 4]),
+        decoder_config: None,
+        width: 0,
+        height: 0,
+        sample_rate: 0,
+        channels: 0,
+        samples: Vec::new(),
+    };
+
+    let mut time_to_sample = Vec::new();
+    let mut composition_offsets = Vec::new();
+    let mut sample_to_chunk = Vec::new();
+    let mut sample_sizes = Vec::new();
+    let mut chunk_offsets = Vec::new();
+    let mut sync_samples = None;
+
+    for mp4_box in Boxes::new(data, stbl) {
+        let mp4_box = mp4_box?;
+        let mut reader = Reader::new(data, mp4_box.data.clone(), "stbl");
+        match &mp4_box.kind {
+            b"stsd" => {
+                reader.skip(8)?;
+                let entry_start = reader.pos;
+                if let Some(entry) = Boxes::new(data, entry_start..mp4_box.data.end).next() {
+                    parse_sample_entry(data, entry?, &mut track)?;
+                }
+            }
+            b"stts" => {
+                reader.skip(4)?;
+                for _ in 0..reader.read_u32()? {
+                    time_to_sample.push((reader.read_u32()?, reader.read_u32()?));
+                }
+            }
+            b"ctts" => {
+                reader.skip(4)?;
+                for _ in 0..reader.read_u32()? {
+                    // Version 0 offsets are nominally unsigned, but are
+                    // negative in practice often enough to treat them
+                    // as signed either way.
+                    composition_offsets.push((reader.read_u32()?, reader.read_u32()? as i32));
+                }
+            }
+            b"stsc" => {
+                reader.skip(4)?;
+                for _ in 0..reader.read_u32()? {
+                    let first_chunk = reader.read_u32()?;
+                    let samples_per_chunk = reader.read_u32()?;
+                    reader.skip(4)?;
+                    sample_to_chunk.push((first_chunk, samples_per_chunk));
+                }
+            }
+            b"stsz" => {
+                reader.skip(4)?;
+                let sample_size = reader.read_u32()?;
+                let sample_count = reader.read_u32()?;
+                if sample_size == 0 {
+                    for _ in 0..sample_count {
+                        sample_sizes.push(reader.read_u32()?);
+                    }
+                } else {
+                    // Nothing about the box bounds a constant sample size's
+                    // count, so don't trust it with an infallible allocation.
+                    let sample_count = sample_count as usize;
+                    sample_sizes = Vec::new();
+                    sample_sizes
+                        .try_reserve_exact(sample_count)
+                        .map_err(|_| Mp4Error::InvalidBox("stsz"))?;
+                    sample_sizes.resize(sample_count, sample_size);
+                }
+            }
+            b"stco" => {
+                reader.skip(4)?;
+                for _ in 0..reader.read_u32()? {
+                    chunk_offsets.push(u64::from(reader.read_u32()?));
+                }
+            }
+            b"co64" => {
+                reader.skip(4)?;
+                for _ in 0..reader.read_u32()? {
+                    chunk_offsets.push(reader.read_u64()?);
+                }
+            }
+            b"stss" => {
+                reader.skip(4)?;
+                let mut samples = Vec::new();
+                for _ in 0..reader.read_u32()? {
+                    samples.push(reader.read_u32()?);
+                }
+                sync_samples = Some(samples);
+            }
+            _ => {}
+        }
+    }
+
+    // Sample offsets come from laying the samples of each chunk out one
+    // after another.
+    let mut offsets = Vec::with_capacity(sample_sizes.len());
+    let mut sample_sizes_iter = sample_sizes.iter();
+    for (chunk_index, chunk_offset) in chunk_offsets.iter().enumerate() {
+        let chunk_number = chunk_index as u32 + 1;
+        let samples_per_chunk = sample_to_chunk
+            .iter()
+            .rev()
+            .find(|(first_chunk, _)| *first_chunk <= chunk_number)
+            .map_or(0, |(_, samples_per_chunk)| *samples_per_chunk);
+        let mut offset =
+            usize::try_from(*chunk_offset).map_err(|_| Mp4Error::InvalidBox("stco"))?;
+        for _ in 0..samples_per_chunk {
+            let Some(size) = sample_sizes_iter.next() else {
+                break;
+            };
+            offsets.push(offset);
+            offset = offset
+                .checked_add(*size as usize)
+                .ok_or(Mp4Error::InvalidBox("stsz"))?;
+        }
+    }
+
+    let mut decode_times = time_to_sample
+        .iter()
+        .flat_map(|(count, delta)| std::iter::repeat(*delta).take(*count as usize))
+        .scan(0u64, |time, delta| {
+            let sample_time = *time;
+            *time += u64::from(delta);
+            Some(sample_time)
+        });
+    let mut composition_offsets = composition_offsets
+        .iter()
+        .flat_map(|(count, offset)| std::iter::repeat(*offset).take(*count as usize));
+
+    for (index, (offset, size)) in offsets.into_iter().zip(sample_sizes).enumerate() {
+        let time = decode_times.next().unwrap_or_default();
+        let composition_time_offset = composition_offsets.next().unwrap_or_default();
+        let sample_number = index as u32 + 1;
+        track.samples.push(Mp4Sample {
+            offset,
+            size: size as usize,
+            time: time as f64 * 1000.0 / timescale,
+            composition_time_offset: f64::from(composition_time_offset) * 1000.0 / timescale,
+            is_sync: sync_samples.as_ref().map_or(true, |samples| {
+                samples.binary_search(&sample_number).is_ok()
+            }),
+        });
+    }
+
+    Ok(track)
+}
+
+/// Parse the first sample description of a track.
+fn parse_sample_entry(data: &[u8], entry: Mp4Box, track: &mut Mp4Track) -> Result<(), Mp4Error> {
+    let mut reader = Reader::new(data, entry.data.clone(), "stsd");
+    // Reserved bytes and the data reference index.
+    reader.skip(8)?;
+
+    match &entry.kind {
+        b"avc1" => {
+            track.codec = Mp4Codec::Avc;
+            reader.skip(16)?;
+            track.width = reader.read_u16()?;
+            track.height = reader.read_u16()?;
+            reader.skip(50)?;
+            for mp4_box in Boxes::new(data, reader.pos..entry.data.end) {
+                let mp4_box = mp4_box?;
+                if &mp4_box.kind == b"avcC" {
+                    track.decoder_config = Some(mp4_box.data);
+                }
+            }
+        }
+        b"mp4a" | b".mp3" => {
+            track.codec = if &entry.kind == b".mp3" {
+                Mp4Codec::Mp3
+            } else {
+                Mp4Codec::Aac
+            };
+            let version = reader.read_u16()?;
+            reader.skip(6)?;
+            track.channels = reader.read_u16()?;
+            reader.skip(6)?;
+            track.sample_rate = reader.read_u32()? >> 16;
+            // QuickTime sound descriptions may carry extra fields.
+            match version {
+                1 => reader.skip(16)?,
+                2 => reader.skip(36)?,
+                _ => {}
+            }
+            for mp4_box in Boxes::new(data, reader.pos..entry.data.end) {
+                let mp4_box = mp4_box?;
+                if &mp4_box.kind == b"esds" {
+                    parse_esds(data, mp4_box.data, track)?;
+                }
+            }
+        }
+        kind => track.codec = Mp4Codec::Unknown(*kind),
+    }
+
+    Ok(())
+}
+
+/// Parse an elementary stream descriptor, which holds the object type
+/// and decoder configuration of MPEG-4 audio.
+fn parse_esds(data: &[u8], esds: Range<usize>, track: &mut Mp4Track) -> Result<(), Mp4Error> {
+    let mut reader = Reader::new(data, esds, "esds");
+    reader.skip(4)?;
+
+    while reader.pos < reader.end {
+        let tag = reader.read_u8()?;
+        let mut length = 0;
+        for _ in 0..4 {
+            let byte = reader.read_u8()?;
+            length = (length << 7) | usize::from(byte & 0x7F);
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+
+        match tag {
+            // ES_Descriptor
+            0x03 => {
+                reader.skip(2)?;
+                let flags = reader.read_u8()?;
+                if flags & 0x80 != 0 {
+                    reader.skip(2)?;
+                }
+                if flags & 0x40 != 0 {
+                    let url_length = reader.read_u8()?;
+                    reader.skip(usize::from(url_length))?;
+                }
+                if flags & 0x20 != 0 {
+                    reader.skip(2)?;
+                }
+            }
+            // DecoderConfigDescriptor
+            0x04 => {
+                let object_type = reader.read_u8()?;
+                if matches!(object_type, 0x69 | 0x6B) {
+                    track.codec = Mp4Codec::Mp3;
+                }
+                reader.skip(12)?;
+            }
+            // DecoderSpecificInfo
+            0x05 => {
+                let start = reader.pos;
+                reader.skip(length)?;
+                track.decoder_config = Some(start..reader.pos);
+                break;
+            }
+            _ => reader.skip(length)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// A box header, along with the range of the data it contains.
+struct Mp4Box {
+    kind: [u8; 4],
+    data: Range<usize>,
+}
+
+/// An iterator over the boxes in a range of the file.
+struct Boxes<'a> {
+    data: &'a [u8],
+    pos: usize,
+    end: usize,
+}
+
+impl<'a> Boxes<'a> {
+    fn new(data: &'a [u8], range: Range<usize>) -> Self {
+        Self {
+            data,
+            pos: range.start,
+            end: range.end.min(data.len()),
+        }
+    }
+}
+
+impl<'a> Iterator for Boxes<'a> {
+    type Item = Result<Mp4Box, Mp4Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos + 8 > self.end {
+            return None;
+        }
+
+        let mut reader = Reader::new(self.data, self.pos..self.end, "box");
+        let result = (|| {
+            let start = reader.pos;
+            let size = reader.read_u32()?;
+            let kind = reader.read_kind()?;
+            let size = match size {
+                // The box extends to the end of the file.
+                0 => (self.end - start) as u64,
+                1 => reader.read_u64()?,
+                size => u64::from(size),
+            };
+            let end = usize::try_from(size)
+                .ok()
+                .and_then(|size| start.checked_add(size))
+                .ok_or(Mp4Error::InvalidBox("box"))?;
+            if end < reader.pos {
+                return Err(Mp4Error::InvalidBox("box"));
+            }
+            if end > self.end {
+                return Err(Mp4Error::EndOfData);
+            }
+            Ok(Mp4Box {
+                kind,
+                data: reader.pos..end,
+            })
+        })();
+
+        match &result {
+            Ok(mp4_box) => self.pos = mp4_box.data.end,
+            Err(_) => self.pos = self.end,
+        }
+        Some(result)
+    }
+}
+
+/// A big-endian reader over a range of the file.
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    end: usize,
+    box_name: &'static str,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8], range: Range<usize>, box_name: &'static str) -> Self {
+        Self {
+            data,
+            pos: range.start,
+            end: range.end.min(data.len()),
+            box_name,
+        }
+    }
+
+    fn read<const N: usize>(&mut self) -> Result<[u8; N], Mp4Error> {
+        if self.pos + N > self.end {
+            return Err(Mp4Error::InvalidBox(self.box_name));
+        }
+        let bytes = self.data[self.pos..self.pos + N]
+            .try_into()
+            .expect("slice has the right length");
+        self.pos += N;
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, count: usize) -> Result<(), Mp4Error> {
+        if count > self.end - self.pos {
+            return Err(Mp4Error::InvalidBox(self.box_name));
+        }
+        self.pos += count;
+        Ok(())
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Mp4Error> {
+        Ok(self.read::<1>()?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, Mp4Error> {
+        Ok(u16::from_be_bytes(self.read()?))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, Mp4Error> {
+        Ok(u32::from_be_bytes(self.read()?))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, Mp4Error> {
+        Ok(u64::from_be_bytes(self.read()?))
+    }
+
+    fn read_kind(&mut self) -> Result<[u8; 4], Mp4Error> {
+        self.read()
+    }
+}
@@ -5,16 +5,16 @@ use async_channel::{Receiver, TryRecvError};
 use async_io::Timer;
 use async_net::TcpStream;
 use futures::future::select;
-use futures::{AsyncReadExt, AsyncWriteExt};
+use futures::{AsyncRead, AsyncReadExt, AsyncWriteExt};
 use futures_lite::FutureExt;
+use isahc::http::header::CONTENT_LENGTH;
 use isahc::http::{HeaderName, HeaderValue};
-use isahc::{
-    config::RedirectPolicy, prelude::*, AsyncReadResponseExt, HttpClient, Request as IsahcRequest,
-};
+use isahc::{config::RedirectPolicy, prelude::*, AsyncBody, HttpClient, Request as IsahcRequest};
 use rfd::{AsyncMessageDialog, MessageButtons, MessageDialog, MessageLevel};
 use ruffle_core::backend::navigator::{
-    async_return, create_fetch_error, create_specific_fetch_error, ErrorResponse, NavigationMethod,
-    NavigatorBackend, OpenURLMode, OwnedFuture, Request, SocketMode, SuccessResponse,
+    async_return, create_fetch_error, create_specific_fetch_error, ChunkStream, ErrorResponse,
+    NavigationMethod, NavigatorBackend, OpenURLMode, OwnedFuture, Request, ResponseBody,
+    SocketMode, SuccessResponse,
 };
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
@@ -22,9 +22,11 @@ use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
 use std::collections::HashSet;
 use std::io;
 use std::io::ErrorKind;
+use std::pin::Pin;
 use std::rc::Rc;
 use std::str::FromStr;
 use std::sync::mpsc::Sender;
+use std::task::{Context, Poll};
 use std::time::Duration;
 use tracing::warn;
 use url::{ParseError, Url};
@@ -228,9 +230,10 @@ impl NavigatorBackend for ExternalNavigatorBackend {
 
                 Ok(SuccessResponse {
                     url: response_url.to_string(),
-                    body,
+                    body: ResponseBody::from_bytes(body),
                     status: 0,
                     redirected: false,
+                    headers: vec![],
                 })
             }),
             _ => Box::pin(async move {
@@ -264,7 +267,7 @@ impl NavigatorBackend for ExternalNavigatorBackend {
                     error: Error::FetchError(e.to_string()),
                 })?;
 
-                let mut response = client.send_async(body).await.map_err(|e| ErrorResponse {
+                let response = client.send_async(body).await.map_err(|e| ErrorResponse {
                     url: processed_url.to_string(),
                     error: Error::FetchError(e.to_string()),
                 })?;
@@ -286,20 +289,31 @@ impl NavigatorBackend for ExternalNavigatorBackend {
                     return Err(ErrorResponse { url, error });
                 }
 
-                let mut body = vec![];
-                response
-                    .copy_to(&mut body)
-                    .await
-                    .map_err(|e| ErrorResponse {
-                        url: url.clone(),
-                        error: Error::FetchError(e.to_string()),
-                    })?;
+                let headers = response
+                    .headers()
+                    .iter()
+                    .filter_map(|(name, value)| {
+                        Some((name.to_string(), value.to_str().ok()?.to_string()))
+                    })
+                    .collect();
+                let expected_length = response
+                    .headers()
+                    .get(CONTENT_LENGTH)
+                    .and_then(|value| value.to_str().ok()?.parse().ok());
+                let body = ResponseBody::from_stream(
+                    expected_length,
+                    IsahcChunkStream {
+                        body: response.into_body(),
+                        buffer: vec![0; 8192],
+                    },
+                );
 
                 Ok(SuccessResponse {
                     url,
                     body,
                     status,
                     redirected,
+                    headers,
                 })
             }),
         }
@@ -485,3 +499,24 @@ impl NavigatorBackend for ExternalNavigatorBackend {
         self.spawn_future(future);
     }
 }
+
+/// Reads the body of an isahc response as it arrives.
+struct IsahcChunkStream {
+    body: AsyncBody,
+    buffer: Vec<u8>,
+}
+
+impl ChunkStream for IsahcChunkStream {
+    fn poll_chunk(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Vec<u8>, Error>>> {
+        let this = self.get_mut();
+        match Pin::new(&mut this.body).poll_read(cx, &mut this.buffer) {
+            Poll::Ready(Ok(0)) => Poll::Ready(None),
+            Poll::Ready(Ok(len)) => Poll::Ready(Some(Ok(this.buffer[..len].to_vec()))),
+            Poll::Ready(Err(e)) => Poll::Ready(Some(Err(Error::FetchError(e.to_string())))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
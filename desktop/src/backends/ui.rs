@@ -1,10 +1,16 @@
 use anyhow::{Context, Error};
 use arboard::Clipboard;
-use rfd::{MessageButtons, MessageDialog, MessageLevel};
-use ruffle_core::backend::navigator::OpenURLMode;
+use chrono::{DateTime, Utc};
+use rfd::{AsyncFileDialog, FileHandle, MessageButtons, MessageDialog, MessageLevel};
+use ruffle_core::backend::navigator::{async_return, OpenURLMode, OwnedFuture};
 use ruffle_core::backend::ui::{
+    FileDialogError, FileDialogFuture, FileDialogResult, FileDialogUnavailable, FileFilter,
     FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
 };
+use std::cell::Cell;
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
 use std::rc::Rc;
 use sys_locale::get_locale;
 use tracing::error;
@@ -18,6 +24,8 @@ pub struct DesktopUiBackend {
     language: LanguageIdentifier,
     preferred_cursor: MouseCursor,
     open_url_mode: OpenURLMode,
+    /// Whether a file dialog is currently open, as only one may be open at a time.
+    file_dialog_open: Rc<Cell<bool>>,
 }
 
 impl DesktopUiBackend {
@@ -33,6 +41,7 @@ impl DesktopUiBackend {
             language,
             preferred_cursor: MouseCursor::Arrow,
             open_url_mode,
+            file_dialog_open: Rc::new(Cell::new(false)),
         })
     }
 
@@ -48,8 +57,81 @@ impl DesktopUiBackend {
             egui::CursorIcon::None
         }
     }
+
+    /// Displays a file dialog, unless one is already open.
+    fn display_file_dialog(
+        &self,
+        dialog: impl FnOnce(AsyncFileDialog) -> PickedFiles,
+    ) -> Result<FileDialogFuture, FileDialogUnavailable> {
+        if self.file_dialog_open.replace(true) {
+            return Err(FileDialogUnavailable::AlreadyOpen);
+        }
+
+        let guard = FileDialogGuard(self.file_dialog_open.clone());
+        let files = dialog(AsyncFileDialog::new().set_parent(&*self.window));
+        Ok(Box::pin(async move {
+            let files = files.await.unwrap_or_default();
+            drop(guard);
+            files
+                .into_iter()
+                .map(|file| {
+                    Box::new(DesktopFileDialogResult {
+                        path: file.path().to_owned(),
+                    }) as Box<dyn FileDialogResult>
+                })
+                .collect()
+        }))
+    }
 }
 
+/// Marks the file dialog as closed once dropped, even if the dialog's future never completes.
+struct FileDialogGuard(Rc<Cell<bool>>);
+
+impl Drop for FileDialogGuard {
+    fn drop(&mut self) {
+        self.0.set(false);
+    }
+}
+
+/// A file on disk that was picked in a file dialog.
+struct DesktopFileDialogResult {
+    path: PathBuf,
+}
+
+impl FileDialogResult for DesktopFileDialogResult {
+    fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    fn size(&self) -> Option<u64> {
+        std::fs::metadata(&self.path).ok().map(|m| m.len())
+    }
+
+    fn creation_time(&self) -> Option<DateTime<Utc>> {
+        let created = std::fs::metadata(&self.path).ok()?.created().ok()?;
+        Some(created.into())
+    }
+
+    fn modification_time(&self) -> Option<DateTime<Utc>> {
+        let modified = std::fs::metadata(&self.path).ok()?.modified().ok()?;
+        Some(modified.into())
+    }
+
+    fn read(&self) -> OwnedFuture<Vec<u8>, FileDialogError> {
+        async_return(std::fs::read(&self.path).map_err(|e| e.to_string().into()))
+    }
+
+    fn write(&self, data: Vec<u8>) -> OwnedFuture<(), FileDialogError> {
+        async_return(std::fs::write(&self.path, data).map_err(|e| e.to_string().into()))
+    }
+}
+
+/// The files picked in an `AsyncFileDialog`, or `None` if it was cancelled.
+type PickedFiles = Pin<Box<dyn Future<Output = Option<Vec<FileHandle>>>>>;
+
 const DOWNLOAD_FAILED_MESSAGE: &str = "Ruffle failed to open or download this file.";
 
 impl UiBackend for DesktopUiBackend {
@@ -147,4 +229,35 @@ impl UiBackend for DesktopUiBackend {
     fn language(&self) -> &LanguageIdentifier {
         &self.language
     }
+
+    fn display_file_open_dialog(
+        &mut self,
+        filters: Vec<FileFilter>,
+        multiple: bool,
+    ) -> Result<FileDialogFuture, FileDialogUnavailable> {
+        self.display_file_dialog(|mut dialog| {
+            for filter in &filters {
+                let extensions: Vec<&str> = filter.extension_list().collect();
+                if !extensions.is_empty() {
+                    dialog = dialog.add_filter(&filter.description, &extensions);
+                }
+            }
+
+            if multiple {
+                Box::pin(dialog.pick_files())
+            } else {
+                Box::pin(async move { dialog.pick_file().await.map(|file| vec![file]) })
+            }
+        })
+    }
+
+    fn display_file_save_dialog(
+        &mut self,
+        file_name: String,
+    ) -> Result<FileDialogFuture, FileDialogUnavailable> {
+        self.display_file_dialog(|dialog| {
+            let dialog = dialog.set_file_name(&file_name);
+            Box::pin(async move { dialog.save_file().await.map(|file| vec![file]) })
+        })
+    }
 }
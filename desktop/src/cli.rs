@@ -2,7 +2,7 @@ use crate::RUFFLE_VERSION;
 use anyhow::Error;
 use clap::Parser;
 use ruffle_core::backend::navigator::{OpenURLMode, SocketMode};
-use ruffle_core::config::Letterbox;
+use ruffle_core::config::{Letterbox, SecurityMode};
 use ruffle_core::{LoadBehavior, StageAlign, StageScaleMode};
 use ruffle_render::quality::StageQuality;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
@@ -111,6 +111,10 @@ pub struct Opt {
     #[clap(long, default_value = "on")]
     pub letterbox: Letterbox,
 
+    /// How strictly to enforce the security sandbox between movies from different domains.
+    #[clap(long, default_value = "permissive")]
+    pub security_mode: SecurityMode,
+
     /// Spoofs the root SWF URL provided to ActionScript.
     #[clap(long, value_parser)]
     pub spoof_url: Option<Url>,
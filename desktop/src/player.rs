@@ -9,7 +9,7 @@ use crate::gui::MovieView;
 use crate::{CALLSTACK, RENDER_INFO, SWF_INFO};
 use anyhow::anyhow;
 use ruffle_core::backend::navigator::{OpenURLMode, SocketMode};
-use ruffle_core::config::Letterbox;
+use ruffle_core::config::{Letterbox, SecurityMode};
 use ruffle_core::{LoadBehavior, Player, PlayerBuilder, PlayerEvent, StageAlign, StageScaleMode};
 use ruffle_render::backend::RenderBackend;
 use ruffle_render::quality::StageQuality;
@@ -43,6 +43,7 @@ pub struct PlayerOptions {
     pub fullscreen: bool,
     pub load_behavior: LoadBehavior,
     pub letterbox: Letterbox,
+    pub security_mode: SecurityMode,
     pub spoof_url: Option<Url>,
     pub player_version: u8,
     pub frame_rate: Option<f64>,
@@ -67,6 +68,7 @@ impl From<&Opt> for PlayerOptions {
             fullscreen: value.fullscreen,
             load_behavior: value.load_behavior,
             letterbox: value.letterbox,
+            security_mode: value.security_mode,
             spoof_url: value.spoof_url.clone(),
             player_version: value.player_version.unwrap_or(32),
             frame_rate: value.frame_rate,
@@ -148,6 +150,7 @@ impl ActivePlayer {
             )
             .with_autoplay(true)
             .with_letterbox(opt.letterbox)
+            .with_security_mode(opt.security_mode)
             .with_max_execution_duration(max_execution_duration)
             .with_quality(opt.quality)
             .with_align(opt.align, opt.force_align)
@@ -0,0 +1,158 @@
+use ruffle_core::backend::audio::{
+    swf, AudioBackend, AudioMixer, DecodeError, RegisterError, SoundHandle, SoundInstanceHandle,
+    SoundStreamInfo, SoundTransform,
+};
+use ruffle_core::impl_audio_mixer_backend;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// An audio backend that mixes down everything the movie plays, one frame at a time,
+/// so that it can be saved next to the captured frames.
+pub struct ExportAudioBackend {
+    mixer: AudioMixer,
+    frame_rate: f64,
+    /// The exact number of sample frames that are due by the end of the current frame.
+    due_sample_frames: f64,
+    mixed_sample_frames: u64,
+    samples: Vec<i16>,
+}
+
+impl ExportAudioBackend {
+    pub const NUM_CHANNELS: u8 = 2;
+    pub const SAMPLE_RATE: u32 = 44100;
+
+    /// The lowest frame rate Flash Player runs at, which movies declaring 0 fps get instead.
+    const MIN_FRAME_RATE: f64 = 0.01;
+
+    pub fn new() -> Self {
+        Self {
+            mixer: AudioMixer::new(Self::NUM_CHANNELS, Self::SAMPLE_RATE),
+            frame_rate: 1.0,
+            due_sample_frames: 0.0,
+            mixed_sample_frames: 0,
+            samples: vec![],
+        }
+    }
+
+    /// Drops the audio captured so far, such as the audio of skipped frames.
+    pub fn discard_samples(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Takes the interleaved samples captured so far.
+    pub fn take_samples(&mut self) -> Vec<i16> {
+        std::mem::take(&mut self.samples)
+    }
+}
+
+impl AudioBackend for ExportAudioBackend {
+    impl_audio_mixer_backend!(mixer);
+    fn play(&mut self) {}
+    fn pause(&mut self) {}
+
+    fn set_frame_rate(&mut self, frame_rate: f64) {
+        // This also catches NaN.
+        self.frame_rate = if frame_rate >= Self::MIN_FRAME_RATE {
+            frame_rate
+        } else {
+            Self::MIN_FRAME_RATE
+        };
+    }
+
+    fn tick(&mut self) {
+        // Mix however many samples are due by the end of this frame, so that rounding
+        // doesn't make the audio drift away from the video over a long export.
+        self.due_sample_frames += Self::SAMPLE_RATE as f64 / self.frame_rate;
+        let due = self.due_sample_frames.round() as u64;
+        let sample_frames = due.saturating_sub(self.mixed_sample_frames) as usize;
+        self.mixed_sample_frames = due;
+
+        let start = self.samples.len();
+        self.samples
+            .resize(start + sample_frames * Self::NUM_CHANNELS as usize, 0);
+        self.mixer.mix::<i16>(&mut self.samples[start..]);
+    }
+}
+
+/// Writes interleaved 16-bit samples as an uncompressed WAV file.
+pub fn write_wav(path: &Path, samples: &[i16]) -> io::Result<()> {
+    let channels = ExportAudioBackend::NUM_CHANNELS as u16;
+    let sample_rate = ExportAudioBackend::SAMPLE_RATE;
+    let block_align = channels * 2;
+    let data_len = (samples.len() * 2) as u32;
+
+    let mut file = BufWriter::new(File::create(path)?);
+    file.write_all(b"RIFF")?;
+    file.write_all(&(36 + data_len).to_le_bytes())?;
+    file.write_all(b"WAVE")?;
+
+    file.write_all(b"fmt ")?;
+    file.write_all(&16u32.to_le_bytes())?;
+    // PCM
+    file.write_all(&1u16.to_le_bytes())?;
+    file.write_all(&channels.to_le_bytes())?;
+    file.write_all(&sample_rate.to_le_bytes())?;
+    file.write_all(&(sample_rate * block_align as u32).to_le_bytes())?;
+    file.write_all(&block_align.to_le_bytes())?;
+    file.write_all(&16u16.to_le_bytes())?;
+
+    file.write_all(b"data")?;
+    file.write_all(&data_len.to_le_bytes())?;
+    for sample in samples {
+        file.write_all(&sample.to_le_bytes())?;
+    }
+
+    file.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExportAudioBackend;
+    use ruffle_core::backend::audio::AudioBackend;
+
+    fn sample_frames_per_tick(audio: &mut ExportAudioBackend, ticks: usize) -> Vec<usize> {
+        (0..ticks)
+            .map(|_| {
+                audio.tick();
+                audio.take_samples().len() / ExportAudioBackend::NUM_CHANNELS as usize
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tick_mixes_one_frame_of_samples() {
+        let mut audio = ExportAudioBackend::new();
+        audio.set_frame_rate(30.0);
+        assert_eq!(sample_frames_per_tick(&mut audio, 3), [1470, 1470, 1470]);
+    }
+
+    #[test]
+    fn tick_spreads_rounding_across_frames() {
+        let mut audio = ExportAudioBackend::new();
+        audio.set_frame_rate(24.0);
+        // 44100 / 24 = 1837.5
+        assert_eq!(
+            sample_frames_per_tick(&mut audio, 4),
+            [1838, 1837, 1838, 1837]
+        );
+    }
+
+    #[test]
+    fn tick_follows_frame_rate_changes() {
+        let mut audio = ExportAudioBackend::new();
+        audio.set_frame_rate(30.0);
+        audio.tick();
+        audio.set_frame_rate(60.0);
+        assert_eq!(sample_frames_per_tick(&mut audio, 2), [735, 735]);
+    }
+
+    #[test]
+    fn tick_with_invalid_frame_rate() {
+        for frame_rate in [0.0, -1.0, f64::NAN] {
+            let mut audio = ExportAudioBackend::new();
+            audio.set_frame_rate(frame_rate);
+            assert_eq!(sample_frames_per_tick(&mut audio, 1), [4_410_000]);
+        }
+    }
+}
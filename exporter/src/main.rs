@@ -1,18 +1,26 @@
+mod audio;
+mod video;
+
+use crate::audio::{write_wav, ExportAudioBackend};
+use crate::video::{write_apng, write_gif};
 use anyhow::{anyhow, Result};
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use image::RgbaImage;
 use indicatif::{ProgressBar, ProgressStyle};
 use rayon::prelude::*;
+use ruffle_core::backend::audio::AudioBackend;
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::PlayerBuilder;
+use ruffle_render_software::SoftwareRenderBackend;
+use ruffle_render_svg::SvgRenderBackend;
 use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use ruffle_render_wgpu::descriptors::Descriptors;
 use ruffle_render_wgpu::target::TextureTarget;
 use ruffle_render_wgpu::wgpu;
-use std::fs::create_dir_all;
-use std::io::{self, Write};
+use std::fs::{create_dir_all, File};
+use std::io::{self, BufWriter, Write};
 use std::panic::catch_unwind;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -33,6 +41,53 @@ struct SizeOpt {
     height: Option<u32>,
 }
 
+#[derive(ValueEnum, Debug, Copy, Clone)]
+enum RendererOpt {
+    /// Render using wgpu, which requires a GPU.
+    Wgpu,
+
+    /// Rasterize on the CPU. This is slower, but works without a GPU.
+    Software,
+}
+
+#[derive(ValueEnum, Debug, Copy, Clone, PartialEq, Eq)]
+enum FormatOpt {
+    /// One PNG file per frame.
+    Png,
+
+    /// A single animated PNG, played back at the movie's frame rate.
+    Apng,
+
+    /// A single animated GIF, played back at the movie's frame rate.
+    Gif,
+
+    /// One SVG file per frame, drawn as vectors. This always uses the SVG renderer.
+    Svg,
+}
+
+impl FormatOpt {
+    /// Whether all frames are written to one file, rather than one file per frame.
+    fn is_animated(self) -> bool {
+        matches!(self, FormatOpt::Apng | FormatOpt::Gif)
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            FormatOpt::Png | FormatOpt::Apng => "png",
+            FormatOpt::Gif => "gif",
+            FormatOpt::Svg => "svg",
+        }
+    }
+}
+
+/// The renderer that every exported movie is drawn with.
+#[derive(Clone)]
+enum ExportRenderer {
+    Wgpu(Arc<Descriptors>),
+    Software,
+    Svg,
+}
+
 #[derive(Parser, Debug)]
 #[clap(name = "Ruffle Exporter", author, version)]
 struct Opt {
@@ -56,6 +111,14 @@ struct Opt {
     #[clap(long = "skipframes", default_value = "0")]
     skipframes: u32,
 
+    /// How to store the captured frames.
+    #[clap(long, value_enum, default_value = "png")]
+    format: FormatOpt,
+
+    /// Also record the movie's audio, mixed down into a WAV file next to the frames.
+    #[clap(long, action)]
+    audio: bool,
+
     /// Don't show a progress bar
     #[clap(short, long, action)]
     silent: bool,
@@ -63,6 +126,10 @@ struct Opt {
     #[clap(flatten)]
     size: SizeOpt,
 
+    /// Which renderer to capture frames with.
+    #[clap(long, value_enum, default_value = "wgpu")]
+    renderer: RendererOpt,
+
     /// Type of graphics backend to use. Not all options may be supported by your current system.
     /// Default will attempt to pick the most supported graphics backend.
     #[clap(long, short, default_value = "default")]
@@ -83,22 +150,110 @@ struct Opt {
     skip_unsupported: bool,
 }
 
+/// The captured frames, either rasterized or as SVG documents.
+enum Frames {
+    Images(Vec<RgbaImage>),
+    Svg(Vec<String>),
+}
+
+impl Frames {
+    fn len(&self) -> usize {
+        match self {
+            Frames::Images(images) => images.len(),
+            Frames::Svg(documents) => documents.len(),
+        }
+    }
+
+    fn images(&self) -> Result<&[RgbaImage]> {
+        match self {
+            Frames::Images(images) => Ok(images),
+            Frames::Svg(_) => Err(anyhow!("SVG frames can't be saved as images")),
+        }
+    }
+}
+
+/// The frames, and optionally the audio, captured from a single movie.
+struct Capture {
+    frames: Frames,
+    frame_rate: f64,
+    /// Interleaved stereo samples, if audio was recorded.
+    audio: Option<Vec<i16>>,
+}
+
+impl Capture {
+    /// Saves the frames to `output`, which is a directory if there's more than
+    /// one PNG or SVG to write. The audio is saved as `audio.wav` in that directory,
+    /// or next to the output file.
+    fn save(&self, output: &Path, format: FormatOpt) -> Result<()> {
+        let is_directory = !format.is_animated() && self.frames.len() != 1;
+
+        match (format, &self.frames) {
+            (FormatOpt::Svg, Frames::Svg(documents)) if is_directory => {
+                for (frame, document) in documents.iter().enumerate() {
+                    let mut path: PathBuf = output.into();
+                    path.push(format!("{frame}.svg"));
+                    std::fs::write(&path, document)?;
+                }
+            }
+            (FormatOpt::Svg, Frames::Svg(documents)) => {
+                std::fs::write(output, documents.get(0).unwrap())?
+            }
+            (FormatOpt::Png, _) if is_directory => {
+                for (frame, image) in self.frames.images()?.iter().enumerate() {
+                    let mut path: PathBuf = output.into();
+                    path.push(format!("{frame}.png"));
+                    image.save(&path)?;
+                }
+            }
+            (FormatOpt::Png, _) => self.frames.images()?.get(0).unwrap().save(output)?,
+            (FormatOpt::Apng, _) => write_apng(
+                BufWriter::new(File::create(output)?),
+                self.frames.images()?,
+                self.frame_rate,
+            )?,
+            (FormatOpt::Gif, _) => write_gif(
+                BufWriter::new(File::create(output)?),
+                self.frames.images()?,
+                self.frame_rate,
+            )?,
+            (FormatOpt::Svg, Frames::Images(_)) => {
+                return Err(anyhow!("Frames must be captured with the SVG renderer"))
+            }
+        }
+
+        if let Some(audio) = &self.audio {
+            let path = if is_directory {
+                output.join("audio.wav")
+            } else {
+                output.with_extension("wav")
+            };
+            write_wav(&path, audio)?;
+        }
+
+        Ok(())
+    }
+}
+
 /// Captures a screenshot. The resulting image uses straight alpha
+#[allow(clippy::too_many_arguments)]
 fn take_screenshot(
-    descriptors: Arc<Descriptors>,
+    renderer: &ExportRenderer,
     swf_path: &Path,
     frames: u32,
     skipframes: u32,
     progress: &Option<ProgressBar>,
     size: SizeOpt,
     skip_unsupported: bool,
-) -> Result<Vec<RgbaImage>> {
+    with_audio: bool,
+) -> Result<Capture> {
     let movie = SwfMovie::from_path(swf_path, None).map_err(|e| anyhow!(e.to_string()))?;
 
     if movie.is_action_script_3() && skip_unsupported {
         return Err(anyhow!("Skipping unsupported movie"));
     }
 
+    let frame_rate = movie.frame_rate().to_f64();
+
     let width = size
         .width
         .map(f64::from)
@@ -111,17 +266,34 @@ fn take_screenshot(
         .unwrap_or_else(|| movie.height().to_pixels());
     let height = (height * size.scale).round() as u32;
 
-    let target = TextureTarget::new(&descriptors.device, (width, height))
-        .map_err(|e| anyhow!(e.to_string()))?;
-    let player = PlayerBuilder::new()
-        .with_renderer(
-            WgpuRenderBackend::new(descriptors, target).map_err(|e| anyhow!(e.to_string()))?,
-        )
+    let builder = match renderer {
+        ExportRenderer::Wgpu(descriptors) => {
+            let target = TextureTarget::new(&descriptors.device, (width, height))
+                .map_err(|e| anyhow!(e.to_string()))?;
+            PlayerBuilder::new().with_renderer(
+                WgpuRenderBackend::new(descriptors.clone(), target)
+                    .map_err(|e| anyhow!(e.to_string()))?,
+            )
+        }
+        ExportRenderer::Software => {
+            PlayerBuilder::new().with_renderer(SoftwareRenderBackend::new(width, height))
+        }
+        ExportRenderer::Svg => {
+            PlayerBuilder::new().with_renderer(SvgRenderBackend::new(width, height))
+        }
+    };
+    let builder = if with_audio {
+        builder.with_audio(ExportAudioBackend::new())
+    } else {
+        builder
+    };
+    let player = builder
         .with_movie(movie)
         .with_viewport_dimensions(width, height, size.scale)
         .build();
 
-    let mut result = Vec::new();
+    let mut images = Vec::new();
+    let mut documents = Vec::new();
     let totalframes = frames + skipframes;
 
     for i in 0..totalframes {
@@ -136,17 +308,55 @@ fn take_screenshot(
         player.lock().unwrap().preload(&mut ExecutionLimit::none());
 
         player.lock().unwrap().run_frame();
-        if i >= skipframes {
+        if with_audio {
+            // `run_frame` doesn't advance audio on its own, unlike `tick`.
+            let mut player = player.lock().unwrap();
+            let audio = player
+                .audio_mut()
+                .downcast_mut::<ExportAudioBackend>()
+                .unwrap();
+            audio.tick();
+            if i < skipframes {
+                audio.discard_samples();
+            }
+        }
+        if i >= skipframes && matches!(renderer, ExportRenderer::Svg) {
             match catch_unwind(|| {
                 player.lock().unwrap().render();
                 let mut player = player.lock().unwrap();
-                let renderer = player
+                player
                     .renderer_mut()
-                    .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-                    .unwrap();
-                renderer.capture_frame()
+                    .downcast_mut::<SvgRenderBackend>()
+                    .unwrap()
+                    .capture_frame()
             }) {
-                Ok(Some(image)) => result.push(image),
+                Ok(Some(document)) => documents.push(document),
+                Ok(None) => return Err(anyhow!("Unable to capture frame {} of {:?}", i, swf_path)),
+                Err(e) => {
+                    return Err(anyhow!(
+                        "Unable to capture frame {} of {:?}: {:?}",
+                        i,
+                        swf_path,
+                        e
+                    ))
+                }
+            }
+        } else if i >= skipframes {
+            match catch_unwind(|| {
+                player.lock().unwrap().render();
+                let mut player = player.lock().unwrap();
+                let renderer = player.renderer_mut();
+                if let Some(renderer) = renderer.downcast_mut::<WgpuRenderBackend<TextureTarget>>()
+                {
+                    renderer.capture_frame()
+                } else {
+                    renderer
+                        .downcast_mut::<SoftwareRenderBackend>()
+                        .unwrap()
+                        .capture_frame()
+                }
+            }) {
+                Ok(Some(image)) => images.push(image),
                 Ok(None) => return Err(anyhow!("Unable to capture frame {} of {:?}", i, swf_path)),
                 Err(e) => {
                     return Err(anyhow!(
@@ -163,7 +373,29 @@ fn take_screenshot(
             progress.inc(1);
         }
     }
-    Ok(result)
+
+    let audio = if with_audio {
+        let mut player = player.lock().unwrap();
+        let audio = player
+            .audio_mut()
+            .downcast_mut::<ExportAudioBackend>()
+            .unwrap();
+        Some(audio.take_samples())
+    } else {
+        None
+    };
+
+    let frames = if matches!(renderer, ExportRenderer::Svg) {
+        Frames::Svg(documents)
+    } else {
+        Frames::Images(images)
+    };
+
+    Ok(Capture {
+        frames,
+        frame_rate,
+        audio,
+    })
 }
 
 fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
@@ -196,17 +428,17 @@ fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
     results
 }
 
-fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_single_swf(renderer: ExportRenderer, opt: &Opt) -> Result<()> {
     let output = opt.output_path.clone().unwrap_or_else(|| {
         let mut result = PathBuf::new();
         result.set_file_name(opt.swf.file_stem().unwrap());
-        if opt.frames == 1 {
-            result.set_extension("png");
+        if opt.frames == 1 || opt.format.is_animated() {
+            result.set_extension(opt.format.extension());
         }
         result
     });
 
-    if opt.frames > 1 {
+    if opt.frames > 1 && !opt.format.is_animated() {
         let _ = create_dir_all(&output);
     }
 
@@ -224,42 +456,56 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
         None
     };
 
-    let frames = take_screenshot(
-        descriptors,
+    let capture = take_screenshot(
+        &renderer,
         &opt.swf,
         opt.frames,
         opt.skipframes,
         &progress,
         opt.size,
         opt.skip_unsupported,
+        opt.audio,
     )?;
+    let frames = &capture.frames;
 
     if let Some(progress) = &progress {
         progress.set_message(opt.swf.file_stem().unwrap().to_string_lossy().into_owned());
     }
 
-    if frames.len() == 1 {
-        let image = frames.get(0).unwrap();
-        if opt.output_path == Some(PathBuf::from("-")) {
-            let mut bytes: Vec<u8> = Vec::new();
-            image
-                .write_to(
-                    &mut io::Cursor::new(&mut bytes),
-                    image::ImageOutputFormat::Png,
-                )
-                .expect("Encoding failed");
-            io::stdout()
-                .write_all(bytes.as_slice())
-                .expect("Writing to stdout failed");
-        } else {
-            image.save(&output)?;
+    if opt.output_path == Some(PathBuf::from("-"))
+        && (frames.len() == 1 || opt.format.is_animated())
+    {
+        if capture.audio.is_some() {
+            return Err(anyhow!("Audio can't be written to stdout"));
         }
-    } else {
-        for (frame, image) in frames.iter().enumerate() {
-            let mut path: PathBuf = (&output).into();
-            path.push(format!("{frame}.png"));
-            image.save(&path)?;
+        match opt.format {
+            FormatOpt::Png => {
+                let image = frames.images()?.get(0).unwrap();
+                let mut bytes: Vec<u8> = Vec::new();
+                image
+                    .write_to(
+                        &mut io::Cursor::new(&mut bytes),
+                        image::ImageOutputFormat::Png,
+                    )
+                    .expect("Encoding failed");
+                io::stdout()
+                    .write_all(bytes.as_slice())
+                    .expect("Writing to stdout failed");
+            }
+            FormatOpt::Apng => {
+                write_apng(io::stdout().lock(), frames.images()?, capture.frame_rate)?
+            }
+            FormatOpt::Gif => write_gif(io::stdout().lock(), frames.images()?, capture.frame_rate)?,
+            FormatOpt::Svg => {
+                if let Frames::Svg(documents) = frames {
+                    io::stdout()
+                        .write_all(documents.get(0).unwrap().as_bytes())
+                        .expect("Writing to stdout failed");
+                }
+            }
         }
+    } else {
+        capture.save(&output, opt.format)?;
     }
 
     let message = if frames.len() == 1 {
@@ -293,7 +539,7 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
 }
 
 #[allow(clippy::branches_sharing_code)]
-fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_multiple_swfs(renderer: ExportRenderer, opt: &Opt) -> Result<()> {
     let output = opt.output_path.clone().unwrap();
     let files = find_files(&opt.swf, !opt.silent);
 
@@ -321,14 +567,15 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
                     .into_owned(),
             );
         }
-        if let Ok(frames) = take_screenshot(
-            descriptors.clone(),
+        if let Ok(capture) = take_screenshot(
+            &renderer,
             file.path(),
             opt.frames,
             opt.skipframes,
             &progress,
             opt.size,
             opt.skip_unsupported,
+            opt.audio,
         ) {
             let mut relative_path = file
                 .path()
@@ -336,25 +583,19 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
                 .unwrap_or_else(|_| file.path())
                 .to_path_buf();
 
-            if frames.len() == 1 {
-                let mut destination: PathBuf = (&output).into();
-                relative_path.set_extension("png");
+            let mut destination: PathBuf = (&output).into();
+            if capture.frames.len() == 1 || opt.format.is_animated() {
+                relative_path.set_extension(opt.format.extension());
                 destination.push(relative_path);
                 if let Some(parent) = destination.parent() {
                     let _ = create_dir_all(parent);
                 }
-                frames.get(0).unwrap().save(&destination)?;
             } else {
-                let mut parent: PathBuf = (&output).into();
                 relative_path.set_extension("");
-                parent.push(&relative_path);
-                let _ = create_dir_all(&parent);
-                for (frame, image) in frames.iter().enumerate() {
-                    let mut destination = parent.clone();
-                    destination.push(format!("{frame}.png"));
-                    image.save(&destination)?;
-                }
+                destination.push(&relative_path);
+                let _ = create_dir_all(&destination);
             }
+            capture.save(&destination, opt.format)?;
         }
 
         Ok(())
@@ -401,27 +642,33 @@ fn trace_path(_opt: &Opt) -> Option<&Path> {
 
 fn main() -> Result<()> {
     let opt: Opt = Opt::parse();
-    let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
-        backends: opt.graphics.into(),
-        dx12_shader_compiler: wgpu::Dx12Compiler::default(),
-    });
-    let (adapter, device, queue) = futures::executor::block_on(request_adapter_and_device(
-        opt.graphics.into(),
-        &instance,
-        None,
-        opt.power.into(),
-        trace_path(&opt),
-    ))
-    .map_err(|e| anyhow!(e.to_string()))?;
+    let renderer = match opt.renderer {
+        _ if opt.format == FormatOpt::Svg => ExportRenderer::Svg,
+        RendererOpt::Wgpu => {
+            let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+                backends: opt.graphics.into(),
+                dx12_shader_compiler: wgpu::Dx12Compiler::default(),
+            });
+            let (adapter, device, queue) = futures::executor::block_on(request_adapter_and_device(
+                opt.graphics.into(),
+                &instance,
+                None,
+                opt.power.into(),
+                trace_path(&opt),
+            ))
+            .map_err(|e| anyhow!(e.to_string()))?;
 
-    let descriptors = Arc::new(Descriptors::new(instance, adapter, device, queue));
+            ExportRenderer::Wgpu(Arc::new(Descriptors::new(instance, adapter, device, queue)))
+        }
+        RendererOpt::Software => ExportRenderer::Software,
+    };
 
     if opt.swf.is_file() {
-        capture_single_swf(descriptors, &opt)?;
+        capture_single_swf(renderer, &opt)?;
     } else if !opt.swf.is_dir() {
         return Err(anyhow!("Given path is not a file or directory."));
     } else if opt.output_path.is_some() {
-        capture_multiple_swfs(descriptors, &opt)?;
+        capture_multiple_swfs(renderer, &opt)?;
     } else {
         return Err(anyhow!(
             "Output directory is required when exporting multiple files."
@@ -0,0 +1,51 @@
+use anyhow::{anyhow, Result};
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, RgbaImage};
+use std::io::Write;
+
+/// The delay between two frames, as a fraction of a second that stays exact
+/// for frame rates with up to two decimal places (such as 29.97).
+fn frame_delay(frame_rate: f64) -> (u16, u16) {
+    let denominator = (frame_rate * 100.0).round().clamp(1.0, u16::MAX as f64) as u16;
+    (100, denominator)
+}
+
+/// Encodes the frames as an animated PNG that loops forever.
+pub fn write_apng<W: Write>(output: W, frames: &[RgbaImage], frame_rate: f64) -> Result<()> {
+    let first = frames
+        .first()
+        .ok_or_else(|| anyhow!("No frames to encode"))?;
+
+    let mut encoder = png::Encoder::new(output, first.width(), first.height());
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_animated(frames.len() as u32, 0)?;
+    let (numerator, denominator) = frame_delay(frame_rate);
+    encoder.set_frame_delay(numerator, denominator)?;
+
+    let mut writer = encoder.write_header()?;
+    for frame in frames {
+        writer.write_image_data(frame.as_raw())?;
+    }
+    writer.finish()?;
+
+    Ok(())
+}
+
+/// Encodes the frames as an animated GIF that loops forever.
+pub fn write_gif<W: Write>(output: W, frames: &[RgbaImage], frame_rate: f64) -> Result<()> {
+    let (numerator, denominator) = frame_delay(frame_rate);
+    let delay = Delay::from_numer_denom_ms(numerator as u32 * 1000, denominator as u32);
+
+    // Speed 10 is the quantizer's recommended trade-off; the default of 1 is very slow for
+    // anything longer than a few frames.
+    let mut encoder = GifEncoder::new_with_speed(output, 10);
+    encoder.set_repeat(Repeat::Infinite)?;
+    encoder.encode_frames(
+        frames
+            .iter()
+            .map(|image| Frame::from_parts(image.clone(), 0, 0, delay)),
+    )?;
+
+    Ok(())
+}
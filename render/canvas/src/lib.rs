@@ -11,6 +11,7 @@ use ruffle_render::bitmap::{
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::error::Error;
 use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::shape_utils::{DistilledShape, DrawCommand, LineScaleMode, LineScales};
 use ruffle_render::transform::Transform;
@@ -806,6 +807,20 @@ impl CommandHandler for WebCanvasRenderBackend {
         commands.execute(self);
         self.pop_blend_mode();
     }
+
+    fn render_projected(
+        &mut self,
+        commands: CommandList,
+        width: u32,
+        height: u32,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+    ) {
+        // Perspective isn't supported here, so approximate the projection with a 2D matrix.
+        if let Some(matrix) = matrix.affine_approximation(f64::from(width), f64::from(height)) {
+            commands.transformed(matrix, color_transform).execute(self);
+        }
+    }
 }
 
 /// Convert a series of `DrawCommands` to a `Path2d` shape.
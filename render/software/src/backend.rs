@@ -0,0 +1,237 @@
+use crate::bitmap::{as_bitmap, SoftwareBitmap, Texture};
+use crate::mesh::{Draw, Mesh};
+use crate::rasterizer::SamplePattern;
+use crate::surface::Surface;
+use ruffle_render::backend::{
+    BitmapCacheEntry, Context3D, RenderBackend, ShapeHandle, ViewportDimensions,
+};
+use ruffle_render::bitmap::{Bitmap, BitmapHandle, BitmapSource, PixelRegion, SyncHandle};
+use ruffle_render::commands::CommandList;
+use ruffle_render::error::Error;
+use ruffle_render::pixel_bender::{
+    PixelBenderShader, PixelBenderShaderArgument, PixelBenderShaderHandle,
+};
+use ruffle_render::quality::StageQuality;
+use ruffle_render::shape_utils::DistilledShape;
+use ruffle_render::tessellator::ShapeTessellator;
+use std::borrow::Cow;
+use std::sync::Arc;
+use swf::Color;
+
+pub struct SoftwareRenderBackend {
+    viewport_dimensions: ViewportDimensions,
+    target: Texture,
+    quality: StageQuality,
+    shape_tessellator: ShapeTessellator,
+    warned_about_filters: bool,
+}
+
+impl SoftwareRenderBackend {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            viewport_dimensions: ViewportDimensions {
+                width,
+                height,
+                scale_factor: 1.0,
+            },
+            target: Texture::new(width, height),
+            quality: StageQuality::Low,
+            shape_tessellator: ShapeTessellator::new(),
+            warned_about_filters: false,
+        }
+    }
+
+    /// Returns the last rendered frame, using straight alpha.
+    pub fn capture_frame(&self) -> Option<image::RgbaImage> {
+        let region = PixelRegion::for_whole_size(self.target.width(), self.target.height());
+        let (mut bytes, _) = self.target.to_rgba(region);
+        ruffle_render::utils::unmultiply_alpha_rgba(&mut bytes);
+        image::RgbaImage::from_raw(self.target.width(), self.target.height(), bytes)
+    }
+
+    /// Renders `commands` on top of the contents of the given bitmap.
+    fn render_to_bitmap(
+        &self,
+        handle: &BitmapHandle,
+        commands: CommandList,
+        quality: StageQuality,
+    ) {
+        let bitmap = as_bitmap(handle);
+        // Move the texture out while drawing, in case the commands draw the bitmap onto itself.
+        let mut texture = std::mem::replace(&mut *bitmap.texture_mut(), Texture::new(0, 0));
+        let pattern = SamplePattern::new(quality.sample_count());
+        Surface::new(&mut texture, &pattern).draw_commands(commands);
+        *bitmap.texture_mut() = texture;
+    }
+}
+
+#[derive(Debug)]
+struct SoftwareSyncHandle {
+    data: Vec<u8>,
+    bytes_per_row: u32,
+}
+
+impl SyncHandle for SoftwareSyncHandle {
+    fn retrieve_offscreen_texture(
+        self: Box<Self>,
+        with_rgba: ruffle_render::bitmap::RgbaBufRead,
+    ) -> Result<(), Error> {
+        with_rgba(&self.data, self.bytes_per_row);
+        Ok(())
+    }
+}
+
+impl RenderBackend for SoftwareRenderBackend {
+    fn viewport_dimensions(&self) -> ViewportDimensions {
+        self.viewport_dimensions
+    }
+
+    fn set_viewport_dimensions(&mut self, dimensions: ViewportDimensions) {
+        self.viewport_dimensions = dimensions;
+        self.target = Texture::new(dimensions.width, dimensions.height);
+    }
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
+        let lyon_mesh = self
+            .shape_tessellator
+            .tessellate_shape(shape, bitmap_source);
+        let draws = lyon_mesh
+            .into_iter()
+            .filter_map(|draw| Draw::new(self, bitmap_source, draw))
+            .collect();
+        ShapeHandle(Arc::new(Mesh { draws }))
+    }
+
+    fn render_offscreen(
+        &mut self,
+        handle: BitmapHandle,
+        commands: CommandList,
+        quality: StageQuality,
+        bounds: PixelRegion,
+    ) -> Option<Box<dyn SyncHandle>> {
+        self.render_to_bitmap(&handle, commands, quality);
+        let (data, bytes_per_row) = as_bitmap(&handle).texture().to_rgba(bounds);
+        Some(Box::new(SoftwareSyncHandle {
+            data,
+            bytes_per_row,
+        }))
+    }
+
+    fn is_offscreen_supported(&self) -> bool {
+        true
+    }
+
+    fn submit_frame(
+        &mut self,
+        clear: Color,
+        commands: CommandList,
+        cache_entries: Vec<BitmapCacheEntry>,
+    ) {
+        for entry in cache_entries {
+            if !entry.filters.is_empty() && !self.warned_about_filters {
+                tracing::warn!(
+                    "Filters on cached bitmaps are not supported by the software renderer"
+                );
+                self.warned_about_filters = true;
+            }
+            as_bitmap(&entry.handle)
+                .texture_mut()
+                .fill(premultiplied(entry.clear));
+            self.render_to_bitmap(&entry.handle, entry.commands, self.quality);
+        }
+
+        self.target.fill(premultiplied(clear));
+        let pattern = SamplePattern::new(self.quality.sample_count());
+        Surface::new(&mut self.target, &pattern).draw_commands(commands);
+    }
+
+    fn create_empty_texture(&mut self, width: u32, height: u32) -> Result<BitmapHandle, Error> {
+        if width == 0 || height == 0 {
+            return Err(Error::InvalidSize);
+        }
+        Ok(BitmapHandle(Arc::new(SoftwareBitmap::new(Texture::new(
+            width, height,
+        )))))
+    }
+
+    fn register_bitmap(&mut self, bitmap: Bitmap) -> Result<BitmapHandle, Error> {
+        if bitmap.width() == 0 || bitmap.height() == 0 {
+            return Err(Error::InvalidSize);
+        }
+        Ok(BitmapHandle(Arc::new(SoftwareBitmap::new(
+            Texture::from_bitmap(bitmap),
+        ))))
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: &BitmapHandle,
+        bitmap: Bitmap,
+        region: PixelRegion,
+    ) -> Result<(), Error> {
+        as_bitmap(handle).texture_mut().update(bitmap, region);
+        Ok(())
+    }
+
+    fn create_context3d(&mut self) -> Result<Box<dyn Context3D>, Error> {
+        Err(Error::Unimplemented("createContext3D".into()))
+    }
+
+    fn context3d_present(&mut self, _context: &mut dyn Context3D) -> Result<(), Error> {
+        Err(Error::Unimplemented("Context3D.present".into()))
+    }
+
+    fn debug_info(&self) -> Cow<'static, str> {
+        let result = [
+            "Renderer: software".to_string(),
+            format!("Surface quality: {}", self.quality),
+            format!("Surface samples: {}", self.quality.sample_count()),
+            format!(
+                "Surface size: {}x{}",
+                self.target.width(),
+                self.target.height()
+            ),
+        ];
+        Cow::Owned(result.join("\n"))
+    }
+
+    fn name(&self) -> &'static str {
+        "software"
+    }
+
+    fn set_quality(&mut self, quality: StageQuality) {
+        self.quality = quality;
+    }
+
+    fn compile_pixelbender_shader(
+        &mut self,
+        _shader: PixelBenderShader,
+    ) -> Result<PixelBenderShaderHandle, Error> {
+        Err(Error::Unimplemented(
+            "Pixel bender shader compilation".into(),
+        ))
+    }
+
+    fn run_pixelbender_shader(
+        &mut self,
+        _handle: PixelBenderShaderHandle,
+        _arguments: &[PixelBenderShaderArgument],
+        _target: BitmapHandle,
+    ) -> Result<Box<dyn SyncHandle>, Error> {
+        Err(Error::Unimplemented("Pixel bender shader".into()))
+    }
+}
+
+fn premultiplied(color: Color) -> [f32; 4] {
+    let a = f32::from(color.a) / 255.0;
+    [
+        f32::from(color.r) / 255.0 * a,
+        f32::from(color.g) / 255.0 * a,
+        f32::from(color.b) / 255.0 * a,
+        a,
+    ]
+}
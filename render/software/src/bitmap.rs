@@ -0,0 +1,181 @@
+use ruffle_render::bitmap::{Bitmap, BitmapHandle, BitmapHandleImpl, PixelRegion};
+use std::cell::{Ref, RefCell, RefMut};
+
+/// A single pixel, as premultiplied RGBA with each channel in the range [0.0, 1.0].
+pub type Pixel = [f32; 4];
+
+pub const TRANSPARENT: Pixel = [0.0; 4];
+
+/// A block of premultiplied pixels that can be drawn to and sampled from.
+#[derive(Clone, Debug)]
+pub struct Texture {
+    width: u32,
+    height: u32,
+    pixels: Vec<Pixel>,
+}
+
+impl Texture {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![TRANSPARENT; width as usize * height as usize],
+        }
+    }
+
+    pub fn from_bitmap(bitmap: Bitmap) -> Self {
+        let mut texture = Self::new(bitmap.width(), bitmap.height());
+        texture.update(
+            bitmap,
+            PixelRegion::for_whole_size(texture.width, texture.height),
+        );
+        texture
+    }
+
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    #[inline]
+    pub fn pixels(&self) -> &[Pixel] {
+        &self.pixels
+    }
+
+    #[inline]
+    pub fn pixels_mut(&mut self) -> &mut [Pixel] {
+        &mut self.pixels
+    }
+
+    #[inline]
+    pub fn get(&self, x: u32, y: u32) -> Pixel {
+        self.pixels[(y * self.width + x) as usize]
+    }
+
+    pub fn fill(&mut self, color: Pixel) {
+        self.pixels.fill(color);
+    }
+
+    /// Copies the given region of `bitmap` into the same region of this texture.
+    pub fn update(&mut self, bitmap: Bitmap, mut region: PixelRegion) {
+        region.clamp(
+            self.width.min(bitmap.width()),
+            self.height.min(bitmap.height()),
+        );
+        let bitmap = bitmap.to_rgba();
+        let data = bitmap.data();
+        for y in region.y_min..region.y_max {
+            for x in region.x_min..region.x_max {
+                let src = (y * bitmap.width() + x) as usize * 4;
+                let rgba = &data[src..src + 4];
+                self.pixels[(y * self.width + x) as usize] = [
+                    f32::from(rgba[0]) / 255.0,
+                    f32::from(rgba[1]) / 255.0,
+                    f32::from(rgba[2]) / 255.0,
+                    f32::from(rgba[3]) / 255.0,
+                ];
+            }
+        }
+    }
+
+    /// Returns the given region as premultiplied RGBA bytes, along with the bytes per row.
+    pub fn to_rgba(&self, mut region: PixelRegion) -> (Vec<u8>, u32) {
+        region.clamp(self.width, self.height);
+        let mut data = Vec::with_capacity(region.width() as usize * region.height() as usize * 4);
+        for y in region.y_min..region.y_max {
+            for x in region.x_min..region.x_max {
+                data.extend(self.get(x, y).map(to_byte));
+            }
+        }
+        (data, region.width() * 4)
+    }
+
+    /// Samples this texture at the given normalized coordinates.
+    ///
+    /// This mirrors how the GPU backends sample: nearest-neighbour picks the texel the
+    /// coordinate falls into, and smoothing interpolates between the four nearest texel centers.
+    pub fn sample(&self, u: f32, v: f32, smoothed: bool, repeating: bool) -> Pixel {
+        if self.width == 0 || self.height == 0 {
+            return TRANSPARENT;
+        }
+        let x = u * self.width as f32;
+        let y = v * self.height as f32;
+        if !smoothed {
+            let x = self.wrap(x.floor() as i32, self.width, repeating);
+            let y = self.wrap(y.floor() as i32, self.height, repeating);
+            return self.get(x, y);
+        }
+
+        let x = x - 0.5;
+        let y = y - 0.5;
+        let x0 = x.floor();
+        let y0 = y.floor();
+        let fx = x - x0;
+        let fy = y - y0;
+        let x0 = x0 as i32;
+        let y0 = y0 as i32;
+        let (xa, xb) = (
+            self.wrap(x0, self.width, repeating),
+            self.wrap(x0 + 1, self.width, repeating),
+        );
+        let (ya, yb) = (
+            self.wrap(y0, self.height, repeating),
+            self.wrap(y0 + 1, self.height, repeating),
+        );
+        let top = lerp_pixel(self.get(xa, ya), self.get(xb, ya), fx);
+        let bottom = lerp_pixel(self.get(xa, yb), self.get(xb, yb), fx);
+        lerp_pixel(top, bottom, fy)
+    }
+
+    fn wrap(&self, coord: i32, size: u32, repeating: bool) -> u32 {
+        if repeating {
+            coord.rem_euclid(size as i32) as u32
+        } else {
+            coord.clamp(0, size as i32 - 1) as u32
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SoftwareBitmap(RefCell<Texture>);
+
+impl BitmapHandleImpl for SoftwareBitmap {}
+
+impl SoftwareBitmap {
+    pub fn new(texture: Texture) -> Self {
+        Self(RefCell::new(texture))
+    }
+
+    pub fn texture(&self) -> Ref<'_, Texture> {
+        self.0.borrow()
+    }
+
+    pub fn texture_mut(&self) -> RefMut<'_, Texture> {
+        self.0.borrow_mut()
+    }
+}
+
+pub fn as_bitmap(handle: &BitmapHandle) -> &SoftwareBitmap {
+    <dyn BitmapHandleImpl>::downcast_ref(&*handle.0)
+        .expect("Bitmap handle must be a software bitmap")
+}
+
+#[inline]
+pub fn lerp_pixel(a: Pixel, b: Pixel, t: f32) -> Pixel {
+    [
+        a[0] + (b[0] - a[0]) * t,
+        a[1] + (b[1] - a[1]) * t,
+        a[2] + (b[2] - a[2]) * t,
+        a[3] + (b[3] - a[3]) * t,
+    ]
+}
+
+#[inline]
+pub fn to_byte(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
@@ -0,0 +1,16 @@
+//! A render backend that rasterizes everything on the CPU.
+//!
+//! This is much slower than the GPU backends, but doesn't need a GPU (or any graphics API)
+//! to be available, which makes it useful for headless tools and testing.
+
+#![deny(clippy::unwrap_used)]
+// Bitmap handles need interior mutability, but are shared through an `Arc`
+#![allow(clippy::arc_with_non_send_sync)]
+
+pub mod backend;
+mod bitmap;
+mod mesh;
+mod rasterizer;
+mod surface;
+
+pub use backend::SoftwareRenderBackend;
@@ -0,0 +1,229 @@
+use crate::bitmap::{lerp_pixel, Pixel};
+use ruffle_render::backend::{RenderBackend, ShapeHandle, ShapeHandleImpl};
+use ruffle_render::bitmap::{BitmapHandle, BitmapSource};
+use ruffle_render::shape_utils::GradientType;
+use ruffle_render::tessellator::{Draw as LyonDraw, DrawType as TessDrawType, Gradient};
+use swf::{GradientInterpolation, GradientSpread};
+
+/// How many entries to use for gradient lookup tables, matching the GPU backends.
+const GRADIENT_SIZE: usize = 256;
+
+#[derive(Debug)]
+pub struct Mesh {
+    pub draws: Vec<Draw>,
+}
+
+impl ShapeHandleImpl for Mesh {}
+
+pub fn as_mesh(handle: &ShapeHandle) -> &Mesh {
+    <dyn ShapeHandleImpl>::downcast_ref(&*handle.0).expect("Shape handle must be a software mesh")
+}
+
+#[derive(Debug)]
+pub struct Draw {
+    pub paint: Paint,
+    /// Vertex positions, in object space pixels.
+    pub positions: Vec<[f32; 2]>,
+    /// Unmultiplied vertex colors. Only used by `Paint::Color`.
+    pub colors: Vec<[f32; 4]>,
+    pub indices: Vec<u32>,
+    pub mask_index_count: u32,
+}
+
+impl Draw {
+    pub fn new(
+        backend: &mut dyn RenderBackend,
+        source: &dyn BitmapSource,
+        draw: LyonDraw,
+    ) -> Option<Self> {
+        let paint = match draw.draw_type {
+            TessDrawType::Color => Paint::Color,
+            TessDrawType::Gradient(gradient) => Paint::Gradient(GradientPaint::new(gradient)),
+            TessDrawType::Bitmap(bitmap) => {
+                let handle = source.bitmap_handle(bitmap.bitmap_id, backend)?;
+                Paint::Bitmap {
+                    handle,
+                    matrix: bitmap.matrix,
+                    is_smoothed: bitmap.is_smoothed,
+                    is_repeating: bitmap.is_repeating,
+                }
+            }
+        };
+        let positions = draw.vertices.iter().map(|v| [v.x, v.y]).collect();
+        let colors = draw
+            .vertices
+            .iter()
+            .map(|v| {
+                [
+                    f32::from(v.color.r) / 255.0,
+                    f32::from(v.color.g) / 255.0,
+                    f32::from(v.color.b) / 255.0,
+                    f32::from(v.color.a) / 255.0,
+                ]
+            })
+            .collect();
+        Some(Self {
+            paint,
+            positions,
+            colors,
+            indices: draw.indices,
+            mask_index_count: draw.mask_index_count,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum Paint {
+    /// Interpolated vertex colors.
+    Color,
+    Gradient(GradientPaint),
+    Bitmap {
+        handle: BitmapHandle,
+        /// Maps object space pixels to normalized texture coordinates (column-major).
+        matrix: [[f32; 3]; 3],
+        is_smoothed: bool,
+        is_repeating: bool,
+    },
+}
+
+#[derive(Debug)]
+pub struct GradientPaint {
+    /// Maps object space pixels to gradient space (column-major).
+    pub matrix: [[f32; 3]; 3],
+    pub gradient_type: GradientType,
+    pub spread: GradientSpread,
+    pub focal_point: f32,
+    pub is_linear_rgb: bool,
+    /// Unmultiplied colors; in linear RGB space if `is_linear_rgb` is set.
+    pub colors: Vec<Pixel>,
+}
+
+impl GradientPaint {
+    fn new(gradient: Gradient) -> Self {
+        let is_linear_rgb = gradient.interpolation == GradientInterpolation::LinearRgb;
+        let convert = |c: u8| {
+            let c = f32::from(c) / 255.0;
+            if is_linear_rgb {
+                srgb_to_linear(c)
+            } else {
+                c
+            }
+        };
+
+        let mut colors = vec![[0.0; 4]; GRADIENT_SIZE];
+        if !gradient.records.is_empty() {
+            for (t, color) in colors.iter_mut().enumerate() {
+                let mut last = 0;
+                let mut next = 0;
+                for (i, record) in gradient.records.iter().enumerate().rev() {
+                    if (record.ratio as usize) < t {
+                        last = i;
+                        next = (i + 1).min(gradient.records.len() - 1);
+                        break;
+                    }
+                }
+
+                let last_record = &gradient.records[last];
+                let next_record = &gradient.records[next];
+                let a = if next == last {
+                    // Before the first gradient record, or after the last one.
+                    0.0
+                } else {
+                    (t as f32 - f32::from(last_record.ratio))
+                        / (f32::from(next_record.ratio) - f32::from(last_record.ratio))
+                };
+                let lerp = |from: f32, to: f32| from + (to - from) * a;
+                *color = [
+                    lerp(convert(last_record.color.r), convert(next_record.color.r)),
+                    lerp(convert(last_record.color.g), convert(next_record.color.g)),
+                    lerp(convert(last_record.color.b), convert(next_record.color.b)),
+                    lerp(
+                        f32::from(last_record.color.a) / 255.0,
+                        f32::from(next_record.color.a) / 255.0,
+                    ),
+                ];
+            }
+        }
+
+        Self {
+            matrix: gradient.matrix,
+            gradient_type: gradient.gradient_type,
+            spread: gradient.repeat_mode,
+            focal_point: f32::from(gradient.focal_point).clamp(-0.98, 0.98),
+            is_linear_rgb,
+            colors,
+        }
+    }
+
+    /// Returns the unmultiplied sRGB color at the given gradient space coordinates.
+    pub fn color_at(&self, u: f32, v: f32) -> Pixel {
+        let t = match self.gradient_type {
+            GradientType::Linear => u,
+            GradientType::Radial => {
+                let (x, y) = (u * 2.0 - 1.0, v * 2.0 - 1.0);
+                (x * x + y * y).sqrt()
+            }
+            GradientType::Focal => {
+                let f = self.focal_point;
+                let (x, y) = (u * 2.0 - 1.0, v * 2.0 - 1.0);
+                let (dx, dy) = (f - x, -y);
+                let l = (dx * dx + dy * dy).sqrt();
+                if l == 0.0 {
+                    0.0
+                } else {
+                    let (dx, dy) = (dx / l, dy / l);
+                    l / ((1.0 - f * f * dy * dy).sqrt() + f * dx)
+                }
+            }
+        };
+        let t = match self.spread {
+            GradientSpread::Pad => t.clamp(0.0, 1.0),
+            GradientSpread::Reflect => {
+                let t = t.abs();
+                if (t as i32) & 1 == 0 {
+                    t.fract()
+                } else {
+                    1.0 - t.fract()
+                }
+            }
+            GradientSpread::Repeat => t - t.floor(),
+        };
+        if !t.is_finite() {
+            return [0.0; 4];
+        }
+
+        // Linearly sample the lookup table, as a GPU texture sampler would.
+        let x = (t * GRADIENT_SIZE as f32 - 0.5).clamp(0.0, (GRADIENT_SIZE - 1) as f32);
+        let i = x.floor() as usize;
+        let j = (i + 1).min(GRADIENT_SIZE - 1);
+        let color = lerp_pixel(self.colors[i], self.colors[j], x.fract());
+        if self.is_linear_rgb {
+            [
+                linear_to_srgb(color[0]),
+                linear_to_srgb(color[1]),
+                linear_to_srgb(color[2]),
+                color[3],
+            ]
+        } else {
+            color
+        }
+    }
+}
+
+/// Converts a color channel from sRGB space to linear color space.
+fn srgb_to_linear(color: f32) -> f32 {
+    if color <= 0.04045 {
+        color / 12.92
+    } else {
+        f32::powf((color + 0.055) / 1.055, 2.4)
+    }
+}
+
+/// Converts a color channel from linear color space to sRGB space.
+fn linear_to_srgb(color: f32) -> f32 {
+    if color < 0.0031308 {
+        color * 12.92
+    } else {
+        1.055 * color.powf(1.0 / 2.4) - 0.055
+    }
+}
@@ -0,0 +1,252 @@
+/// The positions within a pixel that are tested for coverage, as used for anti-aliasing.
+#[derive(Clone, Debug)]
+pub struct SamplePattern {
+    offsets: Vec<[f32; 2]>,
+}
+
+impl SamplePattern {
+    /// Creates an evenly spaced grid of (at most 16) samples.
+    pub fn new(sample_count: u32) -> Self {
+        let (columns, rows) = match sample_count {
+            0 | 1 => (1, 1),
+            2 | 3 => (2, 1),
+            4..=7 => (2, 2),
+            8..=15 => (4, 2),
+            _ => (4, 4),
+        };
+        let mut offsets = Vec::with_capacity(columns * rows);
+        for row in 0..rows {
+            for column in 0..columns {
+                offsets.push([
+                    (column as f32 + 0.5) / columns as f32,
+                    (row as f32 + 0.5) / rows as f32,
+                ]);
+            }
+        }
+        Self { offsets }
+    }
+
+    #[inline]
+    pub fn sample_count(&self) -> usize {
+        self.offsets.len()
+    }
+}
+
+/// The pixels covered by a set of triangles, clipped to the bounds of the render target.
+pub struct Coverage {
+    x_min: u32,
+    y_min: u32,
+    width: u32,
+    height: u32,
+    sample_count: u32,
+    /// One bit per sample for each pixel in the bounding box.
+    masks: Vec<u16>,
+    /// For each pixel in the bounding box, the index of the first triangle that covered it.
+    triangles: Vec<u32>,
+}
+
+impl Coverage {
+    /// Rasterizes the triangles described by `indices` into `positions` (in target pixels).
+    ///
+    /// Triangles within the same draw never overlap on the GPU backends' sample points, so each
+    /// sample is only counted once here; this also keeps overlapping stroke geometry from being
+    /// blended twice.
+    pub fn rasterize(
+        positions: &[[f32; 2]],
+        indices: &[u32],
+        pattern: &SamplePattern,
+        target_width: u32,
+        target_height: u32,
+    ) -> Self {
+        let mut min = [f32::INFINITY; 2];
+        let mut max = [f32::NEG_INFINITY; 2];
+        for &index in indices {
+            if let Some(p) = positions.get(index as usize) {
+                min = [min[0].min(p[0]), min[1].min(p[1])];
+                max = [max[0].max(p[0]), max[1].max(p[1])];
+            }
+        }
+        let x_min = clamp_coord(min[0].floor(), target_width);
+        let y_min = clamp_coord(min[1].floor(), target_height);
+        let x_max = clamp_coord(max[0].ceil() + 1.0, target_width);
+        let y_max = clamp_coord(max[1].ceil() + 1.0, target_height);
+
+        let width = x_max.saturating_sub(x_min);
+        let height = y_max.saturating_sub(y_min);
+        let mut coverage = Self {
+            x_min,
+            y_min,
+            width,
+            height,
+            sample_count: pattern.sample_count() as u32,
+            masks: vec![0; width as usize * height as usize],
+            triangles: vec![0; width as usize * height as usize],
+        };
+        if width == 0 || height == 0 {
+            return coverage;
+        }
+
+        for (triangle, chunk) in indices.chunks_exact(3).enumerate() {
+            let (Some(a), Some(b), Some(c)) = (
+                positions.get(chunk[0] as usize),
+                positions.get(chunk[1] as usize),
+                positions.get(chunk[2] as usize),
+            ) else {
+                continue;
+            };
+            coverage.add_triangle(triangle as u32, *a, *b, *c, pattern);
+        }
+        coverage
+    }
+
+    fn add_triangle(
+        &mut self,
+        triangle: u32,
+        a: [f32; 2],
+        b: [f32; 2],
+        c: [f32; 2],
+        pattern: &SamplePattern,
+    ) {
+        let area = edge(a, b, c);
+        if area == 0.0 || !area.is_finite() {
+            return;
+        }
+        // Make the winding consistent, so that "inside" is always a positive edge function.
+        let (b, c) = if area < 0.0 { (c, b) } else { (b, c) };
+        let edges = [(b, c), (c, a), (a, b)];
+        let inclusive = edges.map(|(from, to)| is_top_left(from, to));
+
+        let x_start =
+            clamp_coord(a[0].min(b[0]).min(c[0]).floor(), self.x_min + self.width).max(self.x_min);
+        let x_end = clamp_coord(
+            a[0].max(b[0]).max(c[0]).ceil() + 1.0,
+            self.x_min + self.width,
+        );
+        let y_start =
+            clamp_coord(a[1].min(b[1]).min(c[1]).floor(), self.y_min + self.height).max(self.y_min);
+        let y_end = clamp_coord(
+            a[1].max(b[1]).max(c[1]).ceil() + 1.0,
+            self.y_min + self.height,
+        );
+
+        for y in y_start..y_end {
+            for x in x_start..x_end {
+                let index = ((y - self.y_min) * self.width + (x - self.x_min)) as usize;
+                let mut mask = self.masks[index];
+                for (sample, offset) in pattern.offsets.iter().enumerate() {
+                    let bit = 1 << sample;
+                    if mask & bit != 0 {
+                        continue;
+                    }
+                    let p = [x as f32 + offset[0], y as f32 + offset[1]];
+                    let inside = edges.iter().zip(inclusive).all(|(&(from, to), inclusive)| {
+                        let w = edge(from, to, p);
+                        w > 0.0 || (w == 0.0 && inclusive)
+                    });
+                    if inside {
+                        mask |= bit;
+                    }
+                }
+                if self.masks[index] == 0 && mask != 0 {
+                    self.triangles[index] = triangle;
+                }
+                self.masks[index] = mask;
+            }
+        }
+    }
+
+    /// Calls `f` with the position, coverage (in the range (0.0, 1.0]) and first covering
+    /// triangle of every pixel that is at least partially covered.
+    pub fn for_each(&self, mut f: impl FnMut(u32, u32, f32, u32)) {
+        let scale = 1.0 / self.sample_count as f32;
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let index = (y * self.width + x) as usize;
+                let mask = self.masks[index];
+                if mask != 0 {
+                    f(
+                        self.x_min + x,
+                        self.y_min + y,
+                        mask.count_ones() as f32 * scale,
+                        self.triangles[index],
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Returns the barycentric weights of `p` within the triangle `abc`, or `None` if it's degenerate.
+pub fn barycentric(a: [f32; 2], b: [f32; 2], c: [f32; 2], p: [f32; 2]) -> Option<[f32; 3]> {
+    let area = edge(a, b, c);
+    if area == 0.0 {
+        return None;
+    }
+    let wa = edge(b, c, p) / area;
+    let wb = edge(c, a, p) / area;
+    Some([wa, wb, 1.0 - wa - wb])
+}
+
+#[inline]
+fn edge(from: [f32; 2], to: [f32; 2], p: [f32; 2]) -> f32 {
+    (to[0] - from[0]) * (p[1] - from[1]) - (to[1] - from[1]) * (p[0] - from[0])
+}
+
+/// Samples lying exactly on an edge shared by two triangles must only belong to one of them.
+#[inline]
+fn is_top_left(from: [f32; 2], to: [f32; 2]) -> bool {
+    let dx = to[0] - from[0];
+    let dy = to[1] - from[1];
+    dy > 0.0 || (dy == 0.0 && dx < 0.0)
+}
+
+#[inline]
+fn clamp_coord(value: f32, size: u32) -> u32 {
+    if value.is_nan() {
+        0
+    } else {
+        value.clamp(0.0, size as f32) as u32
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Coverage, SamplePattern};
+
+    fn total_coverage(positions: &[[f32; 2]], indices: &[u32], sample_count: u32) -> f32 {
+        let coverage = Coverage::rasterize(
+            positions,
+            indices,
+            &SamplePattern::new(sample_count),
+            16,
+            16,
+        );
+        let mut total = 0.0;
+        coverage.for_each(|_, _, amount, _| total += amount);
+        total
+    }
+
+    #[test]
+    fn shared_edges_are_covered_once() {
+        // A 4x4 square made of two triangles, aligned to the pixel grid.
+        let positions = [[2.0, 2.0], [6.0, 2.0], [6.0, 6.0], [2.0, 6.0]];
+        let indices = [0, 1, 2, 0, 2, 3];
+        assert_eq!(total_coverage(&positions, &indices, 1), 16.0);
+        assert_eq!(total_coverage(&positions, &indices, 16), 16.0);
+    }
+
+    #[test]
+    fn partial_coverage() {
+        // Half a pixel wide, so half of the samples of each pixel in a 2x1 grid are covered.
+        let positions = [[0.0, 0.0], [0.5, 0.0], [0.5, 4.0], [0.0, 4.0]];
+        let indices = [0, 1, 2, 0, 2, 3];
+        assert_eq!(total_coverage(&positions, &indices, 2), 2.0);
+    }
+
+    #[test]
+    fn clipped_to_target() {
+        let positions = [[-10.0, -10.0], [30.0, -10.0], [30.0, 30.0], [-10.0, 30.0]];
+        let indices = [0, 1, 2, 0, 2, 3];
+        assert_eq!(total_coverage(&positions, &indices, 4), 256.0);
+    }
+}
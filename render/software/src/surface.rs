@@ -0,0 +1,528 @@
+use crate::bitmap::{as_bitmap, lerp_pixel, Pixel, Texture, TRANSPARENT};
+use crate::mesh::{as_mesh, Paint};
+use crate::rasterizer::{barycentric, Coverage, SamplePattern};
+use ruffle_render::backend::ShapeHandle;
+use ruffle_render::bitmap::{BitmapHandle, PixelSnapping};
+use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
+use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
+use ruffle_render::transform::Transform;
+use swf::{BlendMode, Color, ColorTransform};
+
+/// The corners of a unit square, as two triangles.
+const QUAD_POSITIONS: [[f32; 2]; 4] = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+const QUAD_INDICES: [u32; 6] = [0, 1, 2, 0, 2, 3];
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum MaskState {
+    /// The mask shape is being drawn; draws add to the mask instead of the target.
+    Drawing,
+    /// The mask is complete; draws are clipped by it.
+    Active,
+    /// The mask shape is being redrawn to clear it; draws are discarded.
+    Clearing,
+}
+
+struct MaskLayer {
+    /// The coverage of the mask for each pixel of the target, already clipped by any parent mask.
+    alpha: Vec<f32>,
+    state: MaskState,
+}
+
+/// Executes a `CommandList` by rasterizing it onto a texture.
+pub struct Surface<'a> {
+    target: &'a mut Texture,
+    pattern: &'a SamplePattern,
+    masks: Vec<MaskLayer>,
+}
+
+impl<'a> Surface<'a> {
+    pub fn new(target: &'a mut Texture, pattern: &'a SamplePattern) -> Self {
+        Self {
+            target,
+            pattern,
+            masks: vec![],
+        }
+    }
+
+    pub fn draw_commands(&mut self, commands: CommandList) {
+        commands.execute(self);
+    }
+
+    /// Rasterizes the given triangles (in object space) and fills them using `shade`, which
+    /// returns the premultiplied color for a target pixel and the triangle that covers it.
+    fn fill(
+        &mut self,
+        matrix: Matrix,
+        positions: &[[f32; 2]],
+        indices: &[u32],
+        shade: impl FnMut(u32, u32, u32) -> Pixel,
+    ) {
+        let Some(top) = self.masks.last().map(|mask| mask.state) else {
+            return self.fill_target(matrix, positions, indices, None, shade);
+        };
+        match top {
+            MaskState::Active => {
+                let mask = self.masks.pop();
+                self.fill_target(
+                    matrix,
+                    positions,
+                    indices,
+                    mask.as_ref().map(|mask| &mask.alpha[..]),
+                    shade,
+                );
+                self.masks.extend(mask);
+            }
+            MaskState::Drawing => {
+                // Only the shape of a mask matters, not its color.
+                let coverage = self.rasterize(matrix, positions, indices);
+                let width = self.target.width();
+                let (parents, current) = self.masks.split_at_mut(self.masks.len() - 1);
+                let parent = parents
+                    .last()
+                    .filter(|parent| parent.state == MaskState::Active);
+                let current = &mut current[0];
+                coverage.for_each(|x, y, amount, _| {
+                    let index = (y * width + x) as usize;
+                    let amount = amount * parent.map_or(1.0, |parent| parent.alpha[index]);
+                    let alpha = &mut current.alpha[index];
+                    *alpha += amount * (1.0 - *alpha);
+                });
+            }
+            MaskState::Clearing => {}
+        }
+    }
+
+    fn fill_target(
+        &mut self,
+        matrix: Matrix,
+        positions: &[[f32; 2]],
+        indices: &[u32],
+        clip: Option<&[f32]>,
+        mut shade: impl FnMut(u32, u32, u32) -> Pixel,
+    ) {
+        let coverage = self.rasterize(matrix, positions, indices);
+        let width = self.target.width();
+        let pixels = self.target.pixels_mut();
+        coverage.for_each(|x, y, amount, triangle| {
+            let index = (y * width + x) as usize;
+            let amount = amount * clip.map_or(1.0, |clip| clip[index]);
+            if amount <= 0.0 {
+                return;
+            }
+            let src = scale(shade(x, y, triangle), amount);
+            pixels[index] = blend_over(src, pixels[index]);
+        });
+    }
+
+    fn rasterize(&self, matrix: Matrix, positions: &[[f32; 2]], indices: &[u32]) -> Coverage {
+        let positions: Vec<[f32; 2]> = positions
+            .iter()
+            .map(|&[x, y]| transform_point(&matrix, x, y))
+            .collect();
+        Coverage::rasterize(
+            &positions,
+            indices,
+            self.pattern,
+            self.target.width(),
+            self.target.height(),
+        )
+    }
+
+    fn draw_bitmap(
+        &mut self,
+        bitmap: BitmapHandle,
+        transform: Transform,
+        smoothing: bool,
+        pixel_snapping: PixelSnapping,
+    ) {
+        let texture = as_bitmap(&bitmap).texture();
+        let mut matrix = transform.matrix;
+        pixel_snapping.apply(&mut matrix);
+        matrix *= Matrix::scale(texture.width() as f32, texture.height() as f32);
+        let Some(inverse) = matrix.inverse() else {
+            return;
+        };
+        let color_transform = transform.color_transform;
+        self.fill(matrix, &QUAD_POSITIONS, &QUAD_INDICES, |x, y, _| {
+            let [u, v] = transform_point(&inverse, x as f32 + 0.5, y as f32 + 0.5);
+            let color = texture.sample(u, v, smoothing, false);
+            apply_to_premultiplied(&color_transform, color)
+        });
+    }
+}
+
+impl<'a> CommandHandler for Surface<'a> {
+    fn render_bitmap(
+        &mut self,
+        bitmap: BitmapHandle,
+        transform: Transform,
+        smoothing: bool,
+        pixel_snapping: PixelSnapping,
+    ) {
+        self.draw_bitmap(bitmap, transform, smoothing, pixel_snapping);
+    }
+
+    fn render_stage3d(&mut self, bitmap: BitmapHandle, transform: Transform) {
+        self.draw_bitmap(bitmap, transform, false, PixelSnapping::Never);
+    }
+
+    fn render_shape(&mut self, shape: ShapeHandle, transform: Transform) {
+        let matrix = transform.matrix;
+        let Some(inverse) = matrix.inverse() else {
+            return;
+        };
+        let drawing_mask = matches!(
+            self.masks.last(),
+            Some(MaskLayer {
+                state: MaskState::Drawing,
+                ..
+            })
+        );
+        let color_transform = transform.color_transform;
+        let mesh = as_mesh(&shape);
+        for draw in &mesh.draws {
+            let indices = if drawing_mask {
+                &draw.indices[..(draw.mask_index_count as usize).min(draw.indices.len())]
+            } else {
+                &draw.indices[..]
+            };
+            let object_point =
+                |x: u32, y: u32| transform_point(&inverse, x as f32 + 0.5, y as f32 + 0.5);
+            match &draw.paint {
+                Paint::Color => {
+                    self.fill(matrix, &draw.positions, indices, |x, y, triangle| {
+                        let base = triangle as usize * 3;
+                        let corners = [
+                            indices[base] as usize,
+                            indices[base + 1] as usize,
+                            indices[base + 2] as usize,
+                        ];
+                        let weights = barycentric(
+                            draw.positions[corners[0]],
+                            draw.positions[corners[1]],
+                            draw.positions[corners[2]],
+                            object_point(x, y),
+                        )
+                        .unwrap_or([1.0, 0.0, 0.0])
+                        .map(|w| w.clamp(0.0, 1.0));
+                        let total: f32 = weights.iter().sum();
+                        let mut color = [0.0; 4];
+                        for (corner, weight) in corners.iter().zip(weights) {
+                            for (channel, value) in color.iter_mut().zip(draw.colors[*corner]) {
+                                *channel += value * weight / total;
+                            }
+                        }
+                        apply_to_unmultiplied(&color_transform, color)
+                    });
+                }
+                Paint::Gradient(gradient) => {
+                    let m = gradient.matrix;
+                    self.fill(matrix, &draw.positions, indices, |x, y, _| {
+                        let [px, py] = object_point(x, y);
+                        let u = m[0][0] * px + m[1][0] * py + m[2][0];
+                        let v = m[0][1] * px + m[1][1] * py + m[2][1];
+                        apply_to_unmultiplied(&color_transform, gradient.color_at(u, v))
+                    });
+                }
+                Paint::Bitmap {
+                    handle,
+                    matrix: m,
+                    is_smoothed,
+                    is_repeating,
+                } => {
+                    let texture = as_bitmap(handle).texture();
+                    self.fill(matrix, &draw.positions, indices, |x, y, _| {
+                        let [px, py] = object_point(x, y);
+                        let u = m[0][0] * px + m[1][0] * py + m[2][0];
+                        let v = m[0][1] * px + m[1][1] * py + m[2][1];
+                        let color = texture.sample(u, v, *is_smoothed, *is_repeating);
+                        apply_to_premultiplied(&color_transform, color)
+                    });
+                }
+            }
+        }
+    }
+
+    fn draw_rect(&mut self, color: Color, matrix: Matrix) {
+        let color = premultiply([
+            f32::from(color.r) / 255.0,
+            f32::from(color.g) / 255.0,
+            f32::from(color.b) / 255.0,
+            f32::from(color.a) / 255.0,
+        ]);
+        self.fill(matrix, &QUAD_POSITIONS, &QUAD_INDICES, |_, _, _| color);
+    }
+
+    fn push_mask(&mut self) {
+        let size = self.target.pixels().len();
+        self.masks.push(MaskLayer {
+            alpha: vec![0.0; size],
+            state: MaskState::Drawing,
+        });
+    }
+
+    fn activate_mask(&mut self) {
+        if let Some(mask) = self.masks.last_mut() {
+            mask.state = MaskState::Active;
+        }
+    }
+
+    fn deactivate_mask(&mut self) {
+        if let Some(mask) = self.masks.last_mut() {
+            mask.state = MaskState::Clearing;
+        }
+    }
+
+    fn pop_mask(&mut self) {
+        self.masks.pop();
+    }
+
+    fn blend(&mut self, commands: CommandList, blend_mode: RenderBlendMode) {
+        match self.masks.last().map(|mask| mask.state) {
+            Some(MaskState::Drawing) => {
+                // Blend modes have no effect on the shape of a mask.
+                return self.draw_commands(commands);
+            }
+            Some(MaskState::Clearing) => return,
+            _ => {}
+        }
+
+        let blend_mode = match blend_mode {
+            RenderBlendMode::Builtin(blend_mode) => blend_mode,
+            RenderBlendMode::Shader(_) => {
+                tracing::warn!("Shader blend mode is not supported by the software renderer");
+                BlendMode::Normal
+            }
+        };
+
+        let mut layer = Texture::new(self.target.width(), self.target.height());
+        Surface::new(&mut layer, self.pattern).draw_commands(commands);
+
+        let clip = self.masks.last().map(|mask| &mask.alpha[..]);
+        let pixels = self.target.pixels_mut();
+        for (index, (dst, src)) in pixels.iter_mut().zip(layer.pixels()).enumerate() {
+            let amount = clip.map_or(1.0, |clip| clip[index]);
+            if amount <= 0.0 || (src[3] <= 0.0 && !transparent_source_affects_dest(blend_mode)) {
+                continue;
+            }
+            let blended = blend_pixel(blend_mode, *src, *dst);
+            *dst = if amount >= 1.0 {
+                blended
+            } else {
+                lerp_pixel(*dst, blended, amount)
+            };
+        }
+    }
+
+    fn render_projected(
+        &mut self,
+        commands: CommandList,
+        width: u32,
+        height: u32,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+    ) {
+        // Perspective isn't supported here, so approximate the projection with a 2D matrix.
+        if let Some(matrix) = matrix.affine_approximation(f64::from(width), f64::from(height)) {
+            self.draw_commands(commands.transformed(matrix, color_transform));
+        }
+    }
+}
+
+#[inline]
+fn transform_point(matrix: &Matrix, x: f32, y: f32) -> [f32; 2] {
+    [
+        matrix.a * x + matrix.c * y + matrix.tx.to_pixels() as f32,
+        matrix.b * x + matrix.d * y + matrix.ty.to_pixels() as f32,
+    ]
+}
+
+#[inline]
+fn scale(color: Pixel, amount: f32) -> Pixel {
+    color.map(|c| c * amount)
+}
+
+#[inline]
+fn premultiply(color: Pixel) -> Pixel {
+    [
+        color[0] * color[3],
+        color[1] * color[3],
+        color[2] * color[3],
+        color[3],
+    ]
+}
+
+#[inline]
+fn unmultiply(color: Pixel) -> Pixel {
+    if color[3] <= 0.0 {
+        TRANSPARENT
+    } else {
+        [
+            color[0] / color[3],
+            color[1] / color[3],
+            color[2] / color[3],
+            color[3],
+        ]
+    }
+}
+
+/// Applies a color transform to an unmultiplied color, returning a premultiplied color.
+fn apply_to_unmultiplied(color_transform: &ColorTransform, color: Pixel) -> Pixel {
+    let mult = color_transform.mult_rgba_normalized();
+    let add = color_transform.add_rgba_normalized();
+    premultiply([0, 1, 2, 3].map(|i| (color[i] * mult[i] + add[i]).clamp(0.0, 1.0)))
+}
+
+/// Applies a color transform to a premultiplied color. Fully transparent texels are unaffected.
+fn apply_to_premultiplied(color_transform: &ColorTransform, color: Pixel) -> Pixel {
+    if color[3] <= 0.0 {
+        return TRANSPARENT;
+    }
+    apply_to_unmultiplied(color_transform, unmultiply(color))
+}
+
+#[inline]
+fn blend_over(src: Pixel, dst: Pixel) -> Pixel {
+    let inverse = 1.0 - src[3];
+    [
+        src[0] + dst[0] * inverse,
+        src[1] + dst[1] * inverse,
+        src[2] + dst[2] * inverse,
+        src[3] + dst[3] * inverse,
+    ]
+}
+
+/// Whether compositing a fully transparent source can still change the destination.
+fn transparent_source_affects_dest(mode: BlendMode) -> bool {
+    match mode {
+        BlendMode::Alpha | BlendMode::Erase => true,
+        BlendMode::Normal
+        | BlendMode::Layer
+        | BlendMode::Multiply
+        | BlendMode::Screen
+        | BlendMode::Lighten
+        | BlendMode::Darken
+        | BlendMode::Difference
+        | BlendMode::Add
+        | BlendMode::Subtract
+        | BlendMode::Invert
+        | BlendMode::Overlay
+        | BlendMode::HardLight => false,
+    }
+}
+
+/// Composites a premultiplied `src` onto a premultiplied `dst` using the given blend mode,
+/// matching the blend shaders of the GPU backends.
+fn blend_pixel(mode: BlendMode, src: Pixel, dst: Pixel) -> Pixel {
+    let over_alpha = src[3] + dst[3] * (1.0 - src[3]);
+    let trivial = |f: &dyn Fn(f32, f32) -> f32| -> Pixel {
+        [
+            f(src[0], dst[0]).clamp(0.0, 1.0),
+            f(src[1], dst[1]).clamp(0.0, 1.0),
+            f(src[2], dst[2]).clamp(0.0, 1.0),
+            over_alpha,
+        ]
+    };
+    // The general form of a separable blend mode, where `f` works on unmultiplied channels.
+    let separable = |f: &dyn Fn(f32, f32) -> f32| -> Pixel {
+        let s = unmultiply(src);
+        let d = unmultiply(dst);
+        let channel = |i: usize| {
+            (src[i] * (1.0 - dst[3]) + dst[i] * (1.0 - src[3]) + src[3] * dst[3] * f(s[i], d[i]))
+                .clamp(0.0, 1.0)
+        };
+        [channel(0), channel(1), channel(2), over_alpha]
+    };
+
+    match mode {
+        BlendMode::Normal | BlendMode::Layer => blend_over(src, dst),
+        BlendMode::Add => trivial(&|s, d| s + d),
+        BlendMode::Subtract => trivial(&|s, d| d - s),
+        BlendMode::Screen => trivial(&|s, d| s + d * (1.0 - s)),
+        BlendMode::Multiply => {
+            if dst[3] <= 0.0 {
+                src
+            } else {
+                separable(&|s, d| s * d)
+            }
+        }
+        BlendMode::Lighten => separable(&|s, d| s.max(d)),
+        BlendMode::Darken => separable(&|s, d| s.min(d)),
+        BlendMode::Difference => separable(&|s, d| (d - s).abs()),
+        BlendMode::Invert => separable(&|_, d| 1.0 - d),
+        BlendMode::Overlay => separable(&|s, d| {
+            if d <= 0.5 {
+                2.0 * s * d
+            } else {
+                1.0 - 2.0 * (1.0 - d) * (1.0 - s)
+            }
+        }),
+        BlendMode::HardLight => separable(&|s, d| {
+            if s <= 0.5 {
+                2.0 * s * d
+            } else {
+                1.0 - 2.0 * (1.0 - d) * (1.0 - s)
+            }
+        }),
+        BlendMode::Alpha => [
+            dst[0] * src[3],
+            dst[1] * src[3],
+            dst[2] * src[3],
+            src[3] * dst[3],
+        ],
+        BlendMode::Erase => {
+            let inverse = 1.0 - src[3];
+            [
+                dst[0] * inverse,
+                dst[1] * inverse,
+                dst[2] * inverse,
+                dst[3] * inverse,
+            ]
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Surface;
+    use crate::bitmap::{Pixel, Texture};
+    use crate::rasterizer::SamplePattern;
+    use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
+    use swf::BlendMode;
+
+    const RED: Pixel = [1.0, 0.0, 0.0, 1.0];
+
+    /// Blends an empty (fully transparent) layer onto an opaque red target.
+    fn blend_transparent_layer(blend_mode: BlendMode) -> Texture {
+        let mut target = Texture::new(4, 4);
+        target.fill(RED);
+        let pattern = SamplePattern::new(1);
+        Surface::new(&mut target, &pattern)
+            .blend(CommandList::new(), RenderBlendMode::Builtin(blend_mode));
+        target
+    }
+
+    #[test]
+    fn transparent_layer_is_no_op() {
+        for blend_mode in [
+            BlendMode::Normal,
+            BlendMode::Layer,
+            BlendMode::Add,
+            BlendMode::Multiply,
+            BlendMode::Erase,
+        ] {
+            let target = blend_transparent_layer(blend_mode);
+            assert!(
+                target.pixels().iter().all(|pixel| *pixel == RED),
+                "{blend_mode:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn transparent_layer_with_alpha_clears_target() {
+        let target = blend_transparent_layer(BlendMode::Alpha);
+        assert!(target.pixels().iter().all(|pixel| pixel[3] == 0.0));
+    }
+}
@@ -305,6 +305,42 @@ impl Context3DCompareMode {
     }
 }
 
+#[derive(Copy, Clone, Debug)]
+pub enum Context3DStencilAction {
+    DecrementSaturate,
+    DecrementWrap,
+    IncrementSaturate,
+    IncrementWrap,
+    Invert,
+    Keep,
+    Set,
+    Zero,
+}
+
+impl Context3DStencilAction {
+    pub fn from_wstr(s: &WStr) -> Option<Self> {
+        if s == b"decrementSaturate" {
+            Some(Context3DStencilAction::DecrementSaturate)
+        } else if s == b"decrementWrap" {
+            Some(Context3DStencilAction::DecrementWrap)
+        } else if s == b"incrementSaturate" {
+            Some(Context3DStencilAction::IncrementSaturate)
+        } else if s == b"incrementWrap" {
+            Some(Context3DStencilAction::IncrementWrap)
+        } else if s == b"invert" {
+            Some(Context3DStencilAction::Invert)
+        } else if s == b"keep" {
+            Some(Context3DStencilAction::Keep)
+        } else if s == b"set" {
+            Some(Context3DStencilAction::Set)
+        } else if s == b"zero" {
+            Some(Context3DStencilAction::Zero)
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum Context3DWrapMode {
     Clamp,
@@ -431,6 +467,21 @@ pub enum Context3DCommand<'a> {
         dest: Rc<dyn Texture>,
         layer: u32,
     },
+    /// Copies the current contents of a `BitmapHandle` (such as a decoded video frame)
+    /// into a texture. `dest` must have the same size as `source`.
+    CopyBitmapHandleToTexture {
+        source: BitmapHandle,
+        dest: Rc<dyn Texture>,
+    },
+    /// Copies `source_region` of the back buffer into `destination` at `dest_point`.
+    /// The copy is submitted immediately; `sync_handle` is set to a handle that
+    /// can be used to read the copied pixels back.
+    DrawToBitmapData {
+        destination: BitmapHandle,
+        source_region: PixelRegion,
+        dest_point: (u32, u32),
+        sync_handle: &'a mut Option<Box<dyn SyncHandle>>,
+    },
     SetTextureAt {
         sampler: u32,
         texture: Option<Rc<dyn Texture>>,
@@ -446,6 +497,18 @@ pub enum Context3DCommand<'a> {
         depth_mask: bool,
         pass_compare_mode: Context3DCompareMode,
     },
+    SetStencilActions {
+        triangle_face: Context3DTriangleFace,
+        compare_mode: Context3DCompareMode,
+        action_on_both_pass: Context3DStencilAction,
+        action_on_depth_fail: Context3DStencilAction,
+        action_on_depth_pass_stencil_fail: Context3DStencilAction,
+    },
+    SetStencilReferenceValue {
+        reference_value: u32,
+        read_mask: u32,
+        write_mask: u32,
+    },
     SetBlendFactors {
         source_factor: Context3DBlendFactor,
         destination_factor: Context3DBlendFactor,
@@ -1,9 +1,10 @@
 use crate::backend::ShapeHandle;
 use crate::bitmap::{BitmapHandle, PixelSnapping};
 use crate::matrix::Matrix;
+use crate::matrix3d::Matrix3D;
 use crate::pixel_bender::PixelBenderShaderHandle;
 use crate::transform::Transform;
-use swf::{BlendMode, Color};
+use swf::{BlendMode, Color, ColorTransform};
 
 pub trait CommandHandler {
     fn render_bitmap(
@@ -22,6 +23,17 @@ pub trait CommandHandler {
     fn pop_mask(&mut self);
 
     fn blend(&mut self, commands: CommandList, blend_mode: RenderBlendMode);
+
+    /// Draws `commands`, which cover the `width` x `height` pixel area at the origin,
+    /// projected onto the stage by `matrix`.
+    fn render_projected(
+        &mut self,
+        commands: CommandList,
+        width: u32,
+        height: u32,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+    );
 }
 
 /// Holds either a normal BlendMode, or the shader for BlendMode.SHADER.
@@ -68,6 +80,13 @@ impl CommandList {
                 Command::DeactivateMask => handler.deactivate_mask(),
                 Command::PopMask => handler.pop_mask(),
                 Command::Blend(commands, blend_mode) => handler.blend(commands, blend_mode),
+                Command::RenderProjected {
+                    commands,
+                    width,
+                    height,
+                    matrix,
+                    color_transform,
+                } => handler.render_projected(commands, width, height, matrix, color_transform),
             }
         }
     }
@@ -75,6 +94,52 @@ impl CommandList {
     pub fn drawing_mask(&self) -> bool {
         self.maskers_in_progress > 0
     }
+
+    /// Applies `matrix` and `color_transform` on top of every command in this list.
+    ///
+    /// Backends without perspective support use this to draw the commands of
+    /// `render_projected` with `Matrix3D::affine_approximation`.
+    pub fn transformed(mut self, matrix: Matrix, color_transform: ColorTransform) -> Self {
+        for command in &mut self.commands {
+            match command {
+                Command::RenderBitmap { transform, .. }
+                | Command::RenderStage3D { transform, .. }
+                | Command::RenderShape { transform, .. } => {
+                    transform.matrix = matrix * transform.matrix;
+                    transform.color_transform = color_transform * transform.color_transform;
+                }
+                Command::DrawRect {
+                    color,
+                    matrix: rect_matrix,
+                } => {
+                    *rect_matrix = matrix * *rect_matrix;
+                    let multiply = color_transform.mult_rgba_normalized();
+                    let add = color_transform.add_rgba_normalized();
+                    let channels = [&mut color.r, &mut color.g, &mut color.b, &mut color.a];
+                    for ((channel, multiply), add) in channels.into_iter().zip(multiply).zip(add) {
+                        let value = f32::from(*channel) / 255.0 * multiply + add;
+                        *channel = (value.clamp(0.0, 1.0) * 255.0) as u8;
+                    }
+                }
+                Command::Blend(commands, _) => {
+                    *commands = std::mem::take(commands).transformed(matrix, color_transform);
+                }
+                Command::RenderProjected {
+                    matrix: projected_matrix,
+                    color_transform: projected_color_transform,
+                    ..
+                } => {
+                    *projected_matrix = Matrix3D::from(matrix) * *projected_matrix;
+                    *projected_color_transform = color_transform * *projected_color_transform;
+                }
+                Command::PushMask
+                | Command::ActivateMask
+                | Command::DeactivateMask
+                | Command::PopMask => {}
+            }
+        }
+        self
+    }
 }
 
 impl CommandHandler for CommandList {
@@ -157,6 +222,26 @@ impl CommandHandler for CommandList {
             self.commands.push(Command::Blend(commands, blend_mode));
         }
     }
+
+    #[inline]
+    fn render_projected(
+        &mut self,
+        commands: CommandList,
+        width: u32,
+        height: u32,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+    ) {
+        if self.maskers_in_progress <= 1 {
+            self.commands.push(Command::RenderProjected {
+                commands,
+                width,
+                height,
+                matrix,
+                color_transform,
+            });
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -184,4 +269,11 @@ pub enum Command {
     DeactivateMask,
     PopMask,
     Blend(CommandList, RenderBlendMode),
+    RenderProjected {
+        commands: CommandList,
+        width: u32,
+        height: u32,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+    },
 }
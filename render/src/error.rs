@@ -24,6 +24,12 @@ pub enum Error {
     #[error("Invalid PNG")]
     InvalidPng(#[from] png::DecodingError),
 
+    #[error("Couldn't encode PNG")]
+    PngEncoding(#[from] png::EncodingError),
+
+    #[error("Couldn't encode JPEG")]
+    JpegEncoding(#[from] jpeg_encoder::EncodingError),
+
     #[error("Invalid GIF")]
     InvalidGif(#[from] gif::DecodingError),
 
@@ -4,7 +4,7 @@ use crate::{
 };
 use downcast_rs::{impl_downcast, Downcast};
 use std::fmt::Debug;
-use swf::{Color, Rectangle, Twips};
+use swf::{Color, GradientRecord, Rectangle, Twips};
 
 #[derive(Debug, Clone)]
 pub enum Filter {
@@ -67,6 +67,8 @@ impl Filter {
             Filter::DropShadowFilter(filter) => filter.calculate_dest_rect(source_rect),
             Filter::BevelFilter(filter) => filter.calculate_dest_rect(source_rect),
             Filter::DisplacementMapFilter(filter) => filter.calculate_dest_rect(source_rect),
+            Filter::GradientBevelFilter(filter) => filter.calculate_dest_rect(source_rect),
+            Filter::GradientGlowFilter(filter) => filter.calculate_dest_rect(source_rect),
             _ => source_rect,
         }
     }
@@ -83,6 +85,44 @@ impl Filter {
     }
 }
 
+/// The number of entries in the color lookup table of a gradient filter.
+pub const GRADIENT_LUT_SIZE: usize = 256;
+
+/// Builds the color lookup table of a `GradientGlowFilter` or `GradientBevelFilter`.
+/// Entry `t` contains the (non-premultiplied) gradient color at ratio `t`; a gradient without
+/// any records is transparent everywhere.
+pub fn gradient_lut(records: &[GradientRecord]) -> [Color; GRADIENT_LUT_SIZE] {
+    let mut colors = [Color::TRANSPARENT; GRADIENT_LUT_SIZE];
+    if records.is_empty() {
+        return colors;
+    }
+    for (t, color) in colors.iter_mut().enumerate() {
+        let next = records.iter().position(|record| record.ratio as usize >= t);
+        *color = match next {
+            Some(0) => records[0].color,
+            None => records[records.len() - 1].color,
+            Some(next) => {
+                let last_record = &records[next - 1];
+                let next_record = &records[next];
+                let a = (t as f32 - last_record.ratio as f32)
+                    / (next_record.ratio as f32 - last_record.ratio as f32);
+                lerp_color(&last_record.color, &next_record.color, a)
+            }
+        };
+    }
+    colors
+}
+
+fn lerp_color(a: &Color, b: &Color, t: f32) -> Color {
+    let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t) as u8;
+    Color {
+        r: lerp(a.r, b.r),
+        g: lerp(a.g, b.g),
+        b: lerp(a.b, b.b),
+        a: lerp(a.a, b.a),
+    }
+}
+
 impl From<&swf::Filter> for Filter {
     fn from(value: &swf::Filter) -> Self {
         match value {
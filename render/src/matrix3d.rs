@@ -0,0 +1,348 @@
+use crate::matrix::Matrix;
+use swf::Twips;
+
+/// A 4x4 transformation matrix, used by display objects with a 3D transform.
+///
+/// The values are stored in column-major order, the same layout as `flash.geom.Matrix3D.rawData`,
+/// and translations are in pixels.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Matrix3D {
+    pub raw_data: [f64; 16],
+}
+
+impl Matrix3D {
+    pub const IDENTITY: Self = Self {
+        raw_data: [
+            1.0, 0.0, 0.0, 0.0, //
+            0.0, 1.0, 0.0, 0.0, //
+            0.0, 0.0, 1.0, 0.0, //
+            0.0, 0.0, 0.0, 1.0, //
+        ],
+    };
+
+    /// Builds a matrix from its rows, which is easier to read than the column-major layout.
+    fn from_rows(rows: [[f64; 4]; 4]) -> Self {
+        let mut raw_data = [0.0; 16];
+        for (row, values) in rows.iter().enumerate() {
+            for (column, value) in values.iter().enumerate() {
+                raw_data[column * 4 + row] = *value;
+            }
+        }
+        Self { raw_data }
+    }
+
+    /// Returns the value at the given row and column.
+    #[inline]
+    pub fn get(&self, row: usize, column: usize) -> f64 {
+        self.raw_data[column * 4 + row]
+    }
+
+    pub fn translation(x: f64, y: f64, z: f64) -> Self {
+        Self::from_rows([
+            [1.0, 0.0, 0.0, x],
+            [0.0, 1.0, 0.0, y],
+            [0.0, 0.0, 1.0, z],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn scale(x: f64, y: f64, z: f64) -> Self {
+        Self::from_rows([
+            [x, 0.0, 0.0, 0.0],
+            [0.0, y, 0.0, 0.0],
+            [0.0, 0.0, z, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn rotation_x(radians: f64) -> Self {
+        let (sin, cos) = radians.sin_cos();
+        Self::from_rows([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, cos, -sin, 0.0],
+            [0.0, sin, cos, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn rotation_y(radians: f64) -> Self {
+        let (sin, cos) = radians.sin_cos();
+        Self::from_rows([
+            [cos, 0.0, sin, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [-sin, 0.0, cos, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn rotation_z(radians: f64) -> Self {
+        let (sin, cos) = radians.sin_cos();
+        Self::from_rows([
+            [cos, -sin, 0.0, 0.0],
+            [sin, cos, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Builds `translation * rotation_z * rotation_y * rotation_x * scale`,
+    /// which is the order used by display objects and `Matrix3D.recompose`.
+    pub fn from_components(translation: [f64; 3], rotation: [f64; 3], scale: [f64; 3]) -> Self {
+        Self::translation(translation[0], translation[1], translation[2])
+            * Self::rotation_z(rotation[2])
+            * Self::rotation_y(rotation[1])
+            * Self::rotation_x(rotation[0])
+            * Self::scale(scale[0], scale[1], scale[2])
+    }
+
+    /// Splits this matrix into its translation, euler rotation (in radians) and scale,
+    /// such that `from_components` rebuilds it. Any skew is lost.
+    pub fn decompose(&self) -> ([f64; 3], [f64; 3], [f64; 3]) {
+        let translation = [self.get(0, 3), self.get(1, 3), self.get(2, 3)];
+        let column_length = |column: usize| {
+            (self.get(0, column).powi(2)
+                + self.get(1, column).powi(2)
+                + self.get(2, column).powi(2))
+            .sqrt()
+        };
+        let mut scale = [column_length(0), column_length(1), column_length(2)];
+        if self.determinant() < 0.0 {
+            scale[0] = -scale[0];
+        }
+
+        // The rotation part, with the scale divided back out of each column.
+        let r = |row: usize, column: usize| {
+            if scale[column] == 0.0 {
+                0.0
+            } else {
+                self.get(row, column) / scale[column]
+            }
+        };
+        let rotation_y = (-r(2, 0)).clamp(-1.0, 1.0).asin();
+        let rotation = if rotation_y.cos().abs() > 1e-6 {
+            [r(2, 1).atan2(r(2, 2)), rotation_y, r(1, 0).atan2(r(0, 0))]
+        } else {
+            // Gimbal lock: only the difference between the X and Z rotation matters.
+            [(-r(1, 2)).atan2(r(1, 1)), rotation_y, 0.0]
+        };
+
+        (translation, rotation, scale)
+    }
+
+    /// A perspective projection looking down the Z axis from `focal_length` pixels in front
+    /// of `(center_x, center_y)`.
+    ///
+    /// The projected Z coordinate is always 0, as display objects are drawn in list order
+    /// rather than depth sorted.
+    pub fn perspective(focal_length: f64, center_x: f64, center_y: f64) -> Self {
+        Self::from_rows([
+            [1.0, 0.0, center_x / focal_length, 0.0],
+            [0.0, 1.0, center_y / focal_length, 0.0],
+            [0.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0 / focal_length, 1.0],
+        ])
+    }
+
+    pub fn determinant(&self) -> f64 {
+        let m = |row: usize, column: usize| self.get(row, column);
+        let minor = |r0: usize, r1: usize, r2: usize, c0: usize, c1: usize, c2: usize| {
+            m(r0, c0) * (m(r1, c1) * m(r2, c2) - m(r1, c2) * m(r2, c1))
+                - m(r0, c1) * (m(r1, c0) * m(r2, c2) - m(r1, c2) * m(r2, c0))
+                + m(r0, c2) * (m(r1, c0) * m(r2, c1) - m(r1, c1) * m(r2, c0))
+        };
+        m(0, 0) * minor(1, 2, 3, 1, 2, 3) - m(0, 1) * minor(1, 2, 3, 0, 2, 3)
+            + m(0, 2) * minor(1, 2, 3, 0, 1, 3)
+            - m(0, 3) * minor(1, 2, 3, 0, 1, 2)
+    }
+
+    /// Inverts this matrix using Gauss-Jordan elimination, or returns `None` if it is singular.
+    pub fn inverse(&self) -> Option<Self> {
+        // Each row holds a row of this matrix, followed by the same row of the identity matrix.
+        let mut rows = [[0.0; 8]; 4];
+        for (row, values) in rows.iter_mut().enumerate() {
+            for (column, value) in values.iter_mut().take(4).enumerate() {
+                *value = self.get(row, column);
+            }
+            values[4 + row] = 1.0;
+        }
+
+        for column in 0..4 {
+            let pivot = (column..4)
+                .max_by(|x, y| rows[*x][column].abs().total_cmp(&rows[*y][column].abs()))?;
+            if rows[pivot][column].abs() < f64::EPSILON {
+                return None;
+            }
+            rows.swap(column, pivot);
+
+            let divisor = rows[column][column];
+            let pivot_row = rows[column].map(|value| value / divisor);
+            for (row, values) in rows.iter_mut().enumerate() {
+                if row == column {
+                    *values = pivot_row;
+                } else {
+                    let factor = values[column];
+                    for (value, pivot_value) in values.iter_mut().zip(pivot_row) {
+                        *value -= factor * pivot_value;
+                    }
+                }
+            }
+        }
+
+        Some(Self::from_rows(
+            rows.map(|values| [values[4], values[5], values[6], values[7]]),
+        ))
+    }
+
+    /// Transforms the given point, returning its homogeneous `[x, y, z, w]` coordinates.
+    pub fn transform_point(&self, x: f64, y: f64, z: f64) -> [f64; 4] {
+        let mut result = [0.0; 4];
+        for (row, value) in result.iter_mut().enumerate() {
+            *value = self.get(row, 0) * x
+                + self.get(row, 1) * y
+                + self.get(row, 2) * z
+                + self.get(row, 3);
+        }
+        result
+    }
+
+    /// Returns this matrix as `f32` columns, ready to be uploaded to the GPU.
+    pub fn to_f32_columns(&self) -> [[f32; 4]; 4] {
+        let mut columns = [[0.0; 4]; 4];
+        for (column, values) in columns.iter_mut().enumerate() {
+            for (row, value) in values.iter_mut().enumerate() {
+                *value = self.get(row, column) as f32;
+            }
+        }
+        columns
+    }
+
+    /// Returns the 2D matrix that matches this projection at three corners of the
+    /// `width` x `height` pixel rectangle at the origin.
+    ///
+    /// Backends without perspective support use this to approximate a projected quad.
+    /// Returns `None` if any of those corners are behind the viewer.
+    pub fn affine_approximation(&self, width: f64, height: f64) -> Option<Matrix> {
+        if width <= 0.0 || height <= 0.0 {
+            return None;
+        }
+        let project = |x: f64, y: f64| {
+            let [x, y, _, w] = self.transform_point(x, y, 0.0);
+            if w > 0.0 {
+                Some((x / w, y / w))
+            } else {
+                None
+            }
+        };
+        let origin = project(0.0, 0.0)?;
+        let right = project(width, 0.0)?;
+        let bottom = project(0.0, height)?;
+        Some(Matrix {
+            a: ((right.0 - origin.0) / width) as f32,
+            b: ((right.1 - origin.1) / width) as f32,
+            c: ((bottom.0 - origin.0) / height) as f32,
+            d: ((bottom.1 - origin.1) / height) as f32,
+            tx: Twips::from_pixels(origin.0),
+            ty: Twips::from_pixels(origin.1),
+        })
+    }
+}
+
+impl Default for Matrix3D {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl From<Matrix> for Matrix3D {
+    fn from(matrix: Matrix) -> Self {
+        Self::from_rows([
+            [matrix.a.into(), matrix.c.into(), 0.0, matrix.tx.to_pixels()],
+            [matrix.b.into(), matrix.d.into(), 0.0, matrix.ty.to_pixels()],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+}
+
+impl std::ops::Mul for Matrix3D {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let mut raw_data = [0.0; 16];
+        for column in 0..4 {
+            for row in 0..4 {
+                raw_data[column * 4 + row] =
+                    (0..4).map(|i| self.get(row, i) * rhs.get(i, column)).sum();
+            }
+        }
+        Self { raw_data }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    fn assert_matrix_eq(left: Matrix3D, right: Matrix3D) {
+        for (l, r) in left.raw_data.iter().zip(right.raw_data.iter()) {
+            assert_abs_diff_eq!(*l, *r, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn from_matrix_matches_2d_transform() {
+        let matrix = Matrix {
+            a: 2.0,
+            b: 0.5,
+            c: -1.0,
+            d: 3.0,
+            tx: Twips::from_pixels(10.0),
+            ty: Twips::from_pixels(-20.0),
+        };
+        let [x, y, z, w] = Matrix3D::from(matrix).transform_point(4.0, 5.0, 0.0);
+        assert_abs_diff_eq!(x, 2.0 * 4.0 - 5.0 + 10.0);
+        assert_abs_diff_eq!(y, 0.5 * 4.0 + 3.0 * 5.0 - 20.0);
+        assert_abs_diff_eq!(z, 0.0);
+        assert_abs_diff_eq!(w, 1.0);
+    }
+
+    #[test]
+    fn decompose_roundtrip() {
+        let translation = [10.0, -4.0, 250.0];
+        let rotation = [0.3, -0.7, 1.2];
+        let scale = [2.0, 0.5, 1.5];
+        let matrix = Matrix3D::from_components(translation, rotation, scale);
+        let (t, r, s) = matrix.decompose();
+        for (expected, actual) in translation
+            .iter()
+            .chain(&rotation)
+            .chain(&scale)
+            .zip(t.iter().chain(&r).chain(&s))
+        {
+            assert_abs_diff_eq!(*expected, *actual, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn inverse() {
+        let matrix = Matrix3D::from_components([5.0, 6.0, 7.0], [0.1, 0.2, 0.3], [1.0, 2.0, 3.0]);
+        let inverse = matrix.inverse().expect("Matrix is invertible");
+        assert_matrix_eq(matrix * inverse, Matrix3D::IDENTITY);
+        assert_eq!(Matrix3D::scale(1.0, 0.0, 1.0).inverse(), None);
+    }
+
+    #[test]
+    fn perspective_projects_towards_center() {
+        let projection = Matrix3D::perspective(100.0, 50.0, 50.0);
+        let [x, y, z, w] = projection.transform_point(150.0, 50.0, 100.0);
+        assert_abs_diff_eq!(x / w, 100.0);
+        assert_abs_diff_eq!(y / w, 50.0);
+        assert_abs_diff_eq!(z, 0.0);
+
+        let affine = projection
+            .affine_approximation(10.0, 10.0)
+            .expect("Corners are in front of the viewer");
+        assert_eq!(affine, Matrix::IDENTITY);
+    }
+}
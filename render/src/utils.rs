@@ -398,6 +398,54 @@ fn decode_gif(data: &[u8]) -> Result<Bitmap, Error> {
     ))
 }
 
+/// Encodes unmultiplied RGBA pixels as a PNG file.
+/// The alpha channel is dropped if `transparent` is false.
+pub fn encode_png(
+    width: u32,
+    height: u32,
+    rgba: &[u8],
+    transparent: bool,
+    fast_compression: bool,
+) -> Result<Vec<u8>, Error> {
+    let mut output = Vec::new();
+    let mut encoder = png::Encoder::new(&mut output, width, height);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_compression(if fast_compression {
+        png::Compression::Fast
+    } else {
+        png::Compression::Default
+    });
+
+    let data = if transparent {
+        encoder.set_color(png::ColorType::Rgba);
+        Cow::Borrowed(rgba)
+    } else {
+        encoder.set_color(png::ColorType::Rgb);
+        Cow::Owned(
+            rgba.chunks_exact(4)
+                .flat_map(|pixel| [pixel[0], pixel[1], pixel[2]])
+                .collect(),
+        )
+    };
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&data)?;
+    writer.finish()?;
+    Ok(output)
+}
+
+/// Encodes unmultiplied RGBA pixels as a baseline JPEG file, discarding the alpha channel.
+/// `quality` is clamped to the range 1-100.
+pub fn encode_jpeg(width: u16, height: u16, rgba: &[u8], quality: u8) -> Result<Vec<u8>, Error> {
+    let rgb: Vec<u8> = rgba
+        .chunks_exact(4)
+        .flat_map(|pixel| [pixel[0], pixel[1], pixel[2]])
+        .collect();
+    let mut output = Vec::new();
+    let encoder = jpeg_encoder::Encoder::new(&mut output, quality.clamp(1, 100));
+    encoder.encode(&rgb, width, height, jpeg_encoder::ColorType::Rgb)?;
+    Ok(output)
+}
+
 /// Converts standard RBGA to premultiplied alpha.
 fn premultiply_alpha_rgba(rgba: &mut [u8]) {
     rgba.chunks_exact_mut(4).for_each(|rgba| {
@@ -0,0 +1,211 @@
+use crate::bitmap::{as_bitmap, SvgBitmap};
+use crate::document::Document;
+use crate::shape::SvgShape;
+use ruffle_render::backend::{
+    BitmapCacheEntry, Context3D, RenderBackend, ShapeHandle, ViewportDimensions,
+};
+use ruffle_render::bitmap::{Bitmap, BitmapHandle, BitmapSource, PixelRegion, SyncHandle};
+use ruffle_render::commands::{CommandHandler, CommandList};
+use ruffle_render::error::Error;
+use ruffle_render::matrix::Matrix;
+use ruffle_render::pixel_bender::{
+    PixelBenderShader, PixelBenderShaderArgument, PixelBenderShaderHandle,
+};
+use ruffle_render::quality::StageQuality;
+use ruffle_render::shape_utils::DistilledShape;
+use std::borrow::Cow;
+use std::sync::Arc;
+use swf::Color;
+
+pub struct SvgRenderBackend {
+    viewport_dimensions: ViewportDimensions,
+    quality: StageQuality,
+    next_shape_id: u64,
+    last_frame: Option<String>,
+    warned_about_filters: bool,
+    warned_about_overlays: bool,
+}
+
+impl SvgRenderBackend {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            viewport_dimensions: ViewportDimensions {
+                width,
+                height,
+                scale_factor: 1.0,
+            },
+            quality: StageQuality::High,
+            next_shape_id: 0,
+            last_frame: None,
+            warned_about_filters: false,
+            warned_about_overlays: false,
+        }
+    }
+
+    /// Returns the last rendered frame as an SVG document.
+    pub fn capture_frame(&self) -> Option<String> {
+        self.last_frame.clone()
+    }
+}
+
+#[derive(Debug)]
+struct SvgSyncHandle {
+    data: Vec<u8>,
+    bytes_per_row: u32,
+}
+
+impl SyncHandle for SvgSyncHandle {
+    fn retrieve_offscreen_texture(
+        self: Box<Self>,
+        with_rgba: ruffle_render::bitmap::RgbaBufRead,
+    ) -> Result<(), Error> {
+        with_rgba(&self.data, self.bytes_per_row);
+        Ok(())
+    }
+}
+
+impl RenderBackend for SvgRenderBackend {
+    fn viewport_dimensions(&self) -> ViewportDimensions {
+        self.viewport_dimensions
+    }
+
+    fn set_viewport_dimensions(&mut self, dimensions: ViewportDimensions) {
+        self.viewport_dimensions = dimensions;
+    }
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
+        self.next_shape_id += 1;
+        let id = self.next_shape_id;
+        ShapeHandle(Arc::new(SvgShape::new(id, shape, bitmap_source, self)))
+    }
+
+    fn render_offscreen(
+        &mut self,
+        handle: BitmapHandle,
+        commands: CommandList,
+        _quality: StageQuality,
+        bounds: PixelRegion,
+    ) -> Option<Box<dyn SyncHandle>> {
+        // The drawing is kept as vectors, so reading it back only sees the pixels underneath.
+        // That's also why `is_offscreen_supported` is false.
+        let bitmap = as_bitmap(&handle);
+        if !bitmap.add_overlay(commands) && !self.warned_about_overlays {
+            tracing::warn!("Too many drawings onto a bitmap, dropping the oldest ones");
+            self.warned_about_overlays = true;
+        }
+        let (data, bytes_per_row) = bitmap.to_rgba(bounds);
+        Some(Box::new(SvgSyncHandle {
+            data,
+            bytes_per_row,
+        }))
+    }
+
+    fn is_offscreen_supported(&self) -> bool {
+        // Offscreen rendering can't be read back, so don't cache display objects as bitmaps.
+        false
+    }
+
+    fn submit_frame(
+        &mut self,
+        clear: Color,
+        commands: CommandList,
+        cache_entries: Vec<BitmapCacheEntry>,
+    ) {
+        for entry in cache_entries {
+            if !entry.filters.is_empty() && !self.warned_about_filters {
+                tracing::warn!("Filters on cached bitmaps are not supported by the SVG renderer");
+                self.warned_about_filters = true;
+            }
+            let bitmap = as_bitmap(&entry.handle);
+            let mut overlay = CommandList::new();
+            if entry.clear.a > 0 {
+                overlay.draw_rect(
+                    entry.clear,
+                    Matrix::scale(bitmap.width() as f32, bitmap.height() as f32),
+                );
+            }
+            overlay.commands.extend(entry.commands.commands);
+            bitmap.set_overlays(vec![overlay]);
+        }
+
+        let mut document = Document::new(
+            self.viewport_dimensions.width,
+            self.viewport_dimensions.height,
+        );
+        commands.execute(&mut document);
+        self.last_frame = Some(document.finish(clear));
+    }
+
+    fn create_empty_texture(&mut self, width: u32, height: u32) -> Result<BitmapHandle, Error> {
+        if width == 0 || height == 0 {
+            return Err(Error::InvalidSize);
+        }
+        Ok(BitmapHandle(Arc::new(SvgBitmap::new(width, height))))
+    }
+
+    fn register_bitmap(&mut self, bitmap: Bitmap) -> Result<BitmapHandle, Error> {
+        if bitmap.width() == 0 || bitmap.height() == 0 {
+            return Err(Error::InvalidSize);
+        }
+        Ok(BitmapHandle(Arc::new(SvgBitmap::from_bitmap(bitmap))))
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: &BitmapHandle,
+        bitmap: Bitmap,
+        region: PixelRegion,
+    ) -> Result<(), Error> {
+        as_bitmap(handle).update(bitmap, region);
+        Ok(())
+    }
+
+    fn create_context3d(&mut self) -> Result<Box<dyn Context3D>, Error> {
+        Err(Error::Unimplemented("createContext3D".into()))
+    }
+
+    fn context3d_present(&mut self, _context: &mut dyn Context3D) -> Result<(), Error> {
+        Err(Error::Unimplemented("Context3D.present".into()))
+    }
+
+    fn debug_info(&self) -> Cow<'static, str> {
+        let result = [
+            "Renderer: svg".to_string(),
+            format!(
+                "Document size: {}x{}",
+                self.viewport_dimensions.width, self.viewport_dimensions.height
+            ),
+        ];
+        Cow::Owned(result.join("\n"))
+    }
+
+    fn name(&self) -> &'static str {
+        "svg"
+    }
+
+    fn set_quality(&mut self, quality: StageQuality) {
+        self.quality = quality;
+    }
+
+    fn compile_pixelbender_shader(
+        &mut self,
+        _shader: PixelBenderShader,
+    ) -> Result<PixelBenderShaderHandle, Error> {
+        Err(Error::Unimplemented(
+            "Pixel bender shader compilation".into(),
+        ))
+    }
+
+    fn run_pixelbender_shader(
+        &mut self,
+        _handle: PixelBenderShaderHandle,
+        _arguments: &[PixelBenderShaderArgument],
+        _target: BitmapHandle,
+    ) -> Result<Box<dyn SyncHandle>, Error> {
+        Err(Error::Unimplemented("Pixel bender shader".into()))
+    }
+}
@@ -0,0 +1,151 @@
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use ruffle_render::bitmap::{Bitmap, BitmapHandle, BitmapHandleImpl, PixelRegion};
+use ruffle_render::commands::CommandList;
+use ruffle_render::utils::{encode_png, unmultiply_alpha_rgba};
+use std::cell::{Ref, RefCell};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static NEXT_BITMAP_ID: AtomicU64 = AtomicU64::new(1);
+
+/// How many drawings are kept on top of a bitmap before the oldest ones are dropped.
+const MAX_OVERLAYS: usize = 64;
+
+/// A bitmap, along with everything that has been drawn onto it.
+///
+/// Drawing onto a bitmap can't be rasterized here, so the commands are kept instead,
+/// and drawn as vectors on top of the pixels wherever the bitmap is rendered.
+#[derive(Debug)]
+pub struct SvgBitmap {
+    id: u64,
+    width: u32,
+    height: u32,
+    /// Premultiplied RGBA pixels.
+    pixels: RefCell<Vec<u8>>,
+    /// Whether any pixels were ever uploaded, as opposed to an empty texture.
+    has_pixels: RefCell<bool>,
+    data_uri: RefCell<Option<String>>,
+    overlays: RefCell<Vec<CommandList>>,
+}
+
+impl BitmapHandleImpl for SvgBitmap {}
+
+impl SvgBitmap {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            id: NEXT_BITMAP_ID.fetch_add(1, Ordering::Relaxed),
+            width,
+            height,
+            pixels: RefCell::new(vec![0; width as usize * height as usize * 4]),
+            has_pixels: RefCell::new(false),
+            data_uri: RefCell::new(None),
+            overlays: RefCell::new(vec![]),
+        }
+    }
+
+    pub fn from_bitmap(bitmap: Bitmap) -> Self {
+        let this = Self::new(bitmap.width(), bitmap.height());
+        this.update(bitmap, PixelRegion::for_whole_size(this.width, this.height));
+        this
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// The id of the `<image>` element that holds the pixels of this bitmap.
+    pub fn element_id(&self) -> String {
+        format!("bitmap{}", self.id)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn has_pixels(&self) -> bool {
+        *self.has_pixels.borrow()
+    }
+
+    /// Copies the given region of `bitmap` into the same region of this bitmap.
+    pub fn update(&self, bitmap: Bitmap, mut region: PixelRegion) {
+        region.clamp(
+            self.width.min(bitmap.width()),
+            self.height.min(bitmap.height()),
+        );
+        // New pixels over the whole bitmap replace anything that was drawn onto it.
+        if region == PixelRegion::for_whole_size(self.width, self.height) {
+            self.overlays.borrow_mut().clear();
+        }
+
+        let bitmap = bitmap.to_rgba();
+        let data = bitmap.data();
+        let mut pixels = self.pixels.borrow_mut();
+        for y in region.y_min..region.y_max {
+            let src = (y * bitmap.width() + region.x_min) as usize * 4;
+            let dest = (y * self.width + region.x_min) as usize * 4;
+            let len = region.width() as usize * 4;
+            pixels[dest..dest + len].copy_from_slice(&data[src..src + len]);
+        }
+        *self.has_pixels.borrow_mut() = true;
+        *self.data_uri.borrow_mut() = None;
+    }
+
+    /// Returns the given region as premultiplied RGBA bytes, along with the bytes per row.
+    pub fn to_rgba(&self, mut region: PixelRegion) -> (Vec<u8>, u32) {
+        region.clamp(self.width, self.height);
+        let pixels = self.pixels.borrow();
+        let mut data = Vec::with_capacity(region.width() as usize * region.height() as usize * 4);
+        for y in region.y_min..region.y_max {
+            let start = (y * self.width + region.x_min) as usize * 4;
+            data.extend_from_slice(&pixels[start..start + region.width() as usize * 4]);
+        }
+        (data, region.width() * 4)
+    }
+
+    /// The pixels of this bitmap as a PNG data URI, encoded the first time it's needed.
+    pub fn data_uri(&self) -> String {
+        self.data_uri
+            .borrow_mut()
+            .get_or_insert_with(|| {
+                let mut rgba = self.pixels.borrow().clone();
+                unmultiply_alpha_rgba(&mut rgba);
+                match encode_png(self.width, self.height, &rgba, true, true) {
+                    Ok(png) => format!("data:image/png;base64,{}", BASE64_STANDARD.encode(png)),
+                    Err(e) => {
+                        tracing::error!("Couldn't encode bitmap as PNG: {e}");
+                        String::new()
+                    }
+                }
+            })
+            .clone()
+    }
+
+    pub fn overlays(&self) -> Ref<'_, Vec<CommandList>> {
+        self.overlays.borrow()
+    }
+
+    /// Replaces everything drawn onto this bitmap, such as when a cached bitmap is redrawn.
+    pub fn set_overlays(&self, overlays: Vec<CommandList>) {
+        *self.overlays.borrow_mut() = overlays;
+    }
+
+    /// Draws `commands` on top of this bitmap. Returns `false` if an older drawing had to be
+    /// dropped to stay within `MAX_OVERLAYS`.
+    pub fn add_overlay(&self, commands: CommandList) -> bool {
+        let mut overlays = self.overlays.borrow_mut();
+        let kept_all = overlays.len() < MAX_OVERLAYS;
+        if !kept_all {
+            overlays.remove(0);
+        }
+        overlays.push(commands);
+        kept_all
+    }
+}
+
+pub fn as_bitmap(handle: &BitmapHandle) -> &SvgBitmap {
+    <dyn BitmapHandleImpl>::downcast_ref(&*handle.0).expect("Bitmap handle must be an SVG bitmap")
+}
@@ -0,0 +1,397 @@
+use crate::bitmap::as_bitmap;
+use crate::shape::{as_shape, color_attributes, matrix_value, number, smoothing_attribute};
+use ruffle_render::backend::ShapeHandle;
+use ruffle_render::bitmap::{BitmapHandle, PixelSnapping};
+use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
+use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
+use ruffle_render::transform::Transform;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use swf::{BlendMode, Color, ColorTransform};
+
+#[derive(Debug, PartialEq)]
+enum LayerKind {
+    Root,
+    /// The content of a mask, which becomes a clip path once activated.
+    Mask,
+    /// Content clipped by the given clip path.
+    /// Masks nested inside another mask have no clip path, as SVG can't nest them.
+    Masked {
+        clip_id: Option<String>,
+    },
+    /// Drawing that clears a mask again, which has no equivalent here.
+    Discard,
+}
+
+struct Layer {
+    kind: LayerKind,
+    content: String,
+}
+
+/// Builds a single SVG document out of the commands of a frame.
+pub struct Document {
+    width: u32,
+    height: u32,
+    defs: String,
+    /// The ids of the shapes and bitmaps that are already in `defs`.
+    defined: HashSet<String>,
+    /// The filter ids of the color transforms used so far, by their matrix values.
+    color_filters: HashMap<String, String>,
+    layers: Vec<Layer>,
+    next_id: u32,
+    /// The bitmaps whose overlays are being drawn, to stop a bitmap that was drawn
+    /// onto itself from recursing forever.
+    drawing_overlays: Vec<u64>,
+    warned_about_blend_modes: bool,
+}
+
+impl Document {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            defs: String::new(),
+            defined: HashSet::new(),
+            color_filters: HashMap::new(),
+            layers: vec![Layer {
+                kind: LayerKind::Root,
+                content: String::new(),
+            }],
+            next_id: 0,
+            drawing_overlays: vec![],
+            warned_about_blend_modes: false,
+        }
+    }
+
+    /// Finishes the document, drawing everything on top of a `clear` background.
+    pub fn finish(mut self, clear: Color) -> String {
+        // Unbalanced masks shouldn't happen, but keep whatever was drawn if they do.
+        while self.layers.len() > 1 {
+            self.pop_layer();
+        }
+        let content = self.layers.pop().map(|layer| layer.content);
+
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+            self.width, self.height
+        );
+        if !self.defs.is_empty() {
+            let _ = write!(svg, "<defs>{}</defs>", self.defs);
+        }
+        if clear.a > 0 {
+            let _ = write!(
+                svg,
+                r#"<rect width="100%" height="100%" {}/>"#,
+                color_attributes("fill", clear)
+            );
+        }
+        svg.push_str(&content.unwrap_or_default());
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    fn next_id(&mut self, prefix: &str) -> String {
+        self.next_id += 1;
+        format!("{prefix}{}", self.next_id)
+    }
+
+    fn output(&mut self) -> &mut String {
+        &mut self
+            .layers
+            .last_mut()
+            .expect("The root layer is never popped")
+            .content
+    }
+
+    /// Whether drawing currently goes into a clip path, where only geometry counts.
+    fn in_clip_path(&self) -> bool {
+        self.layers
+            .iter()
+            .any(|layer| layer.kind == LayerKind::Mask)
+    }
+
+    fn push_layer(&mut self, kind: LayerKind) {
+        self.layers.push(Layer {
+            kind,
+            content: String::new(),
+        });
+    }
+
+    /// Pops the top layer, writing its content into the layer below where it's visible.
+    fn pop_layer(&mut self) {
+        if self.layers.len() <= 1 {
+            tracing::warn!("Unbalanced mask commands in SVG renderer");
+            return;
+        }
+        let Some(layer) = self.layers.pop() else {
+            return;
+        };
+        match layer.kind {
+            LayerKind::Masked {
+                clip_id: Some(clip_id),
+            } => {
+                let _ = write!(
+                    self.output(),
+                    r#"<g clip-path="url(#{clip_id})">{}</g>"#,
+                    layer.content
+                );
+            }
+            LayerKind::Masked { clip_id: None } => self.output().push_str(&layer.content),
+            LayerKind::Root | LayerKind::Mask | LayerKind::Discard => {}
+        }
+    }
+
+    fn define_bitmap(&mut self, bitmap: &BitmapHandle) {
+        let bitmap = as_bitmap(bitmap);
+        let id = bitmap.element_id();
+        if self.defined.insert(id.clone()) {
+            let _ = write!(
+                self.defs,
+                r#"<image id="{id}" width="{}" height="{}" preserveAspectRatio="none" href="{}"/>"#,
+                bitmap.width(),
+                bitmap.height(),
+                bitmap.data_uri()
+            );
+        }
+    }
+
+    /// Opens a group that applies `color_transform` to its content, if it does anything.
+    /// Returns whether a group was opened, which needs to be closed with `end_group`.
+    fn begin_color_transform(&mut self, color_transform: &ColorTransform) -> bool {
+        if self.in_clip_path() || *color_transform == ColorTransform::IDENTITY {
+            return false;
+        }
+
+        let multiply = color_transform.mult_rgba_normalized();
+        let add = color_transform.add_rgba_normalized();
+        if add == [0.0; 4] && multiply[..3] == [1.0; 3] {
+            let opacity = number(multiply[3].clamp(0.0, 1.0).into());
+            let _ = write!(self.output(), r#"<g opacity="{opacity}">"#);
+            return true;
+        }
+
+        let values = (0..4)
+            .map(|channel| {
+                let mut row = [0.0; 5];
+                row[channel] = multiply[channel];
+                row[4] = add[channel];
+                row.map(|value| number(value.into())).join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        let id = match self.color_filters.get(&values) {
+            Some(id) => id.clone(),
+            None => {
+                let id = self.next_id("color");
+                // Flash applies color transforms to sRGB values, not to linear ones.
+                let _ = write!(
+                    self.defs,
+                    r#"<filter id="{id}" color-interpolation-filters="sRGB"><feColorMatrix type="matrix" values="{values}"/></filter>"#
+                );
+                self.color_filters.insert(values, id.clone());
+                id
+            }
+        };
+        let _ = write!(self.output(), r#"<g filter="url(#{id})">"#);
+        true
+    }
+
+    fn end_group(&mut self, opened: bool) {
+        if opened {
+            self.output().push_str("</g>");
+        }
+    }
+}
+
+impl CommandHandler for Document {
+    fn render_bitmap(
+        &mut self,
+        bitmap: BitmapHandle,
+        transform: Transform,
+        smoothing: bool,
+        _pixel_snapping: PixelSnapping,
+    ) {
+        let svg_bitmap = as_bitmap(&bitmap);
+        let matrix = matrix_value(&transform.matrix);
+
+        if self.in_clip_path() {
+            let _ = write!(
+                self.output(),
+                r#"<rect width="{}" height="{}" transform="{matrix}"/>"#,
+                svg_bitmap.width(),
+                svg_bitmap.height()
+            );
+            return;
+        }
+
+        if svg_bitmap.has_pixels() {
+            self.define_bitmap(&bitmap);
+            let group = self.begin_color_transform(&transform.color_transform);
+            let _ = write!(
+                self.output(),
+                r##"<use href="#{}" transform="{matrix}"{}/>"##,
+                svg_bitmap.element_id(),
+                smoothing_attribute(smoothing)
+            );
+            self.end_group(group);
+        }
+
+        // Anything drawn onto the bitmap is drawn on top of it, cut to the bitmap's bounds.
+        let overlays = svg_bitmap.overlays().clone();
+        if overlays.is_empty() || self.drawing_overlays.contains(&svg_bitmap.id()) {
+            return;
+        }
+        let clip_id = self.next_id("clip");
+        let _ = write!(
+            self.defs,
+            r#"<clipPath id="{clip_id}"><rect width="{}" height="{}" transform="{matrix}"/></clipPath>"#,
+            svg_bitmap.width(),
+            svg_bitmap.height()
+        );
+        let _ = write!(self.output(), r#"<g clip-path="url(#{clip_id})">"#);
+        self.drawing_overlays.push(svg_bitmap.id());
+        for overlay in overlays {
+            overlay
+                .transformed(transform.matrix, transform.color_transform)
+                .execute(self);
+        }
+        self.drawing_overlays.pop();
+        self.output().push_str("</g>");
+    }
+
+    fn render_stage3d(&mut self, bitmap: BitmapHandle, transform: Transform) {
+        self.render_bitmap(bitmap, transform, true, PixelSnapping::Never);
+    }
+
+    fn render_shape(&mut self, shape: ShapeHandle, transform: Transform) {
+        let svg_shape = as_shape(&shape);
+        let matrix = matrix_value(&transform.matrix);
+
+        if self.in_clip_path() {
+            for path in &svg_shape.paths {
+                if let Some(fill_rule) = path.fill_rule {
+                    let _ = write!(
+                        self.output(),
+                        r#"<path transform="{matrix}" d="{}" clip-rule="{fill_rule}"/>"#,
+                        path.data
+                    );
+                }
+            }
+            return;
+        }
+
+        if self.defined.insert(format!("shape{}", svg_shape.id())) {
+            self.defs.push_str(&svg_shape.defs);
+            for bitmap in &svg_shape.bitmaps {
+                self.define_bitmap(bitmap);
+            }
+        }
+
+        let group = self.begin_color_transform(&transform.color_transform);
+        for path in &svg_shape.paths {
+            let _ = write!(
+                self.output(),
+                r#"<path transform="{matrix}" d="{}" {}/>"#,
+                path.data,
+                path.attributes
+            );
+        }
+        self.end_group(group);
+    }
+
+    fn draw_rect(&mut self, color: Color, matrix: Matrix) {
+        let matrix = matrix_value(&matrix);
+        let paint = if self.in_clip_path() {
+            String::new()
+        } else {
+            format!(" {}", color_attributes("fill", color))
+        };
+        let _ = write!(
+            self.output(),
+            r#"<rect width="1" height="1" transform="{matrix}"{paint}/>"#
+        );
+    }
+
+    fn push_mask(&mut self) {
+        self.push_layer(LayerKind::Mask);
+    }
+
+    fn activate_mask(&mut self) {
+        if self.layers.len() <= 1 {
+            tracing::warn!("Unbalanced mask commands in SVG renderer");
+            return;
+        }
+        let Some(mask) = self.layers.pop() else {
+            return;
+        };
+        let clip_id = if self.in_clip_path() {
+            None
+        } else {
+            let clip_id = self.next_id("mask");
+            let _ = write!(
+                self.defs,
+                r#"<clipPath id="{clip_id}">{}</clipPath>"#,
+                mask.content
+            );
+            Some(clip_id)
+        };
+        self.push_layer(LayerKind::Masked { clip_id });
+    }
+
+    fn deactivate_mask(&mut self) {
+        self.pop_layer();
+        self.push_layer(LayerKind::Discard);
+    }
+
+    fn pop_mask(&mut self) {
+        self.pop_layer();
+    }
+
+    fn blend(&mut self, commands: CommandList, blend_mode: RenderBlendMode) {
+        let style = match blend_mode {
+            RenderBlendMode::Builtin(BlendMode::Normal) => None,
+            RenderBlendMode::Builtin(BlendMode::Layer) => Some("isolation:isolate"),
+            RenderBlendMode::Builtin(BlendMode::Multiply) => Some("mix-blend-mode:multiply"),
+            RenderBlendMode::Builtin(BlendMode::Screen) => Some("mix-blend-mode:screen"),
+            RenderBlendMode::Builtin(BlendMode::Lighten) => Some("mix-blend-mode:lighten"),
+            RenderBlendMode::Builtin(BlendMode::Darken) => Some("mix-blend-mode:darken"),
+            RenderBlendMode::Builtin(BlendMode::Difference) => Some("mix-blend-mode:difference"),
+            RenderBlendMode::Builtin(BlendMode::Add) => Some("mix-blend-mode:plus-lighter"),
+            RenderBlendMode::Builtin(BlendMode::Overlay) => Some("mix-blend-mode:overlay"),
+            RenderBlendMode::Builtin(BlendMode::HardLight) => Some("mix-blend-mode:hard-light"),
+            blend_mode => {
+                if !self.warned_about_blend_modes {
+                    tracing::warn!(
+                        "Blend mode {blend_mode:?} is not supported by the SVG renderer"
+                    );
+                    self.warned_about_blend_modes = true;
+                }
+                None
+            }
+        };
+
+        match style {
+            Some(style) if !self.in_clip_path() => {
+                let _ = write!(self.output(), r#"<g style="{style}">"#);
+                commands.execute(self);
+                self.output().push_str("</g>");
+            }
+            _ => commands.execute(self),
+        }
+    }
+
+    fn render_projected(
+        &mut self,
+        commands: CommandList,
+        width: u32,
+        height: u32,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+    ) {
+        // SVG has no perspective, so approximate the projection with a 2D matrix.
+        if let Some(matrix) = matrix.affine_approximation(f64::from(width), f64::from(height)) {
+            commands.transformed(matrix, color_transform).execute(self);
+        }
+    }
+}
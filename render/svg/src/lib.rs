@@ -0,0 +1,16 @@
+//! A render backend that records each frame as an SVG document.
+//!
+//! Shapes are written out as vector paths, with real gradients and bitmap patterns,
+//! masks become clip paths, and color transforms become filters. Nothing is rasterized,
+//! which makes this useful for recovering the vector art of a movie.
+
+#![deny(clippy::unwrap_used)]
+// Bitmap handles need interior mutability, but are shared through an `Arc`
+#![allow(clippy::arc_with_non_send_sync)]
+
+pub mod backend;
+mod bitmap;
+mod document;
+mod shape;
+
+pub use backend::SvgRenderBackend;
@@ -0,0 +1,392 @@
+use crate::bitmap::as_bitmap;
+use ruffle_render::backend::{RenderBackend, ShapeHandle, ShapeHandleImpl};
+use ruffle_render::bitmap::{BitmapHandle, BitmapSource};
+use ruffle_render::matrix::Matrix;
+use ruffle_render::shape_utils::{DistilledShape, DrawCommand, DrawPath, FillRule};
+use std::fmt::Write;
+use swf::{
+    Color, FillStyle, Gradient, GradientInterpolation, GradientSpread, LineCapStyle, LineJoinStyle,
+    LineStyle, Twips,
+};
+
+/// Half the size of the gradient square, in pixels.
+/// SWF gradients span -16384 to 16384 twips before their matrix is applied.
+const GRADIENT_EXTENT: f64 = 16384.0 / Twips::TWIPS_PER_PIXEL as f64;
+
+#[derive(Debug)]
+pub struct SvgShape {
+    /// Gradients and bitmap patterns used by the paths of this shape.
+    /// These are in the coordinate space of the shape, like the paths themselves.
+    pub defs: String,
+    pub paths: Vec<SvgPath>,
+    /// The bitmaps that the patterns in `defs` refer to.
+    pub bitmaps: Vec<BitmapHandle>,
+    id: u64,
+}
+
+#[derive(Debug)]
+pub struct SvgPath {
+    pub data: String,
+    /// The paint attributes of this path, such as `fill` or `stroke-width`.
+    pub attributes: String,
+    /// The fill rule of a fill, or `None` for a stroke.
+    /// Strokes have no area of their own, so they're left out of clip paths.
+    pub fill_rule: Option<&'static str>,
+}
+
+impl ShapeHandleImpl for SvgShape {}
+
+impl SvgShape {
+    pub fn new(
+        id: u64,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+        backend: &mut dyn RenderBackend,
+    ) -> Self {
+        let mut converter = PaintConverter {
+            shape_id: id,
+            next_paint: 0,
+            defs: String::new(),
+            bitmaps: vec![],
+            bitmap_source,
+            backend,
+        };
+
+        let paths = shape
+            .paths
+            .into_iter()
+            .map(|path| match path {
+                DrawPath::Fill {
+                    style,
+                    commands,
+                    winding_rule,
+                } => {
+                    let fill_rule = match winding_rule {
+                        FillRule::EvenOdd => "evenodd",
+                        FillRule::NonZero => "nonzero",
+                    };
+                    let mut attributes = converter.paint("fill", style);
+                    let _ = write!(attributes, r#" fill-rule="{fill_rule}""#);
+                    SvgPath {
+                        data: path_data(&commands, false),
+                        attributes,
+                        fill_rule: Some(fill_rule),
+                    }
+                }
+                DrawPath::Stroke {
+                    style,
+                    commands,
+                    is_closed,
+                } => SvgPath {
+                    data: path_data(&commands, is_closed),
+                    attributes: converter.stroke(style),
+                    fill_rule: None,
+                },
+            })
+            .collect();
+
+        Self {
+            defs: converter.defs,
+            paths,
+            bitmaps: converter.bitmaps,
+            id,
+        }
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+}
+
+pub fn as_shape(handle: &ShapeHandle) -> &SvgShape {
+    <dyn ShapeHandleImpl>::downcast_ref(&*handle.0).expect("Shape handle must be an SVG shape")
+}
+
+/// Turns fill and line styles into paint attributes, collecting the paint servers they need.
+struct PaintConverter<'a> {
+    shape_id: u64,
+    next_paint: u32,
+    defs: String,
+    bitmaps: Vec<BitmapHandle>,
+    bitmap_source: &'a dyn BitmapSource,
+    backend: &'a mut dyn RenderBackend,
+}
+
+impl PaintConverter<'_> {
+    fn next_paint_id(&mut self) -> String {
+        self.next_paint += 1;
+        format!("shape{}-paint{}", self.shape_id, self.next_paint)
+    }
+
+    /// Returns the attributes for painting with `style`, as `fill` or `stroke`.
+    fn paint(&mut self, property: &str, style: &FillStyle) -> String {
+        match style {
+            FillStyle::Color(color) => color_attributes(property, *color),
+            FillStyle::LinearGradient(gradient) => {
+                let id = self.next_paint_id();
+                let _ = write!(
+                    self.defs,
+                    r#"<linearGradient id="{id}" x1="{}" y1="0" x2="{}" y2="0"{}>{}</linearGradient>"#,
+                    number(-GRADIENT_EXTENT),
+                    number(GRADIENT_EXTENT),
+                    gradient_attributes(gradient),
+                    gradient_stops(gradient),
+                );
+                format!(r#"{property}="url(#{id})""#)
+            }
+            FillStyle::RadialGradient(gradient) => self.radial_gradient(property, gradient, 0.0),
+            FillStyle::FocalGradient {
+                gradient,
+                focal_point,
+            } => self.radial_gradient(property, gradient, focal_point.to_f64()),
+            FillStyle::Bitmap {
+                id: bitmap_id,
+                matrix,
+                is_smoothed,
+                ..
+            } => {
+                let Some(handle) = self.bitmap_source.bitmap_handle(*bitmap_id, self.backend)
+                else {
+                    return format!(r#"{property}="none""#);
+                };
+                let bitmap = as_bitmap(&handle);
+                let id = self.next_paint_id();
+                // Bitmap fill matrices map bitmap pixels to twips.
+                let matrix = Matrix::from(*matrix);
+                let scale = 1.0 / Twips::TWIPS_PER_PIXEL as f32;
+                let matrix = Matrix {
+                    a: matrix.a * scale,
+                    b: matrix.b * scale,
+                    c: matrix.c * scale,
+                    d: matrix.d * scale,
+                    ..matrix
+                };
+                // SVG patterns always repeat, so non-repeating fills are approximated
+                // by repeating them as well, rather than clamping the edge pixels.
+                let _ = write!(
+                    self.defs,
+                    r##"<pattern id="{id}" patternUnits="userSpaceOnUse" width="{}" height="{}" patternTransform="{}"><use href="#{}"{}/></pattern>"##,
+                    bitmap.width(),
+                    bitmap.height(),
+                    matrix_value(&matrix),
+                    bitmap.element_id(),
+                    smoothing_attribute(*is_smoothed),
+                );
+                self.bitmaps.push(handle);
+                format!(r#"{property}="url(#{id})""#)
+            }
+        }
+    }
+
+    fn radial_gradient(&mut self, property: &str, gradient: &Gradient, focal_point: f64) -> String {
+        let id = self.next_paint_id();
+        let _ = write!(
+            self.defs,
+            r#"<radialGradient id="{id}" cx="0" cy="0" r="{}" fx="{}" fy="0"{}>{}</radialGradient>"#,
+            number(GRADIENT_EXTENT),
+            number(focal_point * GRADIENT_EXTENT),
+            gradient_attributes(gradient),
+            gradient_stops(gradient),
+        );
+        format!(r#"{property}="url(#{id})""#)
+    }
+
+    fn stroke(&mut self, style: &LineStyle) -> String {
+        let mut attributes = String::from(r#"fill="none" "#);
+        attributes.push_str(&self.paint("stroke", style.fill_style()));
+
+        // Hairlines are always one pixel wide, however the stroke is scaled.
+        let width = style.width().to_pixels();
+        let non_scaling = width == 0.0 || !style.allow_scale_x() || !style.allow_scale_y();
+        let _ = write!(
+            attributes,
+            r#" stroke-width="{}""#,
+            number(if width == 0.0 { 1.0 } else { width })
+        );
+        if non_scaling {
+            attributes.push_str(r#" vector-effect="non-scaling-stroke""#);
+        }
+
+        let cap = match style.start_cap() {
+            LineCapStyle::None => "butt",
+            LineCapStyle::Round => "round",
+            LineCapStyle::Square => "square",
+        };
+        let _ = write!(attributes, r#" stroke-linecap="{cap}""#);
+
+        match style.join_style() {
+            LineJoinStyle::Round => attributes.push_str(r#" stroke-linejoin="round""#),
+            LineJoinStyle::Bevel => attributes.push_str(r#" stroke-linejoin="bevel""#),
+            LineJoinStyle::Miter(limit) => {
+                let _ = write!(
+                    attributes,
+                    r#" stroke-linejoin="miter" stroke-miterlimit="{}""#,
+                    number(limit.to_f64().max(1.0))
+                );
+            }
+        }
+
+        attributes
+    }
+}
+
+fn gradient_attributes(gradient: &Gradient) -> String {
+    let spread = match gradient.spread {
+        GradientSpread::Pad => "pad",
+        GradientSpread::Reflect => "reflect",
+        GradientSpread::Repeat => "repeat",
+    };
+    let mut attributes = format!(
+        r#" gradientUnits="userSpaceOnUse" gradientTransform="{}" spreadMethod="{spread}""#,
+        matrix_value(&Matrix::from(gradient.matrix)),
+    );
+    if gradient.interpolation == GradientInterpolation::LinearRgb {
+        attributes.push_str(r#" color-interpolation="linearRGB""#);
+    }
+    attributes
+}
+
+fn gradient_stops(gradient: &Gradient) -> String {
+    let mut stops = String::new();
+    for record in &gradient.records {
+        let _ = write!(
+            stops,
+            r#"<stop offset="{}" stop-color="{}""#,
+            number(f64::from(record.ratio) / 255.0),
+            hex_color(record.color),
+        );
+        if record.color.a != 255 {
+            let _ = write!(
+                stops,
+                r#" stop-opacity="{}""#,
+                number(f64::from(record.color.a) / 255.0)
+            );
+        }
+        stops.push_str("/>");
+    }
+    stops
+}
+
+fn path_data(commands: &[DrawCommand], is_closed: bool) -> String {
+    let mut data = String::new();
+    let point = |data: &mut String, to: &swf::Point<Twips>| {
+        let _ = write!(
+            data,
+            "{} {}",
+            number(to.x.to_pixels()),
+            number(to.y.to_pixels())
+        );
+    };
+
+    for command in commands {
+        match command {
+            DrawCommand::MoveTo(to) => {
+                data.push('M');
+                point(&mut data, to);
+            }
+            DrawCommand::LineTo(to) => {
+                data.push('L');
+                point(&mut data, to);
+            }
+            DrawCommand::QuadraticCurveTo { control, anchor } => {
+                data.push('Q');
+                point(&mut data, control);
+                data.push(' ');
+                point(&mut data, anchor);
+            }
+            DrawCommand::CubicCurveTo {
+                control_a,
+                control_b,
+                anchor,
+            } => {
+                data.push('C');
+                point(&mut data, control_a);
+                data.push(' ');
+                point(&mut data, control_b);
+                data.push(' ');
+                point(&mut data, anchor);
+            }
+        }
+    }
+
+    if is_closed {
+        data.push('Z');
+    }
+    data
+}
+
+/// Formats a number with at most four decimal places, without trailing zeroes.
+pub fn number(value: f64) -> String {
+    let formatted = format!("{value:.4}");
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Formats a matrix as the value of a `transform` attribute.
+pub fn matrix_value(matrix: &Matrix) -> String {
+    format!(
+        "matrix({} {} {} {} {} {})",
+        number(matrix.a.into()),
+        number(matrix.b.into()),
+        number(matrix.c.into()),
+        number(matrix.d.into()),
+        number(matrix.tx.to_pixels()),
+        number(matrix.ty.to_pixels()),
+    )
+}
+
+pub fn hex_color(color: Color) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+}
+
+/// Returns the attributes for painting `property` with a solid color.
+pub fn color_attributes(property: &str, color: Color) -> String {
+    let mut attributes = format!(r#"{property}="{}""#, hex_color(color));
+    if color.a != 255 {
+        let _ = write!(
+            attributes,
+            r#" {property}-opacity="{}""#,
+            number(f64::from(color.a) / 255.0)
+        );
+    }
+    attributes
+}
+
+pub fn smoothing_attribute(smoothing: bool) -> &'static str {
+    if smoothing {
+        ""
+    } else {
+        r#" style="image-rendering:pixelated""#
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_numbers() {
+        assert_eq!(number(0.0), "0");
+        assert_eq!(number(-0.00001), "0");
+        assert_eq!(number(12.5), "12.5");
+        assert_eq!(number(-3.0), "-3");
+        assert_eq!(number(1.0 / 3.0), "0.3333");
+    }
+
+    #[test]
+    fn converts_path_commands_to_pixels() {
+        let commands = [
+            DrawCommand::MoveTo(swf::Point::new(Twips::new(0), Twips::new(0))),
+            DrawCommand::LineTo(swf::Point::new(Twips::new(200), Twips::new(10))),
+            DrawCommand::QuadraticCurveTo {
+                control: swf::Point::new(Twips::new(-20), Twips::new(40)),
+                anchor: swf::Point::new(Twips::new(0), Twips::new(0)),
+            },
+        ];
+        assert_eq!(path_data(&commands, true), "M0 0L10 0.5Q-1 2 0 0Z");
+    }
+}
@@ -12,6 +12,7 @@ use ruffle_render::bitmap::{
 };
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::error::Error as BitmapError;
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::shape_utils::{DistilledShape, GradientType};
 use ruffle_render::tessellator::{
@@ -21,7 +22,7 @@ use ruffle_render::transform::Transform;
 use ruffle_web_common::{JsError, JsResult};
 use std::borrow::Cow;
 use std::sync::Arc;
-use swf::{BlendMode, Color};
+use swf::{BlendMode, Color, ColorTransform};
 use thiserror::Error;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{
@@ -1512,6 +1513,20 @@ impl CommandHandler for WebGlRenderBackend {
         commands.execute(self);
         self.pop_blend_mode();
     }
+
+    fn render_projected(
+        &mut self,
+        commands: CommandList,
+        width: u32,
+        height: u32,
+        matrix: Matrix3D,
+        color_transform: ColorTransform,
+    ) {
+        // Perspective isn't supported here, so approximate the projection with a 2D matrix.
+        if let Some(matrix) = matrix.affine_approximation(f64::from(width), f64::from(height)) {
+            commands.transformed(matrix, color_transform).execute(self);
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -0,0 +1,81 @@
+struct Filter {
+    matrix: array<vec4<f32>, 64>,
+    default_color: vec4<f32>,
+    bias: f32,
+    divisor: f32,
+    matrix_x: u32,
+    matrix_y: u32,
+    source_x: i32,
+    source_y: i32,
+    source_width: i32,
+    source_height: i32,
+    clamp: u32,
+    preserve_alpha: u32,
+    _padding1: u32,
+    _padding2: u32,
+}
+
+@group(0) @binding(0) var texture: texture_2d<f32>;
+@group(0) @binding(1) var<uniform> filter_args: Filter;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+};
+
+struct VertexInput {
+    /// The position of the vertex in texture space (topleft 0,0, bottomright 1,1)
+    @location(0) position: vec2<f32>,
+
+    /// The coordinate of the source texture to sample in texture space (topleft 0,0, bottomright 1,1)
+    @location(1) uv: vec2<f32>,
+};
+
+@vertex
+fn main_vertex(in: VertexInput) -> VertexOutput {
+    // Convert texture space (topleft 0,0 to bottomright 1,1) to render space (topleft -1,1 to bottomright 1,-1)
+    let pos = vec4<f32>((in.position.x * 2.0 - 1.0), (1.0 - in.position.y * 2.0), 0.0, 1.0);
+    return VertexOutput(pos);
+}
+
+fn unmultiply(color: vec4<f32>) -> vec4<f32> {
+    if (color.a == 0.0) {
+        return vec4<f32>(0.0);
+    }
+    return vec4<f32>(color.rgb / color.a, color.a);
+}
+
+fn load_source(offset: vec2<i32>) -> vec4<f32> {
+    let is_clamped = filter_args.clamp > 0u;
+    var position = offset;
+    if (position.x < 0 || position.y < 0 || position.x >= filter_args.source_width || position.y >= filter_args.source_height) {
+        if (!is_clamped) {
+            return filter_args.default_color;
+        }
+        position = clamp(position, vec2<i32>(0), vec2<i32>(filter_args.source_width - 1, filter_args.source_height - 1));
+    }
+    let source = vec2<i32>(filter_args.source_x, filter_args.source_y);
+    return unmultiply(textureLoad(texture, source + position, 0));
+}
+
+@fragment
+fn main_fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let preserve_alpha = filter_args.preserve_alpha > 0u;
+    let center = vec2<i32>(floor(in.position.xy));
+    let half_size = vec2<i32>(i32(filter_args.matrix_x / 2u), i32(filter_args.matrix_y / 2u));
+
+    var total = vec4<f32>(0.0);
+    for (var y = 0u; y < filter_args.matrix_y; y++) {
+        for (var x = 0u; x < filter_args.matrix_x; x++) {
+            let index = y * filter_args.matrix_x + x;
+            let weight = filter_args.matrix[index / 4u][index % 4u];
+            let offset = vec2<i32>(i32(x), i32(y)) - half_size;
+            total += load_source(center + offset) * weight;
+        }
+    }
+
+    var color = saturate(total / filter_args.divisor + vec4<f32>(filter_args.bias));
+    if (preserve_alpha) {
+        color.a = load_source(center).a;
+    }
+    return vec4<f32>(color.rgb * color.a, color.a);
+}
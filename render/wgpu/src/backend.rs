@@ -415,7 +415,10 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
     fn create_context3d(
         &mut self,
     ) -> Result<Box<dyn ruffle_render::backend::Context3D>, BitmapError> {
-        Ok(Box::new(WgpuContext3D::new(self.descriptors.clone())))
+        Ok(Box::new(WgpuContext3D::new(
+            self.descriptors.clone(),
+            self.offscreen_buffer_pool.clone(),
+        )))
     }
 
     #[instrument(level = "debug", skip_all)]
@@ -831,9 +834,11 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 | Filter::GlowFilter(_)
                 | Filter::DropShadowFilter(_)
                 | Filter::ColorMatrixFilter(_)
-                | Filter::ShaderFilter(_)
                 | Filter::BevelFilter(_)
                 | Filter::DisplacementMapFilter(_)
+                | Filter::ConvolutionFilter(_)
+                | Filter::GradientGlowFilter(_)
+                | Filter::GradientBevelFilter(_)
         )
     }
 
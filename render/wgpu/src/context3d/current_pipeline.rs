@@ -75,6 +75,11 @@ pub struct CurrentPipeline {
     depth_mask: bool,
     pass_compare_mode: wgpu::CompareFunction,
 
+    stencil_front: StencilFaceState,
+    stencil_back: StencilFaceState,
+    stencil_read_mask: u32,
+    stencil_write_mask: u32,
+
     color_component: wgpu::BlendComponent,
     alpha_component: wgpu::BlendComponent,
 
@@ -151,6 +156,12 @@ impl CurrentPipeline {
 
             depth_mask: true,
             pass_compare_mode: wgpu::CompareFunction::LessEqual,
+
+            stencil_front: StencilFaceState::IGNORE,
+            stencil_back: StencilFaceState::IGNORE,
+            stencil_read_mask: 0xFF,
+            stencil_write_mask: 0xFF,
+
             color_component: wgpu::BlendComponent::REPLACE,
             alpha_component: wgpu::BlendComponent::REPLACE,
             sample_count: 1,
@@ -413,12 +424,11 @@ impl CurrentPipeline {
                 format: TextureFormat::Depth24PlusStencil8,
                 depth_write_enabled: self.depth_mask,
                 depth_compare: self.pass_compare_mode,
-                // FIXME - implement this
                 stencil: wgpu::StencilState {
-                    front: StencilFaceState::IGNORE,
-                    back: StencilFaceState::IGNORE,
-                    read_mask: !0,
-                    write_mask: !0,
+                    front: self.stencil_front,
+                    back: self.stencil_back,
+                    read_mask: self.stencil_read_mask,
+                    write_mask: self.stencil_write_mask,
                 },
                 bias: Default::default(),
             })
@@ -490,6 +500,32 @@ impl CurrentPipeline {
         Some((compiled, bind_group))
     }
 
+    pub fn update_stencil_actions(
+        &mut self,
+        triangle_face: Context3DTriangleFace,
+        face_state: StencilFaceState,
+    ) {
+        let (front, back) = match triangle_face {
+            Context3DTriangleFace::Front => (face_state, self.stencil_back),
+            Context3DTriangleFace::Back => (self.stencil_front, face_state),
+            Context3DTriangleFace::FrontAndBack => (face_state, face_state),
+            Context3DTriangleFace::None => return,
+        };
+        if front != self.stencil_front || back != self.stencil_back {
+            self.stencil_front = front;
+            self.stencil_back = back;
+            self.dirty.set(true);
+        }
+    }
+
+    pub fn update_stencil_masks(&mut self, read_mask: u32, write_mask: u32) {
+        if read_mask != self.stencil_read_mask || write_mask != self.stencil_write_mask {
+            self.stencil_read_mask = read_mask;
+            self.stencil_write_mask = write_mask;
+            self.dirty.set(true);
+        }
+    }
+
     pub fn set_culling(&mut self, face: Context3DTriangleFace) {
         self.culling = face;
         self.dirty.set(true);
@@ -1,8 +1,9 @@
 use ruffle_render::backend::{
     Context3D, Context3DBlendFactor, Context3DCommand, Context3DCompareMode,
-    Context3DTextureFormat, Context3DVertexBufferFormat, IndexBuffer, ProgramType, VertexBuffer,
+    Context3DStencilAction, Context3DTextureFormat, Context3DVertexBufferFormat, IndexBuffer,
+    ProgramType, VertexBuffer,
 };
-use ruffle_render::bitmap::{BitmapFormat, BitmapHandle};
+use ruffle_render::bitmap::{BitmapFormat, BitmapHandle, PixelRegion};
 use ruffle_render::error::Error;
 use std::borrow::Cow;
 use std::cell::Cell;
@@ -15,9 +16,11 @@ use wgpu::{
 };
 use wgpu::{CommandEncoder, Extent3d, RenderPass};
 
+use crate::buffer_pool::BufferPool;
 use crate::context3d::current_pipeline::{BoundTextureData, AGAL_FLOATS_PER_REGISTER};
 use crate::descriptors::Descriptors;
-use crate::Texture;
+use crate::utils::BufferDimensions;
+use crate::{as_texture, QueueSyncHandle, Texture};
 
 use std::num::NonZeroU64;
 use std::rc::Rc;
@@ -53,6 +56,9 @@ pub struct WgpuContext3D {
     // we need in individual `Arc`s.
     descriptors: Arc<Descriptors>,
 
+    // Used to read back pixels copied by `drawToBitmapData`.
+    offscreen_buffer_pool: Arc<BufferPool<wgpu::Buffer, BufferDimensions>>,
+
     buffer_staging_belt: StagingBelt,
 
     current_texture_view: Option<Rc<wgpu::TextureView>>,
@@ -95,10 +101,16 @@ pub struct WgpuContext3D {
     seen_clear_command: bool,
 
     scissor_rectangle: Option<Rectangle<Twips>>,
+    // The stencil reference value is dynamic render pass state in wgpu,
+    // so it's tracked here rather than in `CurrentPipeline`.
+    stencil_reference: u32,
 }
 
 impl WgpuContext3D {
-    pub fn new(descriptors: Arc<Descriptors>) -> Self {
+    pub fn new(
+        descriptors: Arc<Descriptors>,
+        offscreen_buffer_pool: Arc<BufferPool<wgpu::Buffer, BufferDimensions>>,
+    ) -> Self {
         let make_dummy_handle = || {
             let texture_label = create_debug_label!("Render target texture");
             let format = wgpu::TextureFormat::Rgba8Unorm;
@@ -141,6 +153,7 @@ impl WgpuContext3D {
 
         Self {
             descriptors,
+            offscreen_buffer_pool,
             buffer_staging_belt,
             back_buffer_raw_texture_handle,
             front_buffer_raw_texture_handle,
@@ -168,6 +181,7 @@ impl WgpuContext3D {
             clear_color: None,
             seen_clear_command: false,
             scissor_rectangle: None,
+            stencil_reference: 0,
         }
     }
 
@@ -287,6 +301,7 @@ impl WgpuContext3D {
             depth_stencil_attachment,
         });
         pass.set_bind_group(0, self.bind_group.as_ref().unwrap(), &[]);
+        pass.set_stencil_reference(self.stencil_reference);
         pass.set_pipeline(
             self.compiled_pipeline
                 .as_ref()
@@ -1046,6 +1061,97 @@ impl Context3D for WgpuContext3D {
                     },
                 );
             }
+            Context3DCommand::CopyBitmapHandleToTexture { source, dest } => {
+                let source = as_texture(&source);
+                let dest = dest.as_any().downcast_ref::<TextureWrapper>().unwrap();
+
+                // The source texture can be updated at any time (e.g. by decoding the next
+                // video frame), so submit the copy immediately rather than batching it with
+                // `buffer_command_encoder`.
+                let mut encoder = self.descriptors.device.create_command_encoder(
+                    &wgpu::CommandEncoderDescriptor {
+                        label: create_debug_label!("Context3D texture copy encoder").as_deref(),
+                    },
+                );
+                encoder.copy_texture_to_texture(
+                    source.texture.as_image_copy(),
+                    dest.texture.as_image_copy(),
+                    Extent3d {
+                        width: source.texture.width().min(dest.texture.width()),
+                        height: source.texture.height().min(dest.texture.height()),
+                        depth_or_array_layers: 1,
+                    },
+                );
+                self.descriptors.queue.submit(Some(encoder.finish()));
+            }
+            Context3DCommand::DrawToBitmapData {
+                destination,
+                source_region,
+                dest_point,
+                sync_handle,
+            } => {
+                let Some(back_buffer_size) = self.back_buffer_size else {
+                    // `configureBackBuffer` hasn't been called yet, so there's nothing to copy.
+                    return;
+                };
+                let back_buffer = as_texture(&self.back_buffer_raw_texture_handle);
+                let dest = as_texture(&destination);
+
+                let width = source_region
+                    .x_max
+                    .min(back_buffer_size.width)
+                    .saturating_sub(source_region.x_min)
+                    .min(dest.texture.width().saturating_sub(dest_point.0));
+                let height = source_region
+                    .y_max
+                    .min(back_buffer_size.height)
+                    .saturating_sub(source_region.y_min)
+                    .min(dest.texture.height().saturating_sub(dest_point.1));
+                if width == 0 || height == 0 {
+                    return;
+                }
+
+                let mut encoder = self.descriptors.device.create_command_encoder(
+                    &wgpu::CommandEncoderDescriptor {
+                        label: create_debug_label!("Context3D drawToBitmapData encoder").as_deref(),
+                    },
+                );
+                encoder.copy_texture_to_texture(
+                    wgpu::ImageCopyTexture {
+                        texture: &back_buffer.texture,
+                        mip_level: 0,
+                        origin: wgpu::Origin3d {
+                            x: source_region.x_min,
+                            y: source_region.y_min,
+                            z: 0,
+                        },
+                        aspect: wgpu::TextureAspect::All,
+                    },
+                    wgpu::ImageCopyTexture {
+                        texture: &dest.texture,
+                        mip_level: 0,
+                        origin: wgpu::Origin3d {
+                            x: dest_point.0,
+                            y: dest_point.1,
+                            z: 0,
+                        },
+                        aspect: wgpu::TextureAspect::All,
+                    },
+                    Extent3d {
+                        width,
+                        height,
+                        depth_or_array_layers: 1,
+                    },
+                );
+                self.descriptors.queue.submit(Some(encoder.finish()));
+
+                *sync_handle = Some(Box::new(QueueSyncHandle::NotCopied {
+                    handle: destination,
+                    copy_area: PixelRegion::for_region(dest_point.0, dest_point.1, width, height),
+                    descriptors: self.descriptors.clone(),
+                    pool: self.offscreen_buffer_pool.clone(),
+                }));
+            }
             Context3DCommand::SetTextureAt {
                 sampler,
                 texture,
@@ -1090,17 +1196,33 @@ impl Context3D for WgpuContext3D {
                 depth_mask,
                 pass_compare_mode,
             } => {
-                let function = match pass_compare_mode {
-                    Context3DCompareMode::Always => wgpu::CompareFunction::Always,
-                    Context3DCompareMode::Equal => wgpu::CompareFunction::Equal,
-                    Context3DCompareMode::Greater => wgpu::CompareFunction::Greater,
-                    Context3DCompareMode::GreaterEqual => wgpu::CompareFunction::GreaterEqual,
-                    Context3DCompareMode::Less => wgpu::CompareFunction::Less,
-                    Context3DCompareMode::LessEqual => wgpu::CompareFunction::LessEqual,
-                    Context3DCompareMode::Never => wgpu::CompareFunction::Never,
-                    Context3DCompareMode::NotEqual => wgpu::CompareFunction::NotEqual,
+                self.current_pipeline
+                    .update_depth(depth_mask, convert_compare_mode(pass_compare_mode));
+            }
+            Context3DCommand::SetStencilActions {
+                triangle_face,
+                compare_mode,
+                action_on_both_pass,
+                action_on_depth_fail,
+                action_on_depth_pass_stencil_fail,
+            } => {
+                let face_state = wgpu::StencilFaceState {
+                    compare: convert_compare_mode(compare_mode),
+                    fail_op: convert_stencil_action(action_on_depth_pass_stencil_fail),
+                    depth_fail_op: convert_stencil_action(action_on_depth_fail),
+                    pass_op: convert_stencil_action(action_on_both_pass),
                 };
-                self.current_pipeline.update_depth(depth_mask, function);
+                self.current_pipeline
+                    .update_stencil_actions(triangle_face, face_state);
+            }
+            Context3DCommand::SetStencilReferenceValue {
+                reference_value,
+                read_mask,
+                write_mask,
+            } => {
+                self.stencil_reference = reference_value;
+                self.current_pipeline
+                    .update_stencil_masks(read_mask, write_mask);
             }
             Context3DCommand::SetBlendFactors {
                 source_factor,
@@ -1188,6 +1310,32 @@ pub struct ClearColor {
     mask: u32,
 }
 
+fn convert_compare_mode(mode: Context3DCompareMode) -> wgpu::CompareFunction {
+    match mode {
+        Context3DCompareMode::Always => wgpu::CompareFunction::Always,
+        Context3DCompareMode::Equal => wgpu::CompareFunction::Equal,
+        Context3DCompareMode::Greater => wgpu::CompareFunction::Greater,
+        Context3DCompareMode::GreaterEqual => wgpu::CompareFunction::GreaterEqual,
+        Context3DCompareMode::Less => wgpu::CompareFunction::Less,
+        Context3DCompareMode::LessEqual => wgpu::CompareFunction::LessEqual,
+        Context3DCompareMode::Never => wgpu::CompareFunction::Never,
+        Context3DCompareMode::NotEqual => wgpu::CompareFunction::NotEqual,
+    }
+}
+
+fn convert_stencil_action(action: Context3DStencilAction) -> wgpu::StencilOperation {
+    match action {
+        Context3DStencilAction::DecrementSaturate => wgpu::StencilOperation::DecrementClamp,
+        Context3DStencilAction::DecrementWrap => wgpu::StencilOperation::DecrementWrap,
+        Context3DStencilAction::IncrementSaturate => wgpu::StencilOperation::IncrementClamp,
+        Context3DStencilAction::IncrementWrap => wgpu::StencilOperation::IncrementWrap,
+        Context3DStencilAction::Invert => wgpu::StencilOperation::Invert,
+        Context3DStencilAction::Keep => wgpu::StencilOperation::Keep,
+        Context3DStencilAction::Set => wgpu::StencilOperation::Replace,
+        Context3DStencilAction::Zero => wgpu::StencilOperation::Zero,
+    }
+}
+
 fn convert_texture_format(input: Context3DTextureFormat) -> Result<wgpu::TextureFormat, Error> {
     match input {
         // All of these formats are unsupported by wgpu to various degrees:
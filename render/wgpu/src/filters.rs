@@ -1,22 +1,26 @@
 mod bevel;
 mod blur;
 mod color_matrix;
+mod convolution;
 mod displacement_map;
 mod drop_shadow;
 mod glow;
+mod gradient;
+mod gradient_bevel;
+mod gradient_glow;
 mod shader;
 
-use std::collections::HashSet;
-use std::sync::{Mutex, OnceLock};
-
 use crate::buffer_pool::TexturePool;
 use crate::descriptors::Descriptors;
 use crate::filters::bevel::BevelFilter;
 use crate::filters::blur::BlurFilter;
 use crate::filters::color_matrix::ColorMatrixFilter;
+use crate::filters::convolution::ConvolutionFilter;
 use crate::filters::displacement_map::DisplacementMapFilter;
 use crate::filters::drop_shadow::DropShadowFilter;
 use crate::filters::glow::GlowFilter;
+use crate::filters::gradient_bevel::GradientBevelFilter;
+use crate::filters::gradient_glow::GradientGlowFilter;
 use crate::filters::shader::ShaderFilter;
 use crate::surface::target::CommandTarget;
 use bytemuck::{Pod, Zeroable};
@@ -209,6 +213,9 @@ pub struct Filters {
     pub glow: GlowFilter,
     pub bevel: BevelFilter,
     pub displacement_map: DisplacementMapFilter,
+    pub convolution: ConvolutionFilter,
+    pub gradient_glow: GradientGlowFilter,
+    pub gradient_bevel: GradientBevelFilter,
 }
 
 impl Filters {
@@ -220,6 +227,9 @@ impl Filters {
             glow: GlowFilter::new(device),
             bevel: BevelFilter::new(device),
             displacement_map: DisplacementMapFilter::new(device),
+            convolution: ConvolutionFilter::new(device),
+            gradient_glow: GradientGlowFilter::new(device),
+            gradient_bevel: GradientBevelFilter::new(device),
         }
     }
 
@@ -231,85 +241,85 @@ impl Filters {
         source: FilterSource,
         filter: Filter,
     ) -> CommandTarget {
-        let target =
-            match filter {
-                Filter::ColorMatrixFilter(filter) => Some(descriptors.filters.color_matrix.apply(
-                    descriptors,
-                    texture_pool,
-                    draw_encoder,
-                    &source,
-                    &filter,
-                )),
-                Filter::BlurFilter(filter) => descriptors.filters.blur.apply(
-                    descriptors,
-                    texture_pool,
-                    draw_encoder,
-                    &source,
-                    &filter,
-                ),
-                Filter::ShaderFilter(shader) => Some(descriptors.filters.shader.apply(
-                    descriptors,
-                    texture_pool,
-                    draw_encoder,
-                    &source,
-                    shader,
-                )),
-                Filter::GlowFilter(filter) => Some(descriptors.filters.glow.apply(
-                    descriptors,
-                    texture_pool,
-                    draw_encoder,
-                    &source,
-                    &filter,
-                    &self.blur,
-                    (0.0, 0.0),
-                )),
-                Filter::DropShadowFilter(filter) => Some(DropShadowFilter::apply(
-                    descriptors,
-                    texture_pool,
-                    draw_encoder,
-                    &source,
-                    &filter,
-                    &self.blur,
-                    &self.glow,
-                )),
-                Filter::BevelFilter(filter) => Some(descriptors.filters.bevel.apply(
-                    descriptors,
-                    texture_pool,
-                    draw_encoder,
-                    &source,
-                    &filter,
-                    &self.blur,
-                )),
-                Filter::DisplacementMapFilter(filter) => descriptors
-                    .filters
-                    .displacement_map
-                    .apply(descriptors, texture_pool, draw_encoder, &source, &filter),
-                filter => {
-                    static WARNED_FILTERS: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
-                    let name = match filter {
-                        Filter::GradientGlowFilter(_) => "GradientGlowFilter",
-                        Filter::GradientBevelFilter(_) => "GradientBevelFilter",
-                        Filter::ConvolutionFilter(_) => "ConvolutionFilter",
-                        Filter::ColorMatrixFilter(_)
-                        | Filter::BlurFilter(_)
-                        | Filter::GlowFilter(_)
-                        | Filter::DropShadowFilter(_)
-                        | Filter::BevelFilter(_)
-                        | Filter::DisplacementMapFilter(_)
-                        | Filter::ShaderFilter(_) => unreachable!(),
-                    };
-                    // Only warn once per filter type
-                    if WARNED_FILTERS
-                        .get_or_init(Default::default)
-                        .lock()
-                        .unwrap()
-                        .insert(name)
-                    {
-                        tracing::warn!("Unsupported filter {filter:?}");
-                    }
-                    None
-                }
-            };
+        let target = match filter {
+            Filter::ColorMatrixFilter(filter) => Some(descriptors.filters.color_matrix.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                &source,
+                &filter,
+            )),
+            Filter::BlurFilter(filter) => descriptors.filters.blur.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                &source,
+                &filter,
+            ),
+            Filter::ShaderFilter(shader) => Some(descriptors.filters.shader.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                &source,
+                shader,
+            )),
+            Filter::GlowFilter(filter) => Some(descriptors.filters.glow.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                &source,
+                &filter,
+                &self.blur,
+                (0.0, 0.0),
+            )),
+            Filter::DropShadowFilter(filter) => Some(DropShadowFilter::apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                &source,
+                &filter,
+                &self.blur,
+                &self.glow,
+            )),
+            Filter::BevelFilter(filter) => Some(descriptors.filters.bevel.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                &source,
+                &filter,
+                &self.blur,
+            )),
+            Filter::DisplacementMapFilter(filter) => descriptors.filters.displacement_map.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                &source,
+                &filter,
+            ),
+            Filter::ConvolutionFilter(filter) => descriptors.filters.convolution.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                &source,
+                &filter,
+            ),
+            Filter::GradientGlowFilter(filter) => Some(descriptors.filters.gradient_glow.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                &source,
+                &filter,
+                &self.blur,
+            )),
+            Filter::GradientBevelFilter(filter) => Some(descriptors.filters.gradient_bevel.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                &source,
+                &filter,
+                &self.blur,
+            )),
+        };
 
         let target = target.unwrap_or_else(|| {
             // Apply a default color matrix - it's essentially a blit
@@ -0,0 +1,64 @@
+use crate::descriptors::Descriptors;
+use ruffle_render::filters::{gradient_lut, GRADIENT_LUT_SIZE};
+use std::sync::{Arc, Mutex};
+use swf::GradientRecord;
+use wgpu::util::DeviceExt;
+
+/// Keeps the lookup texture of the most recently used gradient, as the same filter is usually
+/// applied again every frame.
+#[derive(Default)]
+pub struct GradientTextureCache {
+    last: Mutex<Option<(Vec<GradientRecord>, Arc<wgpu::TextureView>)>>,
+}
+
+impl GradientTextureCache {
+    pub fn get(
+        &self,
+        descriptors: &Descriptors,
+        records: &[GradientRecord],
+    ) -> Arc<wgpu::TextureView> {
+        let mut last = self
+            .last
+            .lock()
+            .expect("Gradient texture cache lock poisoned");
+        match &*last {
+            Some((last_records, view)) if last_records.as_slice() == records => view.clone(),
+            _ => {
+                let view = Arc::new(create_gradient_texture(descriptors, records));
+                *last = Some((records.to_vec(), view.clone()));
+                view
+            }
+        }
+    }
+}
+
+/// Creates a lookup texture for the colors of a `GradientGlowFilter` or `GradientBevelFilter`.
+fn create_gradient_texture(
+    descriptors: &Descriptors,
+    records: &[GradientRecord],
+) -> wgpu::TextureView {
+    let colors: Vec<u8> = gradient_lut(records)
+        .iter()
+        .flat_map(|color| [color.r, color.g, color.b, color.a])
+        .collect();
+
+    let texture = descriptors.device.create_texture_with_data(
+        &descriptors.queue,
+        &wgpu::TextureDescriptor {
+            label: create_debug_label!("Gradient filter colors").as_deref(),
+            size: wgpu::Extent3d {
+                width: GRADIENT_LUT_SIZE as u32,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        },
+        &colors,
+    );
+    texture.create_view(&Default::default())
+}
@@ -26,6 +26,9 @@ pub struct Shaders {
     pub glow_filter: wgpu::ShaderModule,
     pub bevel_filter: wgpu::ShaderModule,
     pub displacement_map_filter: wgpu::ShaderModule,
+    pub convolution_filter: wgpu::ShaderModule,
+    pub gradient_glow_filter: wgpu::ShaderModule,
+    pub gradient_bevel_filter: wgpu::ShaderModule,
 }
 
 impl Shaders {
@@ -111,6 +114,27 @@ impl Shaders {
             "filter/displacement_map.wgsl",
             include_str!("../shaders/filter/displacement_map.wgsl"),
         );
+        let convolution_filter = make_shader(
+            device,
+            &mut composer,
+            &shader_defs,
+            "filter/convolution.wgsl",
+            include_str!("../shaders/filter/convolution.wgsl"),
+        );
+        let gradient_glow_filter = make_shader(
+            device,
+            &mut composer,
+            &shader_defs,
+            "filter/gradient_glow.wgsl",
+            include_str!("../shaders/filter/gradient_glow.wgsl"),
+        );
+        let gradient_bevel_filter = make_shader(
+            device,
+            &mut composer,
+            &shader_defs,
+            "filter/gradient_bevel.wgsl",
+            include_str!("../shaders/filter/gradient_bevel.wgsl"),
+        );
         let gradient_shader = make_shader(
             device,
             &mut composer,
@@ -144,6 +168,9 @@ impl Shaders {
             glow_filter,
             bevel_filter,
             displacement_map_filter,
+            convolution_filter,
+            gradient_glow_filter,
+            gradient_bevel_filter,
         }
     }
 }
@@ -13,6 +13,7 @@ use ruffle_render::backend::ShapeHandle;
 use ruffle_render::bitmap::{BitmapHandle, PixelSnapping};
 use ruffle_render::commands::{Command, RenderBlendMode};
 use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::pixel_bender::PixelBenderShaderHandle;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::transform::Transform;
@@ -96,6 +97,12 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
                 transform,
                 blend_mode,
             } => self.render_texture(transform, binds, *blend_mode),
+            DrawCommand::RenderProjected {
+                _texture,
+                binds,
+                world_matrix,
+                color_transform,
+            } => self.render_projected(binds, world_matrix, color_transform),
             DrawCommand::RenderShape { shape, transform } => self.render_shape(shape, transform),
             DrawCommand::DrawRect { color, matrix } => self.draw_rect(color, matrix),
             DrawCommand::PushMask => self.push_mask(),
@@ -195,7 +202,14 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
                 1.0,
             ],
         ];
+        self.apply_world_matrix(world_matrix, color_adjustments);
+    }
 
+    pub fn apply_world_matrix(
+        &mut self,
+        world_matrix: [[f32; 4]; 4],
+        color_adjustments: &ColorTransform,
+    ) {
         if self.descriptors.limits.max_push_constant_size > 0 {
             self.render_pass.set_push_constants(
                 wgpu::ShaderStages::VERTEX_FRAGMENT,
@@ -299,6 +313,30 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
         }
     }
 
+    pub fn render_projected(
+        &mut self,
+        bind_group: &'frame wgpu::BindGroup,
+        world_matrix: &[[f32; 4]; 4],
+        color_transform: &ColorTransform,
+    ) {
+        if cfg!(feature = "render_debug_labels") {
+            self.render_pass.push_debug_group("render_projected");
+        }
+        self.prep_bitmap(bind_group, TrivialBlend::Normal, false);
+        // The `w` component of the world matrix makes the GPU interpolate the texture
+        // coordinates with the correct perspective.
+        self.apply_world_matrix(*world_matrix, color_transform);
+
+        self.draw(
+            self.descriptors.quad.vertices_pos.slice(..),
+            self.descriptors.quad.indices.slice(..),
+            6,
+        );
+        if cfg!(feature = "render_debug_labels") {
+            self.render_pass.pop_debug_group();
+        }
+    }
+
     pub fn render_shape(&mut self, shape: &'frame ShapeHandle, transform: &Transform) {
         if cfg!(feature = "render_debug_labels") {
             self.render_pass.push_debug_group("render_shape");
@@ -441,6 +479,12 @@ pub enum DrawCommand {
         transform: Transform,
         blend_mode: TrivialBlend,
     },
+    RenderProjected {
+        _texture: PoolOrArcTexture,
+        binds: wgpu::BindGroup,
+        world_matrix: [[f32; 4]; 4],
+        color_transform: ColorTransform,
+    },
     RenderShape {
         shape: ShapeHandle,
         transform: Transform,
@@ -522,36 +566,7 @@ pub fn chunk_blends<'a>(
                             color_transform: Default::default(),
                         };
                         let texture = target.take_color_texture();
-                        let bind_group =
-                            descriptors
-                                .device
-                                .create_bind_group(&wgpu::BindGroupDescriptor {
-                                    layout: &descriptors.bind_layouts.bitmap,
-                                    entries: &[
-                                        wgpu::BindGroupEntry {
-                                            binding: 0,
-                                            resource: descriptors
-                                                .quad
-                                                .texture_transforms
-                                                .as_entire_binding(),
-                                        },
-                                        wgpu::BindGroupEntry {
-                                            binding: 1,
-                                            resource: wgpu::BindingResource::TextureView(
-                                                texture.view(),
-                                            ),
-                                        },
-                                        wgpu::BindGroupEntry {
-                                            binding: 2,
-                                            resource: wgpu::BindingResource::Sampler(
-                                                descriptors
-                                                    .bitmap_samplers
-                                                    .get_sampler(false, false),
-                                            ),
-                                        },
-                                    ],
-                                    label: None,
-                                });
+                        let bind_group = texture_bind_group(descriptors, &texture, false);
                         current.push(DrawCommand::RenderTexture {
                             _texture: texture,
                             binds: bind_group,
@@ -577,6 +592,52 @@ pub fn chunk_blends<'a>(
                     }
                 }
             }
+            Command::RenderProjected {
+                commands,
+                width,
+                height,
+                matrix,
+                color_transform,
+            } => {
+                let max_size = descriptors.limits.max_texture_dimension_2d;
+                if width > max_size || height > max_size {
+                    tracing::warn!(
+                        "Skipping 3D projected object of {width} x {height}, larger than the maximum texture size of {max_size}"
+                    );
+                    continue;
+                }
+                let mut surface = Surface::new(
+                    descriptors,
+                    quality,
+                    width,
+                    height,
+                    wgpu::TextureFormat::Rgba8Unorm,
+                );
+                let target = surface.draw_commands(
+                    RenderTargetMode::FreshWithColor(wgpu::Color::TRANSPARENT),
+                    descriptors,
+                    meshes,
+                    commands,
+                    uniform_buffers,
+                    color_buffers,
+                    uniform_encoder,
+                    draw_encoder,
+                    nearest_layer,
+                    texture_pool,
+                );
+                target.ensure_cleared(draw_encoder);
+
+                let texture = target.take_color_texture();
+                let bind_group = texture_bind_group(descriptors, &texture, true);
+                // The quad covers the unit square, so stretch it over the whole texture first.
+                let matrix = matrix * Matrix3D::scale(f64::from(width), f64::from(height), 1.0);
+                current.push(DrawCommand::RenderProjected {
+                    _texture: texture,
+                    binds: bind_group,
+                    world_matrix: matrix.to_f32_columns(),
+                    color_transform,
+                })
+            }
             Command::RenderBitmap {
                 bitmap,
                 transform,
@@ -633,3 +694,33 @@ pub fn chunk_blends<'a>(
 
     result
 }
+
+/// Creates a bind group for drawing an intermediate texture with the bitmap pipeline.
+fn texture_bind_group(
+    descriptors: &Descriptors,
+    texture: &PoolOrArcTexture,
+    smoothing: bool,
+) -> wgpu::BindGroup {
+    descriptors
+        .device
+        .create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &descriptors.bind_layouts.bitmap,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: descriptors.quad.texture_transforms.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(texture.view()),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(
+                        descriptors.bitmap_samplers.get_sampler(false, smoothing),
+                    ),
+                },
+            ],
+            label: None,
+        })
+}
@@ -469,29 +469,11 @@ impl<'a> Reader<'a> {
                 Tag::EnableTelemetry { password_hash }
             }
             TagCode::ImportAssets => {
-                let url = tag_reader.read_str()?;
-                let num_imports = tag_reader.read_u16()?;
-                let mut imports = Vec::with_capacity(num_imports as usize);
-                for _ in 0..num_imports {
-                    imports.push(ExportedAsset {
-                        id: tag_reader.read_u16()?,
-                        name: tag_reader.read_str()?,
-                    });
-                }
+                let (url, imports) = tag_reader.read_import_assets()?;
                 Tag::ImportAssets { url, imports }
             }
             TagCode::ImportAssets2 => {
-                let url = tag_reader.read_str()?;
-                tag_reader.read_u8()?; // Reserved; must be 1
-                tag_reader.read_u8()?; // Reserved; must be 0
-                let num_imports = tag_reader.read_u16()?;
-                let mut imports = Vec::with_capacity(num_imports as usize);
-                for _ in 0..num_imports {
-                    imports.push(ExportedAsset {
-                        id: tag_reader.read_u16()?,
-                        name: tag_reader.read_str()?,
-                    });
-                }
+                let (url, imports) = tag_reader.read_import_assets_2()?;
                 Tag::ImportAssets { url, imports }
             }
 
@@ -1858,6 +1840,20 @@ impl<'a> Reader<'a> {
         Ok(exports)
     }
 
+    pub fn read_import_assets(&mut self) -> Result<(&'a SwfStr, ExportAssets<'a>)> {
+        let url = self.read_str()?;
+        let imports = self.read_export_assets()?;
+        Ok((url, imports))
+    }
+
+    pub fn read_import_assets_2(&mut self) -> Result<(&'a SwfStr, ExportAssets<'a>)> {
+        let url = self.read_str()?;
+        self.read_u8()?; // Reserved; must be 1
+        self.read_u8()?; // Reserved; must be 0
+        let imports = self.read_export_assets()?;
+        Ok((url, imports))
+    }
+
     pub fn read_place_object(&mut self) -> Result<PlaceObject<'a>> {
         Ok(PlaceObject {
             version: 1,
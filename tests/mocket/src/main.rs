@@ -1,6 +1,6 @@
 use anyhow::Error;
 use clap::Parser;
-use ruffle_socket_format::SocketEvent;
+use ruffle_socket_format::{SocketEvent, POLICY_FILE_REQUEST};
 use std::{
     io::{Read, Write},
     net::TcpListener,
@@ -8,11 +8,13 @@ use std::{
 };
 use tracing_subscriber::filter::{EnvFilter, LevelFilter};
 
-static POLICY: &[u8] = b"<?xml version=\"1.0\"?>
+static DEFAULT_POLICY: &str = "<?xml version=\"1.0\"?>
 <!DOCTYPE cross-domain-policy SYSTEM \"http://www.adobe.com/xml/dtds/cross-domain-policy.dtd\">
 <cross-domain-policy>
 <allow-access-from domain=\"*\" to-ports=\"*\"/>
-</cross-domain-policy>\0";
+</cross-domain-policy>";
+
+const PORT: u16 = 8001;
 
 #[derive(Parser, Debug)]
 struct Opt {
@@ -34,19 +36,25 @@ fn main() -> Result<(), Error> {
     // Ignore error if it's already been set
     let _ = tracing::subscriber::set_global_default(subscriber);
 
-    let events = SocketEvent::from_file(opt.file_path)?;
+    let (mut policies, events) =
+        SocketEvent::split_policies(SocketEvent::from_file(opt.file_path)?);
     let event_count = events.len();
 
-    let listener = TcpListener::bind("0.0.0.0:8001")?;
+    let listener = TcpListener::bind(("0.0.0.0", PORT))?;
     tracing::info!("Listening on {}", listener.local_addr()?);
-    let (mut stream, addr) = listener.accept()?;
-    tracing::info!("Incoming connection from {}", addr);
 
-    // Handle socket policy stuff. (Required as Flash Player wont want to connect otherwise.)
-    let mut buffer = [0; 4096];
-    let _ = stream.read(&mut buffer);
-    stream.write_all(POLICY)?;
-    tracing::info!("Policy sent successfully!");
+    // Flash Player won't connect before it gets a policy file, so allow everything
+    // unless the test says otherwise.
+    if policies.is_empty() {
+        policies.push((PORT, DEFAULT_POLICY.to_string()));
+    }
+    for (port, policy) in policies {
+        if port == PORT {
+            serve_policy(&listener, &policy)?;
+        } else {
+            serve_policy(&TcpListener::bind(("0.0.0.0", port))?, &policy)?;
+        }
+    }
 
     // Now we listen again as flash reopens socket connection.
     let (mut stream, addr) = listener.accept()?;
@@ -120,8 +128,35 @@ fn main() -> Result<(), Error> {
                 drop(stream);
                 break;
             }
+            SocketEvent::Policy { .. } => unreachable!("Policy files are served before"),
         }
     }
 
     Ok(())
 }
+
+/// Answers a single policy file request, then closes the connection.
+fn serve_policy(listener: &TcpListener, policy: &str) -> Result<(), Error> {
+    let (mut stream, addr) = listener.accept()?;
+    tracing::info!("Incoming policy file request from {}", addr);
+
+    let mut request = vec![];
+    let mut buffer = [0; 4096];
+    while !request.ends_with(b"\0") {
+        match stream.read(&mut buffer) {
+            Err(_) | Ok(0) => break,
+            Ok(read) => request.extend_from_slice(&buffer[..read]),
+        }
+    }
+    if request != POLICY_FILE_REQUEST {
+        tracing::error!(
+            "Expected a policy file request, but received: {:?}",
+            String::from_utf8_lossy(&request)
+        );
+    }
+
+    stream.write_all(policy.as_bytes())?;
+    stream.write_all(b"\0")?;
+    tracing::info!("Policy sent successfully!");
+    Ok(())
+}
@@ -14,8 +14,14 @@ pub enum SocketEvent {
     WaitForDisconnect,
     /// Disconnect the client.
     Disconnect,
+    /// Serve a socket policy file on a connection of its own, which the client makes to
+    /// `port` before connecting for real. Policy files are served in the order they appear.
+    Policy { port: u16, policy: String },
 }
 
+/// What a client sends to ask a server for its socket policy file.
+pub const POLICY_FILE_REQUEST: &[u8] = b"<policy-file-request/>\0";
+
 impl SocketEvent {
     pub fn from_file<P>(path: P) -> Result<Vec<Self>, io::Error>
     where
@@ -25,4 +31,18 @@ impl SocketEvent {
 
         Ok(from_reader(file)?)
     }
+
+    /// Separates the policy files to serve, as `(port, policy)` pairs, from the events
+    /// of the main connection.
+    pub fn split_policies(events: Vec<Self>) -> (Vec<(u16, String)>, Vec<Self>) {
+        let mut policies = vec![];
+        let mut main_events = vec![];
+        for event in events {
+            match event {
+                SocketEvent::Policy { port, policy } => policies.push((port, policy)),
+                event => main_events.push(event),
+            }
+        }
+        (policies, main_events)
+    }
 }
@@ -0,0 +1,70 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.BitmapDataChannel;
+	import flash.display.MovieClip;
+	import flash.filters.BitmapFilter;
+	import flash.filters.BlurFilter;
+	import flash.filters.ColorMatrixFilter;
+	import flash.filters.ConvolutionFilter;
+	import flash.filters.DisplacementMapFilter;
+	import flash.filters.DropShadowFilter;
+	import flash.geom.Point;
+
+	// Without a renderer, BitmapData.applyFilter has to apply every filter in software.
+	public class Test extends MovieClip {
+		public function Test() {
+			var dot: BitmapData = new BitmapData(5, 5, true, 0);
+			dot.setPixel32(2, 2, 0xFFFFFFFF);
+
+			trace("// BlurFilter(3, 3, 1)");
+			printRow(apply(dot, new BlurFilter(3, 3, 1)), 2);
+			printRow(apply(dot, new BlurFilter(3, 3, 1)), 1);
+
+			trace("// DropShadowFilter(1, 0, 0x0000FF, 1, 0, 0)");
+			printRow(apply(dot, new DropShadowFilter(1, 0, 0x0000FF, 1, 0, 0)), 2);
+
+			var colors: BitmapData = new BitmapData(1, 1, true, 0xFF336699);
+			trace("// ColorMatrixFilter (swap red and blue, add 16 to green)");
+			printRow(apply(colors, new ColorMatrixFilter([
+				0, 0, 1, 0, 0,
+				0, 1, 0, 0, 16,
+				1, 0, 0, 0, 0,
+				0, 0, 0, 1, 0
+			])), 0);
+
+			var ramp: BitmapData = new BitmapData(3, 1, true, 0);
+			ramp.setPixel32(0, 0, 0xFF300000);
+			ramp.setPixel32(1, 0, 0xFF600000);
+			ramp.setPixel32(2, 0, 0xFF900000);
+			trace("// ConvolutionFilter(3, 1, [1, 1, 1], 3, 0, true, false)");
+			printRow(apply(ramp, new ConvolutionFilter(3, 1, [1, 1, 1], 3, 0, true, false)), 0);
+			trace("// ConvolutionFilter(1, 1, [2])");
+			printRow(apply(ramp, new ConvolutionFilter(1, 1, [2])), 0);
+
+			var strip: BitmapData = new BitmapData(4, 1, true, 0);
+			strip.setPixel32(0, 0, 0xFF110000);
+			strip.setPixel32(1, 0, 0xFF220000);
+			strip.setPixel32(2, 0, 0xFF330000);
+			strip.setPixel32(3, 0, 0xFF440000);
+			var map: BitmapData = new BitmapData(4, 1, true, 0xFFC08080);
+			trace("// DisplacementMapFilter(map, (0, 0), RED, 0, 4, 0, wrap)");
+			printRow(apply(strip, new DisplacementMapFilter(map, new Point(0, 0), BitmapDataChannel.RED, 0, 4, 0, "wrap")), 0);
+			trace("// DisplacementMapFilter(map, (0, 0), RED, 0, 4, 0, color, 0x00FF00, 1)");
+			printRow(apply(strip, new DisplacementMapFilter(map, new Point(0, 0), BitmapDataChannel.RED, 0, 4, 0, "color", 0x00FF00, 1)), 0);
+		}
+
+		private function apply(source: BitmapData, filter: BitmapFilter): BitmapData {
+			var dest: BitmapData = new BitmapData(source.width, source.height, true, 0);
+			dest.applyFilter(source, source.rect, new Point(0, 0), filter);
+			return dest;
+		}
+
+		private function printRow(bitmap: BitmapData, y: int) {
+			var row: Array = [];
+			for (var x: int = 0; x < bitmap.width; x++) {
+				row.push(bitmap.getPixel32(x, y).toString(16));
+			}
+			trace(row.join(" "));
+		}
+	}
+}
@@ -0,0 +1,96 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.JPEGEncoderOptions;
+	import flash.display.JPEGXREncoderOptions;
+	import flash.display.MovieClip;
+	import flash.display.PNGEncoderOptions;
+	import flash.geom.Rectangle;
+	import flash.utils.ByteArray;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var bd:BitmapData = new BitmapData(20, 10, true, 0x80FF0000);
+			bd.fillRect(new Rectangle(5, 0, 5, 10), 0xFF00FF00);
+
+			trace("// PNG");
+			var png:ByteArray = bd.encode(bd.rect, new PNGEncoderOptions());
+			dumpHeader(png, 8);
+			trace("IHDR width: " + readUint(png, 16));
+			trace("IHDR height: " + readUint(png, 20));
+			trace("IHDR color type: " + png[25]);
+
+			trace("// PNG with fastCompression");
+			var fast:ByteArray = bd.encode(bd.rect, new PNGEncoderOptions(true));
+			dumpHeader(fast, 8);
+
+			trace("// PNG of a sub-rectangle, opaque");
+			var opaque:BitmapData = new BitmapData(20, 10, false, 0x336699);
+			var partial:ByteArray = opaque.encode(new Rectangle(2, 3, 7, 4), new PNGEncoderOptions());
+			trace("IHDR width: " + readUint(partial, 16));
+			trace("IHDR height: " + readUint(partial, 20));
+			trace("IHDR color type: " + partial[25]);
+
+			trace("// PNG into an existing ByteArray");
+			var existing:ByteArray = new ByteArray();
+			existing.writeUTFBytes("abc");
+			var returned:ByteArray = bd.encode(bd.rect, new PNGEncoderOptions(), existing);
+			trace("same object: " + (returned === existing));
+			trace("prefix kept: " + (existing[0] == 0x61 && existing[1] == 0x62 && existing[2] == 0x63));
+			trace("signature after prefix: " + (existing[3] == 0x89 && existing[4] == 0x50));
+
+			trace("// JPEG");
+			var jpeg:ByteArray = bd.encode(bd.rect, new JPEGEncoderOptions());
+			dumpHeader(jpeg, 3);
+			trace("ends with EOI: " + (jpeg[jpeg.length - 2] == 0xFF && jpeg[jpeg.length - 1] == 0xD9));
+
+			trace("// JPEG quality");
+			var noisy:BitmapData = new BitmapData(64, 64, false);
+			noisy.noise(1234);
+			var low:ByteArray = noisy.encode(noisy.rect, new JPEGEncoderOptions(10));
+			var high:ByteArray = noisy.encode(noisy.rect, new JPEGEncoderOptions(100));
+			trace("quality 100 is larger than quality 10: " + (high.length > low.length));
+
+			trace("// JPEG-XR");
+			try {
+				var xr:ByteArray = bd.encode(bd.rect, new JPEGXREncoderOptions());
+				trace("returned ByteArray: " + (xr is ByteArray));
+			} catch (e:Error) {
+				trace("Error: " + e);
+			}
+
+			trace("// Invalid compressor");
+			try {
+				bd.encode(bd.rect, {});
+			} catch (e:Error) {
+				trace("Error: " + e);
+			}
+
+			trace("// Null compressor");
+			try {
+				bd.encode(bd.rect, null);
+			} catch (e:Error) {
+				trace("Error: " + e);
+			}
+
+			trace("// Null rect");
+			try {
+				bd.encode(null, new PNGEncoderOptions());
+			} catch (e:Error) {
+				trace("Error: " + e);
+			}
+		}
+
+		private function dumpHeader(bytes:ByteArray, count:int):void {
+			var out:Array = [];
+			for (var i:int = 0; i < count; i++) {
+				out.push(bytes[i].toString(16));
+			}
+			trace("header: " + out.join(" "));
+		}
+
+		private function readUint(bytes:ByteArray, offset:int):uint {
+			bytes.position = offset;
+			return bytes.readUnsignedInt();
+		}
+	}
+}
@@ -0,0 +1,69 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.MovieClip;
+	import flash.geom.Rectangle;
+	import flash.utils.ByteArray;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var bd:BitmapData = new BitmapData(4, 4, true, 0xFF102030);
+			bd.fillRect(new Rectangle(0, 0, 2, 2), 0x80FF0000);
+			bd.setPixel32(3, 3, 0x00000000);
+
+			trace("// histogram()");
+			dumpHistogram(bd.histogram());
+
+			trace("// histogram(rect)");
+			dumpHistogram(bd.histogram(new Rectangle(1, 1, 2, 2)));
+
+			trace("// histogram(rect) outside of bitmap");
+			dumpHistogram(bd.histogram(new Rectangle(10, 10, 2, 2)));
+
+			trace("// copyPixelsToByteArray");
+			var bytes:ByteArray = new ByteArray();
+			bytes.writeByte(0x7F);
+			bd.copyPixelsToByteArray(new Rectangle(1, 1, 3, 3), bytes);
+			trace("length: " + bytes.length);
+			trace("position: " + bytes.position);
+			bytes.position = 0;
+			trace("prefix: " + bytes.readByte());
+			var pixels:Array = [];
+			while (bytes.bytesAvailable > 0) {
+				pixels.push(bytes.readUnsignedInt().toString(16));
+			}
+			trace("pixels: " + pixels.join(","));
+
+			trace("// copyPixelsToByteArray matches getPixels");
+			var copied:ByteArray = new ByteArray();
+			bd.copyPixelsToByteArray(bd.rect, copied);
+			var got:ByteArray = bd.getPixels(bd.rect);
+			var same:Boolean = copied.length == got.length;
+			for (var i:int = 0; same && i < got.length; i++) {
+				same = copied[i] == got[i];
+			}
+			trace("same: " + same);
+
+			trace("// copyPixelsToByteArray with null data");
+			try {
+				bd.copyPixelsToByteArray(bd.rect, null);
+			} catch (e:Error) {
+				trace("Error: " + e);
+			}
+		}
+
+		private function dumpHistogram(histogram:Vector.<Vector.<Number>>):void {
+			trace("channels: " + histogram.length);
+			var names:Array = ["red", "green", "blue", "alpha"];
+			for (var c:int = 0; c < histogram.length; c++) {
+				var channel:Vector.<Number> = histogram[c];
+				var out:Array = [];
+				for (var v:int = 0; v < channel.length; v++) {
+					if (channel[v] != 0) {
+						out.push(v + "=" + channel[v]);
+					}
+				}
+				trace(names[c] + " (" + channel.length + "): " + out.join(" "));
+			}
+		}
+	}
+}
@@ -0,0 +1,101 @@
+package {
+	import flash.display.Sprite;
+	import flash.geom.Matrix;
+	import flash.geom.Matrix3D;
+	import flash.geom.PerspectiveProjection;
+	import flash.geom.Point;
+
+	public class Test extends Sprite {
+		public function Test() {
+			var sprite:Sprite = new Sprite();
+			addChild(sprite);
+
+			trace("// defaults");
+			trace("z, rotationX, rotationY: " + sprite.z + ", " + sprite.rotationX + ", " + sprite.rotationY);
+			trace("matrix3D: " + sprite.transform.matrix3D);
+			trace("matrix is null: " + (sprite.transform.matrix == null));
+			trace("perspectiveProjection: " + sprite.transform.perspectiveProjection);
+
+			trace("// x = 10, y = 20, z = 30");
+			sprite.x = 10;
+			sprite.y = 20;
+			sprite.z = 30;
+			trace("z: " + sprite.z);
+			trace("matrix is null: " + (sprite.transform.matrix == null));
+			trace("matrix3D: " + raw(sprite.transform.matrix3D));
+
+			trace("// rotationX = 90");
+			sprite.rotationX = 90;
+			trace("rotationX: " + round(sprite.rotationX));
+			trace("matrix3D: " + raw(sprite.transform.matrix3D));
+
+			trace("// rotationX = 0, rotationY = 90");
+			sprite.rotationX = 0;
+			sprite.rotationY = 90;
+			trace("rotationX, rotationY: " + round(sprite.rotationX) + ", " + round(sprite.rotationY));
+			trace("matrix3D: " + raw(sprite.transform.matrix3D));
+
+			trace("// matrix3D = translation(5, 6, 7)");
+			var matrix3D:Matrix3D = new Matrix3D();
+			matrix3D.appendTranslation(5, 6, 7);
+			sprite.transform.matrix3D = matrix3D;
+			trace("x, y, z: " + sprite.x + ", " + sprite.y + ", " + sprite.z);
+			trace("rotationX, rotationY: " + round(sprite.rotationX) + ", " + round(sprite.rotationY));
+			trace("matrix3D: " + raw(sprite.transform.matrix3D));
+
+			trace("// matrix = identity");
+			sprite.transform.matrix = new Matrix();
+			trace("matrix3D: " + sprite.transform.matrix3D);
+			trace("matrix is null: " + (sprite.transform.matrix == null));
+
+			trace("// matrix3D = null");
+			sprite.z = 1;
+			sprite.transform.matrix3D = null;
+			trace("matrix3D: " + sprite.transform.matrix3D);
+			trace("matrix is null: " + (sprite.transform.matrix == null));
+
+			trace("// root perspectiveProjection");
+			var rootProjection:PerspectiveProjection = this.transform.perspectiveProjection;
+			trace("fieldOfView: " + rootProjection.fieldOfView);
+			trace("focalLength: " + round(rootProjection.focalLength));
+			trace("projectionCenter: " + rootProjection.projectionCenter);
+
+			trace("// sprite perspectiveProjection");
+			var projection:PerspectiveProjection = new PerspectiveProjection();
+			projection.fieldOfView = 90;
+			projection.projectionCenter = new Point(10, 20);
+			sprite.transform.perspectiveProjection = projection;
+			trace("fieldOfView: " + sprite.transform.perspectiveProjection.fieldOfView);
+			trace("projectionCenter: " + sprite.transform.perspectiveProjection.projectionCenter);
+			try {
+				projection.fieldOfView = 180;
+			} catch (e:ArgumentError) {
+				trace("fieldOfView = 180: " + e.errorID);
+			}
+
+			trace("// getRelativeMatrix3D");
+			sprite.transform.matrix = new Matrix(1, 0, 0, 1, 10, 20);
+			sprite.z = 30;
+			var child:Sprite = new Sprite();
+			child.x = 1;
+			sprite.addChild(child);
+			trace("child relative to root: " + raw(child.transform.getRelativeMatrix3D(this)));
+			trace("sprite relative to child: " + raw(sprite.transform.getRelativeMatrix3D(child)));
+			sprite.rotationY = 90;
+			trace("child relative to root, rotated: " + raw(child.transform.getRelativeMatrix3D(this)));
+			trace("child relative to null: " + child.transform.getRelativeMatrix3D(null));
+		}
+
+		private static function round(value:Number):Number {
+			return Math.round(value * 1000) / 1000;
+		}
+
+		private static function raw(matrix:Matrix3D):String {
+			var values:Array = [];
+			for each (var value:Number in matrix.rawData) {
+				values.push(round(value));
+			}
+			return values.join(",");
+		}
+	}
+}
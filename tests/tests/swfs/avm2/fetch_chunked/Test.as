@@ -0,0 +1,65 @@
+package {
+	import flash.display.Loader;
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.events.HTTPStatusEvent;
+	import flash.events.ProgressEvent;
+	import flash.net.URLLoader;
+	import flash.net.URLRequest;
+	import flash.net.URLStream;
+
+	public class Test extends Sprite {
+		public function Test() {
+			loadMovie();
+		}
+
+		private function loadMovie():void {
+			trace("// Loader");
+			var loader:Loader = new Loader();
+			var info = loader.contentLoaderInfo;
+			info.addEventListener(Event.OPEN, function(e:Event):void {
+				trace("open");
+			});
+			info.addEventListener(ProgressEvent.PROGRESS, function(e:ProgressEvent):void {
+				trace("progress: " + e.bytesLoaded + "/" + e.bytesTotal + ", loaderInfo: " + info.bytesLoaded + "/" + info.bytesTotal + ", content: " + loader.content);
+			});
+			info.addEventListener(Event.COMPLETE, function(e:Event):void {
+				trace("complete: loaderInfo: " + info.bytesLoaded + "/" + info.bytesTotal + ", content: " + loader.content);
+				loadText();
+			});
+			loader.load(new URLRequest("child.swf"));
+		}
+
+		private function loadText():void {
+			trace("// URLLoader");
+			var loader:URLLoader = new URLLoader();
+			loader.addEventListener(ProgressEvent.PROGRESS, function(e:ProgressEvent):void {
+				trace("progress: " + e.bytesLoaded + "/" + e.bytesTotal + ", loader: " + loader.bytesLoaded + "/" + loader.bytesTotal);
+			});
+			loader.addEventListener(HTTPStatusEvent.HTTP_STATUS, function(e:HTTPStatusEvent):void {
+				trace("httpStatus: " + e.status);
+				for each (var header in e.responseHeaders) {
+					trace("  " + header.name + ": " + header.value);
+				}
+			});
+			loader.addEventListener(Event.COMPLETE, function(e:Event):void {
+				trace("complete: " + loader.data.length + " characters");
+				loadStream();
+			});
+			loader.load(new URLRequest("data.txt"));
+		}
+
+		private function loadStream():void {
+			trace("// URLStream");
+			var stream:URLStream = new URLStream();
+			stream.addEventListener(ProgressEvent.PROGRESS, function(e:ProgressEvent):void {
+				trace("progress: " + e.bytesLoaded + "/" + e.bytesTotal + ", bytesAvailable: " + stream.bytesAvailable);
+				trace("  read: " + stream.readUTFBytes(10));
+			});
+			stream.addEventListener(Event.COMPLETE, function(e:Event):void {
+				trace("complete: bytesAvailable: " + stream.bytesAvailable);
+			});
+			stream.load(new URLRequest("data.txt"));
+		}
+	}
+}
@@ -0,0 +1,47 @@
+package
+{
+    import flash.display.Sprite;
+
+    public class Test extends Sprite
+    {
+    }
+}
+
+import flash.events.Event;
+import flash.events.IOErrorEvent;
+import flash.events.ProgressEvent;
+import flash.net.FileReference;
+
+var fileReference:FileReference = new FileReference();
+
+function traceEvent(event:Event):void
+{
+    trace("Event: " + event.type);
+}
+
+fileReference.addEventListener(Event.SELECT, function(event:Event):void
+{
+    traceEvent(event);
+    trace("name: " + fileReference.name);
+    trace("size: " + fileReference.size);
+    trace("type: " + fileReference.type);
+    trace("data: " + fileReference.data);
+    fileReference.load();
+});
+fileReference.addEventListener(Event.CANCEL, traceEvent);
+fileReference.addEventListener(Event.OPEN, traceEvent);
+fileReference.addEventListener(ProgressEvent.PROGRESS, function(event:ProgressEvent):void
+{
+    trace("Event: " + event.type + " " + event.bytesLoaded + "/" + event.bytesTotal);
+});
+fileReference.addEventListener(IOErrorEvent.IO_ERROR, traceEvent);
+fileReference.addEventListener(Event.COMPLETE, function(event:Event):void
+{
+    traceEvent(event);
+    trace("data: " + fileReference.data.readUTFBytes(fileReference.data.length));
+
+    // The second dialog is cancelled.
+    trace("browse: " + fileReference.browse());
+});
+
+trace("browse: " + fileReference.browse());
@@ -0,0 +1,57 @@
+package
+{
+    import flash.display.Sprite;
+
+    public class Test extends Sprite
+    {
+    }
+}
+
+import flash.events.Event;
+import flash.events.IOErrorEvent;
+import flash.events.ProgressEvent;
+import flash.net.FileReference;
+import flash.utils.ByteArray;
+
+function save(data:*, fileName:String):void
+{
+    var fileReference:FileReference = new FileReference();
+
+    function traceEvent(event:Event):void
+    {
+        trace(fileName + ": " + event.type);
+    }
+
+    fileReference.addEventListener(Event.SELECT, function(event:Event):void
+    {
+        traceEvent(event);
+        trace(fileName + ": name: " + fileReference.name);
+    });
+    fileReference.addEventListener(Event.CANCEL, traceEvent);
+    fileReference.addEventListener(Event.OPEN, traceEvent);
+    fileReference.addEventListener(ProgressEvent.PROGRESS, function(event:ProgressEvent):void
+    {
+        trace(fileName + ": " + event.type + " " + event.bytesLoaded + "/" + event.bytesTotal);
+    });
+    fileReference.addEventListener(IOErrorEvent.IO_ERROR, traceEvent);
+    fileReference.addEventListener(Event.COMPLETE, traceEvent);
+
+    fileReference.save(data, fileName);
+}
+
+save("Some text", "text.txt");
+
+var bytes:ByteArray = new ByteArray();
+bytes.writeUTFBytes("Some bytes");
+save(bytes, "bytes.bin");
+
+save(<item>text</item>, "data.xml");
+
+try
+{
+    save("Anything", "in/valid.txt");
+}
+catch (e:Error)
+{
+    trace(e);
+}
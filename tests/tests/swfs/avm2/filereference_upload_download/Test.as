@@ -0,0 +1,72 @@
+package
+{
+    import flash.display.Sprite;
+
+    public class Test extends Sprite
+    {
+    }
+}
+
+import flash.events.DataEvent;
+import flash.events.Event;
+import flash.events.HTTPStatusEvent;
+import flash.events.IOErrorEvent;
+import flash.events.ProgressEvent;
+import flash.events.SecurityErrorEvent;
+import flash.net.FileReference;
+import flash.net.URLRequest;
+import flash.net.URLRequestMethod;
+import flash.net.URLVariables;
+
+var uploaded:FileReference = new FileReference();
+var downloaded:FileReference = new FileReference();
+
+function listen(fileReference:FileReference, name:String, onComplete:Function):void
+{
+    function traceEvent(event:Event):void
+    {
+        trace(name + ": " + event.type);
+    }
+
+    fileReference.addEventListener(Event.SELECT, function(event:Event):void
+    {
+        traceEvent(event);
+        trace(name + ": name: " + fileReference.name);
+    });
+    fileReference.addEventListener(Event.CANCEL, traceEvent);
+    fileReference.addEventListener(Event.OPEN, traceEvent);
+    fileReference.addEventListener(ProgressEvent.PROGRESS, function(event:ProgressEvent):void
+    {
+        trace(name + ": " + event.type + " " + event.bytesLoaded + "/" + event.bytesTotal);
+    });
+    fileReference.addEventListener(HTTPStatusEvent.HTTP_STATUS, traceEvent);
+    fileReference.addEventListener(IOErrorEvent.IO_ERROR, traceEvent);
+    fileReference.addEventListener(SecurityErrorEvent.SECURITY_ERROR, traceEvent);
+    fileReference.addEventListener(Event.COMPLETE, function(event:Event):void
+    {
+        traceEvent(event);
+        onComplete();
+    });
+}
+
+listen(uploaded, "upload", function():void {});
+uploaded.addEventListener(Event.SELECT, function(event:Event):void
+{
+    var request:URLRequest = new URLRequest("upload.txt");
+    request.method = URLRequestMethod.POST;
+    var variables:URLVariables = new URLVariables();
+    variables.user = "tester";
+    request.data = variables;
+    uploaded.upload(request, "Filedata");
+});
+uploaded.addEventListener(DataEvent.UPLOAD_COMPLETE_DATA, function(event:DataEvent):void
+{
+    trace("upload: " + event.type + ": " + event.data);
+    downloaded.download(new URLRequest("download.txt"), "saved.txt");
+});
+
+listen(downloaded, "download", function():void {
+    trace("download: name: " + downloaded.name);
+});
+
+uploaded.browse();
@@ -0,0 +1,62 @@
+package {
+	import flash.display.Sprite;
+	import flash.text.Font;
+	import flash.text.TextField;
+	import flash.text.TextFormat;
+
+	public class Test extends Sprite {
+		public function Test() {
+			var font:Font = new TestFont();
+
+			trace("///font.fontName");
+			trace(font.fontName);
+
+			trace("///font.fontStyle");
+			trace(font.fontStyle);
+
+			trace("///font.fontType");
+			trace(font.fontType);
+
+			trace("///font.hasGlyphs(\"AB\")");
+			trace(font.hasGlyphs("AB"));
+
+			trace("///font.hasGlyphs(\"ABC\")");
+			trace(font.hasGlyphs("ABC"));
+
+			trace("///Font.enumerateFonts().length");
+			trace(Font.enumerateFonts().length);
+
+			trace("///Font.registerFont(TestFont)");
+			Font.registerFont(TestFont);
+			Font.registerFont(TestFont);
+
+			trace("///Font.enumerateFonts().length");
+			trace(Font.enumerateFonts().length);
+
+			trace("///Font.enumerateFonts()[0].fontName");
+			trace(Font.enumerateFonts()[0].fontName);
+
+			trace("///Font.registerFont(Test)");
+			try {
+				Font.registerFont(Test);
+			} catch (e:Error) {
+				trace(e.errorID);
+			}
+
+			var format:TextFormat = new TextFormat("Ruffle Test CFF", 20);
+			var field:TextField = new TextField();
+			field.embedFonts = true;
+			field.defaultTextFormat = format;
+			field.text = "AB";
+
+			trace("///field.textWidth");
+			trace(field.textWidth);
+
+			format.kerning = true;
+			field.setTextFormat(format);
+
+			trace("///field.textWidth (kerning)");
+			trace(field.textWidth);
+		}
+	}
+}
@@ -0,0 +1,8 @@
+package {
+	import flash.text.Font;
+
+	// Linked to a DefineFont4 tag containing a TrueType font with glyphs
+	// for "A" and "B", and a kerning pair between them.
+	public class TestFont extends Font {
+	}
+}
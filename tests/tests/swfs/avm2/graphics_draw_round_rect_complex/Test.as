@@ -0,0 +1,42 @@
+package {
+	import flash.display.Shape;
+	import flash.display.Sprite;
+
+	public class Test extends Sprite {
+		public function Test() {
+			var shape:Shape = new Shape();
+			shape.x = 100;
+			shape.y = 100;
+			addChild(shape);
+
+			trace("// topLeft = 10, topRight = 0, bottomLeft = 0, bottomRight = 20");
+			shape.graphics.beginFill(0xFF0000);
+			shape.graphics.drawRoundRectComplex(0, 0, 100, 50, 10, 0, 0, 20);
+			shape.graphics.endFill();
+			traceShape(shape);
+
+			trace("// radii larger than the rectangle");
+			shape.graphics.clear();
+			shape.graphics.beginFill(0xFF0000);
+			shape.graphics.drawRoundRectComplex(0, 0, 100, 50, 100, 100, 100, 100);
+			shape.graphics.endFill();
+			traceShape(shape);
+
+			trace("// no radii");
+			shape.graphics.clear();
+			shape.graphics.beginFill(0xFF0000);
+			shape.graphics.drawRoundRectComplex(0, 0, 100, 50, 0, 0, 0, 0);
+			shape.graphics.endFill();
+			traceShape(shape);
+		}
+
+		private function traceShape(shape:Shape):void {
+			trace("bounds: " + shape.getBounds(shape));
+			trace("top left corner: " + shape.hitTestPoint(101, 101, true));
+			trace("top right corner: " + shape.hitTestPoint(199, 101, true));
+			trace("bottom left corner: " + shape.hitTestPoint(101, 149, true));
+			trace("bottom right corner: " + shape.hitTestPoint(199, 149, true));
+			trace("center: " + shape.hitTestPoint(150, 125, true));
+		}
+	}
+}
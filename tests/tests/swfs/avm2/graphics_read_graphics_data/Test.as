@@ -0,0 +1,84 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.GradientType;
+	import flash.display.GraphicsBitmapFill;
+	import flash.display.GraphicsGradientFill;
+	import flash.display.GraphicsPath;
+	import flash.display.GraphicsSolidFill;
+	import flash.display.IGraphicsData;
+	import flash.display.Shape;
+	import flash.display.Sprite;
+	import flash.geom.Matrix;
+	import flash.utils.getQualifiedClassName;
+
+	public class Test extends Sprite {
+		public function Test() {
+			trace("// empty");
+			dump(new Shape().graphics.readGraphicsData());
+
+			trace("// solid fill");
+			var solid:Shape = new Shape();
+			solid.graphics.beginFill(0xFF0000);
+			solid.graphics.drawRect(0, 0, 10, 20);
+			solid.graphics.endFill();
+			dump(solid.graphics.readGraphicsData());
+
+			trace("// gradient fill");
+			var gradient:Shape = new Shape();
+			gradient.graphics.beginGradientFill(GradientType.LINEAR, [0xFF0000, 0x0000FF], [1, 1], [0, 255]);
+			gradient.graphics.drawRect(0, 0, 10, 10);
+			gradient.graphics.endFill();
+			dump(gradient.graphics.readGraphicsData());
+
+			trace("// bitmap fill");
+			var bitmap:Shape = new Shape();
+			bitmap.graphics.beginBitmapFill(new BitmapData(10, 5), new Matrix(2, 0, 0, 2, 3, 4), true, false);
+			bitmap.graphics.drawRect(0, 0, 10, 10);
+			bitmap.graphics.endFill();
+			dump(bitmap.graphics.readGraphicsData());
+
+			trace("// child at x = 5");
+			var parent:Sprite = new Sprite();
+			parent.graphics.beginFill(0x00FF00);
+			parent.graphics.drawRect(0, 0, 2, 2);
+			parent.graphics.endFill();
+			var child:Shape = new Shape();
+			child.x = 5;
+			child.graphics.beginFill(0x0000FF);
+			child.graphics.drawRect(0, 0, 1, 1);
+			child.graphics.endFill();
+			parent.addChild(child);
+			trace("recurse = true");
+			dump(parent.graphics.readGraphicsData(true));
+			trace("recurse = false");
+			dump(parent.graphics.readGraphicsData(false));
+		}
+
+		private static function dump(data:Vector.<IGraphicsData>):void {
+			trace("length: " + data.length);
+			for each (var item:IGraphicsData in data) {
+				trace(getQualifiedClassName(item));
+				if (item is GraphicsSolidFill) {
+					var solid:GraphicsSolidFill = item as GraphicsSolidFill;
+					trace("  color: " + solid.color.toString(16) + ", alpha: " + solid.alpha);
+				} else if (item is GraphicsGradientFill) {
+					var gradient:GraphicsGradientFill = item as GraphicsGradientFill;
+					trace("  type: " + gradient.type);
+					trace("  colors: " + gradient.colors);
+					trace("  alphas: " + gradient.alphas);
+					trace("  ratios: " + gradient.ratios);
+					trace("  spreadMethod: " + gradient.spreadMethod);
+				} else if (item is GraphicsBitmapFill) {
+					var bitmap:GraphicsBitmapFill = item as GraphicsBitmapFill;
+					trace("  bitmapData: " + bitmap.bitmapData.width + "x" + bitmap.bitmapData.height);
+					trace("  matrix: " + bitmap.matrix);
+					trace("  repeat: " + bitmap.repeat + ", smooth: " + bitmap.smooth);
+				} else if (item is GraphicsPath) {
+					var path:GraphicsPath = item as GraphicsPath;
+					trace("  commands: " + path.commands);
+					trace("  data: " + path.data);
+				}
+			}
+		}
+	}
+}
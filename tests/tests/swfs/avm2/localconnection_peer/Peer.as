@@ -0,0 +1,33 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.StatusEvent;
+	import flash.net.LocalConnection;
+
+	public class Peer extends Sprite {
+		private var receiver:LocalConnection = new LocalConnection();
+
+		public function Peer() {
+			trace("domain: " + receiver.domain);
+
+			receiver.client = this;
+			receiver.allowDomain("localhost");
+			receiver.connect("_peer");
+
+			send("_test", 1, "two");
+			send("_denied", 3, "four");
+			send("_nobody", 5, "six");
+		}
+
+		private function send(name:String, number:int, text:String):void {
+			var sender:LocalConnection = new LocalConnection();
+			sender.addEventListener(StatusEvent.STATUS, function(e:StatusEvent):void {
+				trace(name + " status: " + e.level);
+			});
+			sender.send(name, "greet", number, text);
+		}
+
+		public function reply(text:String):void {
+			trace("reply: " + text);
+		}
+	}
+}
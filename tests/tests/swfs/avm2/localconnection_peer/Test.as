@@ -0,0 +1,37 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.StatusEvent;
+	import flash.net.LocalConnection;
+
+	public class Test extends Sprite {
+		private var receiver:LocalConnection = new LocalConnection();
+		private var denied:LocalConnection = new LocalConnection();
+		private var sender:LocalConnection = new LocalConnection();
+
+		public function Test() {
+			trace("domain: " + receiver.domain);
+
+			receiver.client = {greet: greet};
+			receiver.allowDomain("peer.example.com");
+			receiver.connect("_test");
+
+			// The peer is on another domain, and this one doesn't allow it.
+			denied.client = {
+				greet: function(number:int, text:String):void {
+					trace("denied greet: " + number + ", " + text);
+				}
+			};
+			denied.connect("_denied");
+
+			sender.addEventListener(StatusEvent.STATUS, function(e:StatusEvent):void {
+				trace("reply status: " + e.level);
+			});
+			trace("connected");
+		}
+
+		private function greet(number:int, text:String):void {
+			trace("greet: " + number + ", " + text);
+			sender.send("_peer", "reply", "hello from " + receiver.domain);
+		}
+	}
+}
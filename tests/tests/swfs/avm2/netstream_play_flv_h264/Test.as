@@ -0,0 +1,33 @@
+package {
+    import flash.display.MovieClip;
+    import flash.events.NetStatusEvent;
+    import flash.media.Video;
+    import flash.net.NetConnection;
+    import flash.net.NetStream;
+
+    public class Test extends MovieClip {
+        private var stream:NetStream;
+
+        public function Test() {
+            super();
+            var connection:NetConnection = new NetConnection();
+            connection.connect(null);
+
+            // The video is three frames of a single red macroblock, coded
+            // as H.264 I_PCM pictures.
+            stream = new NetStream(connection);
+            stream.client = {};
+            stream.addEventListener(NetStatusEvent.NET_STATUS, onNetStatus);
+
+            var video:Video = new Video(320, 240);
+            video.attachNetStream(stream);
+            addChild(video);
+
+            stream.play("test_video.flv");
+        }
+
+        private function onNetStatus(event:NetStatusEvent):void {
+            trace("netStatus: " + event.info.code + " (" + event.info.level + ")");
+        }
+    }
+}
@@ -0,0 +1,50 @@
+package {
+    import flash.display.MovieClip;
+    import flash.events.Event;
+    import flash.events.NetStatusEvent;
+    import flash.media.Video;
+    import flash.net.NetConnection;
+    import flash.net.NetStream;
+    import flash.system.fscommand;
+
+    public class Test extends MovieClip {
+        private var stream:NetStream;
+        private var captured:Boolean = false;
+
+        public function Test() {
+            super();
+            var connection:NetConnection = new NetConnection();
+            connection.connect(null);
+
+            // The video is four single-macroblock I_PCM pictures, in
+            // decoding order:
+            //   IDR, red, presented at 500ms
+            //   I, blue, presented at 1500ms
+            //   B, green, presented at 1000ms
+            //   I, white, presented at 2000ms
+            // Red is on screen until the B-frame is due, and blue is the
+            // last picture presented before the stream ends.
+            stream = new NetStream(connection);
+            stream.client = {};
+            stream.addEventListener(NetStatusEvent.NET_STATUS, onNetStatus);
+
+            var video:Video = new Video(320, 240);
+            video.attachNetStream(stream);
+            addChild(video);
+
+            addEventListener(Event.ENTER_FRAME, onEnterFrame);
+            stream.play("test_video.flv");
+        }
+
+        private function onEnterFrame(event:Event):void {
+            if (!captured && stream.time >= 0.75) {
+                captured = true;
+                fscommand("captureImage", "before_b_frame");
+            }
+        }
+
+        private function onNetStatus(event:NetStatusEvent):void {
+            trace("netStatus: " + event.info.code + " (" + event.info.level + ")");
+        }
+    }
+}
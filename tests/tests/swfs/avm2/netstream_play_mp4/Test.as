@@ -0,0 +1,34 @@
+package {
+    import flash.display.MovieClip;
+    import flash.events.NetStatusEvent;
+    import flash.net.NetConnection;
+    import flash.net.NetStream;
+
+    public class Test extends MovieClip {
+        private var stream:NetStream;
+
+        public function Test() {
+            super();
+            var connection:NetConnection = new NetConnection();
+            connection.connect(null);
+
+            stream = new NetStream(connection);
+            stream.client = {
+                onMetaData: function(metadata:Object):void {
+                    trace("onMetaData");
+                    trace("duration: " + metadata.duration);
+                    trace("width: " + metadata.width);
+                    trace("height: " + metadata.height);
+                    trace("framerate: " + metadata.framerate);
+                    trace("videocodecid: " + metadata.videocodecid);
+                }
+            };
+            stream.addEventListener(NetStatusEvent.NET_STATUS, onNetStatus);
+            stream.play("test_video.mp4");
+        }
+
+        private function onNetStatus(event:NetStatusEvent):void {
+            trace("netStatus: " + event.info.code + " (" + event.info.level + ")");
+        }
+    }
+}
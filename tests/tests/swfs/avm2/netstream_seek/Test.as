@@ -0,0 +1,42 @@
+package {
+    import flash.display.MovieClip;
+    import flash.events.NetStatusEvent;
+    import flash.net.NetConnection;
+    import flash.net.NetStream;
+
+    public class Test extends MovieClip {
+        private var stream:NetStream;
+        private var hasSeeked:Boolean = false;
+
+        public function Test() {
+            super();
+            var connection:NetConnection = new NetConnection();
+            connection.connect(null);
+
+            stream = new NetStream(connection);
+            stream.client = {};
+            stream.addEventListener(NetStatusEvent.NET_STATUS, onNetStatus);
+            trace("bufferTime: " + stream.bufferTime);
+            stream.bufferTime = 2;
+            trace("bufferTime: " + stream.bufferTime);
+            trace("time: " + stream.time);
+
+            stream.play("test_video.flv");
+
+            // Nothing has been loaded yet, so there is nowhere to seek to.
+            stream.seek(1);
+        }
+
+        private function onNetStatus(event:NetStatusEvent):void {
+            trace("netStatus: " + event.info.code + " (" + event.info.level + ")");
+
+            if (event.info.code == "NetStream.Buffer.Full" && !hasSeeked) {
+                hasSeeked = true;
+                stream.seek(0.5);
+            } else if (event.info.code == "NetStream.Seek.Notify") {
+                // Playback restarts from the keyframe before the requested time.
+                trace("time: " + stream.time);
+            }
+        }
+    }
+}
@@ -0,0 +1,11 @@
+package {
+	import flash.display.Sprite;
+
+	public class Private extends Sprite {
+		public function Private() {
+			graphics.beginFill(0xFF0000);
+			graphics.drawRect(0, 0, 10, 10);
+			trace("Private constructed");
+		}
+	}
+}
@@ -0,0 +1,13 @@
+package {
+	import flash.display.Sprite;
+	import flash.system.Security;
+
+	public class Shared extends Sprite {
+		public function Shared() {
+			Security.allowDomain("example.com");
+			graphics.beginFill(0x00FF00);
+			graphics.drawRect(0, 0, 10, 10);
+			trace("Shared constructed");
+		}
+	}
+}
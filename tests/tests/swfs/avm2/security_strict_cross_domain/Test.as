@@ -0,0 +1,64 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.Loader;
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.events.IOErrorEvent;
+	import flash.events.SecurityErrorEvent;
+	import flash.net.URLLoader;
+	import flash.net.URLRequest;
+
+	public class Test extends Sprite {
+		public function Test() {
+			loadData();
+		}
+
+		private function loadData():void {
+			trace("// URLLoader from another domain");
+			var loader:URLLoader = new URLLoader();
+			loader.addEventListener(SecurityErrorEvent.SECURITY_ERROR, function(e:SecurityErrorEvent):void {
+				trace(e.type + " " + e.errorID + ": " + e.text);
+				loadMovie("private.swf");
+			});
+			loader.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+				trace("ioError: " + e.text);
+			});
+			loader.addEventListener(Event.COMPLETE, function(e:Event):void {
+				trace("complete: " + loader.data);
+			});
+			loader.load(new URLRequest("http://data.example.com/scores.txt"));
+		}
+
+		private function loadMovie(url:String):void {
+			trace("// Loader of " + url);
+			var loader:Loader = new Loader();
+			loader.contentLoaderInfo.addEventListener(Event.COMPLETE, function(e:Event):void {
+				try {
+					trace("content: " + loader.contentLoaderInfo.content);
+				} catch (e:SecurityError) {
+					trace("content: " + describe(e));
+				}
+
+				var bitmap:BitmapData = new BitmapData(10, 10, false, 0);
+				try {
+					bitmap.draw(loader);
+					trace("draw: ok");
+				} catch (e:SecurityError) {
+					trace("draw: " + describe(e));
+				}
+
+				if (url == "private.swf") {
+					loadMovie("avm1_shared.swf");
+				} else if (url == "avm1_shared.swf") {
+					loadMovie("shared.swf");
+				}
+			});
+			loader.load(new URLRequest(url));
+		}
+
+		// The loaded movies are local files, so leave out their path.
+		private function describe(e:Error):String {
+			return e.name + " " + e.errorID + ": " + e.message.substr(0, e.message.indexOf(" file:"));
+		}
+	}
+}
@@ -0,0 +1,30 @@
+package
+{
+    import flash.display.Sprite;
+
+    public class Test extends Sprite
+    {
+    }
+}
+
+import flash.events.Event;
+import flash.net.Socket;
+
+// Both sockets wait for the same policy files, which are only requested once.
+function connect(name:String):void
+{
+    var socket:Socket = new Socket();
+
+    socket.addEventListener(Event.CONNECT, function(event:Event):void
+    {
+        trace(name + " connected");
+        socket.writeUTF("Hello!");
+        socket.flush();
+        socket.close();
+    });
+
+    socket.connect("localhost", 8001);
+}
+
+connect("first");
+connect("second");
@@ -0,0 +1,31 @@
+package
+{
+    import flash.display.Sprite;
+
+    public class Test extends Sprite
+    {
+    }
+}
+
+import flash.events.Event;
+import flash.events.SecurityErrorEvent;
+import flash.net.Socket;
+
+var socket:Socket = new Socket();
+
+socket.addEventListener(Event.CONNECT, function(event:Event):void
+{
+    trace("connected");
+    socket.writeUTF("Hello!");
+    socket.flush();
+    socket.close();
+});
+
+socket.addEventListener(SecurityErrorEvent.SECURITY_ERROR, function(event:SecurityErrorEvent):void
+{
+    trace(event.type + " " + event.errorID + ": " + event.text);
+    trace("socket.connected: " + socket.connected);
+});
+
+socket.connect("localhost", 8001);
+
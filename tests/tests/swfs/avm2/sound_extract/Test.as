@@ -0,0 +1,65 @@
+package {
+	import flash.display.Sprite;
+	import flash.media.Sound;
+	import flash.utils.ByteArray;
+
+	public class Test extends Sprite {
+		public function Test() {
+			var empty:Sound = new Sound();
+			var out:ByteArray = new ByteArray();
+			trace("empty extract: " + empty.extract(out, 100));
+			trace("empty out.length: " + out.length);
+
+			var pcm:ByteArray = new ByteArray();
+			for (var i:int = 0; i < 4410; i++) {
+				pcm.writeFloat(0.5);
+				pcm.writeFloat(-0.25);
+			}
+			pcm.position = 0;
+
+			var floatSound:Sound = new Sound();
+			floatSound.loadPCMFromByteArray(pcm, 4410);
+			trace("float length: " + floatSound.length);
+			trace("pcm.position: " + pcm.position);
+
+			out = new ByteArray();
+			trace("extract(1000, 0): " + floatSound.extract(out, 1000, 0));
+			trace("out.length: " + out.length);
+			out.position = 0;
+			trace("first frame: " + out.readFloat().toFixed(3) + " " + out.readFloat().toFixed(3));
+
+			trace("extract(1000): " + floatSound.extract(out, 1000));
+			trace("extract(5000): " + floatSound.extract(out, 5000));
+			trace("extract(100): " + floatSound.extract(out, 100));
+			trace("extract(100, 4400): " + floatSound.extract(out, 100, 4400));
+			trace("out.length: " + out.length);
+
+			try {
+				floatSound.loadPCMFromByteArray(pcm, 10);
+			} catch (e:Error) {
+				trace("reload: " + e.errorID);
+			}
+
+			var shorts:ByteArray = new ByteArray();
+			for (i = 0; i < 2205; i++) {
+				shorts.writeShort(1000);
+			}
+			shorts.position = 0;
+
+			var shortSound:Sound = new Sound();
+			shortSound.loadPCMFromByteArray(shorts, 2205, "short", false, 22050);
+			trace("short length: " + shortSound.length);
+
+			out = new ByteArray();
+			trace("extract(10000, 0): " + shortSound.extract(out, 10000, 0));
+			out.position = 8 * 100;
+			trace("frame 100: " + out.readFloat().toFixed(4) + " " + out.readFloat().toFixed(4));
+
+			try {
+				new Sound().loadPCMFromByteArray(shorts, 1, "double");
+			} catch (e:Error) {
+				trace("bad format: " + e.errorID);
+			}
+		}
+	}
+}
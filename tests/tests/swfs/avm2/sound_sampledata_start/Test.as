@@ -0,0 +1,40 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.events.SampleDataEvent;
+	import flash.media.Sound;
+	import flash.media.SoundChannel;
+
+	public class Test extends Sprite {
+		private var sound:Sound;
+		private var channel:SoundChannel;
+		private var requests:int = 0;
+
+		public function Test() {
+			this.sound = new Sound();
+			this.sound.addEventListener(SampleDataEvent.SAMPLE_DATA, this.onSampleData);
+
+			// The first event asks for the audio at one second in.
+			this.channel = this.sound.play(1000);
+			this.channel.addEventListener(Event.SOUND_COMPLETE, this.onSoundComplete);
+		}
+
+		private function onSampleData(event:SampleDataEvent):void {
+			this.requests++;
+			trace("sampleData: position = " + event.position);
+
+			// Only the first 8192 of the 10000 samples supplied first are played,
+			// and supplying fewer than 2048 samples the second time ends the sound.
+			var samples:int = this.requests == 1 ? 10000 : 1000;
+			for (var i:int = 0; i < samples; i++) {
+				var value:Number = Math.sin((event.position + i) * Math.PI * 2 / 100) * 0.25;
+				event.data.writeFloat(value);
+				event.data.writeFloat(value);
+			}
+		}
+
+		private function onSoundComplete(event:Event):void {
+			trace("soundComplete: requests = " + this.requests + ", position = " + Math.round(this.channel.position));
+		}
+	}
+}
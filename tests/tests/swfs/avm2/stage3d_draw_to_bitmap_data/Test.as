@@ -0,0 +1,127 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.Sprite;
+	import flash.display.Stage3D;
+	import flash.display3D.Context3D;
+	import flash.display3D.Context3DProgramType;
+	import flash.display3D.Context3DRenderMode;
+	import flash.display3D.Context3DVertexBufferFormat;
+	import flash.display3D.IndexBuffer3D;
+	import flash.display3D.Program3D;
+	import flash.display3D.VertexBuffer3D;
+	import flash.events.Event;
+	import flash.geom.Point;
+	import flash.geom.Rectangle;
+	import flash.utils.ByteArray;
+	import flash.utils.Endian;
+
+	public class Test extends Sprite {
+		private var stage3D:Stage3D;
+
+		public function Test() {
+			stage3D = stage.stage3Ds[0];
+			stage3D.addEventListener(Event.CONTEXT3D_CREATE, contextCreated);
+			stage3D.requestContext3D(Context3DRenderMode.AUTO);
+		}
+
+		private function contextCreated(event:Event):void {
+			var context:Context3D = stage3D.context3D;
+			context.configureBackBuffer(64, 64, 0, false);
+
+			// The left half of the back buffer is red, and the right half is blue.
+			var vertices:Vector.<Number> = Vector.<Number>([
+				// x, y, z, w, r, g, b, a
+				-1, -1, 0, 1, 1, 0, 0, 1,
+				0, -1, 0, 1, 1, 0, 0, 1,
+				0, 1, 0, 1, 1, 0, 0, 1,
+				-1, 1, 0, 1, 1, 0, 0, 1,
+				0, -1, 0, 1, 0, 0, 1, 1,
+				1, -1, 0, 1, 0, 0, 1, 1,
+				1, 1, 0, 1, 0, 0, 1, 1,
+				0, 1, 0, 1, 0, 0, 1, 1
+			]);
+			var vertexBuffer:VertexBuffer3D = context.createVertexBuffer(8, 8);
+			vertexBuffer.uploadFromVector(vertices, 0, 8);
+			var indices:Vector.<uint> = Vector.<uint>([0, 1, 2, 0, 2, 3, 4, 5, 6, 4, 6, 7]);
+			var indexBuffer:IndexBuffer3D = context.createIndexBuffer(indices.length);
+			indexBuffer.uploadFromVector(indices, 0, indices.length);
+			context.setVertexBufferAt(0, vertexBuffer, 0, Context3DVertexBufferFormat.FLOAT_4);
+			context.setVertexBufferAt(1, vertexBuffer, 4, Context3DVertexBufferFormat.FLOAT_4);
+
+			var program:Program3D = context.createProgram();
+			program.upload(vertexProgram(), fragmentProgram());
+			context.setProgram(program);
+
+			context.clear(0, 1, 0);
+			context.drawTriangles(indexBuffer, 0, 4);
+
+			trace("// destPoint = (0, 0)");
+			var bitmapData:BitmapData = new BitmapData(64, 64, false, 0xFFFFFF);
+			context.drawToBitmapData(bitmapData, null, new Point(0, 0));
+			tracePixels(bitmapData);
+
+			trace("// destPoint = (-32, 0)");
+			bitmapData = new BitmapData(64, 64, false, 0xFFFFFF);
+			context.drawToBitmapData(bitmapData, null, new Point(-32, 0));
+			tracePixels(bitmapData);
+
+			trace("// destPoint = (16, -16), sourceRect = (8, 0, 32, 64)");
+			bitmapData = new BitmapData(64, 64, false, 0xFFFFFF);
+			context.drawToBitmapData(bitmapData, new Rectangle(8, 0, 32, 64), new Point(16, -16));
+			tracePixels(bitmapData);
+			trace("(16, 47): " + bitmapData.getPixel(16, 47).toString(16));
+			trace("(16, 48): " + bitmapData.getPixel(16, 48).toString(16));
+
+			context.present();
+		}
+
+		private static function tracePixels(bitmapData:BitmapData):void {
+			for each (var x:int in [0, 15, 31, 40, 63]) {
+				trace("(" + x + ", 0): " + bitmapData.getPixel(x, 0).toString(16));
+			}
+		}
+
+		// mov op, va0
+		// mov v0, va1
+		private static function vertexProgram():ByteArray {
+			var bytes:ByteArray = header(0);
+			instruction(bytes, 0x3, 0, 0x0, 0);
+			instruction(bytes, 0x4, 0, 0x0, 1);
+			return bytes;
+		}
+
+		// mov oc, v0
+		private static function fragmentProgram():ByteArray {
+			var bytes:ByteArray = header(1);
+			instruction(bytes, 0x3, 0, 0x4, 0);
+			return bytes;
+		}
+
+		private static function header(type:int):ByteArray {
+			var bytes:ByteArray = new ByteArray();
+			bytes.endian = Endian.LITTLE_ENDIAN;
+			bytes.writeByte(0xA0);
+			bytes.writeUnsignedInt(1);
+			bytes.writeByte(0xA1);
+			bytes.writeByte(type);
+			return bytes;
+		}
+
+		// Writes a `mov` from a whole source register to a whole destination register.
+		private static function instruction(bytes:ByteArray, destType:int, destNumber:int, sourceType:int, sourceNumber:int):void {
+			bytes.writeUnsignedInt(0x00);
+			bytes.writeShort(destNumber);
+			bytes.writeByte(0xF);
+			bytes.writeByte(destType);
+			bytes.writeShort(sourceNumber);
+			bytes.writeByte(0);
+			bytes.writeByte(0xE4);
+			bytes.writeByte(sourceType);
+			bytes.writeByte(0);
+			bytes.writeShort(0);
+			for (var i:int = 0; i < 8; i++) {
+				bytes.writeByte(0);
+			}
+		}
+	}
+}
@@ -0,0 +1,108 @@
+package {
+    import com.adobe.utils.AGALMiniAssembler;
+
+    import flash.display.MovieClip;
+    import flash.display.Stage3D;
+    import flash.display3D.Context3D;
+    import flash.display3D.Context3DCompareMode;
+    import flash.display3D.Context3DProgramType;
+    import flash.display3D.Context3DRenderMode;
+    import flash.display3D.Context3DStencilAction;
+    import flash.display3D.Context3DTriangleFace;
+    import flash.display3D.Context3DVertexBufferFormat;
+    import flash.display3D.IndexBuffer3D;
+    import flash.display3D.Program3D;
+    import flash.display3D.VertexBuffer3D;
+    import flash.events.Event;
+
+    // Writes a stencil mask covering the left half of the back buffer, then uses it
+    // to restrict which pixels are drawn by subsequent full-screen quads.
+    // Expected output: left half green, top-right black, bottom-right blue.
+    public class Test extends MovieClip {
+        public const viewWidth:Number = 100;
+        public const viewHeight:Number = 100;
+
+        private var stage3D:Stage3D;
+        private var renderContext:Context3D;
+        private var indexList:IndexBuffer3D;
+        private var leftHalf:VertexBuffer3D;
+        private var bottomHalf:VertexBuffer3D;
+        private var fullScreen:VertexBuffer3D;
+
+        private const VERTEX_SHADER:String =
+            "mov op, va0";
+
+        private const FRAGMENT_SHADER:String =
+            "mov oc, fc0";
+
+        private var vertexAssembly:AGALMiniAssembler = new AGALMiniAssembler(false);
+        private var fragmentAssembly:AGALMiniAssembler = new AGALMiniAssembler(false);
+        private var programPair:Program3D;
+
+        public function Test() {
+            stage3D = this.stage.stage3Ds[0];
+            stage3D.x = 0;
+            stage3D.y = 0;
+
+            stage3D.addEventListener(Event.CONTEXT3D_CREATE, contextCreated);
+            stage3D.requestContext3D(Context3DRenderMode.AUTO, "standard");
+
+            vertexAssembly.assemble(Context3DProgramType.VERTEX, VERTEX_SHADER, 2);
+            fragmentAssembly.assemble(Context3DProgramType.FRAGMENT, FRAGMENT_SHADER, 2);
+        }
+
+        private function makeQuad(left:Number, top:Number, right:Number, bottom:Number):VertexBuffer3D {
+            var vertexData:Vector.<Number> = Vector.<Number>([
+                left, top, 0,
+                right, top, 0,
+                left, bottom, 0,
+                right, bottom, 0
+            ]);
+            var buffer:VertexBuffer3D = renderContext.createVertexBuffer(4, 3);
+            buffer.uploadFromVector(vertexData, 0, 4);
+            return buffer;
+        }
+
+        private function drawQuad(buffer:VertexBuffer3D, r:Number, g:Number, b:Number):void {
+            renderContext.setVertexBufferAt(0, buffer, 0, Context3DVertexBufferFormat.FLOAT_3);
+            renderContext.setProgramConstantsFromVector(Context3DProgramType.FRAGMENT, 0, Vector.<Number>([r, g, b, 1]));
+            renderContext.drawTriangles(indexList, 0, 2);
+        }
+
+        private function contextCreated(event:Event):void {
+            renderContext = Stage3D(event.target).context3D;
+            renderContext.configureBackBuffer(viewWidth, viewHeight, 0, true);
+
+            var triangles:Vector.<uint> = Vector.<uint>([0, 1, 2, 1, 3, 2]);
+            indexList = renderContext.createIndexBuffer(triangles.length);
+            indexList.uploadFromVector(triangles, 0, triangles.length);
+
+            leftHalf = makeQuad(-1, 1, 0, -1);
+            bottomHalf = makeQuad(-1, 0, 1, -1);
+            fullScreen = makeQuad(-1, 1, 1, -1);
+
+            programPair = renderContext.createProgram();
+            programPair.upload(vertexAssembly.agalcode, fragmentAssembly.agalcode);
+            renderContext.setProgram(programPair);
+
+            renderContext.clear(0, 0, 0, 1);
+
+            // Write 1 into the stencil buffer for the left half, without touching the color buffer.
+            renderContext.setColorMask(false, false, false, false);
+            renderContext.setStencilReferenceValue(1);
+            renderContext.setStencilActions(Context3DTriangleFace.FRONT_AND_BACK, Context3DCompareMode.ALWAYS, Context3DStencilAction.SET);
+            drawQuad(leftHalf, 1, 1, 1);
+
+            // Only pixels with a stencil value of 1 should become green.
+            renderContext.setColorMask(true, true, true, true);
+            renderContext.setStencilActions(Context3DTriangleFace.FRONT_AND_BACK, Context3DCompareMode.EQUAL, Context3DStencilAction.KEEP);
+            drawQuad(fullScreen, 0, 1, 0);
+
+            // Only the bottom-right quarter still has a stencil value of 0.
+            renderContext.setStencilReferenceValue(0);
+            drawQuad(bottomHalf, 0, 0, 1);
+
+            renderContext.present();
+        }
+    }
+}
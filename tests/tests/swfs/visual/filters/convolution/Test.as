@@ -0,0 +1,39 @@
+package {
+	import flash.display.Bitmap;
+	import flash.display.BitmapData;
+	import flash.display.MovieClip;
+	import flash.display.Shape;
+	import flash.filters.ConvolutionFilter;
+	import flash.geom.Rectangle;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var background:Shape = new Shape();
+			background.graphics.beginFill(0x808080);
+			background.graphics.drawRect(0, 0, stage.stageWidth, stage.stageHeight);
+			background.graphics.endFill();
+			addChild(background);
+
+			var filters:Array = [
+				// Horizontal edge detection, clamped
+				new ConvolutionFilter(3, 1, [-1, 0, 1], 1, 128, true, true),
+				// Horizontal edge detection, using the default color outside of the source
+				new ConvolutionFilter(3, 1, [-1, 0, 1], 1, 128, true, false, 0x00FF00, 1),
+				// Horizontal box blur using the divisor
+				new ConvolutionFilter(3, 1, [1, 1, 1], 3, 0, true, true),
+				// Same, but also blurring alpha with a transparent default color
+				new ConvolutionFilter(3, 1, [1, 1, 1], 3, 0, false, false, 0, 0)
+			];
+
+			for (var i:int = 0; i < filters.length; i++) {
+				var data:BitmapData = new BitmapData(40, 20, true, 0xFFFF0000);
+				data.fillRect(new Rectangle(20, 0, 20, 20), 0xFF0000FF);
+				var bitmap:Bitmap = new Bitmap(data);
+				bitmap.x = 10 + i * 50;
+				bitmap.y = 10;
+				bitmap.filters = [filters[i]];
+				addChild(bitmap);
+			}
+		}
+	}
+}
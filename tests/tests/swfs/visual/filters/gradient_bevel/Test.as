@@ -0,0 +1,37 @@
+package {
+	import flash.display.Bitmap;
+	import flash.display.BitmapData;
+	import flash.display.MovieClip;
+	import flash.display.Shape;
+	import flash.filters.BitmapFilterType;
+	import flash.filters.GradientBevelFilter;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var background:Shape = new Shape();
+			background.graphics.beginFill(0x808080);
+			background.graphics.drawRect(0, 0, stage.stageWidth, stage.stageHeight);
+			background.graphics.endFill();
+			addChild(background);
+
+			// Blur is disabled so that the highlight, flat and shadow ratios produce hard edges
+			var colors:Array = [0xFFFFFF, 0x000000, 0x000000];
+			var alphas:Array = [1, 0, 1];
+			var ratios:Array = [0, 128, 255];
+			var filters:Array = [
+				new GradientBevelFilter(5, 0, colors, alphas, ratios, 0, 0, 1, 1, BitmapFilterType.INNER),
+				new GradientBevelFilter(5, 0, colors, alphas, ratios, 0, 0, 1, 1, BitmapFilterType.OUTER),
+				new GradientBevelFilter(5, 0, colors, alphas, ratios, 0, 0, 1, 1, BitmapFilterType.FULL),
+				new GradientBevelFilter(5, 0, colors, alphas, ratios, 0, 0, 1, 1, BitmapFilterType.INNER, true)
+			];
+
+			for (var i:int = 0; i < filters.length; i++) {
+				var bitmap:Bitmap = new Bitmap(new BitmapData(20, 20, true, 0xFFFF0000));
+				bitmap.x = 20 + i * 60;
+				bitmap.y = 20;
+				bitmap.filters = [filters[i]];
+				addChild(bitmap);
+			}
+		}
+	}
+}
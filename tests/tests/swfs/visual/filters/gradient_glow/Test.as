@@ -0,0 +1,34 @@
+package {
+	import flash.display.Bitmap;
+	import flash.display.BitmapData;
+	import flash.display.MovieClip;
+	import flash.display.Shape;
+	import flash.filters.BitmapFilterType;
+	import flash.filters.GradientGlowFilter;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var background:Shape = new Shape();
+			background.graphics.beginFill(0x808080);
+			background.graphics.drawRect(0, 0, stage.stageWidth, stage.stageHeight);
+			background.graphics.endFill();
+			addChild(background);
+
+			// Blur is disabled so that the gradient ratios produce hard edges
+			var filters:Array = [
+				new GradientGlowFilter(10, 0, [0x000000, 0x0000FF], [0, 1], [0, 255], 0, 0, 1, 1, BitmapFilterType.OUTER),
+				new GradientGlowFilter(10, 0, [0x000000, 0x0000FF], [0, 1], [0, 255], 0, 0, 1, 1, BitmapFilterType.INNER),
+				new GradientGlowFilter(10, 0, [0x000000, 0x0000FF], [0, 1], [0, 255], 0, 0, 1, 1, BitmapFilterType.OUTER, true),
+				new GradientGlowFilter(10, 0, [0x000000, 0x0000FF], [0, 1], [0, 255], 0, 0, 1, 1, BitmapFilterType.FULL)
+			];
+
+			for (var i:int = 0; i < filters.length; i++) {
+				var bitmap:Bitmap = new Bitmap(new BitmapData(20, 20, true, 0xFFFF0000));
+				bitmap.x = 20 + i * 60;
+				bitmap.y = 20;
+				bitmap.filters = [filters[i]];
+				addChild(bitmap);
+			}
+		}
+	}
+}
@@ -0,0 +1,49 @@
+package {
+	import flash.display.BlendMode;
+	import flash.display.Shape;
+	import flash.display.Sprite;
+
+	// Every shape is aligned to the pixel grid, so that all renderers draw the same image.
+	public class Test extends Sprite {
+		private static const BACKGROUND:uint = 0x3366CC;
+		private static const FOREGROUND:uint = 0xCC9933;
+
+		public function Test() {
+			addChild(rect(0xFFFFFF, 1, 0, 0, 240, 60));
+
+			// Blended onto an opaque background, in the middle of each column.
+			var modes:Array = [
+				BlendMode.MULTIPLY, BlendMode.SCREEN, BlendMode.LIGHTEN, BlendMode.DARKEN,
+				BlendMode.DIFFERENCE, BlendMode.ADD, BlendMode.SUBTRACT, BlendMode.INVERT,
+				BlendMode.OVERLAY, BlendMode.HARDLIGHT
+			];
+			for (var i:int = 0; i < modes.length; i++) {
+				addChild(rect(BACKGROUND, 1, i * 20, 0, 20, 60));
+				var shape:Shape = rect(FOREGROUND, 1, i * 20, 20, 20, 20);
+				shape.blendMode = modes[i];
+				addChild(shape);
+			}
+
+			// Alpha and erase only work within a layer, and here cover all of it.
+			var layerModes:Array = [BlendMode.ALPHA, BlendMode.ERASE];
+			for (var j:int = 0; j < layerModes.length; j++) {
+				var x:int = (modes.length + j) * 20;
+				var layer:Sprite = new Sprite();
+				layer.blendMode = BlendMode.LAYER;
+				layer.addChild(rect(BACKGROUND, 1, x, 0, 20, 60));
+				var blended:Shape = rect(FOREGROUND, 0.6, x, 0, 20, 60);
+				blended.blendMode = layerModes[j];
+				layer.addChild(blended);
+				addChild(layer);
+			}
+		}
+
+		private static function rect(color:uint, alpha:Number, x:int, y:int, width:int, height:int):Shape {
+			var shape:Shape = new Shape();
+			shape.graphics.beginFill(color, alpha);
+			shape.graphics.drawRect(x, y, width, height);
+			shape.graphics.endFill();
+			return shape;
+		}
+	}
+}
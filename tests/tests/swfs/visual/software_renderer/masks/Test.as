@@ -0,0 +1,39 @@
+package {
+	import flash.display.Shape;
+	import flash.display.Sprite;
+
+	// Every shape is aligned to the pixel grid, so that all renderers draw the same image.
+	public class Test extends Sprite {
+		public function Test() {
+			addChild(rect(0xFFFFFF, 1, 0, 0, 200, 100));
+
+			// A mask clips to its shape, whatever its color and alpha.
+			var red:Shape = rect(0xFF0000, 1, 10, 10, 50, 50);
+			var redMask:Shape = rect(0x0000FF, 0.3, 30, 30, 50, 50);
+			addChild(red);
+			addChild(redMask);
+			red.mask = redMask;
+
+			// A masked child of a masked container is clipped by both masks.
+			var container:Sprite = new Sprite();
+			container.addChild(rect(0x00FF00, 1, 100, 10, 90, 80));
+			var blue:Shape = rect(0x0000FF, 1, 120, 20, 60, 60);
+			var blueMask:Shape = rect(0x000000, 1, 140, 40, 50, 20);
+			container.addChild(blue);
+			container.addChild(blueMask);
+			blue.mask = blueMask;
+			var containerMask:Shape = rect(0x000000, 1, 100, 10, 50, 80);
+			addChild(container);
+			addChild(containerMask);
+			container.mask = containerMask;
+		}
+
+		private static function rect(color:uint, alpha:Number, x:int, y:int, width:int, height:int):Shape {
+			var shape:Shape = new Shape();
+			shape.graphics.beginFill(color, alpha);
+			shape.graphics.drawRect(x, y, width, height);
+			shape.graphics.endFill();
+			return shape;
+		}
+	}
+}
@@ -41,3 +41,45 @@ pub fn wgpu_descriptors() -> Option<&'static Arc<Descriptors>> {
     static WGPU: OnceLock<Option<Arc<Descriptors>>> = OnceLock::new();
     WGPU.get_or_init(build_wgpu_descriptors).as_ref()
 }
+
+/// The render backend used by tests that ask for a renderer.
+#[derive(Clone, Copy)]
+pub enum TestRenderer {
+    Wgpu(&'static Arc<Descriptors>),
+
+    /// The CPU rasterizer, used when `RUFFLE_TEST_RENDERER=software` is set.
+    /// This doesn't need a GPU, but isn't expected to match every reference image exactly,
+    /// so it only runs tests that opt in with `software = true`.
+    Software,
+}
+
+impl TestRenderer {
+    /// A suffix for images saved by failing tests, so that results from different renderers
+    /// don't overwrite each other.
+    pub fn image_suffix(&self) -> String {
+        match self {
+            TestRenderer::Wgpu(descriptors) => format!(
+                "{}-{:?}",
+                std::env::consts::OS,
+                descriptors.adapter.get_info().backend
+            ),
+            TestRenderer::Software => format!("{}-software", std::env::consts::OS),
+        }
+    }
+
+    /// A suffix for expected images that only apply to this renderer, used instead of the
+    /// shared expected image when present.
+    pub fn expected_image_suffix(&self) -> Option<&'static str> {
+        match self {
+            TestRenderer::Wgpu(_) => None,
+            TestRenderer::Software => Some("software"),
+        }
+    }
+}
+
+pub fn test_renderer() -> Option<TestRenderer> {
+    if std::env::var("RUFFLE_TEST_RENDERER").is_ok_and(|renderer| renderer == "software") {
+        return Some(TestRenderer::Software);
+    }
+    wgpu_descriptors().map(TestRenderer::Wgpu)
+}
@@ -1,16 +1,21 @@
+use crate::util::options::FetchResponse;
 use crate::util::runner::TestLogBackend;
 use async_channel::Receiver;
 use ruffle_core::backend::log::LogBackend;
 use ruffle_core::backend::navigator::{
-    fetch_path, resolve_url_with_relative_base_path, ErrorResponse, NavigationMethod,
-    NavigatorBackend, NullExecutor, NullSpawner, OwnedFuture, Request, SuccessResponse,
+    fetch_path, resolve_url_with_relative_base_path, ChunkStream, ErrorResponse, NavigationMethod,
+    NavigatorBackend, NullExecutor, NullSpawner, OwnedFuture, Request, ResponseBody,
+    SuccessResponse,
 };
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
 use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
-use ruffle_socket_format::SocketEvent;
+use ruffle_socket_format::{SocketEvent, POLICY_FILE_REQUEST};
+use std::collections::VecDeque;
 use std::path::{Path, PathBuf};
+use std::pin::Pin;
 use std::sync::mpsc::Sender;
+use std::task::{Context, Poll};
 use std::time::Duration;
 use url::{ParseError, Url};
 
@@ -21,6 +26,9 @@ pub struct TestNavigatorBackend {
     spawner: NullSpawner,
     relative_base_path: PathBuf,
     socket_events: Option<Vec<SocketEvent>>,
+    /// Socket policy files that are yet to be served, as `(port, policy)` pairs.
+    socket_policies: VecDeque<(u16, String)>,
+    fetch_response: FetchResponse,
     log: Option<TestLogBackend>,
 }
 
@@ -29,12 +37,22 @@ impl TestNavigatorBackend {
         path: &Path,
         executor: &NullExecutor,
         socket_events: Option<Vec<SocketEvent>>,
+        fetch_response: FetchResponse,
         log: Option<TestLogBackend>,
     ) -> Result<Self, std::io::Error> {
+        let (socket_policies, socket_events) = match socket_events {
+            Some(events) => {
+                let (policies, events) = SocketEvent::split_policies(events);
+                (policies.into(), Some(events))
+            }
+            None => (VecDeque::new(), None),
+        };
         Ok(Self {
             spawner: executor.spawner(),
             relative_base_path: path.canonicalize()?,
             socket_events,
+            socket_policies,
+            fetch_response,
             log,
         })
     }
@@ -88,7 +106,29 @@ impl NavigatorBackend for TestNavigatorBackend {
             }
         }
 
-        fetch_path(self, "TestNavigatorBackend", request.url())
+        let fetch = fetch_path(self, "TestNavigatorBackend", request.url());
+        let options = self.fetch_response.clone();
+        Box::pin(async move {
+            let mut response = fetch.await?;
+            if let Some(chunk_size) = options.chunk_size {
+                let body = match response.body.read_to_end().await {
+                    Ok(body) => body,
+                    Err(error) => {
+                        return Err(ErrorResponse {
+                            url: response.url,
+                            error,
+                        })
+                    }
+                };
+                let chunks = body.chunks(chunk_size.max(1)).map(<[u8]>::to_vec);
+                response.body = ResponseBody::from_stream(
+                    Some(body.len() as u64),
+                    ChunkedBody(chunks.collect()),
+                );
+            }
+            response.headers = options.headers;
+            Ok(response)
+        })
     }
 
     fn resolve_url(&self, url: &str) -> Result<Url, ParseError> {
@@ -117,6 +157,48 @@ impl NavigatorBackend for TestNavigatorBackend {
             log.avm_trace(&format!("    Host: {}; Port: {}", host, port));
         }
 
+        if self.socket_events.is_none() {
+            return;
+        }
+
+        // Policy files are served in order, on connections of their own.
+        if self
+            .socket_policies
+            .front()
+            .is_some_and(|(policy_port, _)| *policy_port == port)
+        {
+            let (_, policy) = self.socket_policies.pop_front().unwrap();
+            self.spawn_future(Box::pin(async move {
+                sender
+                    .send(SocketAction::Connect(handle, ConnectionState::Connected))
+                    .expect("working channel send");
+
+                match receiver.recv().await {
+                    Ok(request) if request == POLICY_FILE_REQUEST => {}
+                    other => panic!("Expected a policy file request, got {:?}", other),
+                }
+
+                let mut payload = policy.into_bytes();
+                payload.push(0);
+                sender
+                    .send(SocketAction::Data(handle, payload))
+                    .expect("working channel send");
+                sender
+                    .send(SocketAction::Close(handle))
+                    .expect("working channel send");
+                Ok(())
+            }));
+            return;
+        }
+
+        // Nothing listens on the master policy port unless a test serves a policy file there.
+        if port == 843 {
+            sender
+                .send(SocketAction::Connect(handle, ConnectionState::Failed))
+                .expect("working channel send");
+            return;
+        }
+
         if let Some(events) = self.socket_events.clone() {
             self.spawn_future(Box::pin(async move {
                 sender
@@ -149,6 +231,7 @@ impl NavigatorBackend for TestNavigatorBackend {
                         SocketEvent::Send { payload } => {
                             sender.send(SocketAction::Data(handle, payload)).expect("working channel send");
                         }
+                        SocketEvent::Policy { .. } => unreachable!("Policy files are served on their own connections"),
                     }
                 }
 
@@ -157,3 +240,15 @@ impl NavigatorBackend for TestNavigatorBackend {
         }
     }
 }
+
+/// A response body that arrives in several chunks.
+struct ChunkedBody(VecDeque<Vec<u8>>);
+
+impl ChunkStream for ChunkedBody {
+    fn poll_chunk(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Vec<u8>, Error>>> {
+        Poll::Ready(self.0.pop_front().map(Ok))
+    }
+}
@@ -1,13 +1,13 @@
-use crate::util::environment::wgpu_descriptors;
+use crate::util::environment::{test_renderer, TestRenderer};
 use crate::util::image_trigger::ImageTrigger;
 use crate::util::runner::TestAudioBackend;
 use anyhow::{anyhow, Result};
 use approx::assert_relative_eq;
 use regex::Regex;
+use ruffle_core::config::SecurityMode;
 use ruffle_core::tag_utils::SwfMovie;
-use ruffle_core::{PlayerBuilder, ViewportDimensions};
+use ruffle_core::{PlayerBuilder, SandboxType, ViewportDimensions};
 use ruffle_render::quality::StageQuality;
-use ruffle_render_wgpu::wgpu;
 use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
 use std::fs;
@@ -28,7 +28,15 @@ pub struct TestOptions {
     pub approximations: Option<Approximations>,
     pub player_options: PlayerOptions,
     pub log_fetch: bool,
+    pub fetch_response: FetchResponse,
     pub required_features: RequiredFeatures,
+    /// The files picked in each file open dialog, relative to the test directory.
+    /// Dialogs after these run out are cancelled, as are those given no files.
+    pub picked_files: Vec<Vec<PathBuf>>,
+    pub peer: Option<PeerOptions>,
+    /// The URL that the test movie appears to be loaded from, which decides its security domain.
+    /// Defaults to the path of the movie. Relative URLs are still fetched from the test directory.
+    pub movie_url: Option<String>,
 }
 
 impl Default for TestOptions {
@@ -45,7 +53,11 @@ impl Default for TestOptions {
             approximations: None,
             player_options: PlayerOptions::default(),
             log_fetch: false,
+            fetch_response: FetchResponse::default(),
             required_features: RequiredFeatures::default(),
+            picked_files: Vec::new(),
+            peer: None,
+            movie_url: None,
         }
     }
 }
@@ -113,15 +125,39 @@ impl Approximations {
     }
 }
 
+/// A second movie that runs alongside the test, such as the other end of a `LocalConnection`.
+///
+/// Its traces are written to the same output, prefixed with `[peer]`.
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PeerOptions {
+    /// The movie to run, relative to the test directory.
+    pub swf: PathBuf,
+    /// The URL that the movie appears to be loaded from, which decides its domain.
+    /// Defaults to the path of the movie.
+    pub url: Option<String>,
+}
+
+/// How fetched files are served, to imitate a network connection.
+#[derive(Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct FetchResponse {
+    /// Serve bodies in chunks of at most this many bytes, rather than all at once.
+    pub chunk_size: Option<usize>,
+    /// The headers sent with every response, as `[name, value]` pairs.
+    pub headers: Vec<(String, String)>,
+}
+
 #[derive(Deserialize, Default)]
 #[serde(default, deny_unknown_fields)]
 pub struct RequiredFeatures {
     lzma: bool,
+    h264: bool,
 }
 
 impl RequiredFeatures {
     pub fn can_run(&self) -> bool {
-        !self.lzma || cfg!(feature = "lzma")
+        (!self.lzma || cfg!(feature = "lzma")) && (!self.h264 || cfg!(feature = "h264"))
     }
 }
 
@@ -133,6 +169,8 @@ pub struct PlayerOptions {
     with_renderer: Option<RenderOptions>,
     with_audio: bool,
     with_video: bool,
+    security_mode: Option<SecurityMode>,
+    sandbox_type: Option<SandboxType>,
 }
 
 impl PlayerOptions {
@@ -160,27 +198,34 @@ impl PlayerOptions {
         };
 
         if let Some(render_options) = &self.with_renderer {
+            use ruffle_render_software::SoftwareRenderBackend;
             use ruffle_render_wgpu::backend::WgpuRenderBackend;
             use ruffle_render_wgpu::target::TextureTarget;
 
-            if let Some(descriptors) = wgpu_descriptors() {
-                if render_options.is_supported(&descriptors.adapter) {
+            let quality = match render_options.sample_count {
+                16 => StageQuality::High16x16,
+                8 => StageQuality::High8x8,
+                4 => StageQuality::High,
+                2 => StageQuality::Medium,
+                _ => StageQuality::Low,
+            };
+
+            match self.renderer() {
+                Some(TestRenderer::Wgpu(descriptors)) => {
                     let target = TextureTarget::new(&descriptors.device, (width, height))
                         .map_err(|e| anyhow!(e.to_string()))?;
 
+                    player_builder = player_builder.with_quality(quality).with_renderer(
+                        WgpuRenderBackend::new(descriptors.clone(), target)
+                            .map_err(|e| anyhow!(e.to_string()))?,
+                    );
+                }
+                Some(TestRenderer::Software) => {
                     player_builder = player_builder
-                        .with_quality(match render_options.sample_count {
-                            16 => StageQuality::High16x16,
-                            8 => StageQuality::High8x8,
-                            4 => StageQuality::High,
-                            2 => StageQuality::Medium,
-                            _ => StageQuality::Low,
-                        })
-                        .with_renderer(
-                            WgpuRenderBackend::new(descriptors.clone(), target)
-                                .map_err(|e| anyhow!(e.to_string()))?,
-                        );
+                        .with_quality(quality)
+                        .with_renderer(SoftwareRenderBackend::new(width, height));
                 }
+                None => {}
             }
         }
 
@@ -188,6 +233,14 @@ impl PlayerOptions {
             player_builder = player_builder.with_audio(TestAudioBackend::new());
         }
 
+        if let Some(security_mode) = self.security_mode {
+            player_builder = player_builder.with_security_mode(security_mode);
+        }
+
+        if let Some(sandbox_type) = self.sandbox_type {
+            player_builder = player_builder.with_sandbox_type(sandbox_type);
+        }
+
         #[cfg(feature = "imgtests")]
         if self.with_video {
             use ruffle_video_software::backend::SoftwareVideoBackend;
@@ -197,18 +250,18 @@ impl PlayerOptions {
         Ok(player_builder)
     }
 
+    /// The renderer that this test runs with, if it wants one and the one in use supports it.
+    pub fn renderer(&self) -> Option<TestRenderer> {
+        let render_options = self.with_renderer.as_ref()?;
+        test_renderer().filter(|renderer| render_options.is_supported(renderer))
+    }
+
     pub fn can_run(&self, check_renderer: bool) -> bool {
         if let Some(render) = &self.with_renderer {
             // If we don't actually want to check the renderer (ie we're just listing potential tests),
             // don't spend the cost to create it
-            if check_renderer && !render.optional {
-                if let Some(descriptors) = wgpu_descriptors() {
-                    if !render.is_supported(&descriptors.adapter) {
-                        return false;
-                    }
-                } else {
-                    return false;
-                }
+            if check_renderer && !render.optional && self.renderer().is_none() {
+                return false;
             }
         }
         true
@@ -236,12 +289,12 @@ impl ImageComparison {
         actual_image: image::RgbaImage,
         expected_image: image::RgbaImage,
         test_path: &Path,
-        adapter_info: wgpu::AdapterInfo,
+        renderer: TestRenderer,
         known_failure: bool,
     ) -> Result<()> {
         use anyhow::Context;
 
-        let suffix = format!("{}-{:?}", std::env::consts::OS, adapter_info.backend);
+        let suffix = renderer.image_suffix();
 
         let save_actual_image = || {
             if !known_failure {
@@ -368,6 +421,9 @@ pub struct RenderOptions {
     optional: bool,
     sample_count: u32,
     exclude_warp: bool,
+    /// Whether the test is expected to pass with the software renderer.
+    /// Other tests are skipped (or run without a renderer, if it's optional) when it's in use.
+    software: bool,
 }
 
 impl Default for RenderOptions {
@@ -376,13 +432,17 @@ impl Default for RenderOptions {
             optional: false,
             sample_count: 1,
             exclude_warp: false,
+            software: false,
         }
     }
 }
 
 impl RenderOptions {
-    pub fn is_supported(&self, adapter: &wgpu::Adapter) -> bool {
-        let info = adapter.get_info();
+    pub fn is_supported(&self, renderer: &TestRenderer) -> bool {
+        let TestRenderer::Wgpu(descriptors) = renderer else {
+            return self.software;
+        };
+        let info = descriptors.adapter.get_info();
         // 5140 & 140 is WARP, https://learn.microsoft.com/en-us/windows/win32/direct3ddxgi/d3d10-graphics-programming-guide-dxgi#new-info-about-enumerating-adapters-for-windows-8
         if self.exclude_warp && cfg!(windows) && info.vendor == 5140 && info.device == 140 {
             return false;
@@ -1,13 +1,18 @@
+use crate::util::environment::TestRenderer;
 use crate::util::fs_commands::{FsCommand, TestFsCommandProvider};
 use crate::util::image_trigger::ImageTrigger;
 use crate::util::navigator::TestNavigatorBackend;
-use crate::util::options::ImageComparison;
+use crate::util::options::{ImageComparison, PeerOptions};
 use crate::util::test::Test;
+use crate::util::ui::TestUiBackend;
 use anyhow::{anyhow, Result};
 use ruffle_core::backend::audio::{
     swf, AudioBackend, AudioMixer, DecodeError, RegisterError, SoundHandle, SoundInstanceHandle,
     SoundStreamInfo, SoundTransform,
 };
+use ruffle_core::backend::local_connection::{
+    InProcessLocalConnectionBackend, LocalConnectionBroker,
+};
 use ruffle_core::backend::log::LogBackend;
 use ruffle_core::backend::navigator::NullExecutor;
 use ruffle_core::events::MouseButton as RuffleMouseButton;
@@ -20,13 +25,12 @@ use ruffle_input_format::{
     AutomatedEvent, InputInjector, MouseButton as InputMouseButton,
     TextControlCode as InputTextControlCode,
 };
-use ruffle_render_wgpu::descriptors::Descriptors;
 use ruffle_socket_format::SocketEvent;
 use std::cell::RefCell;
 use std::path::Path;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 pub struct TestAudioBackend {
     mixer: AudioMixer,
@@ -64,12 +68,23 @@ impl AudioBackend for TestAudioBackend {
 #[derive(Clone)]
 pub struct TestLogBackend {
     trace_output: Rc<RefCell<String>>,
+    /// Written before every trace, to tell apart the output of several players.
+    prefix: &'static str,
 }
 
 impl TestLogBackend {
     pub fn new() -> Self {
         Self {
             trace_output: Rc::new(RefCell::new(String::new())),
+            prefix: "",
+        }
+    }
+
+    /// A log that writes to the same output, with every trace prefixed by `prefix`.
+    pub fn with_prefix(&self, prefix: &'static str) -> Self {
+        Self {
+            trace_output: self.trace_output.clone(),
+            prefix,
         }
     }
 
@@ -80,11 +95,15 @@ impl TestLogBackend {
 
 impl LogBackend for TestLogBackend {
     fn avm_trace(&self, message: &str) {
+        self.trace_output.borrow_mut().push_str(self.prefix);
         self.trace_output.borrow_mut().push_str(message);
         self.trace_output.borrow_mut().push('\n');
     }
 }
 
+/// How long a frame may spend preloading before the test is considered stuck.
+const PRELOAD_TIMEOUT: Duration = Duration::from_secs(60);
+
 /// Loads an SWF and runs it through the Ruffle core for a number of frames.
 /// Tests that the trace output matches the given expected output.
 pub fn run_swf(
@@ -96,7 +115,7 @@ pub fn run_swf(
 ) -> Result<String> {
     let base_path = Path::new(&test.output_path).parent().unwrap();
     let mut executor = NullExecutor::new();
-    let movie = SwfMovie::from_path(&test.swf_path, None).map_err(|e| anyhow!(e.to_string()))?;
+    let movie = load_movie(&test.swf_path, test.options.movie_url.as_ref())?;
     let mut frame_time = 1000.0 / movie.frame_rate().to_f64();
     if let Some(tr) = test.options.tick_rate {
         frame_time = tr;
@@ -105,19 +124,30 @@ pub fn run_swf(
     let frame_time_duration = Duration::from_millis(frame_time as u64);
 
     let log = TestLogBackend::new();
+    let local_connection_broker = LocalConnectionBroker::new();
     let (fs_command_provider, fs_commands) = TestFsCommandProvider::new();
     let navigator = TestNavigatorBackend::new(
         base_path,
         &executor,
         socket_events,
+        test.options.fetch_response.clone(),
         test.options.log_fetch.then(|| log.clone()),
     )?;
 
     let builder = PlayerBuilder::new()
         .with_log(log.clone())
         .with_navigator(navigator)
+        // Tests run in parallel, so they must not see each other's connections.
+        .with_local_connection(InProcessLocalConnectionBackend::new(
+            &local_connection_broker,
+        ))
         .with_max_execution_duration(Duration::from_secs(300))
         .with_fs_commands(Box::new(fs_command_provider))
+        .with_ui(TestUiBackend::new(
+            base_path,
+            test.options.picked_files.clone(),
+            log.clone(),
+        ))
         .with_viewport_dimensions(
             movie.width().to_pixels() as u32,
             movie.height().to_pixels() as u32,
@@ -133,10 +163,21 @@ pub fn run_swf(
         .with_autoplay(true) //.tick() requires playback
         .build();
 
+    let peer = match &test.options.peer {
+        Some(peer) => Some(build_peer(
+            base_path,
+            peer,
+            &executor,
+            &local_connection_broker,
+            log.with_prefix("[peer] "),
+        )?),
+        None => None,
+    };
+
     let mut images = test.options.image_comparisons.clone();
 
-    let wgpu_descriptors = if cfg!(feature = "imgtests") && !images.is_empty() {
-        crate::util::environment::wgpu_descriptors()
+    let renderer = if cfg!(feature = "imgtests") && !images.is_empty() {
+        test.options.player_options.renderer()
     } else {
         None
     };
@@ -175,18 +216,33 @@ pub fn run_swf(
             std::thread::sleep(frame_time_duration);
         }
 
+        let preload_start = Instant::now();
         while !player
             .lock()
             .unwrap()
             .preload(&mut ExecutionLimit::exhausted())
-        {}
-
-        if test.options.num_ticks.is_some() {
-            player.lock().unwrap().tick(frame_time);
-        } else {
-            player.lock().unwrap().run_frame();
-            player.lock().unwrap().update_timers(frame_time);
-            player.lock().unwrap().audio_mut().tick();
+        {
+            // Preloading may be blocked on a fetch, such as an `ImportAssets` library.
+            executor.run();
+
+            if preload_start.elapsed() > PRELOAD_TIMEOUT {
+                return Err(anyhow!(
+                    "Test {} is still preloading after {:?}; is it waiting on a fetch that never finishes?",
+                    test.swf_path.to_string_lossy(),
+                    PRELOAD_TIMEOUT
+                ));
+            }
+        }
+
+        for player in std::iter::once(&player).chain(&peer) {
+            if test.options.num_ticks.is_some() {
+                player.lock().unwrap().tick(frame_time);
+            } else {
+                player.lock().unwrap().run_frame();
+                player.lock().unwrap().update_timers(frame_time);
+                player.lock().unwrap().update_dynamic_sounds();
+                player.lock().unwrap().audio_mut().tick();
+            }
         }
         remaining_iterations -= 1;
         current_iteration += 1;
@@ -205,7 +261,7 @@ pub fn run_swf(
                         capture_and_compare_image(
                             base_path,
                             &player,
-                            wgpu_descriptors,
+                            renderer,
                             &name,
                             image_comparison,
                             test.options.known_failure,
@@ -277,7 +333,7 @@ pub fn run_swf(
             capture_and_compare_image(
                 base_path,
                 &player,
-                wgpu_descriptors,
+                renderer,
                 &name,
                 image_comparison,
                 test.options.known_failure,
@@ -297,7 +353,7 @@ pub fn run_swf(
         capture_and_compare_image(
             base_path,
             &player,
-            wgpu_descriptors,
+            renderer,
             &name,
             image_comparison,
             test.options.known_failure,
@@ -323,11 +379,53 @@ pub fn run_swf(
     Ok(normalized_trace)
 }
 
+/// Loads a movie from `path`, which appears to have been loaded from `url` if there is one.
+fn load_movie(path: &Path, url: Option<&String>) -> Result<SwfMovie> {
+    match url {
+        Some(url) => SwfMovie::from_data(&std::fs::read(path)?, url.clone(), None),
+        None => SwfMovie::from_path(path, None),
+    }
+    .map_err(|e| anyhow!(e.to_string()))
+}
+
+/// Builds the player of a movie that runs alongside the test, sharing its `LocalConnection`s.
+fn build_peer(
+    base_path: &Path,
+    peer: &PeerOptions,
+    executor: &NullExecutor,
+    local_connection_broker: &LocalConnectionBroker,
+    log: TestLogBackend,
+) -> Result<Arc<Mutex<Player>>> {
+    let movie = load_movie(&base_path.join(&peer.swf), peer.url.as_ref())?;
+
+    Ok(PlayerBuilder::new()
+        .with_log(log)
+        .with_navigator(TestNavigatorBackend::new(
+            base_path,
+            executor,
+            None,
+            Default::default(),
+            None,
+        )?)
+        .with_local_connection(InProcessLocalConnectionBackend::new(
+            local_connection_broker,
+        ))
+        .with_max_execution_duration(Duration::from_secs(300))
+        .with_viewport_dimensions(
+            movie.width().to_pixels() as u32,
+            movie.height().to_pixels() as u32,
+            1.0,
+        )
+        .with_movie(movie)
+        .with_autoplay(true)
+        .build())
+}
+
 #[cfg(not(feature = "imgtests"))]
 fn capture_and_compare_image(
     _base_path: &Path,
     _player: &Arc<Mutex<Player>>,
-    _wgpu_descriptors: Option<&Arc<Descriptors>>,
+    _renderer: Option<TestRenderer>,
     _name: &String,
     _image_comparison: ImageComparison,
     known_failure: bool,
@@ -347,26 +445,38 @@ fn capture_and_compare_image(
 fn capture_and_compare_image(
     base_path: &Path,
     player: &Arc<Mutex<Player>>,
-    wgpu_descriptors: Option<&Arc<Descriptors>>,
+    renderer: Option<TestRenderer>,
     name: &String,
     image_comparison: ImageComparison,
     known_failure: bool,
 ) -> Result<()> {
     use anyhow::Context;
+    use ruffle_render_software::SoftwareRenderBackend;
     use ruffle_render_wgpu::backend::WgpuRenderBackend;
     use ruffle_render_wgpu::target::TextureTarget;
 
-    if let Some(wgpu_descriptors) = wgpu_descriptors {
+    if let Some(renderer) = renderer {
         let mut player_lock = player.lock().unwrap();
         player_lock.render();
-        let renderer = player_lock
-            .renderer_mut()
-            .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-            .unwrap();
-
-        let actual_image = renderer.capture_frame().expect("Failed to capture image");
+        let actual_image = match renderer {
+            TestRenderer::Wgpu(_) => player_lock
+                .renderer_mut()
+                .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
+                .unwrap()
+                .capture_frame(),
+            TestRenderer::Software => player_lock
+                .renderer_mut()
+                .downcast_mut::<SoftwareRenderBackend>()
+                .unwrap()
+                .capture_frame(),
+        }
+        .expect("Failed to capture image");
 
-        let expected_image_path = base_path.join(format!("{name}.expected.png"));
+        let expected_image_path = renderer
+            .expected_image_suffix()
+            .map(|suffix| base_path.join(format!("{name}.expected-{suffix}.png")))
+            .filter(|path| path.is_file())
+            .unwrap_or_else(|| base_path.join(format!("{name}.expected.png")));
         if expected_image_path.is_file() {
             let expected_image = image::open(&expected_image_path)
                 .context("Failed to open expected image")?
@@ -377,7 +487,7 @@ fn capture_and_compare_image(
                 actual_image,
                 expected_image,
                 base_path,
-                wgpu_descriptors.adapter.get_info(),
+                renderer,
                 known_failure,
             )?;
         } else if !known_failure {
@@ -0,0 +1,174 @@
+use crate::util::runner::TestLogBackend;
+use chrono::{DateTime, Utc};
+use ruffle_core::backend::log::LogBackend;
+use ruffle_core::backend::navigator::{async_return, OwnedFuture};
+use ruffle_core::backend::ui::{
+    FileDialogError, FileDialogFuture, FileDialogResult, FileDialogUnavailable, FileFilter,
+    FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
+};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use url::Url;
+
+/// A `UiBackend` used by tests, which answers file dialogs without any user.
+///
+/// Open dialogs pick the files listed in the test's options, in order, and are
+/// cancelled once those run out. Save dialogs always succeed, and log what is saved.
+pub struct TestUiBackend {
+    base_path: PathBuf,
+    picked_files: VecDeque<Vec<PathBuf>>,
+    log: TestLogBackend,
+}
+
+impl TestUiBackend {
+    pub fn new(base_path: &Path, picked_files: Vec<Vec<PathBuf>>, log: TestLogBackend) -> Self {
+        Self {
+            base_path: base_path.to_path_buf(),
+            picked_files: picked_files.into(),
+            log,
+        }
+    }
+}
+
+impl UiBackend for TestUiBackend {
+    fn mouse_visible(&self) -> bool {
+        true
+    }
+
+    fn set_mouse_visible(&mut self, _visible: bool) {}
+
+    fn set_mouse_cursor(&mut self, _cursor: MouseCursor) {}
+
+    fn clipboard_content(&mut self) -> String {
+        "".into()
+    }
+
+    fn set_clipboard_content(&mut self, _content: String) {}
+
+    fn set_fullscreen(&mut self, _is_full: bool) -> Result<(), FullscreenError> {
+        Ok(())
+    }
+
+    fn display_root_movie_download_failed_message(&self) {}
+
+    fn message(&self, _message: &str) {}
+
+    fn open_virtual_keyboard(&self) {}
+
+    fn language(&self) -> &LanguageIdentifier {
+        &US_ENGLISH
+    }
+
+    fn display_unsupported_video(&self, _url: Url) {}
+
+    fn display_file_open_dialog(
+        &mut self,
+        _filters: Vec<FileFilter>,
+        multiple: bool,
+    ) -> Result<FileDialogFuture, FileDialogUnavailable> {
+        let mut paths = self.picked_files.pop_front().unwrap_or_default();
+        if !multiple {
+            paths.truncate(1);
+        }
+
+        let files: Vec<Box<dyn FileDialogResult>> = paths
+            .into_iter()
+            .map(|path| {
+                Box::new(TestOpenedFile {
+                    path: self.base_path.join(path),
+                }) as Box<dyn FileDialogResult>
+            })
+            .collect();
+        Ok(Box::pin(async move { files }))
+    }
+
+    fn display_file_save_dialog(
+        &mut self,
+        file_name: String,
+    ) -> Result<FileDialogFuture, FileDialogUnavailable> {
+        let file: Box<dyn FileDialogResult> = Box::new(TestSavedFile {
+            file_name,
+            data: Default::default(),
+            log: self.log.clone(),
+        });
+        Ok(Box::pin(async move { vec![file] }))
+    }
+}
+
+/// A file from the test directory, picked in an open dialog.
+struct TestOpenedFile {
+    path: PathBuf,
+}
+
+impl FileDialogResult for TestOpenedFile {
+    fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    fn size(&self) -> Option<u64> {
+        fs::metadata(&self.path).ok().map(|metadata| metadata.len())
+    }
+
+    // Times would differ between checkouts, so tests never see them.
+    fn creation_time(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+
+    fn modification_time(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+
+    fn read(&self) -> OwnedFuture<Vec<u8>, FileDialogError> {
+        async_return(fs::read(&self.path).map_err(|e| e.to_string().into()))
+    }
+
+    fn write(&self, _data: Vec<u8>) -> OwnedFuture<(), FileDialogError> {
+        async_return(Err("Test files can't be overwritten".into()))
+    }
+}
+
+/// A file picked in a save dialog, which is kept in memory rather than on disk.
+struct TestSavedFile {
+    file_name: String,
+    data: Rc<RefCell<Option<Vec<u8>>>>,
+    log: TestLogBackend,
+}
+
+impl FileDialogResult for TestSavedFile {
+    fn file_name(&self) -> String {
+        self.file_name.clone()
+    }
+
+    fn size(&self) -> Option<u64> {
+        self.data.borrow().as_ref().map(|data| data.len() as u64)
+    }
+
+    fn creation_time(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+
+    fn modification_time(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+
+    fn read(&self) -> OwnedFuture<Vec<u8>, FileDialogError> {
+        let data = self.data.borrow().clone();
+        async_return(data.ok_or_else(|| "File has not been saved yet".into()))
+    }
+
+    fn write(&self, data: Vec<u8>) -> OwnedFuture<(), FileDialogError> {
+        self.log.avm_trace(&format!(
+            "Saved \"{}\": {}",
+            self.file_name,
+            String::from_utf8_lossy(&data)
+        ));
+        *self.data.borrow_mut() = Some(data);
+        async_return(Ok(()))
+    }
+}
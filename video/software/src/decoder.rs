@@ -10,9 +10,21 @@ pub mod vp6;
 #[cfg(feature = "screenvideo")]
 pub mod screen;
 
+#[cfg(feature = "h264")]
+pub mod h264;
+
 /// Trait for video decoders.
 /// This should be implemented for each video codec.
 pub trait VideoDecoder {
+    /// Configure the decoder with out-of-band codec data.
+    ///
+    /// Some codecs, such as H.264, carry the parameters needed to decode the
+    /// stream separately from its frames. This is called with that data
+    /// before any frame that depends on it is preloaded or decoded.
+    fn configure_decoder(&mut self, _configuration_data: &[u8]) -> Result<(), Error> {
+        Ok(())
+    }
+
     /// Preload a frame.
     ///
     /// No decoding is intended to happen at this point in time. Instead, the
@@ -35,7 +47,11 @@ pub trait VideoDecoder {
     /// Frames may be decoded in any order that does not violate the frame
     /// dependencies declared by the output of `preload_video_stream_frame`.
     ///
-    /// The decoded frame should be returned. An `Error` can be returned if
+    /// The decoded frame should be returned, or `None` if the codec needs
+    /// more frames before it can output one. An `Error` can be returned if
     /// a drawable bitmap can not be produced.
-    fn decode_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<DecodedFrame, Error>;
+    fn decode_frame(
+        &mut self,
+        encoded_frame: EncodedFrame<'_>,
+    ) -> Result<Option<DecodedFrame>, Error>;
 }
@@ -0,0 +1,443 @@
+use crate::decoder::VideoDecoder;
+use openh264::decoder::{DecodedYUV, Decoder};
+use ruffle_render::bitmap::BitmapFormat;
+use ruffle_video::error::Error;
+use ruffle_video::frame::{DecodedFrame, EncodedFrame, FrameDependency};
+
+/// NAL unit type of a slice of an instantaneous decoder refresh picture.
+const NAL_UNIT_TYPE_IDR_SLICE: u8 = 5;
+
+#[derive(thiserror::Error, Debug)]
+pub enum H264Error {
+    #[error("Decoder error: {0}")]
+    DecoderError(#[from] openh264::Error),
+
+    #[error("Invalid AVC decoder configuration record")]
+    InvalidConfiguration,
+
+    #[error("Frame was received before the AVC decoder configuration record")]
+    MissingConfiguration,
+
+    #[error("Truncated NAL unit")]
+    TruncatedNalUnit,
+}
+
+impl From<H264Error> for Error {
+    fn from(error: H264Error) -> Self {
+        Error::DecoderError(Box::new(error))
+    }
+}
+
+/// A decoded picture waiting in the reorder buffer.
+struct BufferedPicture {
+    /// The time at which this picture is to be presented, in milliseconds.
+    presentation_time: i64,
+
+    picture: DecodedFrame,
+}
+
+/// Holds decoded pictures until it is time to present them.
+///
+/// B-frames are decoded after the frames they reference, but presented
+/// before them. The decoder hands pictures back in decoding order, so they
+/// are put back into presentation order here using the composition time of
+/// each frame.
+#[derive(Default)]
+struct ReorderBuffer {
+    pictures: Vec<BufferedPicture>,
+}
+
+impl ReorderBuffer {
+    fn push(&mut self, presentation_time: i64, picture: DecodedFrame) {
+        self.pictures.push(BufferedPicture {
+            presentation_time,
+            picture,
+        });
+    }
+
+    /// Takes the picture that should be on screen once the stream has been
+    /// decoded up to `decode_time`.
+    ///
+    /// Frames are never presented before they are decoded, so no picture
+    /// that is still to come can be presented before `decode_time`. Of the
+    /// pictures that are due, only the latest one is returned; the others
+    /// would already have been replaced on screen.
+    fn pop(&mut self, decode_time: i64) -> Option<DecodedFrame> {
+        let index = self
+            .pictures
+            .iter()
+            .enumerate()
+            .filter(|(_, buffered)| buffered.presentation_time <= decode_time)
+            .max_by_key(|(_, buffered)| buffered.presentation_time)
+            .map(|(index, _)| index)?;
+
+        let buffered = self.pictures.swap_remove(index);
+        self.pictures
+            .retain(|other| other.presentation_time > buffered.presentation_time);
+        Some(buffered.picture)
+    }
+
+    fn clear(&mut self) {
+        self.pictures.clear();
+    }
+}
+
+/// H.264 (AVC) video decoder.
+///
+/// Frames are expected in the format used by FLV: a sequence of NAL units,
+/// each prefixed with its length, as described by the AVC decoder
+/// configuration record passed to `configure_decoder`.
+pub struct H264Decoder {
+    decoder: Decoder,
+
+    /// The number of bytes used to encode the length of each NAL unit.
+    length_size: Option<usize>,
+
+    /// Decoded pictures that have not been presented yet.
+    reorder_buffer: ReorderBuffer,
+
+    /// The ID of the last decoded frame, used to detect seeks.
+    last_frame_id: Option<u32>,
+}
+
+impl H264Decoder {
+    pub fn new() -> Result<Self, Error> {
+        Ok(Self {
+            decoder: Decoder::new().map_err(H264Error::DecoderError)?,
+            length_size: None,
+            reorder_buffer: ReorderBuffer::default(),
+            last_frame_id: None,
+        })
+    }
+
+    /// Converts length-prefixed NAL units into an Annex B byte stream, which
+    /// is what the decoder consumes.
+    fn to_annex_b(&self, data: &[u8]) -> Result<Vec<u8>, H264Error> {
+        let length_size = self.length_size.ok_or(H264Error::MissingConfiguration)?;
+        let mut annex_b = Vec::with_capacity(data.len() + 16);
+        for nal_unit in NalUnits::new(data, length_size) {
+            annex_b.extend_from_slice(&[0, 0, 0, 1]);
+            annex_b.extend_from_slice(nal_unit?);
+        }
+        Ok(annex_b)
+    }
+}
+
+impl VideoDecoder for H264Decoder {
+    fn configure_decoder(&mut self, configuration_data: &[u8]) -> Result<(), Error> {
+        let config = AvcDecoderConfiguration::parse(configuration_data)
+            .ok_or(H264Error::InvalidConfiguration)?;
+
+        let mut annex_b = Vec::new();
+        for parameter_set in config
+            .sequence_parameter_sets
+            .iter()
+            .chain(config.picture_parameter_sets.iter())
+        {
+            annex_b.extend_from_slice(&[0, 0, 0, 1]);
+            annex_b.extend_from_slice(parameter_set);
+        }
+        self.decoder
+            .decode(&annex_b)
+            .map_err(H264Error::DecoderError)?;
+        self.length_size = Some(config.length_size);
+
+        Ok(())
+    }
+
+    fn preload_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<FrameDependency, Error> {
+        let Some(length_size) = self.length_size else {
+            return Ok(FrameDependency::Past);
+        };
+
+        // Only IDR pictures allow decoding to restart; every other picture,
+        // including B-frames, depends on what was decoded before it.
+        for nal_unit in NalUnits::new(encoded_frame.data, length_size) {
+            let nal_unit = nal_unit?;
+            if nal_unit.first().map(|header| header & 0x1F) == Some(NAL_UNIT_TYPE_IDR_SLICE) {
+                return Ok(FrameDependency::None);
+            }
+        }
+        Ok(FrameDependency::Past)
+    }
+
+    fn decode_frame(
+        &mut self,
+        encoded_frame: EncodedFrame<'_>,
+    ) -> Result<Option<DecodedFrame>, Error> {
+        // Pictures buffered before a seek will never be shown.
+        if self
+            .last_frame_id
+            .map_or(false, |id| id.wrapping_add(1) != encoded_frame.frame_id)
+        {
+            self.reorder_buffer.clear();
+        }
+        self.last_frame_id = Some(encoded_frame.frame_id);
+
+        // `Decoder::decode` flushes the decoder's own reorder queue after
+        // every frame, so pictures come back in decoding order.
+        let annex_b = self.to_annex_b(encoded_frame.data)?;
+        let decode_time = i64::from(encoded_frame.timestamp);
+        if let Some(yuv) = self
+            .decoder
+            .decode(&annex_b)
+            .map_err(H264Error::DecoderError)?
+        {
+            self.reorder_buffer.push(
+                decode_time + i64::from(encoded_frame.composition_time_offset),
+                yuv_to_frame(&yuv),
+            );
+        }
+
+        Ok(self.reorder_buffer.pop(decode_time))
+    }
+}
+
+/// Copies the planes of a decoded picture into a tightly packed frame.
+fn yuv_to_frame(yuv: &DecodedYUV<'_>) -> DecodedFrame {
+    let (width, height) = yuv.dimension_y();
+    let (chroma_width, chroma_height) = yuv.dimension_u();
+    let (y_stride, u_stride, v_stride) = yuv.strides_yuv();
+
+    let mut data = Vec::with_capacity(width * height + 2 * chroma_width * chroma_height);
+    for row in yuv.y_with_stride().chunks(y_stride).take(height) {
+        data.extend_from_slice(&row[..width]);
+    }
+    for row in yuv.u_with_stride().chunks(u_stride).take(chroma_height) {
+        data.extend_from_slice(&row[..chroma_width]);
+    }
+    for row in yuv.v_with_stride().chunks(v_stride).take(chroma_height) {
+        data.extend_from_slice(&row[..chroma_width]);
+    }
+
+    DecodedFrame::new(width as u32, height as u32, BitmapFormat::Yuv420p, data)
+}
+
+/// An iterator over length-prefixed NAL units.
+struct NalUnits<'a> {
+    data: &'a [u8],
+    length_size: usize,
+}
+
+impl<'a> NalUnits<'a> {
+    fn new(data: &'a [u8], length_size: usize) -> Self {
+        Self { data, length_size }
+    }
+}
+
+impl<'a> Iterator for NalUnits<'a> {
+    type Item = Result<&'a [u8], H264Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+        if self.data.len() < self.length_size {
+            self.data = &[];
+            return Some(Err(H264Error::TruncatedNalUnit));
+        }
+
+        let (length, rest) = self.data.split_at(self.length_size);
+        let length = length
+            .iter()
+            .fold(0usize, |length, byte| (length << 8) | usize::from(*byte));
+        if rest.len() < length {
+            self.data = &[];
+            return Some(Err(H264Error::TruncatedNalUnit));
+        }
+
+        let (nal_unit, rest) = rest.split_at(length);
+        self.data = rest;
+        Some(Ok(nal_unit))
+    }
+}
+
+/// The parts of an `AVCDecoderConfigurationRecord` (ISO/IEC 14496-15) needed
+/// for decoding.
+struct AvcDecoderConfiguration<'a> {
+    length_size: usize,
+    sequence_parameter_sets: Vec<&'a [u8]>,
+    picture_parameter_sets: Vec<&'a [u8]>,
+}
+
+impl<'a> AvcDecoderConfiguration<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        fn read_parameter_sets<'a>(data: &mut &'a [u8], count: usize) -> Option<Vec<&'a [u8]>> {
+            let mut parameter_sets = Vec::with_capacity(count);
+            for _ in 0..count {
+                let length = usize::from(u16::from_be_bytes([*data.first()?, *data.get(1)?]));
+                parameter_sets.push(data.get(2..2 + length)?);
+                *data = &data[2 + length..];
+            }
+            Some(parameter_sets)
+        }
+
+        // configurationVersion, AVCProfileIndication, profile_compatibility
+        // and AVCLevelIndication precede the fields we care about.
+        if *data.first()? != 1 {
+            return None;
+        }
+        let length_size = usize::from(data.get(4)? & 0b11) + 1;
+        let num_sequence_parameter_sets = usize::from(data.get(5)? & 0b1_1111);
+        let mut rest = data.get(6..)?;
+        let sequence_parameter_sets = read_parameter_sets(&mut rest, num_sequence_parameter_sets)?;
+        let num_picture_parameter_sets = usize::from(*rest.first()?);
+        rest = &rest[1..];
+        let picture_parameter_sets = read_parameter_sets(&mut rest, num_picture_parameter_sets)?;
+
+        Some(Self {
+            length_size,
+            sequence_parameter_sets,
+            picture_parameter_sets,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AvcDecoderConfiguration, H264Error, NalUnits, ReorderBuffer};
+    use ruffle_render::bitmap::BitmapFormat;
+    use ruffle_video::frame::DecodedFrame;
+
+    /// A picture told apart from the others by its width.
+    fn picture(id: u32) -> DecodedFrame {
+        DecodedFrame::new(id, 1, BitmapFormat::Rgba, vec![0; id as usize * 4])
+    }
+
+    #[test]
+    fn parse_configuration() {
+        let data = [
+            0x01, 0x42, 0xC0, 0x0A, 0xFF, 0xE1, // version, profile, level, length size, 1 SPS
+            0x00, 0x03, 0x67, 0x42, 0xC0, // SPS
+            0x02, // 2 PPSes
+            0x00, 0x02, 0x68, 0xCE, // PPS
+            0x00, 0x01, 0x68, // PPS
+        ];
+        let config = AvcDecoderConfiguration::parse(&data).unwrap();
+
+        assert_eq!(config.length_size, 4);
+        assert_eq!(config.sequence_parameter_sets, [&[0x67, 0x42, 0xC0][..]]);
+        assert_eq!(
+            config.picture_parameter_sets,
+            [&[0x68, 0xCE][..], &[0x68][..]]
+        );
+    }
+
+    #[test]
+    fn parse_configuration_without_parameter_sets() {
+        let data = [0x01, 0x42, 0xC0, 0x0A, 0xFD, 0xE0, 0x00];
+        let config = AvcDecoderConfiguration::parse(&data).unwrap();
+
+        assert_eq!(config.length_size, 2);
+        assert!(config.sequence_parameter_sets.is_empty());
+        assert!(config.picture_parameter_sets.is_empty());
+    }
+
+    #[test]
+    fn parse_configuration_invalid_version() {
+        let data = [0x00, 0x42, 0xC0, 0x0A, 0xFF, 0xE0, 0x00];
+        assert!(AvcDecoderConfiguration::parse(&data).is_none());
+    }
+
+    #[test]
+    fn parse_configuration_truncated() {
+        // The SPS claims to be longer than the rest of the record.
+        let data = [0x01, 0x42, 0xC0, 0x0A, 0xFF, 0xE1, 0x00, 0x05, 0x67, 0x42];
+        assert!(AvcDecoderConfiguration::parse(&data).is_none());
+
+        // The PPS count is missing.
+        let data = [0x01, 0x42, 0xC0, 0x0A, 0xFF, 0xE1, 0x00, 0x01, 0x67];
+        assert!(AvcDecoderConfiguration::parse(&data).is_none());
+
+        assert!(AvcDecoderConfiguration::parse(&[]).is_none());
+    }
+
+    #[test]
+    fn read_nal_units() {
+        let data = [0x00, 0x02, 0x65, 0x88, 0x00, 0x00, 0x00, 0x01, 0x41];
+        let nal_units: Vec<_> = NalUnits::new(&data, 2).collect::<Result<_, _>>().unwrap();
+
+        assert_eq!(nal_units, [&[0x65, 0x88][..], &[][..], &[0x41][..]]);
+    }
+
+    #[test]
+    fn read_nal_units_four_byte_length() {
+        let data = [
+            0x00, 0x00, 0x00, 0x01, 0x65, 0x00, 0x00, 0x00, 0x02, 0x41, 0x9A,
+        ];
+        let nal_units: Vec<_> = NalUnits::new(&data, 4).collect::<Result<_, _>>().unwrap();
+
+        assert_eq!(nal_units, [&[0x65][..], &[0x41, 0x9A][..]]);
+    }
+
+    #[test]
+    fn read_nal_units_truncated() {
+        // The second NAL unit is longer than the remaining data.
+        let data = [0x00, 0x01, 0x65, 0x00, 0x03, 0x41];
+        let mut nal_units = NalUnits::new(&data, 2);
+
+        assert!(matches!(nal_units.next(), Some(Ok([0x65]))));
+        assert!(matches!(
+            nal_units.next(),
+            Some(Err(H264Error::TruncatedNalUnit))
+        ));
+        assert!(nal_units.next().is_none());
+
+        // The length prefix itself is cut off.
+        let mut nal_units = NalUnits::new(&[0x00], 2);
+        assert!(matches!(
+            nal_units.next(),
+            Some(Err(H264Error::TruncatedNalUnit))
+        ));
+        assert!(nal_units.next().is_none());
+    }
+
+    #[test]
+    fn reorder_without_b_frames() {
+        let mut buffer = ReorderBuffer::default();
+        for (id, time) in [(1, 0), (2, 100), (3, 200)] {
+            buffer.push(time, picture(id));
+            assert_eq!(buffer.pop(time).map(|p| p.width()), Some(id));
+        }
+    }
+
+    #[test]
+    fn reorder_b_frames() {
+        // Decoding order I0 P3 B1 B2 P6 B4 B5, one frame every 100ms,
+        // delayed by one frame so that the B-frames can be presented on
+        // time.
+        let frames = [
+            (1, 0, 100),
+            (4, 100, 400),
+            (2, 200, 200),
+            (3, 300, 300),
+            (7, 400, 700),
+            (5, 500, 500),
+            (6, 600, 600),
+        ];
+        let mut buffer = ReorderBuffer::default();
+        let mut presented = Vec::new();
+        for (id, decode_time, presentation_time) in frames {
+            buffer.push(presentation_time, picture(id));
+            presented.extend(buffer.pop(decode_time).map(|p| p.width()));
+        }
+
+        assert_eq!(presented, [1, 2, 3, 4, 5, 6]);
+        assert_eq!(buffer.pop(700).map(|p| p.width()), Some(7));
+        assert!(buffer.pop(i64::MAX).is_none());
+    }
+
+    #[test]
+    fn reorder_skips_overdue_pictures() {
+        let mut buffer = ReorderBuffer::default();
+        buffer.push(100, picture(1));
+        buffer.push(300, picture(3));
+        buffer.push(200, picture(2));
+
+        // Everything up to 250ms is due at once, so only the picture that
+        // is still on screen at that time is presented.
+        assert_eq!(buffer.pop(250).map(|p| p.width()), Some(2));
+        assert_eq!(buffer.pop(300).map(|p| p.width()), Some(3));
+        assert!(buffer.pop(300).is_none());
+    }
+}
@@ -24,6 +24,15 @@ pub enum ScreenError {
 
     #[error("Not all blocks were updated by a supposed keyframe")]
     KeyframeInvalid,
+
+    #[error("Invalid color depth: {0}")]
+    InvalidColorDepth(u8),
+
+    #[error("Diff block exceeds the height of its image block")]
+    InvalidDiffBlock,
+
+    #[error("Invalid palette index: {0}")]
+    InvalidPaletteIndex(u8),
 }
 
 impl From<ScreenError> for Error {
@@ -32,8 +41,38 @@ impl From<ScreenError> for Error {
     }
 }
 
-/// Screen Video (V1 only) decoder.
+/// The default palette of Screen Video V2, used by blocks with a color depth
+/// of 15/7 bits until the stream provides a palette of its own.
+#[rustfmt::skip]
+const DEFAULT_PALETTE: [u32; 128] = [
+    0x000000, 0x333333, 0x666666, 0x999999, 0xCCCCCC, 0xFFFFFF,
+    0x330000, 0x660000, 0x990000, 0xCC0000, 0xFF0000, 0x003300,
+    0x006600, 0x009900, 0x00CC00, 0x00FF00, 0x000033, 0x000066,
+    0x000099, 0x0000CC, 0x0000FF, 0x333300, 0x666600, 0x999900,
+    0xCCCC00, 0xFFFF00, 0x003333, 0x006666, 0x009999, 0x00CCCC,
+    0x00FFFF, 0x330033, 0x660066, 0x990099, 0xCC00CC, 0xFF00FF,
+    0xFFFF33, 0xFFFF66, 0xFFFF99, 0xFFFFCC, 0xFF33FF, 0xFF66FF,
+    0xFF99FF, 0xFFCCFF, 0x33FFFF, 0x66FFFF, 0x99FFFF, 0xCCFFFF,
+    0xCCCC33, 0xCCCC66, 0xCCCC99, 0xCCCCFF, 0xCC33CC, 0xCC66CC,
+    0xCC99CC, 0xCCFFCC, 0x33CCCC, 0x66CCCC, 0x99CCCC, 0xFFCCCC,
+    0x999933, 0x999966, 0x9999CC, 0x9999FF, 0x993399, 0x996699,
+    0x99CC99, 0x99FF99, 0x339999, 0x669999, 0xCC9999, 0xFF9999,
+    0x666633, 0x666699, 0x6666CC, 0x6666FF, 0x663366, 0x669966,
+    0x66CC66, 0x66FF66, 0x336666, 0x996666, 0xCC6666, 0xFF6666,
+    0x333366, 0x333399, 0x3333CC, 0x3333FF, 0x336633, 0x339933,
+    0x33CC33, 0x33FF33, 0x663333, 0x993333, 0xCC3333, 0xFF3333,
+    0x003366, 0x336600, 0x660033, 0x006633, 0x330066, 0x663300,
+    0x336699, 0x669933, 0x993366, 0x339966, 0x663399, 0x996633,
+    0x6699CC, 0x99CC66, 0xCC6699, 0x66CC99, 0x9966CC, 0xCC9966,
+    0x99CCFF, 0xCCFF99, 0xFF99CC, 0x99FFCC, 0xCC99FF, 0xFFCC99,
+    0x111111, 0x222222, 0x444444, 0x555555, 0xAAAAAA, 0xBBBBBB,
+    0xDDDDDD, 0xEEEEEE,
+];
+
+/// Screen Video (V1 and V2) decoder.
 pub struct ScreenVideoDecoder {
+    is_v2: bool,
+
     w: usize,
     h: usize,
     block_w: usize,
@@ -42,6 +81,16 @@ pub struct ScreenVideoDecoder {
     tile: Vec<u8>, // acts as a scratch buffer
 
     last_frame: Option<Vec<u8>>,
+
+    /// V2 only: the colors that hybrid blocks can refer to, in BGR order.
+    palette: Vec<[u8; 3]>,
+
+    /// V2 only: the decompressed data of every block of the last frame.
+    blocks: Vec<Vec<u8>>,
+
+    /// V2 only: the decompressed data of every block of the last IFrame
+    /// image, which blocks of later frames may be compressed against.
+    iframe_blocks: Vec<Vec<u8>>,
 }
 
 struct ByteReader<'a> {
@@ -82,12 +131,26 @@ impl<'a> ByteReader<'a> {
 impl ScreenVideoDecoder {
     pub fn new() -> Self {
         Self {
+            is_v2: false,
             w: 0,
             h: 0,
             block_w: 0,
             block_h: 0,
             tile: vec![],
             last_frame: None,
+            palette: DEFAULT_PALETTE
+                .iter()
+                .map(|rgb| [*rgb as u8, (*rgb >> 8) as u8, (*rgb >> 16) as u8])
+                .collect(),
+            blocks: vec![],
+            iframe_blocks: vec![],
+        }
+    }
+
+    pub fn new_v2() -> Self {
+        Self {
+            is_v2: true,
+            ..Self::new()
         }
     }
 
@@ -127,8 +190,152 @@ impl ScreenVideoDecoder {
         Ok(is_intra)
     }
 
+    fn decode_v2(
+        &mut self,
+        src: &mut ByteReader,
+        data: &mut [u8],
+        stride: usize,
+        is_keyframe: bool,
+    ) -> Result<bool, Error> {
+        let flags = src.read_byte()?;
+        let has_iframe_image = flags & 0b10 != 0;
+        let has_palette_info = flags & 0b1 != 0;
+
+        if has_palette_info {
+            let data_size = src.read_u16be()? as usize;
+            let palette = inflate(
+                src.read_buf_ref(data_size)?,
+                None,
+                DEFAULT_PALETTE.len() * 3,
+            )?;
+            self.palette = palette
+                .chunks_exact(3)
+                .map(|bgr| [bgr[0], bgr[1], bgr[2]])
+                .collect();
+        }
+
+        let num_cols = (self.w + self.block_w - 1) / self.block_w;
+        let num_rows = (self.h + self.block_h - 1) / self.block_h;
+        self.blocks.resize(num_cols * num_rows, vec![]);
+
+        let mut is_intra = true;
+        for (yy, row) in data.chunks_mut(stride * self.block_h).enumerate() {
+            let cur_h = (self.h - yy * self.block_h).min(self.block_h);
+            for x in (0..self.w).step_by(self.block_w) {
+                let cur_w = (self.w - x).min(self.block_w);
+                let index = yy * num_cols + x / self.block_w;
+
+                let data_size = src.read_u16be()? as usize;
+                if data_size == 0 {
+                    is_intra = false;
+                    continue;
+                }
+
+                let format = src.read_byte()?;
+                let color_depth = (format >> 3) & 0b11;
+                let has_diff_blocks = format & 0b100 != 0;
+                let zlib_prime_current = format & 0b10 != 0;
+                let zlib_prime_previous = format & 0b1 != 0;
+                let mut header_size = 1;
+
+                let (diff_start, diff_height) = if has_diff_blocks {
+                    header_size += 2;
+                    is_intra = false;
+                    (src.read_byte()? as usize, src.read_byte()? as usize)
+                } else {
+                    (0, cur_h)
+                };
+                if diff_start + diff_height > cur_h {
+                    return Err(ScreenError::InvalidDiffBlock.into());
+                }
+
+                // Blocks may be compressed with the zlib dictionary primed by
+                // the data of another block, either of this frame or of the
+                // last IFrame image.
+                let prime_index = if zlib_prime_current {
+                    header_size += 2;
+                    let col = src.read_byte()? as usize;
+                    let row = src.read_byte()? as usize;
+                    Some(row * num_cols + col)
+                } else if zlib_prime_previous {
+                    Some(index)
+                } else {
+                    None
+                };
+                let prime = prime_index.map(|prime_index| {
+                    let blocks = if zlib_prime_previous {
+                        &self.iframe_blocks
+                    } else {
+                        &self.blocks
+                    };
+                    blocks.get(prime_index).cloned().unwrap_or_default()
+                });
+
+                let compressed_size = data_size
+                    .checked_sub(header_size)
+                    .ok_or(ScreenError::UnexpectedEOF)?;
+                let block = inflate(
+                    src.read_buf_ref(compressed_size)?,
+                    prime.as_deref(),
+                    cur_w * diff_height * 3,
+                )?;
+
+                let rows = row[diff_start * stride + x * 3..]
+                    .chunks_mut(stride)
+                    .take(diff_height);
+                match color_depth {
+                    0 => {
+                        for (dst, src) in rows.zip(block.chunks(cur_w * 3)) {
+                            dst[..src.len()].copy_from_slice(src);
+                        }
+                    }
+                    2 => {
+                        let mut src = ByteReader::new(&block);
+                        for dst in rows {
+                            for pixel in dst[..cur_w * 3].chunks_exact_mut(3) {
+                                pixel.copy_from_slice(&self.read_hybrid_pixel(&mut src)?);
+                            }
+                        }
+                    }
+                    _ => return Err(ScreenError::InvalidColorDepth(color_depth).into()),
+                }
+
+                self.blocks[index] = block;
+            }
+        }
+
+        if is_keyframe || has_iframe_image {
+            self.iframe_blocks = self.blocks.clone();
+        }
+
+        Ok(is_intra)
+    }
+
+    /// Reads a pixel of a block with a color depth of 15/7 bits, in which
+    /// every pixel is either a 15-bit color or an index into the palette.
+    fn read_hybrid_pixel(&self, src: &mut ByteReader) -> Result<[u8; 3], ScreenError> {
+        let byte = src.read_byte()?;
+        if byte & 0x80 != 0 {
+            let color = u16::from_be_bytes([byte & 0x7F, src.read_byte()?]);
+            // Expand each 5-bit component to 8 bits.
+            let expand = |c: u16| ((c << 3) | (c >> 2)) as u8;
+            Ok([
+                expand(color & 0x1F),
+                expand((color >> 5) & 0x1F),
+                expand(color >> 10),
+            ])
+        } else {
+            self.palette
+                .get(byte as usize)
+                .copied()
+                .ok_or(ScreenError::InvalidPaletteIndex(byte))
+        }
+    }
+
     fn flush(&mut self) {
         self.last_frame = None;
+        self.blocks.clear();
+        self.iframe_blocks.clear();
     }
 }
 
@@ -139,7 +346,7 @@ impl VideoDecoder for ScreenVideoDecoder {
         // in FLV. This is super helpful, because it encodes whether the frame is a keyframe or not.
 
         // Just a quick sanity check for codec IDs...
-        debug_assert!(encoded_frame.data[0] & 0xF == 3);
+        debug_assert!(encoded_frame.data[0] & 0xF == if self.is_v2 { 6 } else { 3 });
 
         match encoded_frame.data[0] >> 4 {
             1 => Ok(FrameDependency::None),
@@ -148,7 +355,10 @@ impl VideoDecoder for ScreenVideoDecoder {
         }
     }
 
-    fn decode_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<DecodedFrame, Error> {
+    fn decode_frame(
+        &mut self,
+        encoded_frame: EncodedFrame<'_>,
+    ) -> Result<Option<DecodedFrame>, Error> {
         let is_keyframe = encoded_frame.data[0] >> 4 == 1;
 
         if !is_keyframe && self.last_frame.is_none() {
@@ -184,7 +394,11 @@ impl VideoDecoder for ScreenVideoDecoder {
 
         let stride = w * 3;
 
-        let is_intra = self.decode_v1(&mut br, data.as_mut_slice(), stride)?;
+        let is_intra = if self.is_v2 {
+            self.decode_v2(&mut br, data.as_mut_slice(), stride, is_keyframe)?
+        } else {
+            self.decode_v1(&mut br, data.as_mut_slice(), stride)?
+        };
 
         if is_intra != is_keyframe {
             return Err(ScreenError::KeyframeInvalid.into());
@@ -204,13 +418,46 @@ impl VideoDecoder for ScreenVideoDecoder {
 
         self.last_frame = Some(data);
 
-        Ok(DecodedFrame::new(
+        Ok(Some(DecodedFrame::new(
             w as u32,
             h as u32,
             BitmapFormat::Rgb,
             rgb,
-        ))
+        )))
+    }
+}
+
+/// Decompresses the zlib data of a single block, expecting at most `max_size`
+/// bytes of output.
+///
+/// If `prime` is given, `data` is a continuation of a zlib stream that has
+/// already produced `prime`, rather than a complete stream of its own.
+fn inflate(data: &[u8], prime: Option<&[u8]>, max_size: usize) -> Result<Vec<u8>, ScreenError> {
+    let Some(prime) = prime else {
+        let mut output = vec![0; max_size];
+        let mut decompress = Decompress::new(true);
+        decompress.decompress(data, &mut output, flate2::FlushDecompress::Finish)?;
+        output.truncate(decompress.total_out() as usize);
+        return Ok(output);
+    };
+
+    // Recreate the state of the primed stream by prepending the priming data
+    // as uncompressed deflate blocks, then discard it from the output.
+    let mut stream = Vec::with_capacity(prime.len() + prime.len() / 0xFFFF * 5 + 5 + data.len());
+    for chunk in prime.chunks(0xFFFF) {
+        let len = chunk.len() as u16;
+        stream.push(0);
+        stream.extend_from_slice(&len.to_le_bytes());
+        stream.extend_from_slice(&(!len).to_le_bytes());
+        stream.extend_from_slice(chunk);
     }
+    stream.extend_from_slice(data);
+
+    let mut output = vec![0; prime.len() + max_size];
+    let mut decompress = Decompress::new(false);
+    decompress.decompress(&stream, &mut output, flate2::FlushDecompress::Sync)?;
+    output.truncate(decompress.total_out() as usize);
+    Ok(output.split_off(prime.len().min(output.len())))
 }
 
 impl Default for ScreenVideoDecoder {
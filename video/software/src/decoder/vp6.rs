@@ -112,7 +112,10 @@ impl VideoDecoder for Vp6Decoder {
         )
     }
 
-    fn decode_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<DecodedFrame, Error> {
+    fn decode_frame(
+        &mut self,
+        encoded_frame: EncodedFrame<'_>,
+    ) -> Result<Option<DecodedFrame>, Error> {
         // If this is the first frame, the decoder needs to be initialized.
 
         if !self.init_called {
@@ -232,23 +235,23 @@ impl VideoDecoder for Vp6Decoder {
             data.extend(v);
             data.extend(a);
 
-            Ok(DecodedFrame::new(
+            Ok(Some(DecodedFrame::new(
                 width as u32,
                 height as u32,
                 BitmapFormat::Yuva420p,
                 data,
-            ))
+            )))
         } else {
             let mut data = y.to_vec();
             data.extend(u);
             data.extend(v);
 
-            Ok(DecodedFrame::new(
+            Ok(Some(DecodedFrame::new(
                 width as u32,
                 height as u32,
                 BitmapFormat::Yuv420p,
                 data,
-            ))
+            )))
         }
     }
 }
@@ -26,6 +26,17 @@ pub trait VideoBackend {
         filter: VideoDeblocking,
     ) -> Result<VideoStreamHandle, Error>;
 
+    /// Configure the decoder of a given video stream.
+    ///
+    /// Some codecs carry their decoding parameters outside of the frames
+    /// themselves, such as the AVC decoder configuration record of an H.264
+    /// stream. That data is provided here before any frames that need it.
+    fn configure_video_stream_decoder(
+        &mut self,
+        stream: VideoStreamHandle,
+        configuration_data: &[u8],
+    ) -> Result<(), Error>;
+
     /// Preload a frame of a given video stream.
     ///
     /// No decoding is intended to happen at this point in time. Instead, the
@@ -59,10 +70,14 @@ pub trait VideoBackend {
     ///
     /// Any previously returned bitmaps may be updated, invalidated, or
     /// reclaimed by whatever means the decoder implementation chooses.
+    ///
+    /// `None` is returned if the decoder needs more frames before it can
+    /// output a picture, as H.264 decoders do while they reorder frames. The
+    /// previously returned bitmap, if any, should continue to be shown.
     fn decode_video_stream_frame(
         &mut self,
         stream: VideoStreamHandle,
         encoded_frame: EncodedFrame<'_>,
         renderer: &mut dyn RenderBackend,
-    ) -> Result<BitmapInfo, Error>;
+    ) -> Result<Option<BitmapInfo>, Error>;
 }
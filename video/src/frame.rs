@@ -12,6 +12,19 @@ pub struct EncodedFrame<'a> {
     /// A caller-specified frame ID. Frame IDs must be consistent between
     /// subsequent uses of the same data stream.
     pub frame_id: u32,
+
+    /// The time at which the frame is decoded, in milliseconds.
+    ///
+    /// This is only meaningful for codecs that reorder frames, and may be
+    /// zero otherwise.
+    pub timestamp: i32,
+
+    /// The offset of the frame's presentation time from its decoding time,
+    /// in milliseconds.
+    ///
+    /// This is nonzero for H.264 streams with B-frames, which are presented
+    /// before frames that are decoded earlier than them.
+    pub composition_time_offset: i32,
 }
 
 impl<'a> EncodedFrame<'a> {
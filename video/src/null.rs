@@ -44,6 +44,14 @@ impl VideoBackend for NullVideoBackend {
         Ok(self.streams.insert(()))
     }
 
+    fn configure_video_stream_decoder(
+        &mut self,
+        _stream: VideoStreamHandle,
+        _configuration_data: &[u8],
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
     fn preload_video_stream_frame(
         &mut self,
         _stream: VideoStreamHandle,
@@ -57,7 +65,7 @@ impl VideoBackend for NullVideoBackend {
         _stream: VideoStreamHandle,
         _encoded_frame: EncodedFrame<'_>,
         _renderer: &mut dyn RenderBackend,
-    ) -> Result<BitmapInfo, Error> {
+    ) -> Result<Option<BitmapInfo>, Error> {
         Err(Error::DecodingNotSupported)
     }
 }
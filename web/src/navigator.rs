@@ -1,16 +1,20 @@
 //! Navigator backend for web
 use async_channel::Receiver;
-use js_sys::{Array, ArrayBuffer, Uint8Array};
+use js_sys::{Array, Reflect, Uint8Array};
 use ruffle_core::backend::navigator::{
-    async_return, create_fetch_error, create_specific_fetch_error, ErrorResponse, NavigationMethod,
-    NavigatorBackend, OpenURLMode, OwnedFuture, Request, SuccessResponse,
+    async_return, create_fetch_error, create_specific_fetch_error, ChunkStream, ErrorResponse,
+    NavigationMethod, NavigatorBackend, OpenURLMode, OwnedFuture, Request, ResponseBody,
+    SuccessResponse,
 };
 use ruffle_core::config::NetworkingAccessMode;
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
 use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::mpsc::Sender;
 use std::sync::Arc;
+use std::task::{Context, Poll};
 use std::time::Duration;
 use tracing_subscriber::layer::Layered;
 use tracing_subscriber::Registry;
@@ -19,8 +23,8 @@ use url::{ParseError, Url};
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::{spawn_local, JsFuture};
 use web_sys::{
-    window, Blob, BlobPropertyBag, HtmlFormElement, HtmlInputElement, Request as WebRequest,
-    RequestInit, Response as WebResponse,
+    window, Blob, BlobPropertyBag, HtmlFormElement, HtmlInputElement, ReadableStreamDefaultReader,
+    Request as WebRequest, RequestInit, Response as WebResponse,
 };
 
 pub struct WebNavigatorBackend {
@@ -300,31 +304,41 @@ impl NavigatorBackend for WebNavigatorBackend {
                 return Err(ErrorResponse { url, error });
             }
 
-            let body: ArrayBuffer = JsFuture::from(response.array_buffer().map_err(|_| {
-                ErrorResponse {
-                    url: url.clone(),
-                    error: Error::FetchError("Got JS error".to_string()),
-                }
-            })?)
-            .await
-            .map_err(|_| ErrorResponse {
-                url: url.clone(),
-                error: Error::FetchError(
-                    "Could not allocate array buffer for response".to_string(),
+            let headers = js_sys::try_iter(&response.headers())
+                .ok()
+                .flatten()
+                .map(|entries| {
+                    entries
+                        .filter_map(|entry| {
+                            let entry: Array = entry.ok()?.dyn_into().ok()?;
+                            Some((entry.get(0).as_string()?, entry.get(1).as_string()?))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            let expected_length = response
+                .headers()
+                .get("content-length")
+                .ok()
+                .flatten()
+                .and_then(|value| value.parse().ok());
+            let body = match response.body() {
+                Some(stream) => ResponseBody::from_stream(
+                    expected_length,
+                    ReaderChunkStream {
+                        reader: stream.get_reader().unchecked_into(),
+                        pending: None,
+                    },
                 ),
-            })?
-            .dyn_into()
-            .map_err(|_| ErrorResponse {
-                url: url.clone(),
-                error: Error::FetchError("array_buffer result wasn't an ArrayBuffer".to_string()),
-            })?;
-            let body = Uint8Array::new(&body).to_vec();
+                None => ResponseBody::from_bytes(vec![]),
+            };
 
             Ok(SuccessResponse {
                 url,
                 body,
                 status,
                 redirected,
+                headers,
             })
         })
     }
@@ -375,3 +389,41 @@ impl NavigatorBackend for WebNavigatorBackend {
             .expect("working channel send");
     }
 }
+
+/// Reads the body of a fetch response as it arrives.
+struct ReaderChunkStream {
+    reader: ReadableStreamDefaultReader,
+    pending: Option<JsFuture>,
+}
+
+impl ChunkStream for ReaderChunkStream {
+    fn poll_chunk(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Vec<u8>, Error>>> {
+        let this = self.get_mut();
+        let pending = this
+            .pending
+            .get_or_insert_with(|| JsFuture::from(this.reader.read()));
+        let result = match Pin::new(pending).poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+        this.pending = None;
+
+        let Ok(result) = result else {
+            return Poll::Ready(Some(Err(Error::FetchError("Got JS error".to_string()))));
+        };
+        let done = Reflect::get(&result, &"done".into())
+            .ok()
+            .and_then(|done| done.as_bool())
+            .unwrap_or(true);
+        if done {
+            return Poll::Ready(None);
+        }
+        match Reflect::get(&result, &"value".into()) {
+            Ok(value) => Poll::Ready(Some(Ok(Uint8Array::new(&value).to_vec()))),
+            Err(_) => Poll::Ready(Some(Err(Error::FetchError("Got JS error".to_string())))),
+        }
+    }
+}
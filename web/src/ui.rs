@@ -1,12 +1,21 @@
 use super::JavascriptPlayer;
+use chrono::{DateTime, TimeZone, Utc};
+use js_sys::{Array, Uint8Array};
+use ruffle_core::backend::navigator::OwnedFuture;
 use ruffle_core::backend::ui::{
+    FileDialogError, FileDialogFuture, FileDialogResult, FileDialogUnavailable, FileFilter,
     FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
 };
 use ruffle_web_common::JsResult;
 use std::borrow::Cow;
 use url::Url;
+use wasm_bindgen::closure::Closure;
 use wasm_bindgen::JsCast;
-use web_sys::{HtmlCanvasElement, HtmlDocument, HtmlTextAreaElement};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    Blob, BlobPropertyBag, Event, File, HtmlAnchorElement, HtmlCanvasElement, HtmlDocument,
+    HtmlInputElement, HtmlTextAreaElement,
+};
 
 /// An implementation of `UiBackend` utilizing `web_sys` bindings to input APIs.
 pub struct WebUiBackend {
@@ -53,6 +62,99 @@ impl WebUiBackend {
     }
 }
 
+/// A file picked by the user through an `<input type="file">` element.
+struct WebOpenedFile(File);
+
+impl FileDialogResult for WebOpenedFile {
+    fn file_name(&self) -> String {
+        self.0.name()
+    }
+
+    fn size(&self) -> Option<u64> {
+        Some(self.0.size() as u64)
+    }
+
+    fn creation_time(&self) -> Option<DateTime<Utc>> {
+        // Browsers don't expose when files were created.
+        None
+    }
+
+    fn modification_time(&self) -> Option<DateTime<Utc>> {
+        Utc.timestamp_millis_opt(self.0.last_modified() as i64)
+            .single()
+    }
+
+    fn read(&self) -> OwnedFuture<Vec<u8>, FileDialogError> {
+        let contents = JsFuture::from(self.0.array_buffer());
+        Box::pin(async move {
+            let contents = contents
+                .await
+                .map_err(|_| Cow::Borrowed("Couldn't read the file"))?;
+            Ok(Uint8Array::new(&contents).to_vec())
+        })
+    }
+
+    fn write(&self, _data: Vec<u8>) -> OwnedFuture<(), FileDialogError> {
+        Box::pin(async { Err(Cow::Borrowed("Opened files can't be written to")) })
+    }
+}
+
+/// A file that will be offered to the user as a download once it's written.
+///
+/// Browsers don't let pages pick where downloads are saved, so the user
+/// picks that once the download starts.
+struct WebSavedFile {
+    file_name: String,
+}
+
+impl FileDialogResult for WebSavedFile {
+    fn file_name(&self) -> String {
+        self.file_name.clone()
+    }
+
+    fn size(&self) -> Option<u64> {
+        None
+    }
+
+    fn creation_time(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+
+    fn modification_time(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+
+    fn read(&self) -> OwnedFuture<Vec<u8>, FileDialogError> {
+        Box::pin(async { Err(Cow::Borrowed("Saved files can't be read from")) })
+    }
+
+    fn write(&self, data: Vec<u8>) -> OwnedFuture<(), FileDialogError> {
+        let result = download_file(&self.file_name, &data)
+            .map_err(|_| Cow::Borrowed("Couldn't download the file"));
+        Box::pin(async move { result })
+    }
+}
+
+/// Offers `data` to the user as a download named `file_name`.
+fn download_file(file_name: &str, data: &[u8]) -> Result<(), wasm_bindgen::JsValue> {
+    let parts = Array::of1(&Uint8Array::from(data));
+    let blob = Blob::new_with_u8_array_sequence_and_options(
+        &parts,
+        BlobPropertyBag::new().type_("application/octet-stream"),
+    )?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob)?;
+
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .expect("document()");
+    let anchor: HtmlAnchorElement = document.create_element("a")?.unchecked_into();
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+
+    web_sys::Url::revoke_object_url(&url)
+}
+
 impl UiBackend for WebUiBackend {
     fn mouse_visible(&self) -> bool {
         self.cursor_visible
@@ -144,4 +246,66 @@ impl UiBackend for WebUiBackend {
     fn display_unsupported_video(&self, url: Url) {
         self.js_player.display_unsupported_video(url.as_str());
     }
+
+    fn display_file_open_dialog(
+        &mut self,
+        filters: Vec<FileFilter>,
+        multiple: bool,
+    ) -> Result<FileDialogFuture, FileDialogUnavailable> {
+        let input: HtmlInputElement = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.create_element("input").ok())
+            .ok_or(FileDialogUnavailable::Unsupported)?
+            .unchecked_into();
+        input.set_type("file");
+        input.set_multiple(multiple);
+        let accept: Vec<String> = filters
+            .iter()
+            .flat_map(|filter| filter.extension_list())
+            .map(|extension| format!(".{extension}"))
+            .collect();
+        input.set_accept(&accept.join(","));
+
+        let (sender, receiver) = async_channel::bounded(1);
+        let on_change = {
+            let input = input.clone();
+            let sender = sender.clone();
+            Closure::<dyn FnMut(Event)>::new(move |_| {
+                let files = input.files().map_or_else(Vec::new, |files| {
+                    (0..files.length()).filter_map(|i| files.get(i)).collect()
+                });
+                let _ = sender.try_send(files);
+            })
+        };
+        // Browsers without the `cancel` event never tell us about the dialog
+        // being cancelled, which just means the movie never hears back either.
+        let on_cancel = Closure::<dyn FnMut(Event)>::new(move |_| {
+            let _ = sender.try_send(Vec::new());
+        });
+        input
+            .add_event_listener_with_callback("change", on_change.as_ref().unchecked_ref())
+            .warn_on_error();
+        input
+            .add_event_listener_with_callback("cancel", on_cancel.as_ref().unchecked_ref())
+            .warn_on_error();
+        input.click();
+
+        Ok(Box::pin(async move {
+            let files: Vec<File> = receiver.recv().await.unwrap_or_default();
+            // The listeners must live until the dialog is closed.
+            drop((on_change, on_cancel));
+            files
+                .into_iter()
+                .map(|file| Box::new(WebOpenedFile(file)) as Box<dyn FileDialogResult>)
+                .collect()
+        }))
+    }
+
+    fn display_file_save_dialog(
+        &mut self,
+        file_name: String,
+    ) -> Result<FileDialogFuture, FileDialogUnavailable> {
+        let file = Box::new(WebSavedFile { file_name }) as Box<dyn FileDialogResult>;
+        Ok(Box::pin(async move { vec![file] }))
+    }
 }